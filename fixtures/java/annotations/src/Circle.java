@@ -0,0 +1,22 @@
+@SuppressWarnings("unused")
+public class Circle implements Shape {
+    private final double radius;
+
+    public Circle(double radius) {
+        this.radius = radius;
+    }
+
+    @java.lang.Deprecated
+    public double radius() {
+        return radius;
+    }
+
+    @Override
+    public double area() {
+        return Math.PI * radius * radius;
+    }
+
+    public double diameter() {
+        return 2 * radius;
+    }
+}
@@ -0,0 +1,4 @@
+@FunctionalInterface
+public interface Shape {
+    double area();
+}
@@ -0,0 +1,31 @@
+/** Arithmetic helpers. */
+public class Calc {
+    /**
+     * Constructs a new {@code Calc} instance.
+     */
+    public Calc() {
+        // default
+    }
+
+    /**
+     * Adds two numbers.
+     *
+     * @param a first operand
+     * @param b second operand
+     * @return the sum
+     */
+    public static int add(int a, int b) {
+        return a + b;
+    }
+
+    /**
+     * Subtracts two numbers.
+     *
+     * @param a first operand
+     * @param b second operand
+     * @return the difference
+     */
+    public static int subtract(int a, int b) {
+        return a - b;
+    }
+}
@@ -0,0 +1,33 @@
+import static org.junit.jupiter.api.Assertions.assertAll;
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import org.junit.jupiter.api.Test;
+
+/** Tests of varying rigour. */
+public class CalcTest {
+    @Test
+    void addsNumbers() {
+        assertEquals(5, Calc.add(2, 3));
+    }
+
+    @Test
+    void subtractsNumbers() {
+        Calc.subtract(3, 2);
+    }
+
+    @Test
+    void checksBoth() {
+        assertAll(
+            () -> assertEquals(5, Calc.add(2, 3)),
+            () -> assertEquals(1, Calc.subtract(3, 2)));
+    }
+
+    @org.junit.jupiter.api.Test
+    void usesAssertStatement() {
+        assert Calc.add(1, 1) == 2;
+    }
+
+    void helperWithoutAssertions() {
+        Calc.add(0, 0);
+    }
+}
@@ -0,0 +1,18 @@
+/** Builds initials from a full name. */
+public class Initials {
+    /**
+     * Returns the upper-cased first letter of each word in {@code name}.
+     *
+     * @param name a full name such as "ada lovelace"
+     * @return the initials, e.g. "AL"
+     */
+    public static String of(String name) {
+        StringBuilder out = new StringBuilder();
+        for (String part : name.trim().split("\\s+")) {
+            if (!part.isEmpty()) {
+                out.append(Character.toUpperCase(part.charAt(0)));
+            }
+        }
+        return out.toString();
+    }
+}
@@ -0,0 +1,12 @@
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import org.apache.commons.lang3.StringUtils;
+import org.junit.jupiter.api.Test;
+
+/** Relies on commons-lang3, which only the classpath overlay provides. */
+public class InitialsTest {
+    @Test
+    void matchesCommonsInitials() {
+        assertEquals(StringUtils.upperCase("al"), Initials.of("ada lovelace"));
+    }
+}
@@ -0,0 +1,40 @@
+public class Main {
+    public static int add(int a, int b) {
+        return a + b;
+    }
+
+    public static String classify(int[] values, boolean strict) {
+        int score = 0;
+        for (int v : values) {
+            if (v > 10 && strict) {
+                score += 2;
+            } else if (v > 5 || v < -5) {
+                score++;
+            }
+        }
+
+        int i = 0;
+        while (i < values.length) {
+            i++;
+        }
+
+        switch (score) {
+            case 0:
+                return "none";
+            case 1:
+                return "low";
+            default:
+                break;
+        }
+
+        try {
+            return score > 4 ? "high" : "medium";
+        } catch (RuntimeException e) {
+            return "error";
+        }
+    }
+
+    public static void main(String[] args) {
+        System.out.println(classify(new int[] {1, 20, -7}, true));
+    }
+}
@@ -0,0 +1,17 @@
+public class Grid {
+    private final int[] cells;
+    private final String label;
+
+    public Grid(int cells[]) {
+        this(cells, "grid");
+    }
+
+    public Grid(int[] cells, @SuppressWarnings(value = "unused") String label) {
+        this.cells = cells;
+        this.label = label;
+    }
+
+    public Grid(String label, int... cells) {
+        this(cells, label);
+    }
+}
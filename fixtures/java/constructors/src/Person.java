@@ -0,0 +1,45 @@
+import java.util.Map;
+
+/** Person with several constructor overloads for query helper tests. */
+public class Person {
+    /** Display name. */
+    private final String name;
+    /** Age in years. */
+    private final int age;
+
+    /**
+     * Creates an anonymous person.
+     */
+    public Person() {
+        this("anonymous", 0);
+    }
+
+    /**
+     * Creates a person with an unknown age.
+     *
+     * @param name display name
+     */
+    public Person(final String name) {
+        this(name, 0);
+    }
+
+    /**
+     * Creates a person.
+     *
+     * @param name display name
+     * @param age age in years
+     */
+    public Person(String name, int age) {
+        this.name = name;
+        this.age = age;
+    }
+
+    /**
+     * Creates a person from a record of attributes.
+     *
+     * @param attributes attribute map
+     */
+    public Person(Map<String, Integer> attributes) {
+        this("from-map", attributes.getOrDefault("age", 0));
+    }
+}
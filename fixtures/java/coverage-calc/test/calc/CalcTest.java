@@ -0,0 +1,17 @@
+package calc;
+
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import org.junit.jupiter.api.Test;
+
+public class CalcTest {
+    @Test
+    void addsTwoNumbers() {
+        assertEquals(5, new Calc().add(2, 3));
+    }
+
+    @Test
+    void dividesEvenly() {
+        assertEquals(2, new Calc().divide(6, 3));
+    }
+}
@@ -0,0 +1,17 @@
+public class Main {
+    public static int add(int a, int b) {
+        int sum = a + b;
+        System.out.println("DEBUG sum = " + sum);
+        return sum;
+    }
+
+    static void display(int value) {
+        System.out.printf("Result: %d%n", value);
+    }
+
+    public static void main(String[] args) {
+        int total = add(2, 3);
+        System.out.println("Adding 2 and 3");
+        display(total);
+    }
+}
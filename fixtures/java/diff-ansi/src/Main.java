@@ -0,0 +1,9 @@
+public class Main {
+    private static final String GREEN = "\u001B[32m";
+    private static final String BOLD = "\u001B[1m";
+    private static final String RESET = "\u001B[0m";
+
+    public static void main(String[] args) {
+        System.out.println(GREEN + "hello" + RESET + " " + BOLD + "world" + RESET);
+    }
+}
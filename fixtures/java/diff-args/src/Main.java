@@ -0,0 +1,9 @@
+public class Main {
+    public static void main(String[] args) {
+        if (args.length == 0) {
+            System.out.println("Hello, nobody!");
+        } else {
+            System.out.println("Hello, " + String.join(" and ", args) + "!");
+        }
+    }
+}
@@ -0,0 +1,9 @@
+public class Main {
+    public static void main(String[] args) {
+        System.out.print("caf");
+        System.out.write(0xE9);
+        System.out.write(0xFF);
+        System.out.println();
+        System.out.flush();
+    }
+}
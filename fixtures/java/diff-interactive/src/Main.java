@@ -0,0 +1,13 @@
+import java.util.Scanner;
+
+public class Main {
+    public static void main(String[] args) {
+        Scanner scanner = new Scanner(System.in);
+        System.out.print("First number: ");
+        int first = scanner.nextInt();
+        System.out.print("Second number: ");
+        int second = scanner.nextInt();
+        // Bug: subtracts instead of adding.
+        System.out.println("Sum: " + (first - second));
+    }
+}
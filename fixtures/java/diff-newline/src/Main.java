@@ -0,0 +1,10 @@
+import java.util.Scanner;
+
+public class Main {
+    public static void main(String[] args) {
+        // Splitting on "\n" alone keeps any "\r" as part of the token.
+        Scanner scanner = new Scanner(System.in).useDelimiter("\n");
+        String name = scanner.next();
+        System.out.println("Hello, " + name + "! (" + name.length() + " letters)");
+    }
+}
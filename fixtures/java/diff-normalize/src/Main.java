@@ -0,0 +1,14 @@
+import java.util.HashMap;
+import java.util.Map;
+
+public class Main {
+    public static void main(String[] args) {
+        Map<String, String> colors = new HashMap<>();
+        colors.put("Banana", "YELLOW");
+        colors.put("apple", "Red");
+        colors.put("Cherry", "red");
+        for (Map.Entry<String, String> entry : colors.entrySet()) {
+            System.out.println(entry.getKey() + "  \t" + entry.getValue());
+        }
+    }
+}
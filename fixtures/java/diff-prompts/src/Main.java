@@ -0,0 +1,18 @@
+import java.util.Scanner;
+
+public class Main {
+    public static void main(String[] args) {
+        Scanner scanner = new Scanner(System.in);
+        System.out.print("Name: ");
+        System.out.flush();
+        String name = scanner.nextLine();
+        System.out.print("Age: ");
+        System.out.flush();
+        int age = Integer.parseInt(scanner.nextLine().trim());
+        System.out.print("Colour: ");
+        System.out.flush();
+        String colour = scanner.nextLine();
+        System.out.println();
+        System.out.println(name + " is " + age + " and likes " + colour + ".");
+    }
+}
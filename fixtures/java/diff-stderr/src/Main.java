@@ -0,0 +1,7 @@
+public class Main {
+    public static void main(String[] args) {
+        System.err.println("debug: computing");
+        System.out.println("Result: 42");
+        System.err.println("debug: done");
+    }
+}
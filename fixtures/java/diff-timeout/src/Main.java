@@ -0,0 +1,10 @@
+public class Main {
+    public static void main(String[] args) {
+        System.out.println("counting down");
+        int n = 10;
+        while (n > 0) {
+            n = n % 10 + 1;
+        }
+        System.out.println("liftoff");
+    }
+}
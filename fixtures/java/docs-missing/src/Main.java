@@ -0,0 +1,22 @@
+/** Sample program with exactly one undocumented public method. */
+public class Main {
+    /**
+     * Constructs a new {@code Main} instance.
+     */
+    public Main() {
+        // default
+    }
+
+    /**
+     * Entry point prints a doubled value.
+     *
+     * @param args standard CLI arguments (unused)
+     */
+    public static void main(String[] args) {
+        System.out.println(twice(21));
+    }
+
+    public static int twice(int x) {
+        return 2 * x;
+    }
+}
@@ -0,0 +1,2 @@
+// TODO: implement the helper
+import java.util.List;
@@ -0,0 +1,18 @@
+/** A bank account that keeps its state private. */
+public class Account {
+    public static final double RATE = 0.02;
+    private final String owner;
+    private double balance;
+
+    public Account(String owner) {
+        this.owner = owner;
+    }
+
+    public double getBalance() {
+        return balance;
+    }
+
+    public void deposit(double amount) {
+        balance += amount;
+    }
+}
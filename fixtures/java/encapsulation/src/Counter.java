@@ -0,0 +1,13 @@
+/** A counter that exposes its state. */
+public class Counter {
+    public static final int LIMIT = 10;
+    public final String label;
+    public int count;
+    protected static int instances;
+    int step = 1, offset;
+
+    public Counter(String label) {
+        this.label = label;
+        instances++;
+    }
+}
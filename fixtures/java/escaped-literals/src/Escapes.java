@@ -0,0 +1,13 @@
+public class Escapes {
+    public static String quoted() {
+        return "say \"hi\"";
+    }
+
+    public static String trailingBackslash() {
+        return "C:\\temp\\";
+    }
+
+    public static String backslashQuote() {
+        return "a\\\"b";
+    }
+}
@@ -0,0 +1,31 @@
+/** Arithmetic helpers, one of which is wrong. */
+public class Calc {
+    /**
+     * Constructs a new {@code Calc} instance.
+     */
+    public Calc() {
+        // default
+    }
+
+    /**
+     * Adds two numbers.
+     *
+     * @param a first operand
+     * @param b second operand
+     * @return the sum
+     */
+    public static int add(int a, int b) {
+        return a + b;
+    }
+
+    /**
+     * Subtracts two numbers, but gets the order wrong.
+     *
+     * @param a first operand
+     * @param b second operand
+     * @return {@code b - a} instead of {@code a - b}
+     */
+    public static int subtract(int a, int b) {
+        return b - a;
+    }
+}
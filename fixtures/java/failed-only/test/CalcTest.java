@@ -0,0 +1,21 @@
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import org.junit.jupiter.api.Test;
+
+/** One failing test between two passing ones. */
+public class CalcTest {
+    @Test
+    void addsNumbers() {
+        assertEquals(5, Calc.add(2, 3));
+    }
+
+    @Test
+    void subtractsNumbers() {
+        assertEquals(1, Calc.subtract(3, 2));
+    }
+
+    @Test
+    void addsZero() {
+        assertEquals(4, Calc.add(4, 0));
+    }
+}
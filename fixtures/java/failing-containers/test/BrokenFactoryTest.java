@@ -0,0 +1,20 @@
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import java.util.stream.Stream;
+
+import org.junit.jupiter.api.DynamicTest;
+import org.junit.jupiter.api.Test;
+import org.junit.jupiter.api.TestFactory;
+
+/** A passing test next to a factory that throws before producing any tests. */
+public class BrokenFactoryTest {
+    @Test
+    void squaresZero() {
+        assertEquals(0, Squares.square(0));
+    }
+
+    @TestFactory
+    Stream<DynamicTest> squaresNothing() {
+        throw new IllegalStateException("no cases");
+    }
+}
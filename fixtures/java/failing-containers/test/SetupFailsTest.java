@@ -0,0 +1,22 @@
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import org.junit.jupiter.api.BeforeAll;
+import org.junit.jupiter.api.Test;
+
+/** Two tests whose class fails in `@BeforeAll`, so neither runs. */
+public class SetupFailsTest {
+    @BeforeAll
+    static void setUp() {
+        throw new IllegalStateException("setup failed");
+    }
+
+    @Test
+    void squaresOne() {
+        assertEquals(1, Squares.square(1));
+    }
+
+    @Test
+    void squaresTwo() {
+        assertEquals(4, Squares.square(2));
+    }
+}
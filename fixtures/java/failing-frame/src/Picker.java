@@ -0,0 +1,30 @@
+/** Picks elements out of arrays, with a deliberate off-by-one bug. */
+public class Picker {
+    /**
+     * Constructs a new {@code Picker} instance.
+     */
+    public Picker() {
+        // default
+    }
+
+    /**
+     * Returns the element at the given index.
+     *
+     * @param items source array
+     * @param index position to read
+     * @return element at {@code index}
+     */
+    public static int pick(int[] items, int index) {
+        return items[index + 1];
+    }
+
+    /**
+     * Returns the number of elements.
+     *
+     * @param items source array
+     * @return length of {@code items}
+     */
+    public static int count(int[] items) {
+        return items.length;
+    }
+}
@@ -0,0 +1,16 @@
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import org.junit.jupiter.api.Test;
+
+/** Exercises Picker; {@code picksLast} crashes inside student code. */
+public class PickerTest {
+    @Test
+    void picksLast() {
+        assertEquals(3, Picker.pick(new int[] {1, 2, 3}, 2));
+    }
+
+    @Test
+    void counts() {
+        assertEquals(3, Picker.count(new int[] {1, 2, 3}));
+    }
+}
@@ -0,0 +1,51 @@
+import java.util.ArrayList;
+import java.util.List;
+
+/** Keeps track of enrolled students. */
+public class Roster {
+    private List<String> names;
+    private int capacity = 30;
+
+    /**
+     * Creates an empty roster.
+     */
+    public Roster() {
+        // forgot to initialize names
+    }
+
+    /**
+     * Returns how many more students fit.
+     *
+     * @return remaining seats
+     */
+    public int remaining() {
+        return capacity - size();
+    }
+
+    /**
+     * Returns the number of enrolled students.
+     *
+     * @return enrolled count
+     */
+    public int size() {
+        return names.size();
+    }
+
+    /**
+     * Enrolls a student.
+     *
+     * @param name student to enroll
+     */
+    public void enroll(String name) {
+        names.add(name);
+    }
+
+    /**
+     * Builds a copy of the roster.
+     *
+     * @return copied names
+     */
+    public List<String> copy() {
+        return new ArrayList<>(names);
+    }
+}
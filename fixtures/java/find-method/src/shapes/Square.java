@@ -0,0 +1,27 @@
+package shapes;
+
+public class Square {
+    private final double side;
+
+    public Square(double side) {
+        this.side = side;
+    }
+
+    public double area() {
+        return side * side;
+    }
+
+    public double perimeter() {
+        return 4 * side;
+    }
+}
+
+class Label {
+    public double perimeter() {
+        return 0;
+    }
+
+    public String text() {
+        return "square";
+    }
+}
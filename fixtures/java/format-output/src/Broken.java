@@ -0,0 +1,7 @@
+public class Broken {
+    public static void main(String[] args) {
+        System.out.println("name=Ada");
+        System.out.println("age: 36");
+        System.out.println("lang=Java");
+    }
+}
@@ -0,0 +1,6 @@
+/** Needs no imports at all. */
+public class NoImports {
+    public int twice(int n) {
+        return 2 * n;
+    }
+}
@@ -0,0 +1,8 @@
+import java.util.List;
+
+/** Uses the standard library only. */
+public class Plain {
+    public int size(List<String> items) {
+        return items.size();
+    }
+}
@@ -0,0 +1,12 @@
+import java.util.*;
+import java.io.BufferedReader;
+import static java.lang.Math.max;
+import com.google.gson.Gson;
+import org.apache.commons.lang3.StringUtils;
+
+/** Reads a line and reformats it. */
+public class Reader {
+    public String read(Scanner in, BufferedReader fallback) {
+        return StringUtils.trim(new Gson().toJson(in.nextLine())) + max(1, 2);
+    }
+}
@@ -0,0 +1,29 @@
+public class Circle {
+    private static final double PI = 3.14159;
+    private static final int[] SIZES = {2, 4, 8};
+    private final double radius;
+
+    public Circle(double radius) {
+        this.radius = radius;
+    }
+
+    public double area() {
+        return PI * radius * radius;
+    }
+
+    public double circumference() {
+        return 2 * 3.14159 * radius;
+    }
+
+    public int clamp(int value) {
+        final int limit = 100;
+        if (value > limit) {
+            return limit;
+        }
+        return value < 0 ? -1 : value + 1;
+    }
+
+    public long mask(long bits) {
+        return bits & 0xFFL;
+    }
+}
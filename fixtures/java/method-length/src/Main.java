@@ -0,0 +1,29 @@
+public class Main {
+    public static int add(int a, int b) {
+        return a + b;
+    }
+
+    /**
+     * Prints a small report; far too long for one method.
+     */
+    public static void report(int[] values) {
+        int total = 0;
+        int max = Integer.MIN_VALUE;
+        int min = Integer.MAX_VALUE;
+
+        for (int v : values) {
+            total += v;
+            max = Math.max(max, v);
+            min = Math.min(min, v);
+        }
+
+        System.out.println("Total: " + total);
+        System.out.println("Max: " + max);
+        System.out.println("Min: " + min);
+        System.out.println("Count: " + values.length);
+    }
+
+    public static void main(String[] args) {
+        report(new int[] {add(1, 2), 5, -4});
+    }
+}
@@ -0,0 +1,22 @@
+/*
+ * A circle, described by its radius.
+ */
+public class Circle implements Shape {
+    private final double radius;
+
+    public Circle(double radius) {
+        this.radius = radius; // must be positive
+    }
+
+    public double area() {
+        return Math.PI * radius * radius;
+    }
+
+    public double perimeter() {
+        return 2 * Math.PI * radius;
+    }
+
+    public static void main(String[] args) {
+        System.out.println(new Circle(1).area());
+    }
+}
@@ -0,0 +1,7 @@
+/** A closed figure. */
+public interface Shape {
+    // Area in square units.
+    double area();
+
+    double perimeter();
+}
@@ -0,0 +1,7 @@
+import shapes.Circle;
+
+public class App {
+    public static void main(String[] args) {
+        System.out.println(new Circle(1.0).area());
+    }
+}
@@ -0,0 +1,11 @@
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import org.junit.jupiter.api.Test;
+import shapes.Circle;
+
+public class CircleTest {
+    @Test
+    public void unitCircle() {
+        assertEquals(Math.PI, new Circle(1.0).area(), 1e-9);
+    }
+}
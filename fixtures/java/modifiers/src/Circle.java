@@ -0,0 +1,19 @@
+/** A circle that is intentionally left open for extension. */
+class Circle extends Shape {
+    /** Radius. */
+    private final double radius;
+
+    /**
+     * Creates a circle.
+     *
+     * @param radius radius
+     */
+    Circle(double radius) {
+        this.radius = radius;
+    }
+
+    @Override
+    public double area() {
+        return Math.PI * radius * radius;
+    }
+}
@@ -0,0 +1,9 @@
+/** Abstract base type for the modifier query fixtures. */
+public abstract class Shape {
+    /**
+     * Area of this shape.
+     *
+     * @return area
+     */
+    public abstract double area();
+}
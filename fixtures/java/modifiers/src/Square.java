@@ -0,0 +1,19 @@
+/** A square that cannot be subclassed. */
+public final class Square extends Shape {
+    /** Side length. */
+    private final double side;
+
+    /**
+     * Creates a square.
+     *
+     * @param side side length
+     */
+    public Square(double side) {
+        this.side = side;
+    }
+
+    @Override
+    public double area() {
+        return side * side;
+    }
+}
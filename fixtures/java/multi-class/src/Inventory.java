@@ -0,0 +1,26 @@
+package shop;
+
+/** Helper declared first, so the primary type must come from the file name. */
+class Item {
+  private final String label;
+
+  Item(String label) {
+    this.label = label;
+  }
+
+  String label() {
+    return label;
+  }
+}
+
+public class Inventory {
+  private int count;
+
+  public void add(Item item) {
+    count++;
+  }
+
+  public static void main(String[] args) {
+    new Inventory().add(new Item("widget"));
+  }
+}
@@ -0,0 +1,19 @@
+package shop;
+
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import org.junit.jupiter.api.Test;
+
+public class InventoryTest {
+  @Test
+  void addsItems() {
+    new Inventory().add(new Item("a"));
+  }
+}
+
+class ItemTest {
+  @Test
+  void keepsLabel() {
+    assertEquals("b", new Item("b").label());
+  }
+}
@@ -0,0 +1,18 @@
+import java.util.Scanner;
+import java.util.TreeSet;
+
+public class DedupSorter {
+    public static void main(String[] args) {
+        Scanner in = new Scanner(System.in);
+        TreeSet<Integer> numbers = new TreeSet<>();
+        while (in.hasNextInt()) {
+            numbers.add(in.nextInt());
+        }
+
+        StringBuilder out = new StringBuilder();
+        for (int number : numbers) {
+            out.append(number).append(' ');
+        }
+        System.out.println(out.toString().trim());
+    }
+}
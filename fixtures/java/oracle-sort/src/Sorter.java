@@ -0,0 +1,21 @@
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.List;
+import java.util.Scanner;
+
+public class Sorter {
+    public static void main(String[] args) {
+        Scanner in = new Scanner(System.in);
+        List<Integer> numbers = new ArrayList<>();
+        while (in.hasNextInt()) {
+            numbers.add(in.nextInt());
+        }
+        Collections.sort(numbers);
+
+        StringBuilder out = new StringBuilder();
+        for (int number : numbers) {
+            out.append(number).append(' ');
+        }
+        System.out.println(out.toString().trim());
+    }
+}
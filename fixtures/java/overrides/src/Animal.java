@@ -0,0 +1,15 @@
+public abstract class Animal {
+    public abstract String speak();
+
+    public String describe(String prefix) {
+        return prefix + " " + speak();
+    }
+
+    public int legs(int[] counts) {
+        return counts.length;
+    }
+
+    public static Animal create() {
+        return new Cat();
+    }
+}
@@ -0,0 +1,11 @@
+public class Cat extends Animal implements Named {
+    @Override
+    public String speak() {
+        return "Meow";
+    }
+
+    @java.lang.Override
+    public String name() {
+        return "Tom";
+    }
+}
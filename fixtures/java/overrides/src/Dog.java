@@ -0,0 +1,32 @@
+public class Dog extends Animal implements Named, Holder<String> {
+    private String toy = "";
+
+    public String speak() {
+        return "Woof";
+    }
+
+    @Override
+    public String describe(String prefix) {
+        return prefix + " dog";
+    }
+
+    public String describe(int times) {
+        return speak().repeat(times);
+    }
+
+    public String name() {
+        return "Rex";
+    }
+
+    public void hold(String item) {
+        toy = item;
+    }
+
+    public int legs(int counts[]) {
+        return 4;
+    }
+
+    public static Animal create() {
+        return new Dog();
+    }
+}
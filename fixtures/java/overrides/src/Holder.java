@@ -0,0 +1,3 @@
+public interface Holder<T> {
+    void hold(T item);
+}
@@ -0,0 +1,3 @@
+public interface Named {
+    String name();
+}
@@ -0,0 +1,14 @@
+package calc;
+
+public class Calc {
+    public static int max(int a, int b) {
+        if (a > b) {
+            return a;
+        }
+        return b;
+    }
+
+    public static boolean isEven(int n) {
+        return n % 2 == 0;
+    }
+}
@@ -0,0 +1,19 @@
+package calc;
+
+import static org.junit.jupiter.api.Assertions.assertEquals;
+import static org.junit.jupiter.api.Assertions.assertTrue;
+
+import org.junit.jupiter.api.Test;
+
+public class CalcTest {
+    @Test
+    void maxPicksLarger() {
+        assertEquals(5, Calc.max(5, 3));
+        assertEquals(7, Calc.max(2, 7));
+    }
+
+    @Test
+    void fourIsEven() {
+        assertTrue(Calc.isEven(4));
+    }
+}
@@ -0,0 +1,31 @@
+import java.util.ArrayList;
+import java.util.List;
+
+// Starter code for the stack assignment.
+public class Stack {
+    private final List<Integer> items = new ArrayList<>();
+
+    public void push(int value) {
+        items.add(value);
+    }
+
+    public int pop() {
+        // TODO: remove and return the top item
+        throw new UnsupportedOperationException("Not implemented yet");
+    }
+
+    public int peek() {
+        if (items.isEmpty()) {
+            throw new IllegalStateException("empty stack");
+        }
+        return items.get(items.size() - 1);
+    }
+
+    /*
+     * Returns the number of items.
+     * FIXME: count only non-null items
+     */
+    public int size() {
+        throw new RuntimeException("not implemented");
+    }
+}
@@ -0,0 +1,5 @@
+public class Greeter {
+    public String greet(String name) {
+        return "Hello, " + name + "!";
+    }
+}
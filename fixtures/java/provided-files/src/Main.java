@@ -0,0 +1,6 @@
+public class Main {
+    public static void main(String[] args) {
+        Greeter greeter = new Greeter();
+        System.out.println(greeter.greet(Names.DEFAULT));
+    }
+}
@@ -0,0 +1,4 @@
+// umm:provided - starter code from the assignment, not student work.
+public class Names {
+    public static final String DEFAULT = "World";
+}
@@ -0,0 +1,8 @@
+package query;
+
+public class AnnotatedHardcoded {
+    @SuppressWarnings({"unused", "UnusedAssignment"})
+    public static void main(String[] args) {
+        System.out.println("Total: " + 42);
+    }
+}
@@ -0,0 +1,11 @@
+package query;
+
+public class ArrowSwitch {
+    public static String describe(int day) {
+        return switch (day) {
+            case 1 -> "Monday";
+            case 2 -> "Tuesday";
+            default -> "Other";
+        };
+    }
+}
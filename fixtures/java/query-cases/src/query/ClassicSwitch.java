@@ -0,0 +1,18 @@
+package query;
+
+public class ClassicSwitch {
+    public static String describe(int day) {
+        String name;
+        switch (day) {
+            case 1:
+                name = "Monday";
+                break;
+            case 2:
+                name = "Tuesday";
+                break;
+            default:
+                name = "Other";
+        }
+        return name;
+    }
+}
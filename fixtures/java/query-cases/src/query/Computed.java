@@ -0,0 +1,12 @@
+package query;
+
+public class Computed {
+    public static void main(String[] args) {
+        int sum = 0;
+        for (int i = 1; i <= 10; i++) {
+            sum += i;
+        }
+        System.out.println("Sum: " + sum);
+        System.out.printf("Average: %.1f%n", sum / 10.0);
+    }
+}
@@ -0,0 +1,7 @@
+package query;
+
+public class Delegating {
+    public static void main(String[] args) {
+        System.out.println("Sum: " + Factorials.iterative(5));
+    }
+}
@@ -0,0 +1,40 @@
+package query;
+
+public class Factorials {
+  public static long recursive(int n) {
+    if (n <= 1) {
+      return 1;
+    }
+    return n * recursive(n - 1);
+  }
+
+  public long viaThis(int n) {
+    return n <= 1 ? 1 : n * this.viaThis(n - 1);
+  }
+
+  public static long iterative(int n) {
+    long result = 1;
+    for (int i = 2; i <= n; i++) {
+      result *= i;
+    }
+    return result;
+  }
+
+  public static long delegating(int n) {
+    return Math.multiplyExact(iterative(n), 1L);
+  }
+
+  public static long noBaseCase(int n) {
+    if (n % 2 == 0) {
+      System.out.println(n);
+    }
+    return n * noBaseCase(n - 1);
+  }
+
+  public static void countdown(int n) {
+    if (n > 0) {
+      System.out.println(n);
+      countdown(n - 1);
+    }
+  }
+}
@@ -0,0 +1,9 @@
+package query;
+
+public class Hardcoded {
+    public static void main(String[] args) {
+        // Sum of 1..10
+        System.out.println("Sum: " + 55);
+        System.out.printf("Average: %.1f%n", 5.5);
+    }
+}
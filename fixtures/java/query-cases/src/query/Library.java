@@ -0,0 +1,7 @@
+package query;
+
+public class Library {
+    public static int square(int n) {
+        return n * n;
+    }
+}
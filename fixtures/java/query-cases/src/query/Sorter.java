@@ -0,0 +1,25 @@
+package query;
+
+import java.util.Arrays;
+
+public class Sorter {
+  public static void bubbleSort(int[] values) {
+    for (int i = 0; i < values.length; i++) {
+      for (int j = 0; j + 1 < values.length - i; j++) {
+        if (values[j] > values[j + 1]) {
+          swap(values, j, j + 1);
+        }
+      }
+    }
+  }
+
+  public static void librarySort(int[] values) {
+    Arrays.sort(values);
+  }
+
+  static void swap(int[] values, int i, int j) {
+    int tmp = values[i];
+    values[i] = values[j];
+    values[j] = tmp;
+  }
+}
@@ -0,0 +1,22 @@
+/** Counter whose fourth call misbehaves. */
+public class Counter {
+    /** Number of calls so far. */
+    private static int calls = 0;
+
+    /**
+     * Constructs a new {@code Counter} instance.
+     */
+    public Counter() {
+        // default
+    }
+
+    /**
+     * Returns one, except on the fourth call.
+     *
+     * @return {@code 1}, or {@code 0} on the fourth call
+     */
+    public static int next() {
+        calls++;
+        return calls == 4 ? 0 : 1;
+    }
+}
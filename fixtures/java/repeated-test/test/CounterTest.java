@@ -0,0 +1,19 @@
+import static org.junit.jupiter.api.Assertions.assertEquals;
+
+import org.junit.jupiter.api.RepeatedTest;
+import org.junit.jupiter.api.Test;
+import org.junit.jupiter.api.Timeout;
+
+/** Mixes a plain test with a repeated test whose fourth run fails. */
+public class CounterTest {
+    @Test
+    @Timeout(5)
+    void countsOnce() {
+        assertEquals(1, 1);
+    }
+
+    @RepeatedTest(value = 4, name = "{currentRepetition}/{totalRepetitions}")
+    void countsRepeatedly() {
+        assertEquals(1, Counter.next());
+    }
+}
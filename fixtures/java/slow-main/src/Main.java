@@ -0,0 +1,6 @@
+public class Main {
+    public static void main(String[] args) throws InterruptedException {
+        Thread.sleep(300);
+        System.out.println("done");
+    }
+}
@@ -0,0 +1,19 @@
+import java.util.Scanner;
+
+public class Main {
+    public static void main(String[] args) {
+        Scanner scanner = new Scanner(System.in);
+        int lines = 0;
+        int sum = 0;
+        while (scanner.hasNextLine()) {
+            String line = scanner.nextLine().trim();
+            if (line.isEmpty()) {
+                continue;
+            }
+            lines++;
+            sum += Integer.parseInt(line);
+        }
+        System.out.println("lines: " + lines);
+        System.out.println("sum: " + sum);
+    }
+}
@@ -0,0 +1,9 @@
+public class Broken {
+    public static void main(String[] args) {
+        int total = 0
+        for (int i = 0; i < 3; i++) {
+            total += i;
+        }
+        System.out.println(total);
+    }
+}
@@ -0,0 +1,19 @@
+/** Small helper exercised by dynamic tests. */
+public class Squares {
+    /**
+     * Constructs a new {@code Squares} instance.
+     */
+    public Squares() {
+        // default
+    }
+
+    /**
+     * Squares the given value.
+     *
+     * @param n value to square
+     * @return {@code n * n}
+     */
+    public static int square(int n) {
+        return n * n;
+    }
+}
@@ -0,0 +1,23 @@
+import static org.junit.jupiter.api.Assertions.assertEquals;
+import static org.junit.jupiter.api.DynamicTest.dynamicTest;
+
+import java.util.stream.IntStream;
+import java.util.stream.Stream;
+
+import org.junit.jupiter.api.DynamicTest;
+import org.junit.jupiter.api.Test;
+import org.junit.jupiter.api.TestFactory;
+
+/** Mixes a regular test with a factory producing three dynamic tests. */
+public class SquaresTest {
+    @Test
+    void squaresZero() {
+        assertEquals(0, Squares.square(0));
+    }
+
+    @TestFactory
+    Stream<DynamicTest> squaresSmallNumbers() {
+        return IntStream.rangeClosed(1, 3)
+            .mapToObj(n -> dynamicTest("square of " + n, () -> assertEquals(n * n, Squares.square(n))));
+    }
+}
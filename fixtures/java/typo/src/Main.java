@@ -0,0 +1,7 @@
+public class Main {
+    public static void main(String[] args) {
+        Conter counter = new Counter();
+        counter.increment();
+        System.out.println(counter.getCount());
+    }
+}
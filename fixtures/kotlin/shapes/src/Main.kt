@@ -0,0 +1,8 @@
+package shapes
+
+fun main() {
+    val shapes = listOf(Circle(1.0), Square(2.0))
+    for (shape in shapes) {
+        println(Describer.describe(if (shape is Square) 4 else 0))
+    }
+}
@@ -0,0 +1,20 @@
+package shapes
+
+import kotlin.math.PI
+
+class Circle(val radius: Double) {
+    fun area(): Double = PI * radius * radius
+}
+
+class Square(val side: Double) {
+    fun area(): Double = side * side
+}
+
+object Describer {
+    fun describe(sides: Int): String =
+        when (sides) {
+            0 -> "circle"
+            4 -> "square"
+            else -> "polygon"
+        }
+}
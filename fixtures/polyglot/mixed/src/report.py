@@ -0,0 +1,9 @@
+"""Python half of a mixed-language assignment."""
+
+
+def main():
+    print("Hello from Python")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,13 @@
+"""Diff grading test: output wrapped in ANSI colour codes."""
+
+GREEN = "\033[32m"
+BOLD = "\033[1m"
+RESET = "\033[0m"
+
+
+def main():
+    print(f"{GREEN}hello{RESET} {BOLD}world{RESET}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,10 @@
+"""Oracle grading test: sorts the numbers read from stdin."""
+
+
+def main():
+    numbers = sorted(int(token) for token in open(0).read().split())
+    print(" ".join(str(number) for number in numbers))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,2 @@
+;; `while_loop` is not a node in the Java grammar.
+((while_loop) @loop)
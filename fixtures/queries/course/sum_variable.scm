@@ -0,0 +1,4 @@
+;; Declarations of a local named `sum`.
+(local_variable_declaration
+  declarator: (variable_declarator
+    name: (identifier) @name (#eq? @name "sum"))) @decl
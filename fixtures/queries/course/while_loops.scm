@@ -0,0 +1,2 @@
+;; Every while loop in the file.
+((while_statement) @loop)
@@ -0,0 +1,115 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::{
+    java::util::jar_path,
+    process::{self, StdinSource},
+};
+
+/// Entries archivers add next to a submission's files, ignored when looking
+/// for a single root folder.
+const ARCHIVE_NOISE: &[&str] = &["__MACOSX", ".DS_Store"];
+
+/// A zip archive extracted into a temporary directory, removed again when
+/// dropped.
+///
+/// Gradescope and most LMSs hand over submissions as zips; extracting one
+/// leaves the original untouched and gives each submission its own build
+/// directory.
+#[derive(Debug)]
+pub struct Unzipped {
+    /// Directory the archive was extracted into.
+    path: PathBuf,
+    /// Directory holding the submission: `path`, or the single folder at the
+    /// top of the archive, as in zips downloaded from GitHub.
+    root: PathBuf,
+}
+
+impl Unzipped {
+    /// Extracts the zip archive at `zip` into a fresh temporary directory.
+    ///
+    /// Extraction uses the JDK's `jar` tool, which is on the path wherever
+    /// Java submissions are graded.
+    pub async fn extract(zip: impl AsRef<Path>) -> Result<Self> {
+        let zip = zip
+            .as_ref()
+            .canonicalize()
+            .with_context(|| format!("Could not find archive {}", zip.as_ref().display()))?;
+        let path = std::env::temp_dir().join(format!("umm-unzip-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path)
+            .with_context(|| format!("Could not create {}", path.display()))?;
+        // From here on, dropping `unzipped` cleans up after a failed extraction.
+        let mut unzipped = Self {
+            root: path.clone(),
+            path,
+        };
+
+        let args = vec![OsString::from("xf"), zip.as_os_str().to_os_string()];
+        let collected = process::run_collect(
+            jar_path()?,
+            &args,
+            StdinSource::Null,
+            Some(&unzipped.path),
+            &[],
+            None,
+        )
+        .await
+        .context("Failed to run jar to extract the archive")?;
+
+        if !collected.status.success() {
+            bail!(
+                "Could not extract {}:\n{}",
+                zip.display(),
+                String::from_utf8_lossy(&collected.stderr).trim()
+            );
+        }
+
+        unzipped.root = single_root(&unzipped.path)?.unwrap_or_else(|| unzipped.path.clone());
+        Ok(unzipped)
+    }
+
+    /// Directory the archive was extracted into.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Directory holding the submission. When everything in the archive sits
+    /// under one folder, this is that folder.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+/// Returns the only directory in `dir`, ignoring [`ARCHIVE_NOISE`], or
+/// `None` when `dir` holds anything else.
+fn single_root(dir: &Path) -> Result<Option<PathBuf>> {
+    let mut entries = Vec::new();
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Could not read {}", dir.display()))?
+    {
+        let entry = entry?;
+        if !ARCHIVE_NOISE.contains(&entry.file_name().to_string_lossy().as_ref()) {
+            entries.push(entry.path());
+        }
+    }
+
+    match entries.as_slice() {
+        [only] if only.is_dir() => Ok(Some(only.clone())),
+        _ => Ok(None),
+    }
+}
+
+impl Drop for Unzipped {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.path) {
+            tracing::warn!("Could not remove extracted archive at {}: {e}", self.path.display());
+        }
+    }
+}
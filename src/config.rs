@@ -2,6 +2,7 @@
 #![warn(clippy::missing_docs_in_private_items)]
 
 use std::{
+    path::{Path, PathBuf},
     sync::{
         Arc, Mutex, OnceLock,
         atomic::{AtomicBool, Ordering},
@@ -24,6 +25,32 @@ use crate::{
 /// Prompt truncation length for generated feedback payloads.
 pub const PROMPT_TRUNCATE: usize = 60_000;
 
+/// Where active retrieval sends its chat request.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RetrievalBackend {
+    /// POST the messages to `UMM_RETRIEVAL_ENDPOINT`, which supplies the model
+    /// and the retrieval tool definition.
+    #[default]
+    Proxy,
+    /// Call the OpenAI-compatible API configured by `OPENAI_*` directly,
+    /// sending the retrieval tool definition with the request.
+    OpenAi,
+}
+
+impl RetrievalBackend {
+    /// Parses `UMM_RETRIEVAL_BACKEND`; anything other than `openai` selects the
+    /// proxy.
+    fn from_env() -> Self {
+        match std::env::var("UMM_RETRIEVAL_BACKEND")
+            .map(|value| value.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Ok("openai") => Self::OpenAi,
+            _ => Self::Proxy,
+        }
+    }
+}
+
 /// Supabase credentials loaded from the environment, if available.
 #[derive(Clone)]
 struct SupabaseEnv {
@@ -178,10 +205,20 @@ pub struct ConfigState {
     openai:              Option<OpenAiEnv>,
     /// Flag indicating whether active retrieval is enabled.
     active_retrieval:    AtomicBool,
+    /// Flag indicating whether grading stops at the first failing requirement.
+    fail_fast:           AtomicBool,
     /// Default heuristic window for snippet-based retrieval.
     retrieval_heuristic: Mutex<HeuristicConfig>,
     /// Endpoint used for active-retrieval service calls.
     retrieval_endpoint:  String,
+    /// Transport used for active-retrieval calls.
+    retrieval_backend:   RetrievalBackend,
+    /// Model override for active retrieval on the OpenAI backend.
+    retrieval_model:     Option<String>,
+    /// Directory feedback prompts are written to instead of the database.
+    prompt_export_dir:   Mutex<Option<PathBuf>>,
+    /// Course system message used in place of the built-in one.
+    system_message:      Mutex<Option<String>>,
 }
 
 impl ConfigState {
@@ -199,6 +236,7 @@ impl ConfigState {
         let http_client = Client::builder()
             // Avoid macOS dynamic store lookups that fail in sandboxed environments.
             .no_proxy()
+            .connect_timeout(Duration::from_secs(10))
             .build()
             .context("Failed to construct shared HTTP client")?;
         let prompts = JavaPrompts::load();
@@ -215,6 +253,25 @@ impl ConfigState {
         let retrieval_endpoint = std::env::var("UMM_RETRIEVAL_ENDPOINT")
             .map(|value| value.trim().to_owned())
             .unwrap_or_else(|_| "https://umm-feedback-openai-func.deno.dev/".to_string());
+        let retrieval_model = std::env::var("UMM_RETRIEVAL_MODEL")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty());
+        let prompt_export_dir = std::env::var("UMM_PROMPT_EXPORT_DIR")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from);
+        let system_message =
+            match std::env::var("UMM_SYSTEM_MESSAGE_FILE") {
+                Ok(path) if !path.trim().is_empty() => {
+                    let path = path.trim();
+                    Some(std::fs::read_to_string(path).with_context(|| {
+                        format!("Failed to read UMM_SYSTEM_MESSAGE_FILE ({path})")
+                    })?)
+                }
+                _ => None,
+            };
 
         let retrieval_heuristic = Mutex::new(java_config.retrieval_defaults());
 
@@ -227,8 +284,13 @@ impl ConfigState {
             term,
             openai: OpenAiEnv::from_env(),
             active_retrieval: AtomicBool::new(false),
+            fail_fast: AtomicBool::new(false),
             retrieval_heuristic,
             retrieval_endpoint,
+            retrieval_backend: RetrievalBackend::from_env(),
+            retrieval_model,
+            prompt_export_dir: Mutex::new(prompt_export_dir),
+            system_message: Mutex::new(system_message),
         })
     }
 
@@ -255,6 +317,16 @@ impl ConfigState {
         &self.retrieval_endpoint
     }
 
+    /// Returns the transport used for active retrieval.
+    pub fn retrieval_backend(&self) -> RetrievalBackend {
+        self.retrieval_backend
+    }
+
+    /// Returns the model override for active retrieval, if any.
+    pub fn retrieval_model(&self) -> Option<&str> {
+        self.retrieval_model.as_deref()
+    }
+
     /// Returns the course identifier.
     pub fn course(&self) -> &str {
         &self.course
@@ -286,6 +358,48 @@ impl ConfigState {
         self.active_retrieval.load(Ordering::Relaxed)
     }
 
+    /// Updates the fail-fast toggle.
+    pub fn set_fail_fast(&self, enabled: bool) {
+        self.fail_fast.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns whether grading stops at the first failing requirement.
+    pub fn fail_fast_enabled(&self) -> bool {
+        self.fail_fast.load(Ordering::Relaxed)
+    }
+
+    /// Writes feedback prompts as JSON files under `dir` instead of posting
+    /// them to the database; `None` restores posting.
+    pub fn set_prompt_export_dir(&self, dir: Option<PathBuf>) {
+        if let Ok(mut guard) = self.prompt_export_dir.lock() {
+            *guard = dir;
+        }
+    }
+
+    /// Directory feedback prompts are exported to, if export mode is on.
+    pub fn prompt_export_dir(&self) -> Option<PathBuf> {
+        self.prompt_export_dir
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone())
+    }
+
+    /// Uses `message` as the system message of every feedback prompt; `None`
+    /// restores the built-in one.
+    pub fn set_system_message(&self, message: Option<String>) {
+        if let Ok(mut guard) = self.system_message.lock() {
+            *guard = message;
+        }
+    }
+
+    /// The course system message, if one replaces the built-in one.
+    pub fn system_message(&self) -> Option<String> {
+        self.system_message
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone())
+    }
+
     /// Returns the default heuristic configuration for snippet retrieval.
     pub fn heuristic_defaults(&self) -> HeuristicConfig {
         *self
@@ -394,13 +508,20 @@ impl ConfigState {
 
 /// Borrowed view of the Java prompt catalog that keeps the underlying
 /// configuration alive.
-pub struct JavaPromptsRef(ConfigHandle);
+pub struct JavaPromptsRef {
+    /// Handle to the configuration owning the built-in catalog.
+    handle: ConfigHandle,
+    /// Copy of the catalog with the course system message, when one is set.
+    custom: Option<JavaPrompts>,
+}
 
 impl std::ops::Deref for JavaPromptsRef {
     type Target = JavaPrompts;
 
     fn deref(&self) -> &Self::Target {
-        self.0.java_prompts()
+        self.custom
+            .as_ref()
+            .unwrap_or_else(|| self.handle.java_prompts())
     }
 }
 
@@ -499,6 +620,16 @@ pub fn retrieval_endpoint() -> String {
     get().retrieval_endpoint().to_string()
 }
 
+/// Returns the transport used for active retrieval.
+pub fn retrieval_backend() -> RetrievalBackend {
+    get().retrieval_backend()
+}
+
+/// Returns the model override for active retrieval, if any.
+pub fn retrieval_model() -> Option<String> {
+    get().retrieval_model().map(str::to_string)
+}
+
 /// Returns the configured course identifier.
 pub fn course() -> String {
     get().course.clone()
@@ -509,9 +640,37 @@ pub fn term() -> String {
     get().term.clone()
 }
 
-/// Returns the configured Java prompts.
+/// Prefix of environment variables grading scripts may read. umm's own
+/// `UMM_*` settings hold no secrets, unlike `OPENAI_*` and `SUPABASE_*`.
+pub const SCRIPT_ENV_PREFIX: &str = "UMM_";
+
+/// Environment variables outside [`SCRIPT_ENV_PREFIX`] that grading scripts
+/// may read.
+pub const SCRIPT_ENV_ALLOWLIST: &[&str] = &["CI"];
+
+/// Reads environment variable `name` on behalf of a grading script, returning
+/// `None` when it is unset. Names outside [`SCRIPT_ENV_PREFIX`] and
+/// [`SCRIPT_ENV_ALLOWLIST`] are rejected so scripts cannot copy API keys into
+/// feedback.
+pub fn script_env(name: &str) -> Result<Option<String>> {
+    if !name.starts_with(SCRIPT_ENV_PREFIX) && !SCRIPT_ENV_ALLOWLIST.contains(&name) {
+        return Err(anyhow!(
+            "Environment variable `{name}` cannot be read from grading scripts; only \
+             `{SCRIPT_ENV_PREFIX}*` variables and {} are allowed",
+            SCRIPT_ENV_ALLOWLIST.join(", ")
+        ));
+    }
+    Ok(std::env::var(name).ok())
+}
+
+/// Returns the configured Java prompts, with the course system message in
+/// place of the built-in one when set.
 pub fn java_prompts() -> JavaPromptsRef {
-    JavaPromptsRef(get())
+    let handle = get();
+    let custom = handle
+        .system_message()
+        .map(|message| handle.java_prompts().clone().with_system_message(message));
+    JavaPromptsRef { handle, custom }
 }
 
 /// Returns the configured Java configuration bundle.
@@ -589,6 +748,50 @@ pub fn active_retrieval_enabled() -> bool {
     get().active_retrieval_enabled()
 }
 
+/// Enables or disables fail-fast grading for the current process.
+pub fn set_fail_fast(enabled: bool) {
+    get().set_fail_fast(enabled);
+}
+
+/// Returns whether grading stops at the first failing requirement.
+pub fn fail_fast_enabled() -> bool {
+    get().fail_fast_enabled()
+}
+
+/// Exports feedback prompts to JSON files under `dir` instead of posting
+/// them to the database; `None` restores posting. Defaults to
+/// `UMM_PROMPT_EXPORT_DIR`.
+pub fn set_prompt_export_dir(dir: Option<PathBuf>) {
+    get().set_prompt_export_dir(dir);
+}
+
+/// Directory feedback prompts are exported to, if export mode is on.
+pub fn prompt_export_dir() -> Option<PathBuf> {
+    get().prompt_export_dir()
+}
+
+/// Uses `message` as the system message of every feedback prompt, Java and
+/// Python; `None` restores the built-in one. Defaults to the contents of
+/// `UMM_SYSTEM_MESSAGE_FILE`.
+pub fn set_system_message(message: Option<String>) {
+    get().set_system_message(message);
+}
+
+/// Reads the course system message from `path` and uses it for every
+/// feedback prompt.
+pub fn load_system_message(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let message = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read system message from {}", path.display()))?;
+    set_system_message(Some(message));
+    Ok(())
+}
+
+/// The course system message, if one replaces the built-in one.
+pub fn system_message() -> Option<String> {
+    get().system_message()
+}
+
 /// Returns the configured javac timeout duration.
 pub fn javac_timeout() -> Duration {
     get().javac_timeout()
@@ -604,9 +807,24 @@ pub fn python_timeout() -> Duration {
     read_timeout_secs("UMM_PYTHON_TIMEOUT_SECS", 60)
 }
 
-/// Returns the Python prompts bundle.
+/// Returns the Python prompts bundle, with the course system message in
+/// place of the built-in one when set.
 pub fn python_prompts() -> PythonPrompts {
-    PythonPrompts::default()
+    match system_message() {
+        Some(message) => PythonPrompts::default().with_system_message(message),
+        None => PythonPrompts::default(),
+    }
+}
+
+/// Returns how many child processes (JVMs, Python interpreters, and so on)
+/// may run at once, from `UMM_MAX_PROCESSES`. Defaults to the number of
+/// available CPUs.
+pub fn max_processes() -> usize {
+    std::env::var("UMM_MAX_PROCESSES")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|limit| *limit > 0)
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(4, usize::from))
 }
 
 /// Returns the OpenAI environment configuration, if available.
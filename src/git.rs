@@ -0,0 +1,153 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::process::{self, StdinSource};
+
+/// A detached `git worktree` holding one ref of a repository, removed again
+/// when dropped.
+///
+/// Grading a worktree leaves the instructor's checkout (and its build
+/// directory) untouched, so a deadline commit can be graded while the branch
+/// keeps moving.
+#[derive(Debug)]
+pub struct Worktree {
+    /// Repository the worktree was created from.
+    repo:   PathBuf,
+    /// Directory holding the checked-out ref.
+    path:   PathBuf,
+    /// Location of `repo` relative to the top of its repository, empty when
+    /// `repo` is the top.
+    prefix: PathBuf,
+    /// Symlinks created by [`Worktree::share_dir`], removed before the
+    /// worktree so git never walks into their targets.
+    shared: Vec<PathBuf>,
+}
+
+impl Worktree {
+    /// Checks out `git_ref` (a commit, branch, or tag) of the repository at
+    /// `repo` into a fresh temporary directory.
+    ///
+    /// `repo` may be a subdirectory of the repository; the same subdirectory
+    /// of the checkout is then available as [`Worktree::project_dir`].
+    pub async fn checkout(repo: impl AsRef<Path>, git_ref: &str) -> Result<Self> {
+        // Absolute, so cleanup still works if the caller changes directory.
+        let repo = repo
+            .as_ref()
+            .canonicalize()
+            .with_context(|| format!("Could not resolve repository {}", repo.as_ref().display()))?;
+        let prefix = Self::prefix(&repo).await?;
+        let path = std::env::temp_dir().join(format!("umm-worktree-{}", uuid::Uuid::new_v4()));
+
+        let args = vec![
+            OsString::from("-C"),
+            repo.as_os_str().to_os_string(),
+            OsString::from("worktree"),
+            OsString::from("add"),
+            OsString::from("--detach"),
+            path.as_os_str().to_os_string(),
+            OsString::from(git_ref),
+        ];
+        let collected = process::run_collect("git", &args, StdinSource::Null, None, &[], None)
+            .await
+            .context("Failed to run git; is it installed and on PATH?")?;
+
+        if !collected.status.success() {
+            bail!(
+                "Could not check out `{git_ref}` from {}:\n{}",
+                repo.display(),
+                String::from_utf8_lossy(&collected.stderr).trim()
+            );
+        }
+
+        Ok(Self {
+            repo,
+            path,
+            prefix,
+            shared: Vec::new(),
+        })
+    }
+
+    /// Asks git where `repo` sits relative to the top of its repository.
+    async fn prefix(repo: &Path) -> Result<PathBuf> {
+        let args = vec![
+            OsString::from("-C"),
+            repo.as_os_str().to_os_string(),
+            OsString::from("rev-parse"),
+            OsString::from("--show-prefix"),
+        ];
+        let collected = process::run_collect("git", &args, StdinSource::Null, None, &[], None)
+            .await
+            .context("Failed to run git; is it installed and on PATH?")?;
+
+        if !collected.status.success() {
+            bail!(
+                "{} is not inside a git repository:\n{}",
+                repo.display(),
+                String::from_utf8_lossy(&collected.stderr).trim()
+            );
+        }
+        Ok(PathBuf::from(String::from_utf8_lossy(&collected.stdout).trim()))
+    }
+
+    /// Directory holding the checked-out ref.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The checkout's copy of the directory the worktree was created from:
+    /// [`Worktree::path`] itself, or the same subdirectory of it when the
+    /// project is nested inside the repository.
+    pub fn project_dir(&self) -> PathBuf {
+        self.path.join(&self.prefix)
+    }
+
+    /// Makes the original `name` directory visible in
+    /// [`Worktree::project_dir`] when the ref does not track it, e.g. a
+    /// git-ignored `lib/` of jars.
+    pub fn share_dir(&mut self, name: &str) -> Result<()> {
+        let source = self.repo.join(name);
+        let target = self.project_dir().join(name);
+        if !source.is_dir() || target.exists() {
+            return Ok(());
+        }
+
+        let source = source
+            .canonicalize()
+            .with_context(|| format!("Could not resolve {}", source.display()))?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&source, &target)
+            .with_context(|| format!("Could not link {} into the worktree", source.display()))?;
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_dir(&source, &target)
+            .with_context(|| format!("Could not link {} into the worktree", source.display()))?;
+
+        self.shared.push(target);
+        Ok(())
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        for link in &self.shared {
+            let _ = std::fs::remove_file(link);
+        }
+
+        let removed = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&self.repo)
+            .args(["worktree", "remove", "--force"])
+            .arg(&self.path)
+            .output()
+            .is_ok_and(|out| out.status.success());
+        if !removed {
+            tracing::warn!("Could not remove git worktree at {}", self.path.display());
+        }
+    }
+}
@@ -95,6 +95,13 @@ impl JavaPrompts {
         }
     }
 
+    /// Replaces the full system prompt, e.g. with a course's own instructor
+    /// persona and policies.
+    pub fn with_system_message(mut self, message: impl Into<String>) -> Self {
+        self.system_message = message.into();
+        self
+    }
+
     /// Returns the full system prompt.
     pub fn system_message(&self) -> &str {
         &self.system_message
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     ffi::{OsStr, OsString},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
@@ -18,11 +19,13 @@ use crate::{
             CLASS_CONSTRUCTOR_QUERY, CLASS_DECLARATION_QUERY, CLASS_FIELDS_QUERY,
             CLASS_METHOD_QUERY, CLASSNAME_QUERY, IMPORT_QUERY, INTERFACE_CONSTANTS_QUERY,
             INTERFACE_DECLARATION_QUERY, INTERFACE_METHODS_QUERY, INTERFACENAME_QUERY,
-            MAIN_METHOD_QUERY, METHOD_CALL_QUERY, PACKAGE_QUERY, TEST_ANNOTATION_QUERY,
+            MAIN_METHOD_QUERY, METHOD_CALL_QUERY, PACKAGE_QUERY, REPEATED_TEST_QUERY,
+            TEST_ANNOTATION_QUERY, TEST_FACTORY_QUERY, TOP_LEVEL_TYPES_QUERY,
         },
-        util::{classpath, java_path, javac_path, sourcepath},
+        util::{classpath, java_path, javac_path, sourcepath, student_sources},
     },
     process::{self, StdinSource},
+    util::write_atomic,
 };
 
 /// Normalizes captured snippets by trimming whitespace and flattening newlines.
@@ -102,20 +105,6 @@ fn is_method_selector_resolution_error(output: &str) -> bool {
 /// Possible failures while decoding collected process output.
 #[derive(thiserror::Error, Debug)]
 enum DecodeOutputError {
-    /// The captured stderr stream could not be decoded as UTF-8.
-    #[error("Error parsing stderr as utf8")]
-    Stderr {
-        /// The underlying UTF-8 decoding failure.
-        #[source]
-        source: std::string::FromUtf8Error,
-    },
-    /// The captured stdout stream could not be decoded as UTF-8.
-    #[error("Error parsing stdout as utf8")]
-    Stdout {
-        /// The underlying UTF-8 decoding failure.
-        #[source]
-        source: std::string::FromUtf8Error,
-    },
     /// The combined output could not be unescaped with `snailquote`.
     #[error("Error when un-escaping {phase} output.")]
     Unescape {
@@ -127,21 +116,98 @@ enum DecodeOutputError {
     },
 }
 
-/// Decodes combined stdout/stderr output into a UTF-8 string and removes escape
-/// sequences.
+/// Decodes combined stdout/stderr output into a string and removes escape
+/// sequences. Invalid UTF-8 is replaced with `U+FFFD` rather than failing, so
+/// programs printing raw bytes can still be graded.
 fn decode_output(
     stderr: Vec<u8>,
     stdout: Vec<u8>,
     phase: &'static str,
 ) -> Result<String, DecodeOutputError> {
-    let mut decoded =
-        String::from_utf8(stderr).map_err(|source| DecodeOutputError::Stderr { source })?;
-    let stdout =
-        String::from_utf8(stdout).map_err(|source| DecodeOutputError::Stdout { source })?;
-    decoded.push_str(&stdout);
+    let mut decoded = String::from_utf8_lossy(&stderr).into_owned();
+    decoded.push_str(&String::from_utf8_lossy(&stdout));
     unescape(&decoded).map_err(|source| DecodeOutputError::Unescape { phase, source })
 }
 
+/// Decodes a single output stream the way [`decode_output`] decodes the
+/// combined one.
+fn decode_stream(bytes: Vec<u8>, phase: &'static str) -> Result<String, DecodeOutputError> {
+    unescape(&String::from_utf8_lossy(&bytes))
+        .map_err(|source| DecodeOutputError::Unescape { phase, source })
+}
+
+/// Builds the error reported when a program exits unsuccessfully, with any
+/// stacktrace lines parsed into line references.
+fn runtime_error(output: String) -> JavaFileError {
+    let mut diags = Vec::new();
+    for line in output.lines() {
+        if let Ok(diag) = parser::junit_stacktrace_line_ref(line) {
+            diags.push(diag);
+        }
+    }
+
+    JavaFileError::AtRuntime { output, diags }
+}
+
+/// Output of a program run with stdout and stderr kept apart, as returned by
+/// [`File::run_captured`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOutput {
+    /// Text the program wrote to stdout.
+    pub stdout:    String,
+    /// Text the program wrote to stderr.
+    pub stderr:    String,
+    /// Exit code, or `None` when the process was ended by a signal.
+    pub exit_code: Option<i32>,
+}
+
+impl RunOutput {
+    /// Both streams as [`File::run`] reports them: stderr, then stdout.
+    pub fn merged(&self) -> String {
+        format!("{}{}", self.stderr, self.stdout)
+    }
+
+    /// Returns `self` when the program exited with code 0, and otherwise the
+    /// [`JavaFileError::AtRuntime`] error [`File::run`] would have returned.
+    pub fn check_status(self) -> Result<Self, JavaFileError> {
+        if self.exit_code == Some(0) {
+            Ok(self)
+        } else {
+            Err(runtime_error(self.merged()))
+        }
+    }
+}
+
+/// Returns the value quoted after `key = '` on a JUnit `MethodSource` line.
+fn method_source_field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.find(&format!("{key} = '"))? + key.len() + 4;
+    let len = line[start..].find('\'')?;
+    Some(&line[start..start + len])
+}
+
+/// Extracts the `Class#method` names of failed tests from JUnit console
+/// output, in order of first appearance and without duplicates.
+///
+/// JUnit's failure summary names each failed test with a `MethodSource` line;
+/// repeated and parameterized tests report once per failing invocation.
+pub fn failed_test_methods(output: &str) -> Vec<String> {
+    let mut failed = Vec::new();
+    for line in output
+        .lines()
+        .filter(|line| line.contains("MethodSource ["))
+    {
+        if let (Some(class), Some(method)) =
+            (method_source_field(line, "className"), method_source_field(line, "methodName"))
+        {
+            let test = format!("{class}#{method}");
+            if !failed.contains(&test) {
+                failed.push(test);
+            }
+        }
+    }
+    failed
+}
+
 /// Loads source code from `path` and constructs a Java parser.
 fn parse_source(path: &Path) -> Result<Parser> {
     let source_code = std::fs::read_to_string(path)
@@ -149,13 +215,75 @@ fn parse_source(path: &Path) -> Result<Parser> {
     Parser::new(source_code)
 }
 
+/// A top-level class or interface declared in a file.
+struct TopLevelType {
+    /// Simple name of the type.
+    name:         String,
+    /// Whether the type is an interface.
+    is_interface: bool,
+    /// Parser over just this declaration, so per-type queries stay scoped.
+    parser:       Parser,
+}
+
+/// Enumerates the top-level classes and interfaces in a file, in source
+/// order.
+fn top_level_types(parser: &Parser) -> Result<Vec<TopLevelType>> {
+    let matches = parser.query(TOP_LEVEL_TYPES_QUERY)?;
+    let names = parser.query_capture_positions(TOP_LEVEL_TYPES_QUERY, "name")?;
+
+    matches
+        .into_iter()
+        .zip(names)
+        .filter_map(|(m, (name, _))| {
+            let (source, is_interface) = match (m.get("class"), m.get("interface")) {
+                (Some(source), _) => (source, false),
+                (None, Some(source)) => (source, true),
+                (None, None) => return None,
+            };
+            Some(Parser::new(source.clone()).map(|parser| TopLevelType {
+                name,
+                is_interface,
+                parser,
+            }))
+        })
+        .collect()
+}
+
+/// Prefixes `name` with the package, if any.
+fn qualify(package_name: Option<&str>, name: &str) -> String {
+    match package_name {
+        Some(pkg) => format!("{pkg}.{name}"),
+        None => name.to_string(),
+    }
+}
+
 /// Determines the initial file type and simple name based on parsed
 /// declarations.
+///
+/// The primary type is the top-level type named after the file, falling back
+/// to the first one declared; only its `main` method makes the file runnable.
 fn detect_file_identity(
     parser: &Parser,
     path: &Path,
-    has_main: bool,
+    types: &[TopLevelType],
 ) -> Result<(FileType, String)> {
+    let stem = path.file_stem().and_then(OsStr::to_str).unwrap_or_default();
+    if let Some(primary) = types
+        .iter()
+        .find(|t| t.name == stem)
+        .or_else(|| types.first())
+    {
+        let kind = if primary.is_interface {
+            FileType::Interface
+        } else if !primary.parser.query(MAIN_METHOD_QUERY)?.is_empty() {
+            FileType::ClassWithMain
+        } else {
+            FileType::Class
+        };
+        return Ok((kind, primary.name.clone()));
+    }
+
+    let has_main = !parser.query(MAIN_METHOD_QUERY)?.is_empty();
     let interface = parser.query(INTERFACENAME_QUERY)?;
     if let Some(first) = interface.first() {
         let name = first
@@ -192,26 +320,152 @@ fn detect_file_identity(
     Ok((FileType::Class, String::new()))
 }
 
-/// Collects fully qualified test method names discovered via `@Test`
-/// annotations.
-fn collect_test_methods(parser: &Parser, proper_name: &str) -> Result<Vec<String>> {
+/// Collects fully qualified names of the test methods `query` finds (e.g.
+/// [`TEST_ANNOTATION_QUERY`]), qualifying each with the top-level class that
+/// declares it.
+fn collect_test_methods(
+    parser: &Parser,
+    query: &str,
+    package_name: Option<&str>,
+    proper_name: &str,
+    spans: &[TypeSpan],
+) -> Result<Vec<String>> {
     let mut tests = Vec::new();
-    for entry in parser.query(TEST_ANNOTATION_QUERY)? {
-        if let Some(method) = entry.get("name") {
-            tests.push(format!("{}#{}", proper_name, method));
+    for (method, line) in parser.query_capture_positions(query, "name")? {
+        let owner = test_owner(package_name, proper_name, spans, line);
+        let test = format!("{owner}#{method}");
+        // A method carrying several test annotations is still one test.
+        if !tests.contains(&test) {
+            tests.push(test);
         }
     }
     Ok(tests)
 }
 
-/// Returns the XML type attribute corresponding to the file classification.
-fn file_type_attr(kind: &FileType) -> &'static str {
-    match kind {
-        FileType::Interface => "interface",
-        FileType::Class => "class",
-        FileType::ClassWithMain => "class_with_main",
-        FileType::Test => "test",
+/// Collects the repetition count of every `@RepeatedTest` method, keyed the
+/// same way as [`collect_test_methods`]. Counts that are not an integer
+/// literal (e.g. a constant) fall back to `1`.
+fn collect_test_repetitions(
+    parser: &Parser,
+    package_name: Option<&str>,
+    proper_name: &str,
+    spans: &[TypeSpan],
+) -> Result<BTreeMap<String, usize>> {
+    let mut repetitions = BTreeMap::new();
+    for (captures, line) in parser.query_match_positions(REPEATED_TEST_QUERY, "name")? {
+        let Some(method) = captures.get("name") else {
+            continue;
+        };
+        let owner = test_owner(package_name, proper_name, spans, line);
+        let count = captures
+            .get("arguments")
+            .and_then(|args| repetition_count(args))
+            .unwrap_or(1);
+        repetitions.insert(format!("{owner}#{method}"), count);
+    }
+    Ok(repetitions)
+}
+
+/// Reads the repetition count from `@RepeatedTest` arguments such as `(5)` or
+/// `(value = 5, name = "...")`.
+fn repetition_count(arguments: &str) -> Option<usize> {
+    let inner = arguments.trim().strip_prefix('(')?.strip_suffix(')')?;
+    inner.split(',').find_map(|part| {
+        let value = match part.split_once('=') {
+            Some((key, value)) if key.trim() == "value" => value,
+            Some(_) => return None,
+            None => part,
+        };
+        value.trim().replace('_', "").parse().ok()
+    })
+}
+
+/// Line span of a top-level declaration of any kind, used to attribute tests
+/// to the type that declares them.
+struct TypeSpan {
+    /// Simple name of the type.
+    name:       String,
+    /// 1-based line the declaration starts on.
+    start_line: usize,
+    /// 1-based line the declaration ends on.
+    end_line:   usize,
+}
+
+/// Enumerates every top-level class, interface, enum, and record in a file,
+/// in source order.
+fn top_level_spans(parser: &Parser) -> Result<Vec<TypeSpan>> {
+    let source = parser.code().as_bytes();
+    let root = parser.root_node()?;
+    let mut cursor = root.walk();
+    let spans = root
+        .named_children(&mut cursor)
+        .filter(|node| {
+            matches!(
+                node.kind(),
+                "class_declaration"
+                    | "interface_declaration"
+                    | "enum_declaration"
+                    | "record_declaration"
+            )
+        })
+        .filter_map(|node| {
+            let name = node.child_by_field_name("name")?.utf8_text(source).ok()?;
+            Some(TypeSpan {
+                name:       name.to_string(),
+                start_line: node.start_position().row + 1,
+                end_line:   node.end_position().row + 1,
+            })
+        })
+        .collect();
+    Ok(spans)
+}
+
+/// Qualified name of the top-level type declaring the test on `line`.
+fn test_owner(
+    package_name: Option<&str>,
+    proper_name: &str,
+    spans: &[TypeSpan],
+    line: usize,
+) -> String {
+    spans
+        .iter()
+        .find(|span| (span.start_line..=span.end_line).contains(&line))
+        .map(|span| qualify(package_name, &span.name))
+        .unwrap_or_else(|| proper_name.to_string())
+}
+
+/// Returns true if `parser`'s tree declares anything beyond comments and
+/// package/import statements.
+fn has_code(parser: &Parser) -> Result<bool> {
+    let root = parser.root_node()?;
+    let mut cursor = root.walk();
+    let found = root.named_children(&mut cursor).any(|node| {
+        !matches!(
+            node.kind(),
+            "line_comment" | "block_comment" | "package_declaration" | "import_declaration"
+        )
+    });
+    Ok(found)
+}
+
+/// Counts lines holding at least one non-comment token, so blank lines and
+/// comment-only lines are left out. Tokens spanning several lines, such as
+/// text blocks, count every line they cover.
+fn code_lines(parser: &Parser) -> Result<usize> {
+    let mut rows = std::collections::BTreeSet::new();
+    let mut stack = vec![parser.root_node()?];
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "line_comment" | "block_comment") {
+            continue;
+        }
+        if node.child_count() == 0 {
+            rows.extend(node.start_position().row..=node.end_position().row);
+            continue;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.children(&mut cursor));
     }
+    Ok(rows.len())
 }
 
 /// Renders declaration and summary sections for interface files.
@@ -311,21 +565,36 @@ fn class_sections(parser: &Parser, proper_name: &str) -> Vec<String> {
 }
 
 /// Builds the XML-like description block used for retrieval context.
+///
+/// Files declaring several top-level types describe each one in turn.
 fn build_description(
     parser: &Parser,
     proper_name: &str,
+    package_name: Option<&str>,
     kind: FileType,
+    types: &[TopLevelType],
     test_methods: &[String],
     file_path: &str,
 ) -> String {
     let mut lines = vec![format!(
         "<file name=\"{proper_name}\" path=\"{file_path}\" type=\"{}\">",
-        file_type_attr(&kind)
+        kind.as_str()
     )];
 
-    match kind {
-        FileType::Interface => lines.extend(interface_sections(parser, proper_name)),
-        _ => lines.extend(class_sections(parser, proper_name)),
+    if types.len() > 1 {
+        for t in types {
+            let name = qualify(package_name, &t.name);
+            if t.is_interface {
+                lines.extend(interface_sections(&t.parser, &name));
+            } else {
+                lines.extend(class_sections(&t.parser, &name));
+            }
+        }
+    } else {
+        match kind {
+            FileType::Interface => lines.extend(interface_sections(parser, proper_name)),
+            _ => lines.extend(class_sections(parser, proper_name)),
+        }
     }
 
     if !test_methods.is_empty() {
@@ -410,7 +679,7 @@ fn constructor_signature(data: &Dict) -> String {
         .join(" ")
 }
 /// Types of Java files -
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum FileType {
     /// - Interface
     Interface,
@@ -422,6 +691,69 @@ pub enum FileType {
     Test,
 }
 
+impl FileType {
+    /// Snake-case name of the classification, as used in project descriptions
+    /// and Rune maps (`interface`, `class`, `class_with_main`, `test`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileType::Interface => "interface",
+            FileType::Class => "class",
+            FileType::ClassWithMain => "class_with_main",
+            FileType::Test => "test",
+        }
+    }
+}
+
+/// Line ending used for text piped to a program's stdin.
+///
+/// Defaults to `\n`. Windows-style `\r\n` leaves a stray `\r` at the end of
+/// each token for programs that split input on `\n` (e.g. a `Scanner` with
+/// `useDelimiter("\n")` or code reading characters one at a time), which shows
+/// up as off-by-one lengths or failed number parsing unrelated to the
+/// student's logic. Use [`LineEnding::CrLf`] only for programs that must see
+/// Windows line endings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineEnding {
+    /// Unix newlines, `\n`.
+    #[default]
+    Lf,
+    /// Windows newlines, `\r\n`.
+    CrLf,
+}
+
+impl LineEnding {
+    /// The line ending's characters.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    /// Rewrites every line break in `input` to this ending and terminates
+    /// the last line with it too.
+    pub fn apply(self, input: &str) -> String {
+        let mut text = input.replace("\r\n", "\n");
+        text.push('\n');
+        match self {
+            LineEnding::Lf => text,
+            LineEnding::CrLf => text.replace('\n', "\r\n"),
+        }
+    }
+}
+
+impl std::str::FromStr for LineEnding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "lf" | "\n" => Ok(LineEnding::Lf),
+            "crlf" | "\r\n" => Ok(LineEnding::CrLf),
+            other => Err(anyhow!("Unknown line ending `{other}`; expected `lf` or `crlf`")),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Struct representing a java file
 ///
@@ -430,29 +762,153 @@ pub enum FileType {
 /// choose whichever is most convenient.
 pub struct File {
     /// path to java file.
-    path:         PathBuf,
+    path:             PathBuf,
     /// Filesystem name (including the `.java` extension).
-    file_name:    String,
+    file_name:        String,
     /// package the java file belongs to.
-    package_name: Option<String>,
+    package_name:     Option<String>,
     /// imports made by the java file.
-    imports:      Option<Vec<Dict>>,
+    imports:          Option<Vec<Dict>>,
     /// Simple, unqualified Java identifier extracted from the declaration.
-    name:         String,
+    name:             String,
     /// Package-qualified Java name (no ANSI colors, just dotted notation).
-    proper_name:  String,
+    proper_name:      String,
     /// Fully qualified `Class#method` strings discovered via `@Test`
     /// annotations.
-    test_methods: Vec<String>,
+    test_methods:     Vec<String>,
+    /// Repetition counts of `@RepeatedTest` methods, keyed like
+    /// `test_methods`.
+    #[serde(default)]
+    test_repetitions: BTreeMap<String, usize>,
+    /// The `@TestFactory` methods among `test_methods`.
+    #[serde(default)]
+    test_factories:   Vec<String>,
+    /// True when the file holds nothing but whitespace, comments, and
+    /// package/import declarations.
+    #[serde(default)]
+    empty:            bool,
     /// Classification of the Java file (class, interface, test, etc.).
-    kind:         FileType,
+    kind:             FileType,
     #[serde(skip)]
     /// The parser for this file
-    parser:       Parser,
+    parser:           Parser,
     /// Concise description of the file
-    description:  String,
+    description:      String,
     /// Workspace paths associated with this file
-    paths:        ProjectPaths,
+    paths:            ProjectPaths,
+    /// How long `main` may run before it is killed; `None` uses
+    /// [`config::java_timeout`].
+    #[serde(skip)]
+    run_timeout:      Option<Duration>,
+    /// Arguments passed to `main` after the class name.
+    #[serde(skip)]
+    program_args:     Vec<String>,
+    /// Pause before writing each line of piped input; `None` writes it all
+    /// at once.
+    #[serde(skip)]
+    input_delay:      Option<Duration>,
+}
+
+/// Size metrics for a single Java file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileMetrics {
+    /// Proper name of the file.
+    pub name:       String,
+    /// Lines of code, excluding blank and comment-only lines.
+    pub loc:        usize,
+    /// Methods declared directly in the file's top-level classes and
+    /// interfaces.
+    pub methods:    usize,
+    /// Top-level classes declared in the file.
+    pub classes:    usize,
+    /// Top-level interfaces declared in the file.
+    pub interfaces: usize,
+}
+
+/// Top-level packages shipped with the JDK.
+pub const STDLIB_PACKAGES: &[&str] = &["java", "javax", "jdk"];
+
+/// Tree-sitter query selecting whole import declarations.
+pub(crate) const IMPORT_DECLARATION_QUERY: &str = "((import_declaration) @import)";
+
+/// A single `import` declaration in a Java file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportDecl {
+    /// Imported name, ending in `.*` for on-demand imports, e.g.
+    /// `java.util.Scanner` or `java.util.*`.
+    pub path:      String,
+    /// 1-based line the import is declared on.
+    pub line:      usize,
+    /// True for `import static`.
+    pub is_static: bool,
+}
+
+impl ImportDecl {
+    /// Parses the source text of an import declaration found on `line`.
+    pub(crate) fn parse(text: &str, line: usize) -> Option<Self> {
+        let rest = text.trim().strip_prefix("import")?.trim_start();
+        let (is_static, rest) = match rest.strip_prefix("static") {
+            Some(rest) if rest.starts_with(char::is_whitespace) => (true, rest),
+            _ => (false, rest),
+        };
+        let path: String = rest
+            .trim_end_matches(|c: char| c == ';' || c.is_whitespace())
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        (!path.is_empty()).then_some(Self {
+            path,
+            line,
+            is_static,
+        })
+    }
+
+    /// Returns true for on-demand (`.*`) imports.
+    pub fn is_wildcard(&self) -> bool {
+        self.path.ends_with(".*")
+    }
+
+    /// Returns true when this import brings in anything `pattern` names.
+    ///
+    /// `pattern` is a class or package, optionally ending in `.*`: `java.util`
+    /// and `java.util.*` cover everything under `java.util`, while
+    /// `java.util.Scanner` is covered by that import or by `java.util.*`.
+    pub fn matches(&self, pattern: &str) -> bool {
+        let pattern = pattern.trim().trim_end_matches(".*");
+        let imported = self.path.trim_end_matches(".*");
+        if pattern.is_empty() {
+            return false;
+        }
+        if imported == pattern
+            || imported
+                .strip_prefix(pattern)
+                .is_some_and(|rest| rest.starts_with('.'))
+        {
+            return true;
+        }
+        self.is_wildcard()
+            && pattern
+                .strip_prefix(imported)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .is_some_and(|member| !member.is_empty() && !member.contains('.'))
+    }
+
+    /// Returns true when the import comes from the JDK (see
+    /// [`STDLIB_PACKAGES`]).
+    pub fn is_stdlib(&self) -> bool {
+        STDLIB_PACKAGES.iter().any(|package| self.matches(package))
+    }
+}
+
+impl std::fmt::Display for ImportDecl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let keyword = if self.is_static {
+            "import static"
+        } else {
+            "import"
+        };
+        write!(f, "`{keyword} {};` on line {}", self.path, self.line)
+    }
 }
 
 /// Two `File`s are equal if their paths are equal
@@ -494,6 +950,15 @@ pub enum JavaFileError {
         /// java stacktrace, parsed with [parser::junit_stacktrace_line_ref]
         diags:  Vec<LineRef>,
     },
+    /// A file without a `main` method was asked to run (running
+    /// [fn@crate::java::File::run])
+    #[error("`{name}` has no `public static void main(String[] args)` method, so it cannot be run")]
+    NoMainMethod {
+        /// proper name of the file that was run
+        name: String,
+        /// what the file was detected as instead
+        kind: FileType,
+    },
     /// An error while testing a Java file (running
     /// [fn@crate::java::File::test])
     #[error("Something went wrong while testing the Java file")]
@@ -503,6 +968,15 @@ pub enum JavaFileError {
         /// junit stacktrace, parsed with [parser::junit_stacktrace_line_ref]
         diags:        Vec<LineRef>,
     },
+    /// A process ran longer than its timeout and was killed (running
+    /// [fn@crate::java::File::run])
+    #[error("The Java program did not finish within {}s", .timeout.as_secs_f64())]
+    TimedOut {
+        /// output written before the process was killed, stderr first
+        partial_output: String,
+        /// the timeout that was exceeded
+        timeout:        Duration,
+    },
     /// Unknown error
     #[error(transparent)]
     Unknown(#[from] anyhow::Error),
@@ -521,19 +995,78 @@ impl File {
         self
     }
 
+    /// Returns a copy of this file whose runs are killed after `timeout`
+    /// instead of the configured java timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.run_timeout = Some(timeout);
+        self
+    }
+
+    /// Returns a copy of this file whose runs pass `args` to `main`, so the
+    /// same class can be run with different `args[]` without recompiling.
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.program_args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Returns a copy of this file whose piped input is written one line at
+    /// a time, waiting `delay` before each, for programs that prompt and
+    /// flush between reads.
+    pub fn with_input_delay(mut self, delay: Duration) -> Self {
+        self.input_delay = Some(delay);
+        self
+    }
+
+    /// Stdin for piping `input` with every line ending in `ending`, paced
+    /// when an input delay is set.
+    fn piped_stdin(&self, input: &str, ending: LineEnding) -> StdinSource {
+        let text = ending.apply(input);
+        match self.input_delay {
+            Some(delay) => StdinSource::Paced {
+                chunks: text
+                    .split_inclusive('\n')
+                    .map(|line| line.as_bytes().to_vec())
+                    .collect(),
+                delay,
+            },
+            None => StdinSource::Bytes(text.into_bytes()),
+        }
+    }
+
+    /// How long `main` may run before it is killed.
+    fn run_timeout(&self) -> Duration {
+        self.run_timeout.unwrap_or_else(config::java_timeout)
+    }
+
     /// Builds the standard set of `javac` arguments for this file.
-    fn javac_args(&self, include_doclint: bool, prefer_source: bool) -> Result<Vec<OsString>> {
+    ///
+    /// With reference classes, javac gets no sourcepath: it compiles this
+    /// file and the student's other sources, and takes every class the
+    /// reference provides from the classpath instead of recompiling it.
+    async fn javac_args(
+        &self,
+        include_doclint: bool,
+        prefer_source: bool,
+    ) -> Result<Vec<OsString>> {
         let mut args = vec![
-            OsString::from("--source-path"),
-            OsString::from(sourcepath(&self.paths)?),
             OsString::from("-g"),
             OsString::from("--class-path"),
             OsString::from(classpath(&self.paths)?),
             OsString::from("-d"),
             OsString::from(self.paths.build_dir().to_str().unwrap_or(".").to_string()),
-            self.path.as_os_str().to_os_string(),
             OsString::from("-Xdiags:verbose"),
         ];
+        if self.paths.reference_classes().is_empty() {
+            args.push(OsString::from("--source-path"));
+            args.push(OsString::from(sourcepath(&self.paths)?));
+            args.push(self.path.as_os_str().to_os_string());
+        } else {
+            let mut sources = student_sources(&self.paths).await?;
+            if !sources.contains(&self.path) {
+                sources.push(self.path.clone());
+            }
+            args.extend(sources.into_iter().map(PathBuf::into_os_string));
+        }
         if include_doclint {
             args.push(OsString::from("-Xdoclint"));
         }
@@ -543,13 +1076,16 @@ impl File {
         Ok(args)
     }
 
-    /// Constructs the `java` invocation for running a main class.
+    /// Constructs the `java` invocation for running a main class, followed by
+    /// any program arguments.
     fn java_run_args(&self) -> Result<Vec<OsString>> {
-        Ok(vec![
+        let mut args = vec![
             OsString::from("--class-path"),
             OsString::from(classpath(&self.paths)?),
             OsString::from(self.proper_name.clone()),
-        ])
+        ];
+        args.extend(self.program_args.iter().map(OsString::from));
+        Ok(args)
     }
 
     /// Constructs the `java` invocation for the JUnit console launcher.
@@ -585,7 +1121,18 @@ impl File {
     ) -> Result<process::Collected, JavaFileError> {
         process::run_collect(program, args, stdin, None, &[], Some(timeout))
             .await
-            .map_err(JavaFileError::Unknown)
+            .map_err(|err| match err.downcast::<process::TimedOut>() {
+                Ok(timed_out) => {
+                    let mut partial_output =
+                        String::from_utf8_lossy(&timed_out.stderr).into_owned();
+                    partial_output.push_str(&String::from_utf8_lossy(&timed_out.stdout));
+                    JavaFileError::TimedOut {
+                        partial_output,
+                        timeout: timed_out.limit,
+                    }
+                }
+                Err(err) => JavaFileError::Unknown(err),
+            })
     }
 
     /// Shared helper to compile and run a main class with the provided stdin
@@ -594,40 +1141,43 @@ impl File {
         &self,
         stdin_mode: StdinSource,
         output_phase: &'static str,
+        timeout: Duration,
     ) -> Result<String, JavaFileError> {
-        if self.kind != FileType::ClassWithMain {
-            return Err(JavaFileError::DuringCompilation {
-                stacktrace: "The file you wish to run does not have a main method.".into(),
-                diags:      vec![],
-            });
-        }
-
-        self.check().await?;
-
-        let java = java_path().map_err(JavaFileError::Unknown)?;
-        let args = self.java_run_args().map_err(JavaFileError::Unknown)?;
-
         let process::Collected {
             status,
             stdout,
             stderr,
-        } = Self::collect_process(java.as_os_str(), &args, stdin_mode, config::java_timeout())
-            .await?;
+        } = self.collect_main(stdin_mode, timeout).await?;
 
         let output = decode_output(stderr, stdout, output_phase)?;
 
         if status.success() {
             Ok(output)
         } else {
-            let mut diags = Vec::new();
-            for line in output.lines() {
-                if let Ok(diag) = parser::junit_stacktrace_line_ref(line) {
-                    diags.push(diag);
-                }
-            }
+            Err(runtime_error(output))
+        }
+    }
 
-            Err(JavaFileError::AtRuntime { output, diags })
+    /// Compiles the file and runs its `main` method, returning the raw
+    /// process output.
+    async fn collect_main(
+        &self,
+        stdin_mode: StdinSource,
+        timeout: Duration,
+    ) -> Result<process::Collected, JavaFileError> {
+        if self.kind != FileType::ClassWithMain {
+            return Err(JavaFileError::NoMainMethod {
+                name: self.proper_name.clone(),
+                kind: self.kind.clone(),
+            });
         }
+
+        self.check().await?;
+
+        let java = java_path().map_err(JavaFileError::Unknown)?;
+        let args = self.java_run_args().map_err(JavaFileError::Unknown)?;
+
+        Self::collect_process(java.as_os_str(), &args, stdin_mode, timeout).await
     }
 
     /// Creates a new `File` from `path`
@@ -655,16 +1205,28 @@ impl File {
             }
         };
 
-        let has_main = !parser.query(MAIN_METHOD_QUERY)?.is_empty();
-        let (kind, name) = detect_file_identity(&parser, path.as_path(), has_main)?;
-
-        let proper_name = if let Some(pkg) = package_name.as_ref() {
-            format!("{pkg}.{name}")
-        } else {
-            name.clone()
-        };
-
-        let test_methods = collect_test_methods(&parser, &proper_name)?;
+        let types = top_level_types(&parser)?;
+        let (kind, name) = detect_file_identity(&parser, path.as_path(), &types)?;
+        let proper_name = qualify(package_name.as_deref(), &name);
+
+        let spans = top_level_spans(&parser)?;
+        let test_methods = collect_test_methods(
+            &parser,
+            TEST_ANNOTATION_QUERY,
+            package_name.as_deref(),
+            &proper_name,
+            &spans,
+        )?;
+        let test_factories = collect_test_methods(
+            &parser,
+            TEST_FACTORY_QUERY,
+            package_name.as_deref(),
+            &proper_name,
+            &spans,
+        )?;
+        let test_repetitions =
+            collect_test_repetitions(&parser, package_name.as_deref(), &proper_name, &spans)?;
+        let empty = !has_code(&parser)?;
         let kind = if !test_methods.is_empty() {
             FileType::Test
         } else {
@@ -672,8 +1234,15 @@ impl File {
         };
 
         let file_path = path.display().to_string();
-        let description =
-            build_description(&parser, &proper_name, kind.clone(), &test_methods, &file_path);
+        let description = build_description(
+            &parser,
+            &proper_name,
+            package_name.as_deref(),
+            kind.clone(),
+            &types,
+            &test_methods,
+            &file_path,
+        );
 
         Ok(Self {
             path: path.to_owned(),
@@ -682,11 +1251,17 @@ impl File {
             imports,
             name,
             test_methods,
+            test_repetitions,
+            test_factories,
+            empty,
             kind,
             proper_name,
             parser,
             description,
             paths,
+            run_timeout: None,
+            program_args: Vec::new(),
+            input_delay: None,
         })
     }
 
@@ -696,6 +1271,7 @@ impl File {
         let javac = javac_path().map_err(JavaFileError::Unknown)?;
         let args = self
             .javac_args(true, false)
+            .await
             .map_err(JavaFileError::Unknown)?;
 
         let collected = Self::collect_process(
@@ -717,6 +1293,7 @@ impl File {
         let javac = javac_path().map_err(JavaFileError::Unknown)?;
         let args = self
             .javac_args(false, true)
+            .await
             .map_err(JavaFileError::Unknown)?;
 
         let collected = Self::collect_process(
@@ -752,30 +1329,77 @@ impl File {
     }
 
     /// Utility method to run a java file that has a main method.
+    ///
+    /// Input is sent with [`LineEnding::Lf`] line endings and arguments set
+    /// with [`File::with_args`] follow the class name. A program still
+    /// running after the file's timeout (see [`File::with_timeout`]) is
+    /// killed and reported as [`JavaFileError::TimedOut`].
     pub async fn run(&self, input: Option<String>) -> Result<String, JavaFileError> {
+        self.run_with_timeout(input, self.run_timeout()).await
+    }
+
+    /// Like [`run`](Self::run), but kills the program after `timeout`.
+    pub async fn run_with_timeout(
+        &self,
+        input: Option<String>,
+        timeout: Duration,
+    ) -> Result<String, JavaFileError> {
         let stdin_mode = match input {
-            Some(mut value) => {
-                value.push_str("\r\n");
-                StdinSource::Bytes(value.into_bytes())
-            }
+            Some(value) => self.piped_stdin(&value, LineEnding::default()),
             None => StdinSource::Inherit,
         };
 
-        self.exec_main(stdin_mode, "java").await
+        self.exec_main(stdin_mode, "java", timeout).await
     }
 
     /// Runs the java file while piping stdin even when no explicit input is
-    /// supplied.
+    /// supplied. Input is sent with [`LineEnding::Lf`] line endings.
     pub async fn run_with_input(&self, input: Option<String>) -> Result<String, JavaFileError> {
+        self.run_with_input_ending(input, LineEnding::default())
+            .await
+    }
+
+    /// Like [`run_with_input`](Self::run_with_input), but writes each line
+    /// of `input` with `ending`.
+    pub async fn run_with_input_ending(
+        &self,
+        input: Option<String>,
+        ending: LineEnding,
+    ) -> Result<String, JavaFileError> {
         let stdin_mode = match input {
-            Some(mut value) => {
-                value.push_str("\r\n");
-                StdinSource::Bytes(value.into_bytes())
-            }
+            Some(value) => self.piped_stdin(&value, ending),
             None => StdinSource::Bytes(Vec::new()),
         };
 
-        self.exec_main(stdin_mode, "java").await
+        self.exec_main(stdin_mode, "java", self.run_timeout()).await
+    }
+
+    /// Runs the java file like
+    /// [`run_with_input_ending`](Self::run_with_input_ending), but keeps
+    /// stdout and stderr apart and reports the exit code instead of failing
+    /// on a non-zero one. Use [`RunOutput::check_status`] to treat that as an
+    /// error.
+    pub async fn run_captured(
+        &self,
+        input: Option<String>,
+        ending: LineEnding,
+    ) -> Result<RunOutput, JavaFileError> {
+        let stdin_mode = match input {
+            Some(value) => self.piped_stdin(&value, ending),
+            None => StdinSource::Bytes(Vec::new()),
+        };
+
+        let process::Collected {
+            status,
+            stdout,
+            stderr,
+        } = self.collect_main(stdin_mode, self.run_timeout()).await?;
+
+        Ok(RunOutput {
+            stdout:    decode_stream(stdout, "java")?,
+            stderr:    decode_stream(stderr, "java")?,
+            exit_code: status.code(),
+        })
     }
 
     /// A utility method that takes a list of strings (or types that implement
@@ -788,27 +1412,116 @@ impl File {
     ///
     /// * `tests`: list of strings (or types that implement `Into<String>`)
     ///   meant to represent test method names,
+    ///
+    /// The tests that fail are recorded under the project's `.umm/` directory
+    /// so [`File::test_failed_only`] can re-run just those.
     pub async fn test(
         &self,
         tests: Vec<&str>,
         project: Option<&Project>,
     ) -> Result<String, JavaFileError> {
-        self.check().await?;
+        let explicit_tests = if tests.is_empty() {
+            self.test_methods.clone()
+        } else {
+            tests
+                .into_iter()
+                .flat_map(|test| self.test_selectors(test))
+                .collect()
+        };
 
-        let java = java_path().map_err(JavaFileError::Unknown)?;
+        self.run_tests(&explicit_tests, project).await
+    }
 
-        let explicit_tests = {
-            let mut mapped = Vec::<String>::new();
-            for t in tests {
-                mapped.push(format!("{}#{}", self.proper_name.clone(), t));
-            }
+    /// Fully qualified `Class#method` selectors for `test`. Names that
+    /// already include a class are kept as-is; a bare method name selects it
+    /// in every top-level class of this file that declares it as a test,
+    /// falling back to the primary class.
+    fn test_selectors(&self, test: &str) -> Vec<String> {
+        if test.contains('#') {
+            return vec![test.to_string()];
+        }
+        let declared: Vec<String> = self
+            .test_methods
+            .iter()
+            .filter(|selector| {
+                selector
+                    .split_once('#')
+                    .is_some_and(|(_, method)| method == test)
+            })
+            .cloned()
+            .collect();
+        if declared.is_empty() {
+            vec![format!("{}#{test}", self.proper_name)]
+        } else {
+            declared
+        }
+    }
 
-            if mapped.is_empty() {
-                self.test_methods.clone()
-            } else {
-                mapped
-            }
-        };
+    /// Re-runs only the tests that failed in this file's last recorded run.
+    ///
+    /// Returns a short note without invoking JUnit when nothing is recorded
+    /// as failing.
+    pub async fn test_failed_only(
+        &self,
+        project: Option<&Project>,
+    ) -> Result<String, JavaFileError> {
+        let failed = self.last_failed_tests().map_err(JavaFileError::Unknown)?;
+        if failed.is_empty() {
+            return Ok(format!("No failed tests recorded for {}.", self.proper_name));
+        }
+        self.run_tests(&failed, project).await
+    }
+
+    /// `Class#method` names of the tests that failed the last time this
+    /// file's tests ran; empty when they all passed or never ran.
+    pub fn last_failed_tests(&self) -> Result<Vec<String>> {
+        Ok(self
+            .read_failed_tests()?
+            .remove(&self.proper_name)
+            .unwrap_or_default())
+    }
+
+    /// Location of the record of failed tests, keyed by test file.
+    fn failed_tests_path(&self) -> PathBuf {
+        self.paths.umm_dir().join("failed-tests.json")
+    }
+
+    /// Loads the record of failed tests, treating a missing record as empty.
+    fn read_failed_tests(&self) -> Result<BTreeMap<String, Vec<String>>> {
+        let path = self.failed_tests_path();
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Could not parse {}", path.display()))
+    }
+
+    /// Replaces this file's entry in the record of failed tests.
+    fn record_failed_tests(&self, failed: Vec<String>) -> Result<()> {
+        let mut record = self.read_failed_tests().unwrap_or_default();
+        if failed.is_empty() {
+            record.remove(&self.proper_name);
+        } else {
+            record.insert(self.proper_name.clone(), failed);
+        }
+
+        std::fs::create_dir_all(self.paths.umm_dir())
+            .with_context(|| format!("Could not create {}", self.paths.umm_dir().display()))?;
+        write_atomic(self.failed_tests_path(), serde_json::to_string_pretty(&record)?)
+    }
+
+    /// Runs JUnit on the given `Class#method` tests (or the whole class path
+    /// when empty) and records which of them failed.
+    async fn run_tests(
+        &self,
+        explicit_tests: &[String],
+        project: Option<&Project>,
+    ) -> Result<String, JavaFileError> {
+        self.check().await?;
+
+        let java = java_path().map_err(JavaFileError::Unknown)?;
 
         let selectors: Vec<String> = explicit_tests
             .iter()
@@ -848,6 +1561,10 @@ impl File {
             output = decode_output(collected.stderr, collected.stdout, "JUnit")?;
         }
 
+        if let Err(err) = self.record_failed_tests(failed_test_methods(&output)) {
+            tracing::warn!("Could not record failed tests: {err:#}");
+        }
+
         if collected.status.success() {
             Ok(output)
         } else {
@@ -905,11 +1622,44 @@ impl File {
         self.file_name.as_ref()
     }
 
+    /// Returns true if the file is empty or contains no code (only
+    /// whitespace, comments, and package/import declarations).
+    pub fn is_empty(&self) -> bool {
+        self.empty
+    }
+
+    /// Returns true if a comment marks the file as instructor-provided with
+    /// [`PROVIDED_MARKER`](crate::util::PROVIDED_MARKER).
+    pub fn is_provided(&self) -> bool {
+        crate::util::has_provided_marker(self.code())
+    }
+
     /// Get a reference to the file's test methods.
     pub fn test_methods(&self) -> Vec<String> {
         self.test_methods.clone()
     }
 
+    /// Get the file's `@TestFactory` methods, as `Class#method` strings.
+    pub fn test_factories(&self) -> Vec<String> {
+        self.test_factories.clone()
+    }
+
+    /// Number of times JUnit runs `test` (a `Class#method` string): its
+    /// `@RepeatedTest` count, or `1` for any other test.
+    pub fn test_repetitions(&self, test: &str) -> usize {
+        self.test_repetitions.get(test).copied().unwrap_or(1)
+    }
+
+    /// Number of test executions this file is expected to report, counting
+    /// each `@RepeatedTest` repetition. Dynamic tests from a `@TestFactory`
+    /// are unknown until run, so each factory counts once.
+    pub fn expected_test_runs(&self) -> usize {
+        self.test_methods
+            .iter()
+            .map(|test| self.test_repetitions(test))
+            .sum()
+    }
+
     /// treesitter query for this file
     pub fn query(&self, q: &str) -> Result<Vec<Dict>> {
         self.parser.query(q)
@@ -968,4 +1718,49 @@ impl File {
     pub fn imports(&self) -> Option<&[Dict]> {
         self.imports.as_deref()
     }
+
+    /// Lists this file's import declarations in source order, with the line
+    /// each appears on.
+    pub fn import_declarations(&self) -> Result<Vec<ImportDecl>> {
+        Ok(self
+            .parser
+            .query_capture_positions(IMPORT_DECLARATION_QUERY, "import")?
+            .into_iter()
+            .filter_map(|(text, line)| ImportDecl::parse(&text, line))
+            .collect())
+    }
+
+    /// Imports that bring in anything `pattern` names; see
+    /// [`ImportDecl::matches`].
+    pub fn forbidden_imports(&self, pattern: &str) -> Result<Vec<ImportDecl>> {
+        let mut imports = self.import_declarations()?;
+        imports.retain(|import| import.matches(pattern));
+        Ok(imports)
+    }
+
+    /// Imports from outside the JDK.
+    pub fn non_stdlib_imports(&self) -> Result<Vec<ImportDecl>> {
+        let mut imports = self.import_declarations()?;
+        imports.retain(|import| !import.is_stdlib());
+        Ok(imports)
+    }
+
+    /// Computes size metrics for this file, counting declarations with the
+    /// same queries used to build its description.
+    pub fn metrics(&self) -> Result<FileMetrics> {
+        let interface_methods = self
+            .parser
+            .query(INTERFACE_METHODS_QUERY)?
+            .iter()
+            .filter(|m| m.contains_key("signature"))
+            .count();
+
+        Ok(FileMetrics {
+            name:       self.proper_name(),
+            loc:        code_lines(&self.parser)?,
+            methods:    self.parser.query(CLASS_METHOD_QUERY)?.len() + interface_methods,
+            classes:    self.parser.query(CLASS_DECLARATION_QUERY)?.len(),
+            interfaces: self.parser.query(INTERFACE_DECLARATION_QUERY)?.len(),
+        })
+    }
 }
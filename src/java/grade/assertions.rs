@@ -0,0 +1,210 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use anyhow::Result;
+use bon::Builder;
+use tree_sitter::Node;
+
+use super::{
+    results::{GradeResult, timed},
+    static_analysis::{StaticCheck, StaticGrading, walk},
+};
+use crate::java::{Parser, Project};
+
+/// JUnit annotations that mark a method as a test.
+const TEST_ANNOTATIONS: &[&str] = &["Test", "TestFactory", "RepeatedTest", "ParameterizedTest"];
+
+/// Method-name prefixes counted as assertions when no others are configured:
+/// JUnit's `assert*` (including `assertThat`) and Mockito's `verify*`.
+pub const DEFAULT_ASSERTION_PREFIXES: &[&str] = &["assert", "verify"];
+
+/// Assertions made by a single JUnit test method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestAssertions {
+    /// Test method qualified by its class, e.g. `CalcTest.addsNumbers`.
+    pub method:     String,
+    /// 1-based line of the method's name, below any annotations.
+    pub line:       usize,
+    /// Number of assertion calls and `assert` statements in the method body.
+    pub assertions: usize,
+}
+
+/// Counts the assertions in every test method of `parser`'s tree, in source
+/// order.
+///
+/// A call counts when its method name starts with one of `prefixes`; Java
+/// `assert` statements always count. Calls inside lambdas (as in `assertAll`)
+/// count towards the enclosing test.
+pub fn test_assertions(parser: &Parser, prefixes: &[String]) -> Result<Vec<TestAssertions>> {
+    let source = parser.code().as_bytes();
+    let mut tests = Vec::new();
+    walk(parser, |node, scope| {
+        if node.kind() == "method_declaration"
+            && is_test(node, source)
+            && let Some(name) = &scope.method
+        {
+            tests.push(TestAssertions {
+                method:     scope.qualify(name),
+                line:       node
+                    .child_by_field_name("name")
+                    .unwrap_or(node)
+                    .start_position()
+                    .row
+                    + 1,
+                assertions: node
+                    .child_by_field_name("body")
+                    .map_or(0, |body| count_assertions(body, source, prefixes)),
+            });
+        }
+        true
+    })?;
+    Ok(tests)
+}
+
+/// Returns true when the method declaration carries a JUnit test annotation,
+/// written either bare (`@Test`) or qualified (`@org.junit.jupiter.api.Test`).
+fn is_test(method: Node, source: &[u8]) -> bool {
+    let mut cursor = method.walk();
+    let Some(modifiers) = method
+        .children(&mut cursor)
+        .find(|child| child.kind() == "modifiers")
+    else {
+        return false;
+    };
+
+    let mut cursor = modifiers.walk();
+    modifiers
+        .named_children(&mut cursor)
+        .filter(|child| matches!(child.kind(), "annotation" | "marker_annotation"))
+        .filter_map(|annotation| annotation.child_by_field_name("name"))
+        .filter_map(|name| name.utf8_text(source).ok())
+        .any(|name| {
+            let simple = name.rsplit_once('.').map_or(name, |(_, simple)| simple);
+            TEST_ANNOTATIONS.contains(&simple)
+        })
+}
+
+/// Counts assertion calls and `assert` statements under `node`, skipping
+/// local and anonymous classes.
+fn count_assertions(node: Node, source: &[u8], prefixes: &[String]) -> usize {
+    let own = match node.kind() {
+        "assert_statement" => 1,
+        "method_invocation" => usize::from(
+            node.child_by_field_name("name")
+                .and_then(|name| name.utf8_text(source).ok())
+                .is_some_and(|name| {
+                    prefixes
+                        .iter()
+                        .any(|prefix| name.starts_with(prefix.as_str()))
+                }),
+        ),
+        _ => 0,
+    };
+
+    let mut cursor = node.walk();
+    own + node
+        .named_children(&mut cursor)
+        .filter(|child| !matches!(child.kind(), "class_body" | "class_declaration"))
+        .map(|child| count_assertions(child, source, prefixes))
+        .sum::<usize>()
+}
+
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+/// A grader that flags JUnit tests making fewer than `min_assertions`
+/// assertions, so tests that trivially pass do not go unnoticed.
+pub struct AssertionDensityGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name:           String,
+    /// points to give if every test asserts enough
+    #[builder(getter)]
+    pub out_of:             f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:            Project,
+    /// test files whose `@Test` methods are checked
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub files:              Vec<String>,
+    /// minimum assertions each test must make. Optional, default is 1
+    #[builder(default = 1)]
+    #[builder(getter)]
+    pub min_assertions:     usize,
+    /// method-name prefixes counted as assertions. Optional, default is
+    /// [`DEFAULT_ASSERTION_PREFIXES`]
+    #[builder(
+        default = DEFAULT_ASSERTION_PREFIXES.iter().map(|s| s.to_string()).collect(),
+        with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+            iter.into_iter().map(Into::into).collect::<Vec<String>>()
+        }
+    )]
+    #[builder(getter)]
+    pub assertion_prefixes: Vec<String>,
+    /// points deducted per test below the minimum. Optional, default is 1
+    #[builder(default = 1.0)]
+    #[builder(getter)]
+    pub penalty:            f64,
+}
+
+impl AssertionDensityGrader {
+    /// Builds and runs the configured assertion density grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move { self.grade_assertions() }).await
+    }
+
+    /// Counts assertions in each test of `files` and deducts `penalty` for
+    /// every test below `min_assertions`.
+    pub fn grade_assertions(&self) -> Result<GradeResult> {
+        StaticGrading {
+            grader:   "AssertionDensityGrader",
+            req_name: &self.req_name,
+            out_of:   self.out_of,
+            penalty:  self.penalty,
+            project:  &self.project,
+            files:    &self.files,
+        }
+        .grade(self)
+    }
+}
+
+impl StaticCheck for AssertionDensityGrader {
+    type Finding = TestAssertions;
+
+    fn find(&self, parser: &Parser) -> Result<Vec<TestAssertions>> {
+        Ok(test_assertions(parser, &self.assertion_prefixes)?
+            .into_iter()
+            .filter(|test| test.assertions < self.min_assertions)
+            .collect())
+    }
+
+    fn describe(&self, file: &str, test: &TestAssertions) -> String {
+        format!("{file}:{} {} ({} assertion(s))", test.line, test.method, test.assertions)
+    }
+
+    fn passed(&self) -> String {
+        format!("Every test makes at least {} assertion(s)", self.min_assertions)
+    }
+
+    fn summary(&self, count: usize) -> String {
+        format!("{count} test(s) make fewer than {} assertion(s):", self.min_assertions)
+    }
+
+    fn advice(&self) -> String {
+        "A test without assertions passes no matter what the code does; each test should check the \
+         behaviour it exercises."
+            .to_string()
+    }
+}
+
+impl<S> AssertionDensityGraderBuilder<S>
+where
+    S: assertion_density_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
@@ -0,0 +1,170 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use anyhow::Result;
+use bon::Builder;
+use tree_sitter::Node;
+
+use super::{
+    results::{GradeResult, timed},
+    static_analysis::{StaticCheck, StaticGrading, walk},
+    syntax::METHOD_KINDS,
+};
+use crate::java::{Parser, Project};
+
+/// Cyclomatic complexity of a single method or constructor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodComplexity {
+    /// Method name qualified by its enclosing type, e.g. `Main.process`.
+    pub name:       String,
+    /// 1-based line the declaration starts on.
+    pub line:       usize,
+    /// One plus the number of decision points in the method body.
+    pub complexity: usize,
+}
+
+/// Computes a simple cyclomatic complexity for every method and constructor
+/// in `parser`'s tree.
+///
+/// Each method starts at 1 and gains a point per `if`, loop (`for`, enhanced
+/// `for`, `while`, `do`), `case` label, `catch`, ternary, `&&`, and `||`.
+/// Lambdas count towards the method they appear in; methods of local and
+/// anonymous classes are measured on their own.
+pub fn method_complexities(parser: &Parser) -> Result<Vec<MethodComplexity>> {
+    let source = parser.code().as_bytes();
+    let mut methods = Vec::new();
+    walk(parser, |node, scope| {
+        if METHOD_KINDS.contains(&node.kind()) {
+            let decisions = node
+                .child_by_field_name("body")
+                .map(|body| count_decisions(body, source))
+                .unwrap_or(0);
+            methods.push(MethodComplexity {
+                name:       scope.method_or("<unknown>"),
+                line:       node.start_position().row + 1,
+                complexity: 1 + decisions,
+            });
+        }
+        true
+    })?;
+    Ok(methods)
+}
+
+/// Counts decision points under `node`, stopping at nested class bodies so
+/// their methods are not charged to the enclosing one.
+fn count_decisions(node: Node, source: &[u8]) -> usize {
+    let own = match node.kind() {
+        "if_statement"
+        | "for_statement"
+        | "enhanced_for_statement"
+        | "while_statement"
+        | "do_statement"
+        | "catch_clause"
+        | "ternary_expression" => 1,
+        "switch_label" => usize::from(node.child(0).is_some_and(|c| c.kind() == "case")),
+        "binary_expression" => usize::from(
+            node.child_by_field_name("operator")
+                .and_then(|op| op.utf8_text(source).ok())
+                .is_some_and(|op| op == "&&" || op == "||"),
+        ),
+        _ => 0,
+    };
+
+    let mut cursor = node.walk();
+    own + node
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() != "class_body")
+        .map(|child| count_decisions(child, source))
+        .sum::<usize>()
+}
+
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+/// A grader that penalizes methods whose cyclomatic complexity exceeds a
+/// threshold, for "keep methods simple" rubrics.
+pub struct ComplexityGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name:       String,
+    /// points to give if no method exceeds the threshold
+    #[builder(getter)]
+    pub out_of:         f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:        Project,
+    /// files whose methods are measured
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub files:          Vec<String>,
+    /// highest complexity a method may have without penalty. Optional,
+    /// default is 10
+    #[builder(default = 10)]
+    #[builder(getter)]
+    pub max_complexity: usize,
+    /// points deducted per offending method. Optional, default is 1
+    #[builder(default = 1.0)]
+    #[builder(getter)]
+    pub penalty:        f64,
+}
+
+impl ComplexityGrader {
+    /// Builds and runs the configured complexity grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move { self.grade_complexity() }).await
+    }
+
+    /// Measures every method in `files` and deducts `penalty` for each one
+    /// above `max_complexity`.
+    pub fn grade_complexity(&self) -> Result<GradeResult> {
+        StaticGrading {
+            grader:   "ComplexityGrader",
+            req_name: &self.req_name,
+            out_of:   self.out_of,
+            penalty:  self.penalty,
+            project:  &self.project,
+            files:    &self.files,
+        }
+        .grade(self)
+    }
+}
+
+impl StaticCheck for ComplexityGrader {
+    type Finding = MethodComplexity;
+
+    fn find(&self, parser: &Parser) -> Result<Vec<MethodComplexity>> {
+        Ok(method_complexities(parser)?
+            .into_iter()
+            .filter(|m| m.complexity > self.max_complexity)
+            .collect())
+    }
+
+    fn describe(&self, _file: &str, m: &MethodComplexity) -> String {
+        format!("{} (line {}): complexity {}", m.name, m.line, m.complexity)
+    }
+
+    fn passed(&self) -> String {
+        format!("Every method has complexity at most {}", self.max_complexity)
+    }
+
+    fn summary(&self, count: usize) -> String {
+        format!("{count} method(s) exceed complexity {}:", self.max_complexity)
+    }
+
+    fn advice(&self) -> String {
+        "Cyclomatic complexity counts the independent paths through a method (branches, loops, \
+         cases, catches, and boolean operators)."
+            .to_string()
+    }
+}
+
+impl<S> ComplexityGraderBuilder<S>
+where
+    S: complexity_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
@@ -8,9 +8,14 @@ use std::{
 };
 
 use anyhow::{Context, Result, anyhow, bail};
-use async_openai::types::chat::{
-    ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs,
-    ChatCompletionRequestUserMessageArgs, CreateChatCompletionResponse,
+use async_openai::{
+    Client as OpenAIClient,
+    config::OpenAIConfig,
+    types::chat::{
+        ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs,
+        ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+        CreateChatCompletionResponse,
+    },
 };
 use bon::Builder;
 use itertools::Itertools;
@@ -19,8 +24,11 @@ use serde_json;
 use tokio::{runtime::Runtime, task::block_in_place};
 
 use crate::{
-    config,
-    java::{File, FileType, Project},
+    config::{self, RetrievalBackend},
+    java::{
+        File, FileType, Project,
+        queries::{CLASS_FIELDS_QUERY, IMPORT_QUERY},
+    },
     types::LineRef,
 };
 
@@ -101,20 +109,91 @@ fn compose_retrieval_messages(
 fn invoke_retrieval_service(
     messages: &[ChatCompletionRequestMessage],
 ) -> Result<CreateChatCompletionResponse> {
-    let payload =
-        serde_json::to_string(messages).context("Failed to serialize retrieval messages")?;
-    let client = config::http_client();
-    let endpoint = config::retrieval_endpoint();
-
-    let call = perform_retrieval_request(client.clone(), endpoint.clone(), payload.clone());
+    let call = request_retrieval(messages.to_vec());
     match tokio::runtime::Handle::try_current() {
         Ok(handle) => block_in_place(move || handle.block_on(call)),
         Err(_) => Runtime::new()
             .context("Failed to create Tokio runtime for retrieval service")?
-            .block_on(perform_retrieval_request(client, endpoint, payload)),
+            .block_on(call),
+    }
+}
+
+/// Sends the retrieval messages through the configured backend.
+async fn request_retrieval(
+    messages: Vec<ChatCompletionRequestMessage>,
+) -> Result<CreateChatCompletionResponse> {
+    match config::retrieval_backend() {
+        RetrievalBackend::Proxy => {
+            let payload = serde_json::to_string(&messages)
+                .context("Failed to serialize retrieval messages")?;
+            perform_retrieval_request(config::http_client(), config::retrieval_endpoint(), payload)
+                .await
+        }
+        RetrievalBackend::OpenAi => perform_openai_retrieval(messages).await,
     }
 }
 
+/// Runs retrieval against the OpenAI-compatible API from `OPENAI_*`, offering
+/// the model a tool that names the methods it wants to see.
+async fn perform_openai_retrieval(
+    messages: Vec<ChatCompletionRequestMessage>,
+) -> Result<CreateChatCompletionResponse> {
+    let (api_base, api_key, default_model) = {
+        let openai = config::openai_config().context(
+            "OPENAI_ENDPOINT, OPENAI_API_KEY_SLO, and OPENAI_MODEL must be set to use \
+             UMM_RETRIEVAL_BACKEND=openai",
+        )?;
+        (
+            openai.api_base().to_owned(),
+            openai.get_api_key().to_owned(),
+            openai.get_model().to_owned(),
+        )
+    };
+    let model = config::retrieval_model().unwrap_or(default_model);
+
+    // Built from JSON so the tool schema reads like the API reference.
+    let request: CreateChatCompletionRequest = serde_json::from_value(serde_json::json!({
+        "model": model,
+        "messages": messages,
+        "tool_choice": "required",
+        "tools": [{
+            "type": "function",
+            "function": {
+                "name": "get_method_bodies",
+                "description": "Fetch method bodies from the student's submission.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "params": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "className": { "type": "string" },
+                                    "methodName": { "type": "string" }
+                                },
+                                "required": ["className", "methodName"]
+                            }
+                        }
+                    },
+                    "required": ["params"]
+                }
+            }
+        }]
+    }))
+    .context("Failed to build retrieval request")?;
+
+    OpenAIClient::with_config(
+        OpenAIConfig::new()
+            .with_api_base(api_base)
+            .with_api_key(api_key),
+    )
+    .chat()
+    .create(request)
+    .await
+    .context("Failed to call retrieval model")
+}
+
 /// Performs the HTTP call to the external retrieval service and returns the raw
 /// completion response.
 async fn perform_retrieval_request(
@@ -161,10 +240,12 @@ fn expand_line_refs<T: Into<LineRef>>(
         })
         .collect::<Result<_, anyhow::Error>>()?;
 
+    // Student code goes ahead of test code so it survives the `max_line_refs`
+    // cut when a failing test's stack trace crosses both.
     expanded.sort_by(|lhs, rhs| {
-        rhs.1
-            .file_name
-            .cmp(&lhs.1.file_name)
+        (*lhs.0.kind() == FileType::Test)
+            .cmp(&(*rhs.0.kind() == FileType::Test))
+            .then(rhs.1.file_name.cmp(&lhs.1.file_name))
             .then(lhs.1.line_number.cmp(&rhs.1.line_number))
     });
     expanded.dedup();
@@ -295,6 +376,9 @@ fn compute_snippet_bounds(
 
 /// Renders the snippet lines with left-padded line numbers for display inside a
 /// code fence.
+///
+/// Lines are read straight from the source file and shown verbatim; escaping
+/// added by javac/JUnit output is undone where that output is parsed, not here.
 fn format_numbered_snippet_lines(
     snippet_lines: &[&str],
     start_line: usize,
@@ -303,16 +387,10 @@ fn format_numbered_snippet_lines(
     snippet_lines
         .iter()
         .enumerate()
-        .map(|(idx, line)| format!("{:width$}|{}", start_line + idx, sanitize_snippet_line(line)))
+        .map(|(idx, line)| format!("{:width$}|{}", start_line + idx, line))
         .collect()
 }
 
-/// Reverts escaping introduced by javac diagnostics so the snippet reflects the
-/// original source.
-fn sanitize_snippet_line(line: &str) -> String {
-    line.replace("\\\\", "\\").replace("\\\"", "\"")
-}
-
 /// Aggregates snippet lines and captured method names grouped by file.
 fn build_snippet_sections(
     merged: Vec<(File, LineRef, RangeInclusive<usize>)>,
@@ -380,6 +458,51 @@ fn collect_method_body_sections(
     Ok(sections)
 }
 
+/// Renders the imports and field declarations of `file` as one fenced
+/// section, or `None` when it has neither.
+fn render_declarations(file: &File) -> Result<Option<String>> {
+    let imports = file.query(IMPORT_QUERY)?.into_iter().filter_map(|import| {
+        let path = import.get("path")?;
+        Some(if import.contains_key("asterisk") {
+            format!("import {path}.*;")
+        } else {
+            format!("import {path};")
+        })
+    });
+    let fields = file
+        .query(CLASS_FIELDS_QUERY)?
+        .into_iter()
+        .filter_map(|field| field.get("field").map(|text| text.replace('\n', " ")));
+    let lines: Vec<String> = imports.chain(fields).collect();
+
+    if lines.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "Imports and fields from student's submission `{}`:\n\n```java\n{}\n```\n",
+        file.proper_name(),
+        lines.join("\n")
+    )))
+}
+
+/// Renders declaration sections for each distinct file among the first
+/// `max_line_refs` references, in reference order.
+fn collect_declaration_sections(
+    merged: &[(File, LineRef, RangeInclusive<usize>)],
+    max_line_refs: usize,
+) -> Result<Vec<String>> {
+    let mut seen = HashSet::new();
+    let mut sections = Vec::new();
+    for (file, _, _) in merged.iter().take(max_line_refs) {
+        if seen.insert(file.proper_name())
+            && let Some(section) = render_declarations(file)?
+        {
+            sections.push(section);
+        }
+    }
+    Ok(sections)
+}
+
 /// Calculates the width required to display line numbers for a file.
 fn line_number_width(total_lines: usize) -> usize {
     if total_lines == 0 {
@@ -400,11 +523,16 @@ fn format_numbered_block(body: &str, start_line: usize, width: usize) -> String
 }
 /// Builds an active-retrieval context using the grader output captured from
 /// stdout/stderr.
+///
+/// Fails without contacting the retrieval service when active retrieval is
+/// off: `active_retrieval` is a grader's override of the global toggle, and
+/// `None` defers to it.
 pub fn build_active_retrieval_context(
     proj: &Project,
     grader_output: String,
+    active_retrieval: Option<bool>,
 ) -> Result<ChatCompletionRequestMessage> {
-    if !config::active_retrieval_enabled() {
+    if !active_retrieval.unwrap_or_else(config::active_retrieval_enabled) {
         bail!("Active retrieval is disabled");
     }
 
@@ -430,28 +558,17 @@ pub fn build_active_retrieval_context(
 
     let mut context = Vec::new();
     for function_call_arg in function_call_args.params {
-        let file = proj.identify(&function_call_arg.class_name)?;
-        let query = format!(
-            include_str!("../queries/method_body_with_name.scm"),
-            &function_call_arg.method_name
-        );
-
-        let results = file
-            .query(&query)
-            .with_context(|| format!("Failed to query method body in {}", file.proper_name()))?;
-
-        for capture in results {
-            let body = match capture.get("body") {
-                Some(body) => body,
-                None => continue,
-            };
-            context.push(format!(
-                "Method body from student's submission for `{}#{}`:",
-                file.proper_name(),
-                function_call_arg.method_name
-            ));
-            context.push(format!("\n```\n{}\n```\n", body));
-        }
+        let Some((file, body)) =
+            proj.find_method(&function_call_arg.class_name, &function_call_arg.method_name)
+        else {
+            continue;
+        };
+        context.push(format!(
+            "Method body from student's submission for `{}#{}`:",
+            file.proper_name(),
+            function_call_arg.method_name
+        ));
+        context.push(format!("\n```\n{}\n```\n", body));
     }
 
     Ok(ChatCompletionRequestSystemMessageArgs::default()
@@ -461,6 +578,52 @@ pub fn build_active_retrieval_context(
         .into())
 }
 
+/// Builds a context message with the bodies of student methods that appear in
+/// failing test stack frames, or `None` when no such frame was found.
+///
+/// * `frames`: `(file name, method name)` pairs taken from the stack trace.
+pub fn build_failing_methods_context(
+    proj: &Project,
+    frames: &[(String, String)],
+) -> Result<Option<ChatCompletionRequestMessage>> {
+    let mut methods_by_file: MethodsByFile = HashMap::new();
+    for (file_name, method_name) in frames {
+        let Ok(file) = proj.identify(file_name) else {
+            continue;
+        };
+        if *file.kind() == FileType::Test {
+            continue;
+        }
+        methods_by_file
+            .entry(file.proper_name())
+            .or_default()
+            .insert(method_name.clone());
+    }
+
+    let sections = collect_method_body_sections(proj, &methods_by_file)?;
+    if sections.is_empty() {
+        return Ok(None);
+    }
+
+    let mut context = format!(
+        "The failing tests ran through these methods of the student's submission; start \
+         here:\n\n{}",
+        sections.join("\n")
+    );
+    if context.len() > config::PROMPT_TRUNCATE {
+        context.truncate(config::PROMPT_TRUNCATE);
+        context.push_str("...[TRUNCATED]");
+    }
+
+    Ok(Some(
+        ChatCompletionRequestSystemMessageArgs::default()
+            .content(context)
+            .name("Instructor".to_string())
+            .build()?
+            .into(),
+    ))
+}
+
 /// Builds a heuristic snippet-based context using the provided configuration.
 pub fn build_heuristic_context(
     line_refs: Vec<LineRef>,
@@ -475,11 +638,40 @@ pub fn build_heuristic_context(
                  indicates might be relevant:\n"
         .to_string();
 
+    let declaration_sections = if cfg.include_declarations {
+        collect_declaration_sections(&merged, cfg.max_line_refs)?
+    } else {
+        Vec::new()
+    };
     let (snippet_lines, method_map) = build_snippet_sections(merged, cfg)?;
     let method_sections = collect_method_body_sections(&proj, &method_map)?;
 
-    let mut context_lines = Vec::with_capacity(1 + snippet_lines.len() + method_sections.len());
+    // Declarations go first but only claim budget the snippets leave over, so
+    // they never push the diagnostic lines past the truncation point.
+    let mut budget = config::PROMPT_TRUNCATE.saturating_sub(
+        intro.len()
+            + snippet_lines
+                .iter()
+                .chain(&method_sections)
+                .map(|line| line.len() + 1)
+                .sum::<usize>(),
+    );
+    let declaration_sections: Vec<String> = declaration_sections
+        .into_iter()
+        .filter(|section| {
+            let fits = section.len() < budget;
+            if fits {
+                budget -= section.len() + 1;
+            }
+            fits
+        })
+        .collect();
+
+    let mut context_lines = Vec::with_capacity(
+        1 + declaration_sections.len() + snippet_lines.len() + method_sections.len(),
+    );
     context_lines.push(intro);
+    context_lines.extend(declaration_sections);
     context_lines.extend(snippet_lines);
     context_lines.extend(method_sections);
 
@@ -497,31 +689,23 @@ pub fn build_heuristic_context(
 }
 
 /// Backwards-compatible wrapper retaining the pre-refactor API.
+///
+/// Snippet windows and the cap on how many are rendered come from `cfg`; pass
+/// [`config::heuristic_defaults`] for the configured policy.
 pub fn get_source_context<T: Into<LineRef>>(
     line_refs: Vec<T>,
     proj: Project,
-    start_offset: usize,
-    num_lines: usize,
-    max_line_refs: usize,
+    cfg: crate::retrieval::HeuristicConfig,
     try_use_active_retrieval: bool,
     active_retrieval_context: Option<String>,
 ) -> Result<ChatCompletionRequestMessage> {
     if try_use_active_retrieval
         && let Some(ctx) = active_retrieval_context.clone()
-        && let Ok(message) = build_active_retrieval_context(&proj, ctx)
+        && let Ok(message) = build_active_retrieval_context(&proj, ctx, Some(true))
     {
         return Ok(message);
     }
 
     let line_refs: Vec<LineRef> = line_refs.into_iter().map(Into::into).collect();
-    build_heuristic_context(
-        line_refs,
-        proj,
-        crate::retrieval::HeuristicConfig {
-            start_offset,
-            num_lines,
-            max_line_refs,
-            full_file_ratio: config::heuristic_full_file_ratio(),
-        },
-    )
+    build_heuristic_context(line_refs, proj, cfg)
 }
@@ -0,0 +1,698 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::{
+    ffi::OsString,
+    fmt,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use async_openai::types::chat::{
+    ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs,
+    ChatCompletionRequestUserMessageArgs,
+};
+use bon::Builder;
+use tabled::{Table, Tabled};
+use uuid::Uuid;
+
+use super::{
+    context::get_source_context,
+    results::{Grade, GradeResult, timed},
+    tests::{DEFAULT_DOWNLOAD_TIMEOUT, download_limited},
+};
+use crate::{
+    config,
+    java::{
+        Project, ProjectPaths,
+        util::{classpath, java_path},
+    },
+    process::{self, StdinSource},
+    retrieval::HeuristicConfig,
+    types::LineRef,
+    util::{find_files, write_atomic},
+};
+
+/// JaCoCo release downloaded when its jars are missing from `lib`.
+const JACOCO_VERSION: &str = "0.8.12";
+
+/// Repository the JaCoCo jars are downloaded from.
+const MAVEN_CENTRAL: &str = "https://repo1.maven.org/maven2";
+
+/// Largest JaCoCo jar accepted from a download.
+const MAX_JACOCO_JAR_BYTES: usize = 16 * 1024 * 1024;
+
+/// File name of JaCoCo's execution data for a run.
+const COVERAGE_EXEC: &str = "jacoco.exec";
+
+/// File name of JaCoCo's CSV report.
+const COVERAGE_REPORT_CSV: &str = "jacoco.csv";
+
+/// File name of JaCoCo's XML report, which carries per-line counters.
+const COVERAGE_REPORT_XML: &str = "jacoco.xml";
+
+/// Which JaCoCo counter coverage is measured with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CoverageMetric {
+    /// Lines with at least one executed instruction.
+    #[default]
+    Line,
+    /// Both outcomes of every `if`, loop condition, `switch` case, and
+    /// boolean operator.
+    Branch,
+    /// Individual bytecode instructions.
+    Instruction,
+}
+
+impl fmt::Display for CoverageMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CoverageMetric::Line => "line",
+            CoverageMetric::Branch => "branch",
+            CoverageMetric::Instruction => "instruction",
+        })
+    }
+}
+
+impl FromStr for CoverageMetric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "line" | "lines" => Ok(CoverageMetric::Line),
+            "branch" | "branches" => Ok(CoverageMetric::Branch),
+            "instruction" | "instructions" => Ok(CoverageMetric::Instruction),
+            other => Err(anyhow!(
+                "Unknown coverage metric `{other}`; expected `line`, `branch`, or `instruction`"
+            )),
+        }
+    }
+}
+
+/// Coverage counters for one class, as reported in `jacoco.csv`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassCoverage {
+    /// Class name qualified by its package, e.g. `calc.Calc`; nested classes
+    /// are written `calc.Calc.Inner`.
+    pub class:               String,
+    /// Instructions never executed.
+    pub instruction_missed:  u64,
+    /// Instructions executed at least once.
+    pub instruction_covered: u64,
+    /// Branch outcomes never taken.
+    pub branch_missed:       u64,
+    /// Branch outcomes taken at least once.
+    pub branch_covered:      u64,
+    /// Lines with no executed instruction.
+    pub line_missed:         u64,
+    /// Lines with at least one executed instruction.
+    pub line_covered:        u64,
+}
+
+impl ClassCoverage {
+    /// Returns `(covered, missed)` for `metric`.
+    pub fn counts(&self, metric: CoverageMetric) -> (u64, u64) {
+        match metric {
+            CoverageMetric::Line => (self.line_covered, self.line_missed),
+            CoverageMetric::Branch => (self.branch_covered, self.branch_missed),
+            CoverageMetric::Instruction => (self.instruction_covered, self.instruction_missed),
+        }
+    }
+}
+
+/// Whether `class` is `target` itself or a class nested in it.
+fn is_part_of(class: &str, target: &str) -> bool {
+    class == target
+        || class
+            .strip_prefix(target)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// Parses the rows of a JaCoCo CSV report, skipping its header.
+pub fn parse_coverage_csv(csv: &str) -> Result<Vec<ClassCoverage>> {
+    csv.lines()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let columns: Vec<&str> = line.trim_end().split(',').collect();
+            if columns.len() < 9 {
+                bail!("Line {} of the coverage report has too few columns: {line}", index + 1);
+            }
+            let count = |column: usize| {
+                columns[column].parse::<u64>().with_context(|| {
+                    format!("Line {} of the coverage report has a bad count: {line}", index + 1)
+                })
+            };
+            let class = match columns[1] {
+                "" => columns[2].to_string(),
+                package => format!("{package}.{}", columns[2]),
+            };
+            Ok(ClassCoverage {
+                class,
+                instruction_missed: count(3)?,
+                instruction_covered: count(4)?,
+                branch_missed: count(5)?,
+                branch_covered: count(6)?,
+                line_missed: count(7)?,
+                line_covered: count(8)?,
+            })
+        })
+        .collect()
+}
+
+/// Lines of one source file that `metric` counts as not covered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UncoveredLines {
+    /// Top-level class the source file declares, qualified by its package,
+    /// e.g. `calc.Calc` for `calc/Calc.java`.
+    pub class:  String,
+    /// 1-based line ranges, in order. A range runs until the next line with
+    /// covered code, so blank lines and comments do not split it.
+    pub ranges: Vec<RangeInclusive<usize>>,
+}
+
+/// Collects the uncovered line ranges of every source file in a JaCoCo XML
+/// report.
+///
+/// A line is uncovered for [`CoverageMetric::Line`] when none of its
+/// instructions ran, for [`CoverageMetric::Instruction`] when any did not,
+/// and for [`CoverageMetric::Branch`] when any of its branch outcomes was
+/// never taken. Files with nothing uncovered are left out. Fails if the
+/// report is not valid XML or a line is missing one of its counters.
+pub fn uncovered_lines(xml: &str, metric: CoverageMetric) -> Result<Vec<UncoveredLines>> {
+    // JaCoCo reports declare a DTD, which roxmltree rejects by default.
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let report = roxmltree::Document::parse_with_options(xml, options)
+        .context("The coverage report is not valid XML")?;
+
+    let mut files = Vec::new();
+    for package in report
+        .descendants()
+        .filter(|node| node.has_tag_name("package"))
+    {
+        let package_name = package
+            .attribute("name")
+            .unwrap_or_default()
+            .replace('/', ".");
+        for source in package
+            .children()
+            .filter(|node| node.has_tag_name("sourcefile"))
+        {
+            let stem = source
+                .attribute("name")
+                .unwrap_or_default()
+                .trim_end_matches(".java");
+            let class = if package_name.is_empty() {
+                stem.to_string()
+            } else {
+                format!("{package_name}.{stem}")
+            };
+
+            let mut ranges = Vec::new();
+            let mut open: Option<RangeInclusive<usize>> = None;
+            for line in source.children().filter(|node| node.has_tag_name("line")) {
+                let count = |name: &str| -> Result<usize> {
+                    line.attribute(name)
+                        .with_context(|| format!("A line of {class} has no `{name}` counter"))?
+                        .parse()
+                        .with_context(|| format!("A line of {class} has a bad `{name}` counter"))
+                };
+                let number = count("nr")?;
+                let missed = match metric {
+                    CoverageMetric::Line => count("ci")? == 0 && count("mi")? > 0,
+                    CoverageMetric::Instruction => count("mi")? > 0,
+                    CoverageMetric::Branch => count("mb")? > 0,
+                };
+                if missed {
+                    open = Some(match open.take() {
+                        Some(range) => *range.start()..=number,
+                        None => number..=number,
+                    });
+                } else if let Some(range) = open.take() {
+                    ranges.push(range);
+                }
+            }
+            ranges.extend(open);
+
+            if !ranges.is_empty() {
+                files.push(UncoveredLines { class, ranges });
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Renders `ranges` as `10-12, 16`.
+fn describe_ranges(ranges: &[RangeInclusive<usize>]) -> String {
+    ranges
+        .iter()
+        .map(|range| {
+            if range.start() == range.end() {
+                range.start().to_string()
+            } else {
+                format!("{}-{}", range.start(), range.end())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// One row of the coverage summary table.
+#[derive(Tabled)]
+struct CoverageRow {
+    /// Class name.
+    #[tabled(rename = "Class")]
+    class:    String,
+    /// Covered items of the chosen metric.
+    #[tabled(rename = "Covered")]
+    covered:  u64,
+    /// Missed items of the chosen metric.
+    #[tabled(rename = "Missed")]
+    missed:   u64,
+    /// Covered share, as a percentage.
+    #[tabled(rename = "Coverage")]
+    coverage: String,
+}
+
+/// Share of `covered` out of `covered + missed`; nothing to cover counts as
+/// fully covered.
+fn ratio(covered: u64, missed: u64) -> f64 {
+    if covered + missed == 0 {
+        1.0
+    } else {
+        covered as f64 / (covered + missed) as f64
+    }
+}
+
+/// Returns true for the JaCoCo agent jar, under its release or Maven name.
+fn is_agent_jar(name: &str) -> bool {
+    name.starts_with("jacocoagent")
+        || (name.starts_with("org.jacoco.agent") && name.contains("runtime"))
+}
+
+/// Returns true for the JaCoCo command-line jar, under its release or Maven
+/// name.
+fn is_cli_jar(name: &str) -> bool {
+    name.starts_with("jacococli") || (name.starts_with("org.jacoco.cli") && name.contains("nodeps"))
+}
+
+/// Finds the JaCoCo agent and command-line jars in the project's `lib`
+/// directory, next to the JUnit and PIT jars. Whichever is missing is
+/// downloaded from Maven Central into `lib` first, so later runs reuse it.
+async fn jacoco_jars(paths: &ProjectPaths) -> Result<(PathBuf, PathBuf)> {
+    let jars = if paths.lib_dir().exists() {
+        find_files("jar", 2, paths.lib_dir())?
+    } else {
+        Vec::new()
+    };
+    let find = |matches: fn(&str) -> bool| {
+        jars.iter()
+            .find(|jar| {
+                jar.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(matches)
+            })
+            .cloned()
+    };
+
+    let agent = match find(is_agent_jar) {
+        Some(jar) => jar,
+        None => download_jacoco_jar(paths, "org.jacoco.agent", "runtime").await?,
+    };
+    let cli = match find(is_cli_jar) {
+        Some(jar) => jar,
+        None => download_jacoco_jar(paths, "org.jacoco.cli", "nodeps").await?,
+    };
+    Ok((agent, cli))
+}
+
+/// Downloads the `classifier` jar of JaCoCo's `artifact` into the project's
+/// `lib` directory and returns its path.
+async fn download_jacoco_jar(
+    paths: &ProjectPaths,
+    artifact: &str,
+    classifier: &str,
+) -> Result<PathBuf> {
+    let file_name = format!("{artifact}-{JACOCO_VERSION}-{classifier}.jar");
+    let url = format!("{MAVEN_CENTRAL}/org/jacoco/{artifact}/{JACOCO_VERSION}/{file_name}");
+    let lib_dir = paths.lib_dir();
+
+    let bytes = download_limited(&url, MAX_JACOCO_JAR_BYTES, DEFAULT_DOWNLOAD_TIMEOUT, "JaCoCo")
+        .await
+        .with_context(|| {
+            format!(
+                "{file_name} is not in {} and could not be downloaded; add it there to run the \
+                 coverage grader",
+                lib_dir.display()
+            )
+        })?;
+    std::fs::create_dir_all(lib_dir)
+        .with_context(|| format!("Failed to create {}", lib_dir.display()))?;
+    let path = lib_dir.join(file_name);
+    write_atomic(&path, bytes)?;
+    Ok(path)
+}
+
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+/// Runs tests under the ![JaCoCo](https://www.jacoco.org/jacoco/) agent and
+/// grades in proportion to how much of the target classes they cover.
+pub struct CoverageGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name:       String,
+    /// points to give for full coverage
+    #[builder(getter)]
+    pub out_of:         f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:        Project,
+    /// classes whose coverage is measured, e.g. `calc.Calc`; classes nested
+    /// in them count too
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub target_classes: Vec<String>,
+    /// test classes to run
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub test_files:     Vec<String>,
+    /// coverage, from 0 to 1, below which the grade is zero. Optional,
+    /// default is 0
+    #[builder(default)]
+    #[builder(getter)]
+    pub min_coverage:   f64,
+    /// counter coverage is measured with. Optional, default is
+    /// [`CoverageMetric::Line`]
+    #[builder(default)]
+    #[builder(getter)]
+    pub metric:         CoverageMetric,
+    /// snippet windows for the uncovered-line context in the prompt.
+    /// Optional, default is [`config::heuristic_defaults`]
+    #[builder(getter)]
+    pub retrieval:      Option<HeuristicConfig>,
+}
+
+impl CoverageGrader {
+    /// Builds and runs the configured coverage grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move {
+            if self.target_classes.is_empty() {
+                bail!("CoverageGrader requires at least one target class");
+            }
+            if self.test_files.is_empty() {
+                bail!("CoverageGrader requires at least one test file");
+            }
+            if !(0.0..=1.0).contains(&self.min_coverage) {
+                bail!("CoverageGrader min_coverage must be between 0 and 1");
+            }
+            self.grade_coverage().await
+        })
+        .await
+    }
+
+    /// Fresh directory for one coverage run: a `coverage-<id>` directory
+    /// under the project's report directory, resolved against the project
+    /// root where the tests run.
+    fn coverage_run_dir(project: &Project) -> PathBuf {
+        let report_dir = project.paths().report_dir();
+        let report_dir = if report_dir.is_relative() {
+            project.paths().root_dir().join(report_dir)
+        } else {
+            report_dir.to_path_buf()
+        };
+        report_dir.join(format!("coverage-{}", Uuid::new_v4()))
+    }
+
+    /// Runs `test_files` under the JaCoCo agent, reports on the result, and
+    /// grades the coverage of `target_classes`.
+    ///
+    /// Coverage is measured whether or not the tests pass. A run that fails
+    /// outright (a test that does not compile, a missing report) gets a zero.
+    pub async fn grade_coverage(&self) -> Result<GradeResult> {
+        let (agent, cli) = jacoco_jars(self.project.paths()).await?;
+        let run_dir = Self::coverage_run_dir(&self.project);
+
+        let measured = self.measure(&agent, &cli, &run_dir).await;
+        // The reports have been read back, so the run leaves nothing behind.
+        let _ = std::fs::remove_dir_all(&run_dir);
+
+        match measured {
+            Ok((rows, uncovered)) => self.grade_rows(rows, uncovered),
+            Err(e) => Ok(GradeResult::builder()
+                .requirement(self.req_name.clone())
+                .grade(Grade::new(0.0, self.out_of))
+                .reason(format!("Coverage grader failed: {e}"))
+                .build()),
+        }
+    }
+
+    /// Compiles and runs the tests under the agent, then turns the execution
+    /// data into reports and reads back the target classes' rows and
+    /// uncovered lines.
+    async fn measure(
+        &self,
+        agent: &Path,
+        cli: &Path,
+        run_dir: &Path,
+    ) -> Result<(Vec<ClassCoverage>, Vec<UncoveredLines>)> {
+        std::fs::create_dir_all(run_dir)
+            .with_context(|| format!("Failed to create {}", run_dir.display()))?;
+
+        let mut selectors = Vec::with_capacity(self.test_files.len());
+        for name in &self.test_files {
+            let file = self.project.identify(name)?;
+            file.check().await?;
+            selectors.push(format!("--select-class={}", file.proper_name()));
+        }
+
+        let java = java_path().context("Failed to locate java runtime for coverage grader")?;
+        let exec = run_dir.join(COVERAGE_EXEC);
+        let includes = self
+            .target_classes
+            .iter()
+            .flat_map(|class| [class.clone(), format!("{class}$*")])
+            .collect::<Vec<_>>()
+            .join(":");
+
+        let mut test_args: Vec<OsString> = vec![
+            format!(
+                "-javaagent:{}=destfile={},includes={includes}",
+                agent.display(),
+                exec.display()
+            )
+            .into(),
+            "-cp".into(),
+            classpath(self.project.paths())
+                .context("Failed to construct classpath for coverage grader")?
+                .into(),
+            "org.junit.platform.console.ConsoleLauncher".into(),
+            "--disable-banner".into(),
+            "--disable-ansi-colors".into(),
+            "--details=summary".into(),
+        ];
+        test_args.extend(selectors.into_iter().map(OsString::from));
+        let tests = self.run_java(&java, &test_args).await?;
+        if !exec.exists() {
+            bail!(
+                "The tests produced no coverage data. Test output:\n{}{}",
+                String::from_utf8_lossy(&tests.stderr),
+                String::from_utf8_lossy(&tests.stdout)
+            );
+        }
+
+        let csv_path = run_dir.join(COVERAGE_REPORT_CSV);
+        let xml_path = run_dir.join(COVERAGE_REPORT_XML);
+        let report_args: Vec<OsString> = vec![
+            "-jar".into(),
+            cli.into(),
+            "report".into(),
+            exec.into(),
+            "--classfiles".into(),
+            self.project.paths().build_dir().into(),
+            "--sourcefiles".into(),
+            self.project.paths().source_dir().into(),
+            "--csv".into(),
+            csv_path.clone().into(),
+            "--xml".into(),
+            xml_path.clone().into(),
+        ];
+        let report = self.run_java(&java, &report_args).await?;
+        if !report.status.success() {
+            bail!(
+                "JaCoCo could not build a report:\n{}{}",
+                String::from_utf8_lossy(&report.stderr),
+                String::from_utf8_lossy(&report.stdout)
+            );
+        }
+
+        let csv = std::fs::read_to_string(&csv_path)
+            .with_context(|| format!("Could not read {}", csv_path.display()))?;
+        let xml = std::fs::read_to_string(&xml_path)
+            .with_context(|| format!("Could not read {}", xml_path.display()))?;
+        let rows = parse_coverage_csv(&csv)
+            .with_context(|| format!("While parsing {}", csv_path.display()))?;
+        let uncovered = uncovered_lines(&xml, self.metric)
+            .with_context(|| format!("While parsing {}", xml_path.display()))?;
+
+        Ok((rows, uncovered))
+    }
+
+    /// Runs `java` with `args` from the project root.
+    async fn run_java(&self, java: &Path, args: &[OsString]) -> Result<process::Collected> {
+        process::run_collect(
+            java.as_os_str(),
+            args,
+            StdinSource::Null,
+            Some(self.project.paths().root_dir()),
+            &[],
+            Some(config::java_timeout()),
+        )
+        .await
+        .context("Failed to spawn or monitor the coverage run")
+    }
+
+    /// Grades the rows belonging to `target_classes`, printing a summary
+    /// table and explaining uncovered lines in the prompt.
+    fn grade_rows(
+        &self,
+        rows: Vec<ClassCoverage>,
+        uncovered: Vec<UncoveredLines>,
+    ) -> Result<GradeResult> {
+        let targeted = |class: &str| {
+            self.target_classes
+                .iter()
+                .any(|target| is_part_of(class, target))
+        };
+        let rows: Vec<ClassCoverage> = rows
+            .into_iter()
+            .filter(|row| targeted(&row.class))
+            .collect();
+        if rows.is_empty() {
+            bail!("The coverage report has no classes matching {}", self.target_classes.join(", "));
+        }
+
+        let (covered, missed) = rows.iter().fold((0, 0), |(covered, missed), row| {
+            let (c, m) = row.counts(self.metric);
+            (covered + c, missed + m)
+        });
+        let coverage = ratio(covered, missed);
+
+        let table = Table::new(rows.iter().map(|row| {
+            let (covered, missed) = row.counts(self.metric);
+            CoverageRow {
+                class: row.class.clone(),
+                covered,
+                missed,
+                coverage: format!("{:.1}%", ratio(covered, missed) * 100.0),
+            }
+        }))
+        .to_string();
+        eprintln!("{} coverage of {} -", self.metric, self.target_classes.join(", "));
+        eprintln!("{table}");
+
+        let below = coverage < self.min_coverage;
+        let grade = if below { 0.0 } else { self.out_of * coverage };
+        let mut reason = format!(
+            "{:.1}% {} coverage of {}",
+            coverage * 100.0,
+            self.metric,
+            self.target_classes.join(", ")
+        );
+        if below {
+            reason.push_str(&format!(" (below the required {:.1}%)", self.min_coverage * 100.0));
+        }
+
+        let uncovered: Vec<UncoveredLines> = uncovered
+            .into_iter()
+            .filter(|file| targeted(&file.class))
+            .collect();
+        let prompt = if missed == 0 {
+            None
+        } else {
+            Some(self.prompt(&table, &uncovered)?)
+        };
+
+        Ok(GradeResult::builder()
+            .requirement(self.req_name.clone())
+            .grade(Grade::new(grade, self.out_of))
+            .reason(reason)
+            .maybe_prompt(prompt)
+            .build())
+    }
+
+    /// Builds a prompt listing the uncovered line ranges, with the source
+    /// around each one.
+    fn prompt(
+        &self,
+        table: &str,
+        uncovered: &[UncoveredLines],
+    ) -> Result<Vec<ChatCompletionRequestMessage>> {
+        let prompts = config::java_prompts();
+        let ranges = uncovered
+            .iter()
+            .map(|file| format!("- {}: lines {}", file.class, describe_ranges(&file.ranges)))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut messages = vec![
+            ChatCompletionRequestSystemMessageArgs::default()
+                .content(prompts.system_message().to_string())
+                .name("Instructor".to_string())
+                .build()?
+                .into(),
+            ChatCompletionRequestUserMessageArgs::default()
+                .content(format!(
+                    "Running {} left parts of {} without {} \
+                     coverage:\n\n```\n{table}\n```\n\nUncovered lines:\n{ranges}\n\nWrite tests \
+                     that exercise these lines.",
+                    self.test_files.join(", "),
+                    self.target_classes.join(", "),
+                    self.metric
+                ))
+                .name("Student".to_string())
+                .build()?
+                .into(),
+        ];
+
+        let line_refs: Vec<LineRef> = uncovered
+            .iter()
+            .flat_map(|file| {
+                file.ranges.iter().map(|range| LineRef {
+                    line_number: *range.start(),
+                    file_name:   file.class.clone(),
+                })
+            })
+            .collect();
+        if !line_refs.is_empty() {
+            messages.push(get_source_context(
+                line_refs,
+                self.project.clone(),
+                self.retrieval.unwrap_or_else(config::heuristic_defaults),
+                false,
+                None,
+            )?);
+        }
+
+        Ok(messages)
+    }
+}
+
+impl<S> CoverageGraderBuilder<S>
+where
+    S: coverage_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
@@ -0,0 +1,178 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use anyhow::Result;
+use bon::Builder;
+use tree_sitter::Node;
+
+use super::{
+    results::{GradeResult, timed},
+    static_analysis::{StaticCheck, StaticGrading, walk},
+};
+use crate::java::{Parser, Project};
+
+/// `System` streams whose writes count as console output.
+const STREAMS: &[&str] = &["System.out", "System.err"];
+
+/// Stream methods that write to the console.
+const PRINT_METHODS: &[&str] = &["print", "println", "printf", "format", "write"];
+
+/// Methods allowed to print when no others are designated.
+pub const DEFAULT_OUTPUT_METHODS: &[&str] = &["main"];
+
+/// A console print found in a Java file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintCall {
+    /// Enclosing method qualified by its type, e.g. `Main.add`; field
+    /// initializers and initializer blocks report `Main.<init>`.
+    pub method: String,
+    /// 1-based line the call starts on.
+    pub line:   usize,
+    /// The receiver and method called, e.g. `System.out.println`.
+    pub call:   String,
+}
+
+impl PrintCall {
+    /// Unqualified name of the enclosing method.
+    pub fn method_name(&self) -> &str {
+        self.method
+            .rsplit_once('.')
+            .map_or(self.method.as_str(), |(_, name)| name)
+    }
+}
+
+/// Finds every `System.out`/`System.err` print in `parser`'s tree, along with
+/// the method it appears in.
+///
+/// Lambdas count towards the method they appear in; methods of local and
+/// anonymous classes are reported on their own.
+pub fn print_calls(parser: &Parser) -> Result<Vec<PrintCall>> {
+    let source = parser.code().as_bytes();
+    let mut calls = Vec::new();
+    walk(parser, |node, scope| {
+        if node.kind() == "method_invocation"
+            && let Some(call) = print_call_text(node, source)
+        {
+            calls.push(PrintCall {
+                method: scope.method_or("<init>"),
+                line: node.start_position().row + 1,
+                call,
+            });
+        }
+        true
+    })?;
+    Ok(calls)
+}
+
+/// Returns `System.out.println`-style text when `node` prints to a console
+/// stream.
+fn print_call_text(node: Node, source: &[u8]) -> Option<String> {
+    let object = node.child_by_field_name("object")?.utf8_text(source).ok()?;
+    let name = node.child_by_field_name("name")?.utf8_text(source).ok()?;
+    let object: String = object.split_whitespace().collect();
+    (STREAMS.contains(&object.as_str()) && PRINT_METHODS.contains(&name))
+        .then(|| format!("{object}.{name}"))
+}
+
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+/// A grader that penalizes leftover debug prints: console output from methods
+/// other than `main` and any instructor-designated output methods.
+pub struct DebugPrintGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name:        String,
+    /// points to give if no stray prints are found
+    #[builder(getter)]
+    pub out_of:          f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:         Project,
+    /// files to scan for prints
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub files:           Vec<String>,
+    /// methods allowed to print, by simple (`display`) or qualified
+    /// (`Main.display`) name. Optional, default is `["main"]`
+    #[builder(
+        default = DEFAULT_OUTPUT_METHODS.iter().map(ToString::to_string).collect(),
+        with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+            iter.into_iter().map(Into::into).collect::<Vec<String>>()
+        }
+    )]
+    #[builder(getter)]
+    pub allowed_methods: Vec<String>,
+    /// points deducted per stray print. Optional, default is 1
+    #[builder(default = 1.0)]
+    #[builder(getter)]
+    pub penalty:         f64,
+}
+
+impl DebugPrintGrader {
+    /// Builds and runs the configured debug-print grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move { self.grade_debug_prints() }).await
+    }
+
+    /// Returns true when prints inside `call`'s method are expected output.
+    fn is_allowed(&self, call: &PrintCall) -> bool {
+        self.allowed_methods
+            .iter()
+            .any(|allowed| allowed == &call.method || allowed == call.method_name())
+    }
+
+    /// Scans `files` for prints outside the allowed methods and deducts
+    /// `penalty` for each one.
+    pub fn grade_debug_prints(&self) -> Result<GradeResult> {
+        StaticGrading {
+            grader:   "DebugPrintGrader",
+            req_name: &self.req_name,
+            out_of:   self.out_of,
+            penalty:  self.penalty,
+            project:  &self.project,
+            files:    &self.files,
+        }
+        .grade(self)
+    }
+}
+
+impl StaticCheck for DebugPrintGrader {
+    type Finding = PrintCall;
+
+    fn find(&self, parser: &Parser) -> Result<Vec<PrintCall>> {
+        Ok(print_calls(parser)?
+            .into_iter()
+            .filter(|call| !self.is_allowed(call))
+            .collect())
+    }
+
+    fn describe(&self, file: &str, call: &PrintCall) -> String {
+        format!("{file}:{} in {}: {}", call.line, call.method, call.call)
+    }
+
+    fn passed(&self) -> String {
+        "No debug prints outside designated output methods".to_string()
+    }
+
+    fn summary(&self, count: usize) -> String {
+        format!("{count} print statement(s) outside {}:", self.allowed_methods.join(", "))
+    }
+
+    fn advice(&self) -> String {
+        "Only designated output methods should print to the console; other prints are usually \
+         leftover debugging."
+            .to_string()
+    }
+}
+
+impl<S> DebugPrintGraderBuilder<S>
+where
+    S: debug_print_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
@@ -10,7 +10,7 @@ use bon::Builder;
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 use tabled::Tabled;
 
-use crate::types::LineRef;
+use crate::{java::parsers::parser, types::LineRef};
 
 #[derive(Tabled, Serialize, Deserialize, Builder, Clone, Debug)]
 #[builder(on(String, into))]
@@ -48,6 +48,171 @@ impl JavacDiagnostic {
     pub fn severity(&self) -> DiagnosticSeverity {
         self.severity
     }
+
+    /// Returns the line number the diagnostic points at.
+    pub fn line_number(&self) -> u32 {
+        self.line_number
+    }
+
+    /// Returns the diagnostic message.
+    pub fn message(&self) -> &str {
+        self.message.as_ref()
+    }
+}
+
+/// `java.lang` and `java.util` types novices use most, offered as
+/// suggestions alongside the project's own types.
+const COMMON_JDK_TYPES: &[&str] = &[
+    "ArrayList",
+    "Arrays",
+    "Boolean",
+    "Character",
+    "Collections",
+    "Double",
+    "HashMap",
+    "HashSet",
+    "Integer",
+    "List",
+    "Map",
+    "Math",
+    "Object",
+    "Random",
+    "Scanner",
+    "Set",
+    "String",
+    "StringBuilder",
+    "System",
+];
+
+/// Type and method names a submission could have meant, used to suggest
+/// fixes for javac's "cannot find symbol" errors.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownSymbols {
+    /// Declared type names plus [`COMMON_JDK_TYPES`], sorted and deduplicated.
+    types:   Vec<String>,
+    /// Declared method names, sorted and deduplicated.
+    methods: Vec<String>,
+}
+
+impl KnownSymbols {
+    /// Collects the given declared names; common JDK types are always
+    /// included.
+    pub fn new(
+        types: impl IntoIterator<Item = impl Into<String>>,
+        methods: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        let mut types: Vec<String> = types
+            .into_iter()
+            .map(Into::into)
+            .chain(COMMON_JDK_TYPES.iter().map(|name| name.to_string()))
+            .collect();
+        types.sort();
+        types.dedup();
+
+        let mut methods: Vec<String> = methods.into_iter().map(Into::into).collect();
+        methods.sort();
+        methods.dedup();
+
+        Self { types, methods }
+    }
+
+    /// Suggests the closest known name for an unresolved symbol of javac's
+    /// `kind` (`class`, `method`, or `variable`), if one is close enough to be
+    /// a likely typo. Variables are matched against types, since `Sytem.out`
+    /// reports `Sytem` as a variable.
+    pub fn suggest(&self, kind: &str, name: &str) -> Option<&str> {
+        let candidates = match kind {
+            "method" => &self.methods,
+            "class" | "variable" => &self.types,
+            _ => return None,
+        };
+        let limit = (name.chars().count() / 3).max(1);
+
+        candidates
+            .iter()
+            .filter(|candidate| candidate.as_str() != name)
+            .map(|candidate| (edit_distance(candidate, name), candidate))
+            .filter(|(distance, _)| *distance <= limit)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, candidate)| candidate.as_str())
+    }
+}
+
+/// Levenshtein distance between `a` and `b`, ignoring case so that
+/// `system` is zero edits from `System`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Parses javac's `symbol:   method incremnt()` context line into the
+/// symbol's kind and bare name.
+fn unresolved_symbol(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_start().strip_prefix("symbol:")?.trim();
+    let (kind, name) = rest.split_once(char::is_whitespace)?;
+    let name = name.trim();
+    let name = name.split_once('(').map_or(name, |(name, _)| name);
+    Some((kind, name))
+}
+
+/// Appends "did you mean" hints for every "cannot find symbol" error in
+/// javac's `output` whose symbol is a likely typo of a name in `known`.
+///
+/// `diags` must be the diagnostics parsed from `output`, in order; matching
+/// messages gain the hint, and the returned output carries it on a line
+/// after javac's `symbol:` line.
+pub fn suggest_unresolved_symbols(
+    output: &str,
+    diags: &mut [JavacDiagnostic],
+    known: &KnownSymbols,
+) -> String {
+    let mut annotated = String::with_capacity(output.len());
+    let mut current: Option<usize> = None;
+    let mut next = 0;
+
+    for line in output.lines() {
+        annotated.push_str(line);
+        annotated.push('\n');
+
+        if parser::parse_diag(line).is_ok() {
+            current = (next < diags.len()).then_some(next);
+            next += 1;
+            continue;
+        }
+
+        let Some(index) = current else {
+            continue;
+        };
+        if !diags[index].message.contains("cannot find symbol") {
+            continue;
+        }
+        let Some((kind, name)) = unresolved_symbol(line) else {
+            continue;
+        };
+        if let Some(suggestion) = known.suggest(kind, name) {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            annotated.push_str(&format!("{indent}did you mean `{suggestion}`?\n"));
+            diags[index].message =
+                format!("{} `{name}`; did you mean `{suggestion}`?", diags[index].message);
+        }
+        current = None;
+    }
+
+    if !output.ends_with('\n') {
+        annotated.pop();
+    }
+    annotated
 }
 
 impl From<JavacDiagnostic> for LineRef {
@@ -1,7 +1,9 @@
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
-use anyhow::{Context, Result, bail, ensure};
+use std::{fmt, str::FromStr, sync::Arc, time::Duration};
+
+use anyhow::{Context, Result, anyhow, bail, ensure};
 use async_openai::types::chat::{
     ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs,
     ChatCompletionRequestUserMessageArgs,
@@ -10,11 +12,12 @@ use bon::Builder;
 use owo_colors::OwoColorize;
 use similar::{Algorithm, ChangeTag, utils::diff_unicode_words};
 
-use super::results::{Grade, GradeResult};
+use super::results::{Grade, GradeResult, timed};
 use crate::{
     config,
-    java::{File, JavaFileError, Project, grade::LineRef},
-    retrieval::build_context_message,
+    java::{File, JavaFileError, LineEnding, Project, RunOutput, grade::LineRef},
+    retrieval::build_context_message_with,
+    util::strip_ansi,
 };
 
 /// Filters line references down to files that exist in the discovered project.
@@ -51,7 +54,7 @@ fn truncate_with_notice(content: &str, limit: usize) -> String {
 
 /// Produces a short preview by trimming surrounding whitespace and capturing
 /// the first non-empty line.
-fn preview_trimmed(text: &str) -> String {
+pub(super) fn preview_trimmed(text: &str) -> String {
     let snippet = text.trim();
     let first_line = snippet.lines().next().unwrap_or("");
     let mut head = first_line.chars().take(80).collect::<String>();
@@ -65,6 +68,53 @@ fn preview_trimmed(text: &str) -> String {
     }
 }
 
+/// Rebuilds what an interactive run would look like in a terminal by writing
+/// each line of `input` after the prompt that asked for it.
+///
+/// Piped stdin is not echoed, so a program that prompts with `print` produces
+/// `First: Second: Sum: 7` for inputs `3` and `4`. A prompt is taken to be a
+/// `:`, `?` or `>` (plus any trailing spaces) that is not followed by a line
+/// break; the input lines are placed after the first such prompts in order,
+/// giving `First: 3\nSecond: 4\nSum: 7`. Returns `None` when there is no
+/// input or no prompt could be found.
+pub fn interleave_stdin(output: &str, input: &str) -> Option<String> {
+    let mut inputs = input.lines().peekable();
+    inputs.peek()?;
+
+    let mut transcript = String::with_capacity(output.len() + input.len());
+    let mut copied = 0;
+    let mut prompts = 0;
+    let mut chars = output.char_indices().peekable();
+
+    while let Some((index, ch)) = chars.next() {
+        if !matches!(ch, ':' | '?' | '>') {
+            continue;
+        }
+        let mut end = index + ch.len_utf8();
+        while let Some(&(next, ' ' | '\t')) = chars.peek() {
+            end = next + 1;
+            chars.next();
+        }
+        if output[end..].starts_with(['\n', '\r']) {
+            continue;
+        }
+        let Some(line) = inputs.next() else {
+            break;
+        };
+        transcript.push_str(&output[copied..end]);
+        transcript.push_str(line);
+        transcript.push('\n');
+        copied = end;
+        prompts += 1;
+    }
+
+    if prompts == 0 {
+        return None;
+    }
+    transcript.push_str(&output[copied..]);
+    Some(transcript)
+}
+
 /// Produces a preview while visualizing whitespace so mismatches stand out in
 /// grader explanations.
 fn preview_preserving_whitespace(text: &str) -> String {
@@ -85,6 +135,114 @@ fn preview_preserving_whitespace(text: &str) -> String {
     }
 }
 
+/// One step of the normalization applied to both expected and actual output
+/// before they are compared.
+///
+/// Steps run in order, so `[IgnoreCase, SortLines]` sorts lowercased lines
+/// while `[SortLines, IgnoreCase]` sorts uppercase lines first.
+#[derive(Clone)]
+pub enum Normalizer {
+    /// Removes ANSI escape sequences (colours, cursor movement).
+    StripAnsi,
+    /// Rewrites `\r\n` and lone `\r` line breaks as `\n`.
+    NormalizeLineEndings,
+    /// Removes leading and trailing whitespace from the whole text.
+    Trim,
+    /// Removes trailing whitespace from every line.
+    TrimLines,
+    /// Replaces every run of spaces and tabs with a single space.
+    CollapseWhitespace,
+    /// Lowercases the text.
+    IgnoreCase,
+    /// Sorts lines, for programs whose output order is unspecified.
+    SortLines,
+    /// A caller-supplied step.
+    Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl Normalizer {
+    /// Wraps `step` as a custom normalizer.
+    pub fn custom<F>(step: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        Normalizer::Custom(Arc::new(step))
+    }
+
+    /// Applies this step to `text`.
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Normalizer::StripAnsi => strip_ansi(text),
+            Normalizer::NormalizeLineEndings => text.replace("\r\n", "\n").replace('\r', "\n"),
+            Normalizer::Trim => text.trim().to_string(),
+            Normalizer::TrimLines => text
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Normalizer::CollapseWhitespace => text
+                .lines()
+                .map(|line| {
+                    line.split([' ', '\t'])
+                        .filter(|word| !word.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Normalizer::IgnoreCase => text.to_lowercase(),
+            Normalizer::SortLines => {
+                let mut lines: Vec<&str> = text.lines().collect();
+                lines.sort_unstable();
+                lines.join("\n")
+            }
+            Normalizer::Custom(step) => step(text),
+        }
+    }
+
+    /// Applies every step in `steps` to `text`, in order.
+    pub fn apply_all(steps: &[Normalizer], text: &str) -> String {
+        steps
+            .iter()
+            .fold(text.to_string(), |text, step| step.apply(&text))
+    }
+}
+
+impl fmt::Debug for Normalizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Normalizer::StripAnsi => f.write_str("StripAnsi"),
+            Normalizer::NormalizeLineEndings => f.write_str("NormalizeLineEndings"),
+            Normalizer::Trim => f.write_str("Trim"),
+            Normalizer::TrimLines => f.write_str("TrimLines"),
+            Normalizer::CollapseWhitespace => f.write_str("CollapseWhitespace"),
+            Normalizer::IgnoreCase => f.write_str("IgnoreCase"),
+            Normalizer::SortLines => f.write_str("SortLines"),
+            Normalizer::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl FromStr for Normalizer {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "strip_ansi" => Ok(Normalizer::StripAnsi),
+            "line_endings" | "normalize_line_endings" => Ok(Normalizer::NormalizeLineEndings),
+            "trim" => Ok(Normalizer::Trim),
+            "trim_lines" => Ok(Normalizer::TrimLines),
+            "collapse_whitespace" => Ok(Normalizer::CollapseWhitespace),
+            "ignore_case" => Ok(Normalizer::IgnoreCase),
+            "sort_lines" => Ok(Normalizer::SortLines),
+            other => Err(anyhow!(
+                "Unknown normalizer `{other}`; expected one of `strip_ansi`, `line_endings`, \
+                 `trim`, `trim_lines`, `collapse_whitespace`, `ignore_case`, or `sort_lines`"
+            )),
+        }
+    }
+}
+
 /// Normalized view of a grader output used for display and comparison.
 #[derive(Clone, Debug)]
 struct NormalizedOutput {
@@ -124,6 +282,9 @@ pub struct DiffCase {
     pub input:    Option<String>,
     /// Expected stdout/stderr from the program execution.
     pub expected: String,
+    /// Arguments passed to `main` for this case; `None` uses the grader's
+    /// `args`.
+    pub args:     Option<Vec<String>>,
 }
 
 impl DiffCase {
@@ -132,6 +293,7 @@ impl DiffCase {
         Self {
             input:    None,
             expected: expected.into(),
+            args:     None,
         }
     }
 
@@ -140,6 +302,34 @@ impl DiffCase {
         self.input = Some(input.into());
         self
     }
+
+    /// Attaches stdin made of `lines`, one per prompt the program reads, joined
+    /// with `\n`.
+    pub fn with_inputs(mut self, lines: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let lines: Vec<String> = lines.into_iter().map(Into::into).collect();
+        self.input = Some(lines.join("\n"));
+        self
+    }
+
+    /// Runs this case with `args` instead of the grader's arguments.
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args = Some(args.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl<E, I> From<(E, Option<I>)> for DiffCase
+where
+    E: Into<String>,
+    I: Into<String>,
+{
+    fn from((expected, input): (E, Option<I>)) -> Self {
+        Self {
+            input:    input.map(Into::into),
+            expected: expected.into(),
+            args:     None,
+        }
+    }
 }
 #[derive(Clone, Default, Builder)]
 #[builder(on(String, into))]
@@ -158,21 +348,25 @@ pub struct DiffGrader {
     /// Java file to run
     #[builder(getter)]
     pub file:                String,
-    /// Diff cases pairing optional stdin with expected output.
+    /// Diff cases, either [`DiffCase`]s or `(expected, input)` pairs.
     #[builder(
         default,
-        with = |iter: impl IntoIterator<
-            Item = (impl Into<String>, Option<impl Into<String>>)
-        >| iter
-            .into_iter()
-            .map(|(expected, input)| DiffCase {
-                expected: expected.into(),
-                input:    input.map(Into::into),
-            })
-            .collect::<Vec<_>>()
+        with = |iter: impl IntoIterator<Item = impl Into<DiffCase>>| {
+            iter.into_iter().map(Into::into).collect::<Vec<_>>()
+        }
     )]
     #[builder(getter)]
     pub cases:               Vec<DiffCase>,
+    /// arguments passed to `main` after the class name for every case that
+    /// does not set its own. Optional, default is none
+    #[builder(
+        default,
+        with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+            iter.into_iter().map(Into::into).collect::<Vec<String>>()
+        }
+    )]
+    #[builder(getter)]
+    pub args:                Vec<String>,
     /// ignore case when comparing
     #[builder(default)]
     #[builder(getter)]
@@ -181,36 +375,150 @@ pub struct DiffGrader {
     #[builder(default)]
     #[builder(getter)]
     pub preserve_whitespace: bool,
+    /// strip ANSI escape sequences (e.g. colours) from the program's output
+    /// before comparing
+    #[builder(default)]
+    #[builder(getter)]
+    pub strip_ansi:          bool,
+    /// normalization steps applied in order to both expected and actual
+    /// output, after those implied by `strip_ansi`, `preserve_whitespace`,
+    /// and `ignore_case`. Optional, default is empty
+    #[builder(default)]
+    #[builder(with = FromIterator::from_iter)]
+    #[builder(getter)]
+    pub normalizers:         Vec<Normalizer>,
+    /// text placed between failing cases in the prompt. Optional, default
+    /// is `"\n---\n"`
+    #[builder(default = DEFAULT_CASE_SEPARATOR.to_string())]
+    #[builder(getter)]
+    pub case_separator:      String,
+    /// heading placed above each failing case when the grader has more than
+    /// one; `{n}` is replaced by the 1-based case number and an empty label
+    /// disables headings. Optional, default is `"Case {n}"`
+    #[builder(default = DEFAULT_CASE_LABEL.to_string())]
+    #[builder(getter)]
+    pub case_label:          String,
+    /// line ending written after each line of a case's input. Optional,
+    /// default is [`LineEnding::Lf`]
+    #[builder(default)]
+    #[builder(getter)]
+    pub line_ending:         LineEnding,
+    /// compare only what the program writes to stdout; stderr is left out of
+    /// the comparison but shown in feedback. Optional, default is false
+    #[builder(default)]
+    #[builder(getter)]
+    pub stdout_only:         bool,
+    /// reference solution run on each case's input; its output replaces the
+    /// case's `expected` string. Optional, default compares against
+    /// `expected`
+    #[builder(getter)]
+    pub reference_file:      Option<String>,
+    /// how long each case may run before the program is killed and the case
+    /// fails. Optional, default is [`DEFAULT_RUN_TIMEOUT`]
+    #[builder(getter)]
+    pub timeout:             Option<Duration>,
+    /// pause before writing each line of a case's input, so programs that
+    /// prompt and flush between reads see input arrive line by line.
+    /// Optional, default writes all input at once
+    #[builder(getter)]
+    pub input_delay:         Option<Duration>,
+    /// overrides the global active-retrieval toggle when building feedback
+    /// context. Optional, default uses the global setting
+    #[builder(getter)]
+    pub active_retrieval:    Option<bool>,
 }
 
+/// Separator placed between failing cases when none is configured.
+pub const DEFAULT_CASE_SEPARATOR: &str = "\n---\n";
+
+/// Per-case heading used when none is configured.
+pub const DEFAULT_CASE_LABEL: &str = "Case {n}";
+
+/// How long each case may run when no timeout is configured.
+pub const DEFAULT_RUN_TIMEOUT: Duration = Duration::from_secs(10);
+
 impl DiffGrader {
     /// Adds a single diff case after construction.
     pub fn case(mut self, expected: impl Into<String>, input: Option<impl Into<String>>) -> Self {
         self.cases.push(DiffCase {
             expected: expected.into(),
             input:    input.map(Into::into),
+            args:     None,
         });
         self
     }
 
+    /// Appends a normalization step after construction.
+    pub fn normalizer(mut self, step: Normalizer) -> Self {
+        self.normalizers.push(step);
+        self
+    }
+
+    /// Returns the full normalization pipeline: the steps implied by the
+    /// convenience flags, followed by `normalizers`.
+    pub fn pipeline(&self) -> Vec<Normalizer> {
+        let mut steps = Vec::with_capacity(self.normalizers.len() + 3);
+        if self.strip_ansi {
+            steps.push(Normalizer::StripAnsi);
+        }
+        if !self.preserve_whitespace {
+            steps.push(Normalizer::Trim);
+        }
+        if self.ignore_case {
+            steps.push(Normalizer::IgnoreCase);
+        }
+        steps.extend(self.normalizers.iter().cloned());
+        steps
+    }
+
     /// Builds and runs the configured diff grader.
     pub async fn run(self) -> Result<GradeResult> {
-        if self.cases.is_empty() {
-            bail!("DiffGrader requires at least one diff case");
-        }
-        self.grade_by_diff().await
+        timed(async move {
+            if self.cases.is_empty() {
+                bail!("DiffGrader requires at least one diff case");
+            }
+            self.grade_by_diff().await
+        })
+        .await
     }
 
     /// Grades by diffing the `expected` and `actual` strings.
     pub async fn grade_by_diff(&self) -> Result<GradeResult> {
-        let file = self.resolve_target()?;
+        let timeout = self.timeout.unwrap_or(DEFAULT_RUN_TIMEOUT);
+        let pace = |file: File| match self.input_delay {
+            Some(delay) => file.with_timeout(timeout).with_input_delay(delay),
+            None => file.with_timeout(timeout),
+        };
+        let file = pace(self.resolve_target()?);
+        if file.is_empty() {
+            return Ok(GradeResult::empty_file(&self.req_name, self.out_of, file.file_name()));
+        }
+        let reference = self
+            .reference_file
+            .as_deref()
+            .map(|name| self.project.identify(name))
+            .transpose()?
+            .map(pace);
         let prompts = config::java_prompts();
+        let mut failures = Vec::new();
 
-        for case in &self.cases {
-            let expected = self.normalize_expected(case);
+        for (index, case) in self.cases.iter().enumerate() {
             let input = case.input.clone();
+            let args = case.args.as_ref().unwrap_or(&self.args);
+            let expected = match &reference {
+                Some(reference) => self.normalize_text(
+                    self.reference_output(
+                        &reference.clone().with_args(args),
+                        index + 1,
+                        input.clone(),
+                    )
+                    .await?,
+                ),
+                None => self.normalize_expected(case),
+            };
 
-            let actual_raw = match file.run_with_input(input.clone()).await {
+            let case_file = file.clone().with_args(args);
+            let (actual_raw, stderr) = match self.run_case(&case_file, input.clone()).await {
                 Ok(out) => out,
                 Err(JavaFileError::AtRuntime { output, diags }) => {
                     return self.execution_failure(
@@ -228,6 +536,30 @@ impl DiffGrader {
                         Some(filter_known_refs(&self.project, diags)),
                     );
                 }
+                Err(e @ JavaFileError::NoMainMethod { .. }) => {
+                    return self.execution_failure(
+                        &prompts,
+                        "File has no main method to run.",
+                        e.to_string(),
+                        None,
+                    );
+                }
+                Err(JavaFileError::TimedOut {
+                    partial_output,
+                    timeout,
+                }) => {
+                    let seconds = timeout.as_secs_f64();
+                    return self.execution_failure(
+                        &prompts,
+                        &format!("Case {} did not finish within {seconds}s.", index + 1),
+                        format!(
+                            "The program was stopped after {seconds}s, possibly because of an \
+                             infinite loop or because it is waiting for more input. Output before \
+                             it was stopped -\n```\n{partial_output}\n```"
+                        ),
+                        None,
+                    );
+                }
                 Err(e) => {
                     return self.execution_failure(
                         &prompts,
@@ -239,13 +571,19 @@ impl DiffGrader {
             };
 
             let actual = self.normalize_actual(actual_raw);
-            if let Some(failure) = self.compare_outputs(&file, &expected, &actual, input.as_deref())
+            if let Some(failure) =
+                self.compare_outputs(&file, &expected, &actual, input.as_deref(), stderr.as_deref())
             {
-                eprintln!("{}", failure.console_output);
-                return self.build_prompt_payload(&file, &prompts, failure);
+                failures.push((index + 1, failure));
             }
         }
 
+        if !failures.is_empty() {
+            let failure = self.combine_failures(failures);
+            eprintln!("{}", failure.console_output);
+            return self.build_prompt_payload(&file, &prompts, failure);
+        }
+
         Ok(GradeResult::builder()
             .requirement(self.req_name.clone())
             .grade(Grade::new(self.out_of, self.out_of))
@@ -254,6 +592,39 @@ impl DiffGrader {
             .build())
     }
 
+    /// Runs `file` on one case's input, returning the output to compare and,
+    /// when only stdout is compared, the stderr shown alongside it.
+    async fn run_case(
+        &self,
+        file: &File,
+        input: Option<String>,
+    ) -> Result<(String, Option<String>), JavaFileError> {
+        if self.stdout_only {
+            file.run_captured(input, self.line_ending)
+                .await
+                .and_then(RunOutput::check_status)
+                .map(|out| (out.stdout, Some(out.stderr)))
+        } else {
+            file.run_with_input_ending(input, self.line_ending)
+                .await
+                .map(|out| (out, None))
+        }
+    }
+
+    /// Runs the reference solution on a case's input to produce its expected
+    /// output. A failing reference is a grading error, not a student failure.
+    async fn reference_output(
+        &self,
+        reference: &File,
+        case_num: usize,
+        input: Option<String>,
+    ) -> Result<String> {
+        let (output, _) = self.run_case(reference, input).await.with_context(|| {
+            format!("Reference solution {} failed on case {case_num}", reference.file_name())
+        })?;
+        Ok(output)
+    }
+
     /// Builds a failing `GradeResult` with the supplied reason and prompt
     /// messages.
     fn failure_result(
@@ -283,14 +654,19 @@ impl DiffGrader {
                 .build()?
                 .into(),
             ChatCompletionRequestUserMessageArgs::default()
-                .content(body)
+                .content(body.clone())
                 .name("Student".to_string())
                 .build()?
                 .into(),
         ];
 
         if let Some(diags) = diags {
-            messages.push(build_context_message(&self.project, None, diags)?);
+            messages.push(build_context_message_with(
+                &self.project,
+                Some(body),
+                diags,
+                self.active_retrieval,
+            )?);
         }
 
         Ok(messages)
@@ -316,18 +692,20 @@ impl DiffGrader {
         self.normalize_text(raw)
     }
 
-    /// Applies whitespace/case rules to produce a reusable normalized payload.
+    /// Runs the pipeline for comparison, keeping a lightly cleaned copy for
+    /// display.
     fn normalize_text(&self, text: String) -> NormalizedOutput {
-        let display = if self.preserve_whitespace {
-            text
+        let compare = Normalizer::apply_all(&self.pipeline(), &text);
+
+        let display = if self.strip_ansi {
+            strip_ansi(&text)
         } else {
-            text.trim().to_string()
+            text
         };
-
-        let compare = if self.ignore_case {
-            display.to_lowercase()
+        let display = if self.preserve_whitespace {
+            display
         } else {
-            display.clone()
+            display.trim().to_string()
         };
 
         NormalizedOutput { display, compare }
@@ -350,6 +728,7 @@ impl DiffGrader {
         expected: &NormalizedOutput,
         actual: &NormalizedOutput,
         input: Option<&str>,
+        stderr: Option<&str>,
     ) -> Option<DiffFailure> {
         let diff = diff_unicode_words(Algorithm::Patience, expected.compare(), actual.compare());
 
@@ -388,14 +767,32 @@ impl DiffGrader {
             return None;
         }
 
+        // For programs that prompt for several inputs, show each input next
+        // to its prompt rather than as one opaque string.
         let input_section = match input {
-            Some(value) if !value.is_empty() => format!("\nInput:\n`{}`\n", value),
+            Some(value) if !value.is_empty() => match interleave_stdin(actual.display(), value) {
+                Some(conversation) => format!(
+                    "\nInput:\n`{}`\nConversation (actual output with the input filled in):\n{}\n",
+                    value,
+                    conversation.trim_end()
+                ),
+                None => format!("\nInput:\n`{}`\n", value),
+            },
+            _ => String::new(),
+        };
+
+        // Stderr left out of a stdout-only comparison still explains crashes
+        // and warnings, so it is shown after the diff.
+        let stderr_section = match stderr.map(str::trim_end) {
+            Some(value) if !value.is_empty() => {
+                format!("Standard error (not compared):\n{value}\n")
+            }
             _ => String::new(),
         };
 
         let console_output = format!(
             "Comparing expected and actual output for \
-             {}:\n```{input_section}Expected:\n{}\nActual:\n{}\n```\n",
+             {}:\n```{input_section}Expected:\n{}\nActual:\n{}\n{stderr_section}```\n",
             file.file_name(),
             colored_expected,
             colored_actual,
@@ -404,7 +801,7 @@ impl DiffGrader {
 
         let prompt_body = format!(
             "Comparing expected and actual output for \
-             {}:\n```{input_section}Expected:\n{}\nActual:\n{}\n```\n",
+             {}:\n```{input_section}Expected:\n{}\nActual:\n{}\n{stderr_section}```\n",
             file.file_name(),
             plain_expected,
             plain_actual,
@@ -434,6 +831,45 @@ impl DiffGrader {
         })
     }
 
+    /// Returns the heading for case `number`, or `None` when headings are
+    /// disabled or there is only one case.
+    fn label_for(&self, number: usize) -> Option<String> {
+        (self.cases.len() > 1 && !self.case_label.is_empty())
+            .then(|| self.case_label.replace("{n}", &number.to_string()))
+    }
+
+    /// Merges every failing case into one failure: prompt bodies are labelled
+    /// and joined with `case_separator`, and the reason reports the first
+    /// mismatch.
+    fn combine_failures(&self, failures: Vec<(usize, DiffFailure)>) -> DiffFailure {
+        let failed = failures.len();
+        let mut reason = None;
+        let mut console = Vec::with_capacity(failed);
+        let mut bodies = Vec::with_capacity(failed);
+
+        for (number, failure) in failures {
+            let label = self.label_for(number);
+            let labelled = |text: String| match &label {
+                Some(label) => format!("{label}\n{text}"),
+                None => text,
+            };
+            console.push(labelled(failure.console_output));
+            bodies.push(labelled(failure.prompt_body));
+            reason.get_or_insert(failure.reason);
+        }
+
+        let mut reason = reason.unwrap_or_default();
+        if failed > 1 {
+            reason.push_str(&format!(" ({failed} of {} cases failed)", self.cases.len()));
+        }
+
+        DiffFailure {
+            console_output: console.join(&self.case_separator),
+            prompt_body: bodies.join(&self.case_separator),
+            reason,
+        }
+    }
+
     /// Converts execution errors into a failing grade result with helpful
     /// context.
     fn execution_failure(
@@ -455,6 +891,7 @@ impl DiffGrader {
         prompts: &crate::java::JavaPrompts,
         failure: DiffFailure,
     ) -> Result<GradeResult> {
+        let grader_output = failure.prompt_body.clone();
         let mut user_content = failure.prompt_body;
         let mut appended_note = false;
 
@@ -476,7 +913,12 @@ impl DiffGrader {
             user_content = truncate_with_notice(&user_content, config::PROMPT_TRUNCATE);
         }
 
-        let retrieval_message = build_context_message(&self.project, None, Vec::<LineRef>::new())?;
+        let retrieval_message = build_context_message_with(
+            &self.project,
+            Some(grader_output),
+            Vec::<LineRef>::new(),
+            self.active_retrieval,
+        )?;
 
         let system_message = ChatCompletionRequestSystemMessageArgs::default()
             .content(prompts.system_message().to_string())
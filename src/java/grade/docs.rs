@@ -11,46 +11,108 @@ use tabled::{
     settings::{Alignment, Modify, Panel, Style, Width, object::Rows},
 };
 
-use super::results::{Grade, GradeResult};
+use super::results::{Grade, GradeResult, timed};
 use crate::{
     config,
     java::{JavaFileError, Project, parsers::parser},
-    retrieval::build_context_message,
+    retrieval::build_context_message_with,
 };
+/// How the per-nit `penalty` accumulates as documentation nits pile up.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PenaltyMode {
+    /// Every nit costs `penalty` points.
+    #[default]
+    Linear,
+    /// Every nit costs `penalty` points, up to the given total.
+    Capped(f64),
+    /// Total deduction is `penalty * log2(1 + nits)`: the first nit costs
+    /// `penalty`, and each further doubling of nits costs `penalty` more.
+    Logarithmic,
+}
+
+impl PenaltyMode {
+    /// Total deduction for `nits` documentation nits at `penalty` points each.
+    pub fn total(&self, nits: usize, penalty: f64) -> f64 {
+        let count = nits as f64;
+        match self {
+            PenaltyMode::Linear => count * penalty,
+            PenaltyMode::Capped(max) => (count * penalty).min(*max),
+            PenaltyMode::Logarithmic => penalty * (1.0 + count).log2(),
+        }
+    }
+
+    /// Describes the deduction applied for `nits` nits, suitable for a grade
+    /// reason.
+    pub fn describe(&self, nits: usize, penalty: f64) -> String {
+        if nits == 0 {
+            return "No documentation nits found.".to_string();
+        }
+
+        let total = round_points(self.total(nits, penalty));
+        let penalty = round_points(penalty);
+        match self {
+            PenaltyMode::Linear => format!("-{total} for {nits} nits ({penalty} each)"),
+            PenaltyMode::Capped(max) => format!(
+                "-{total} for {nits} nits ({penalty} each, capped at {})",
+                round_points(*max)
+            ),
+            PenaltyMode::Logarithmic => {
+                format!("-{total} for {nits} nits ({penalty} * log2(1 + nits))")
+            }
+        }
+    }
+}
+
+/// Rounds a point value to two decimals so curved penalties print cleanly.
+fn round_points(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
 #[derive(Clone, Builder)]
 #[builder(on(String, into))]
 /// A struct representing arguments to grade_docs function
 pub struct DocsGrader {
     /// * `project`: the project to grade
     #[builder(getter)]
-    pub project:  Project,
+    pub project:          Project,
     /// * `files`: the files to grade
     #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
         iter.into_iter().map(Into::into).collect::<Vec<String>>()
     })]
     #[builder(getter)]
-    pub files:    Vec<String>,
+    pub files:            Vec<String>,
     /// * `out_of`: the total points for the requirement
     #[builder(getter)]
-    pub out_of:   f64,
+    pub out_of:           f64,
     /// * `req_name`: the name of the requirement
     #[builder(getter)]
-    pub req_name: String,
+    pub req_name:         String,
     /// * `penalty`: the penalty to apply for each instance of a violation.
     ///   Optional, default is 3
     #[builder(default = 3.0)]
     #[builder(getter)]
-    pub penalty:  f64,
+    pub penalty:          f64,
+    /// * `penalty_mode`: how `penalty` accumulates across nits. Optional,
+    ///   default is linear
+    #[builder(default)]
+    #[builder(getter)]
+    pub penalty_mode:     PenaltyMode,
+    /// * `active_retrieval`: overrides the global active-retrieval toggle when
+    ///   building feedback context. Optional, default uses the global setting
+    #[builder(getter)]
+    pub active_retrieval: Option<bool>,
 }
 
 impl Default for DocsGrader {
     fn default() -> Self {
         Self {
-            project:  Project::default(),
-            files:    Vec::new(),
-            out_of:   0.0,
-            req_name: String::new(),
-            penalty:  3.0,
+            project:          Project::default(),
+            files:            Vec::new(),
+            out_of:           0.0,
+            req_name:         String::new(),
+            penalty:          3.0,
+            penalty_mode:     PenaltyMode::default(),
+            active_retrieval: None,
         }
     }
 }
@@ -67,6 +129,9 @@ impl DocsGrader {
         let mut outputs = vec![];
         for name in &files {
             let file = self.project.identify(name)?;
+            if file.is_empty() {
+                return Ok(GradeResult::empty_file(&self.req_name, out_of, file.file_name()));
+            }
             let output = match file.doc_check().await {
                 Ok(o) => o,
                 Err(JavaFileError::DuringCompilation { stacktrace, diags }) => {
@@ -81,7 +146,12 @@ impl DocsGrader {
                             .name("Student".to_string())
                             .build()?
                             .into(),
-                        build_context_message(&self.project, None, diags)?,
+                        build_context_message_with(
+                            &self.project,
+                            Some(stacktrace),
+                            diags,
+                            self.active_retrieval,
+                        )?,
                     ];
 
                     return Ok(GradeResult::builder()
@@ -124,14 +194,14 @@ impl DocsGrader {
             }
         }
 
-        let penalty = diags.len() as f64 * self.penalty;
+        let num_diags = diags.len();
+        let penalty = round_points(self.penalty_mode.total(num_diags, self.penalty));
         let grade = if out_of - penalty > 0.0 {
             out_of - penalty
         } else {
             0.0
         };
 
-        let num_diags = diags.len();
         eprintln!(
             "{}",
             Table::new(&diags)
@@ -152,7 +222,12 @@ impl DocsGrader {
         );
 
         let prompt = if num_diags > 0 {
-            let context = build_context_message(&self.project, None, all_diags)?;
+            let context = build_context_message_with(
+                &self.project,
+                Some(outputs.join("\n")),
+                all_diags,
+                self.active_retrieval,
+            )?;
 
             let mut outputs = outputs
                 .iter()
@@ -189,7 +264,7 @@ impl DocsGrader {
         Ok(GradeResult::builder()
             .requirement(self.req_name.clone())
             .grade(Grade::new(grade, out_of))
-            .reason("See above.")
+            .reason(self.penalty_mode.describe(num_diags, self.penalty))
             .maybe_prompt(prompt)
             .build())
     }
@@ -198,10 +273,13 @@ impl DocsGrader {
 impl DocsGrader {
     /// Builds and runs the documentation grader.
     pub async fn run(self) -> Result<GradeResult> {
-        if self.files.is_empty() {
-            bail!("DocsGrader requires at least one file to grade");
-        }
-        self.grade_docs().await
+        timed(async move {
+            if self.files.is_empty() {
+                bail!("DocsGrader requires at least one file to grade");
+            }
+            self.grade_docs().await
+        })
+        .await
     }
 }
 
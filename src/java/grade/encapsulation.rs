@@ -0,0 +1,223 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use anyhow::Result;
+use bon::Builder;
+use tree_sitter::Node;
+
+use super::{
+    results::{GradeResult, timed},
+    static_analysis::{StaticCheck, StaticGrading, walk},
+    syntax::declared_name,
+};
+use crate::java::{Parser, Project};
+
+/// Bodies whose field declarations hold instance or class state. Interface
+/// fields are implicitly `public static final`, so they are left out.
+const STATE_BODIES: &[&str] = &["class_body", "enum_body_declarations"];
+
+/// A single field declared in a class or enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDecl {
+    /// Field name qualified by its enclosing type, e.g. `Account.balance`.
+    pub name:      String,
+    /// 1-based line the field is declared on.
+    pub line:      usize,
+    /// Keyword modifiers in source order, e.g. `["public", "static"]`.
+    /// Annotations are left out.
+    pub modifiers: Vec<String>,
+}
+
+impl FieldDecl {
+    /// Unqualified name of the field.
+    pub fn simple_name(&self) -> &str {
+        self.name
+            .rsplit_once('.')
+            .map_or(self.name.as_str(), |(_, name)| name)
+    }
+
+    /// Returns true when the field carries `modifier`.
+    pub fn has(&self, modifier: &str) -> bool {
+        self.modifiers.iter().any(|m| m == modifier)
+    }
+
+    /// Renders the field as `public static int count`-style text.
+    fn signature(&self) -> String {
+        let mut parts = self.modifiers.clone();
+        if !self.has("public") && !self.has("protected") && !self.has("private") {
+            parts.insert(0, "package-private".to_string());
+        }
+        parts.push(self.simple_name().to_string());
+        parts.join(" ")
+    }
+}
+
+/// Lists every field declared in the classes and enums of `parser`'s tree,
+/// one entry per declared variable, in source order.
+pub fn field_declarations(parser: &Parser) -> Result<Vec<FieldDecl>> {
+    let source = parser.code().as_bytes();
+    let mut fields = Vec::new();
+    walk(parser, |node, scope| {
+        if node.kind() == "field_declaration"
+            && node
+                .parent()
+                .is_some_and(|parent| STATE_BODIES.contains(&parent.kind()))
+        {
+            let modifiers = modifier_keywords(node, source);
+            let mut cursor = node.walk();
+            for declarator in node.children_by_field_name("declarator", &mut cursor) {
+                if let Some(name) = declared_name(declarator, source) {
+                    fields.push(FieldDecl {
+                        name:      scope.qualify(&name),
+                        line:      declarator.start_position().row + 1,
+                        modifiers: modifiers.clone(),
+                    });
+                }
+            }
+        }
+        true
+    })?;
+    Ok(fields)
+}
+
+/// Keyword modifiers (`public`, `static`, ...) of a declaration, skipping
+/// annotations.
+fn modifier_keywords(node: Node, source: &[u8]) -> Vec<String> {
+    let mut cursor = node.walk();
+    let Some(modifiers) = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "modifiers")
+    else {
+        return Vec::new();
+    };
+
+    let mut cursor = modifiers.walk();
+    modifiers
+        .children(&mut cursor)
+        .filter(|child| !child.is_named())
+        .filter_map(|child| child.utf8_text(source).ok().map(str::to_string))
+        .collect()
+}
+
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+/// A grader that checks field encapsulation: every field should be `private`
+/// unless it is exempted as a constant, as `final`, or by name.
+pub struct EncapsulationGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name:        String,
+    /// points to give if every field is encapsulated
+    #[builder(getter)]
+    pub out_of:          f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:         Project,
+    /// files whose fields are checked
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub files:           Vec<String>,
+    /// allow non-private `static final` constants. Optional, default is true
+    #[builder(default = true)]
+    #[builder(getter)]
+    pub allow_constants: bool,
+    /// allow non-private `final` fields, whose value cannot be reassigned.
+    /// Optional, default is true
+    #[builder(default = true)]
+    #[builder(getter)]
+    pub allow_final:     bool,
+    /// fields exempt from the check, by simple (`count`) or qualified
+    /// (`Main.count`) name. Optional, default is empty
+    #[builder(
+        default,
+        with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+            iter.into_iter().map(Into::into).collect::<Vec<String>>()
+        }
+    )]
+    #[builder(getter)]
+    pub allowed_fields:  Vec<String>,
+    /// points deducted per exposed field. Optional, default is 1
+    #[builder(default = 1.0)]
+    #[builder(getter)]
+    pub penalty:         f64,
+}
+
+impl EncapsulationGrader {
+    /// Builds and runs the configured encapsulation grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move { self.grade_encapsulation() }).await
+    }
+
+    /// Returns true when `field` breaks encapsulation under this grader's
+    /// exceptions.
+    fn is_exposed(&self, field: &FieldDecl) -> bool {
+        if field.has("private") {
+            return false;
+        }
+        if self.allow_final && field.has("final") {
+            return false;
+        }
+        if self.allow_constants && field.has("static") && field.has("final") {
+            return false;
+        }
+        !self
+            .allowed_fields
+            .iter()
+            .any(|allowed| allowed == &field.name || allowed == field.simple_name())
+    }
+
+    /// Checks the fields of `files` and deducts `penalty` for each exposed
+    /// one.
+    pub fn grade_encapsulation(&self) -> Result<GradeResult> {
+        StaticGrading {
+            grader:   "EncapsulationGrader",
+            req_name: &self.req_name,
+            out_of:   self.out_of,
+            penalty:  self.penalty,
+            project:  &self.project,
+            files:    &self.files,
+        }
+        .grade(self)
+    }
+}
+
+impl StaticCheck for EncapsulationGrader {
+    type Finding = FieldDecl;
+
+    fn find(&self, parser: &Parser) -> Result<Vec<FieldDecl>> {
+        Ok(field_declarations(parser)?
+            .into_iter()
+            .filter(|field| self.is_exposed(field))
+            .collect())
+    }
+
+    fn describe(&self, file: &str, field: &FieldDecl) -> String {
+        format!("{file}:{} {}: {}", field.line, field.name, field.signature())
+    }
+
+    fn passed(&self) -> String {
+        "All fields are encapsulated".to_string()
+    }
+
+    fn summary(&self, count: usize) -> String {
+        format!("{count} field(s) are not private:")
+    }
+
+    fn advice(&self) -> String {
+        "Fields should be private and accessed through methods; these fields expose mutable state \
+         directly."
+            .to_string()
+    }
+}
+
+impl<S> EncapsulationGraderBuilder<S>
+where
+    S: encapsulation_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
@@ -1,7 +1,7 @@
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
-use std::fs;
+use std::path::Path;
 
 use anyhow::{Context, Result, anyhow};
 use async_openai::types::chat::ChatCompletionRequestMessage;
@@ -12,7 +12,11 @@ use tokio::{runtime::Runtime, task::block_in_place};
 use uuid::Uuid;
 
 use super::results::GradeResult;
-use crate::config;
+use crate::{config, util::write_atomic};
+
+/// File `generate_feedback` writes to when no path is given.
+pub const FEEDBACK_FILE: &str = "FEEDBACK";
+
 /// Schema for `prompts` table
 #[derive(Serialize, Debug, Builder)]
 #[builder(on(String, into))]
@@ -31,8 +35,16 @@ pub struct PromptRow {
     pub(crate) status:           String,
 }
 
-/// Generates feedback for a single `GradeResult` and posts it to the database.
+/// Generates feedback for a single `GradeResult` and posts it to the database,
+/// or, when [`config::prompt_export_dir`] is set, writes it to a local JSON
+/// file instead.
 pub(crate) fn generate_single_feedback(result: &GradeResult) -> Result<String> {
+    if result.grade_value() < result.out_of_value()
+        && let Some(dir) = config::prompt_export_dir()
+    {
+        return export_prompt_row(result, &dir);
+    }
+
     if result.grade_value() < result.out_of_value() {
         let client = config::postgrest_client().ok_or_else(|| {
             anyhow!(
@@ -76,12 +88,42 @@ pub(crate) fn generate_single_feedback(result: &GradeResult) -> Result<String> {
     }
 }
 
-/// Generates a FEEDBACK file after prompting ChatGPT for feedback on a
+/// Writes the prompt row for `result` to `<dir>/<id>.json` without touching
+/// the database, returning a feedback line that points at the file.
+fn export_prompt_row(result: &GradeResult, dir: &Path) -> Result<String> {
+    let id = Uuid::new_v4().to_string();
+    let body = PromptRow::builder()
+        .id(id.clone())
+        .maybe_messages(result.prompt.clone())
+        .requirement_name(result.requirement.clone())
+        .reason(result.reason.clone())
+        .grade(result.grade_struct().to_string())
+        .status("not_started")
+        .build();
+
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create prompt export directory {}", dir.display()))?;
+    let path = dir.join(format!("{id}.json"));
+    write_atomic(&path, serde_json::to_string_pretty(&body)?)
+        .with_context(|| format!("Failed to write prompt to {}", path.display()))?;
+
+    Ok(format!(
+        "- Prompt for `{}` (refer rubric) was saved to {}",
+        result.requirement,
+        path.display()
+    ))
+}
+
+/// Generates a feedback file after prompting ChatGPT for feedback on a
 /// collection of results.
-pub fn generate_feedback<I>(results: I) -> Result<()>
+///
+/// * `path`: where to write the feedback; defaults to [`FEEDBACK_FILE`] in the
+///   current directory.
+pub fn generate_feedback<I>(results: I, path: Option<&Path>) -> Result<()>
 where
     I: IntoIterator<Item = GradeResult>,
 {
+    let path = path.unwrap_or(Path::new(FEEDBACK_FILE));
     let mut feedback = vec!["## Understanding Your Autograder Results\n"];
 
     for result in results.into_iter() {
@@ -91,14 +133,16 @@ where
 
     if !feedback.is_empty() {
         let feedback = feedback.join("\n");
-        fs::write("FEEDBACK", &feedback).context("Something went wrong writing FEEDBACK file.")?;
+        write_atomic(path, &feedback).with_context(|| {
+            format!("Something went wrong writing feedback to {}.", path.display())
+        })?;
         eprintln!("{}", &feedback);
     } else {
-        fs::write(
-            "FEEDBACK",
+        write_atomic(
+            path,
             "This type of feedback cannot be generated for submissions without penalty.",
         )
-        .context("Something went wrong writing FEEDBACK file.")?;
+        .with_context(|| format!("Something went wrong writing feedback to {}.", path.display()))?;
     }
 
     Ok(())
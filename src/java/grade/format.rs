@@ -0,0 +1,145 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use anyhow::{Context, Result, bail};
+use bon::Builder;
+use regex::Regex;
+
+use super::{
+    diff::preview_trimmed,
+    results::{Grade, GradeResult, timed},
+};
+use crate::{config, java::Project};
+
+#[derive(Clone, Default, Builder)]
+#[builder(on(String, into))]
+/// A grader that runs a file once and checks the shape of its output: how
+/// many lines it prints and what each line looks like. The first violation
+/// results in a `0` grade.
+pub struct FormatConstraintGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name:     String,
+    /// points to give if the output satisfies every constraint
+    #[builder(getter)]
+    pub out_of:       f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:      Project,
+    /// Java file to run
+    #[builder(getter)]
+    pub file:         String,
+    /// Optional stdin supplied to the program.
+    #[builder(getter)]
+    pub input:        Option<String>,
+    /// Exact number of lines the output must contain.
+    #[builder(getter)]
+    pub line_count:   Option<usize>,
+    /// Regular expression every output line must match in full.
+    #[builder(getter)]
+    pub line_pattern: Option<String>,
+}
+
+impl FormatConstraintGrader {
+    /// Builds and runs the configured format constraint grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move {
+            if self.line_count.is_none() && self.line_pattern.is_none() {
+                bail!("FormatConstraintGrader requires a line_count or a line_pattern");
+            }
+            self.grade_format().await
+        })
+        .await
+    }
+
+    /// Runs the file and checks the line count, then each line against the
+    /// pattern. Trailing blank lines are ignored; blank lines in between
+    /// count and must match the pattern like any other line.
+    pub async fn grade_format(&self) -> Result<GradeResult> {
+        let regex = self
+            .line_pattern
+            .as_deref()
+            .map(|pattern| {
+                Regex::new(&format!("^(?:{pattern})$"))
+                    .with_context(|| format!("Invalid line pattern: {pattern}"))
+            })
+            .transpose()?;
+
+        let file = self.project.identify(&self.file)?;
+        if file.is_empty() {
+            return Ok(GradeResult::empty_file(&self.req_name, self.out_of, file.file_name()));
+        }
+
+        let output = match file.run_with_input(self.input.clone()).await {
+            Ok(out) => out,
+            Err(e) => return GradeResult::run_error(&self.req_name, self.out_of, None, e),
+        };
+
+        let output = output.trim_end();
+        let lines: Vec<&str> = if output.is_empty() {
+            Vec::new()
+        } else {
+            output.lines().collect()
+        };
+        let input_note = match self.input.as_deref().filter(|input| !input.is_empty()) {
+            Some(input) => format!(" (input: `{}`)", preview_trimmed(input)),
+            None => String::new(),
+        };
+
+        if let Some(expected) = self.line_count
+            && lines.len() != expected
+        {
+            return GradeResult::failure(
+                &self.req_name,
+                self.out_of,
+                config::java_prompts().system_message(),
+                format!("Expected {expected} line(s) of output{input_note}; got {}", lines.len()),
+                format!(
+                    "Running {}{input_note}, the output was expected to be exactly {expected} \
+                     line(s) long, but it was {} line(s).\n\nActual output:\n```\n{output}\n```",
+                    file.file_name(),
+                    lines.len()
+                ),
+            );
+        }
+
+        if let Some(regex) = &regex
+            && let Some((idx, line)) = lines.iter().enumerate().find(|(_, l)| !regex.is_match(l))
+        {
+            let pattern = self.line_pattern.as_deref().unwrap_or_default();
+            let line_num = idx + 1;
+            return GradeResult::failure(
+                &self.req_name,
+                self.out_of,
+                config::java_prompts().system_message(),
+                format!(
+                    "Line {line_num}{input_note} did not match `{pattern}`; got \"{}\"",
+                    preview_trimmed(line)
+                ),
+                format!(
+                    "Running {}{input_note}, every line of output was expected to match the \
+                     regular expression `{pattern}`. Line {line_num} did \
+                     not:\n```\n{line}\n```\n\nActual output:\n```\n{output}\n```",
+                    file.file_name()
+                ),
+            );
+        }
+
+        Ok(GradeResult::builder()
+            .requirement(self.req_name.clone())
+            .grade(Grade::new(self.out_of, self.out_of))
+            .reason("Output matched the expected format")
+            .maybe_prompt(None)
+            .build())
+    }
+}
+
+impl<S> FormatConstraintGraderBuilder<S>
+where
+    S: format_constraint_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
@@ -1,7 +1,7 @@
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
-use std::{collections::HashSet, fs, io::Write};
+use std::{collections::HashSet, io::Write};
 
 use anyhow::{Context, Result, ensure};
 use async_openai::{
@@ -17,17 +17,34 @@ use bon::Builder;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use tabled::{
-    Table,
-    settings::{Alignment, Modify, Panel, Style, Width, object::Rows},
+    Table, Tabled,
+    settings::{
+        Alignment, Modify, Panel, Style, Width,
+        object::{Columns, Object, Rows},
+    },
 };
 use tokio::{runtime::Runtime, task::block_in_place};
 
-use super::{feedback::generate_single_feedback, results::GradeResult};
+use super::{
+    feedback::generate_single_feedback,
+    results::{GradeResult, GradeStyle, ReasonTarget, total_grade},
+};
 use crate::{
     config::{self, OpenAiEnv},
-    java::{File, Project},
+    java::{File, Project, ProjectPaths},
+    util::write_atomic,
 };
 
+/// How much of each result the grading overview table shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableDetail {
+    /// Requirement names and grades only.
+    Summary,
+    /// Requirement names, grades, and wrapped reasons.
+    #[default]
+    Full,
+}
+
 /// Configuration options that control how Gradescope output is rendered.
 #[derive(Debug, Clone, Builder)]
 #[builder(on(String, into))]
@@ -42,6 +59,12 @@ pub struct GradescopeConfig {
         iter.into_iter().map(Into::into).collect::<Vec<String>>()
     })]
     pub test_files:          Vec<String>,
+    /// Instructor-provided files left out of SLO summaries, in addition to
+    /// files marked with [`PROVIDED_MARKER`](crate::util::PROVIDED_MARKER).
+    #[builder(default, with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    pub provided_files:      Vec<String>,
     /// Title displayed alongside SLO feedback.
     #[builder(default)]
     pub project_title:       String,
@@ -69,6 +92,20 @@ pub struct GradescopeConfig {
         iter.into_iter().map(Into::into).collect::<HashSet<String>>()
     })]
     pub enabled_slos:        HashSet<String>,
+    /// How grades are rendered in the overview table.
+    #[builder(default)]
+    pub grade_style:         GradeStyle,
+    /// Which columns the overview table shows.
+    #[builder(default)]
+    pub table_detail:        TableDetail,
+    /// Width the overview table should fit in. When unset, the terminal
+    /// width is read from `COLUMNS`; without either, reasons wrap at 24
+    /// characters.
+    pub table_width:         Option<usize>,
+    /// Whether to print a [`PredictedScore`] JSON line to stdout before any
+    /// SLO feedback is requested.
+    #[builder(default)]
+    pub emit_score:          bool,
 }
 
 impl Default for GradescopeConfig {
@@ -76,6 +113,7 @@ impl Default for GradescopeConfig {
         Self {
             source_files:        Vec::new(),
             test_files:          Vec::new(),
+            provided_files:      Vec::new(),
             project_title:       String::new(),
             project_description: String::new(),
             pass_threshold:      0.7,
@@ -84,9 +122,66 @@ impl Default for GradescopeConfig {
             feedback:            false,
             debug:               false,
             enabled_slos:        HashSet::new(),
+            grade_style:         GradeStyle::default(),
+            table_detail:        TableDetail::default(),
+            table_width:         None,
+            emit_score:          false,
         }
     }
 }
+/// Keys of the Student Learning Outcomes that can be enabled for feedback.
+pub const SLO_KEYS: [&str; 9] = [
+    "slo_algorithmic_solutions",
+    "slo_code_readability",
+    "slo_comments",
+    "slo_error_handling",
+    "slo_logic",
+    "slo_naming_conventions",
+    "slo_oop_programming",
+    "slo_syntax",
+    "slo_testing",
+];
+
+/// Resolves SLO names such as `logic` or `slo_logic` into the keys stored in
+/// [`GradescopeConfig::enabled_slos`], rejecting unknown names.
+pub fn parse_slos(names: impl IntoIterator<Item = impl AsRef<str>>) -> Result<HashSet<String>> {
+    names
+        .into_iter()
+        .map(|name| {
+            let name = name.as_ref().trim().to_lowercase();
+            let key = if name.starts_with("slo_") {
+                name.clone()
+            } else {
+                format!("slo_{name}")
+            };
+            ensure!(
+                SLO_KEYS.contains(&key.as_str()),
+                "Unknown SLO `{name}`; expected one of: {}",
+                SLO_KEYS
+                    .iter()
+                    .map(|k| k.trim_start_matches("slo_"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            Ok(key)
+        })
+        .collect()
+}
+
+/// Machine-readable summary of the final score, printed as one JSON line
+/// before the (slow) SLO feedback step so harnesses can act on it early.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PredictedScore {
+    /// Always `predicted_score`, so the line can be picked out of other output.
+    pub event:  String,
+    /// Sum of requirement grades.
+    pub score:  f64,
+    /// Sum of requirement maximums, excluding extra credit.
+    pub out_of: f64,
+    /// Whether the score clears the configured pass threshold.
+    pub passed: bool,
+}
+
 /// Represents output format settings for Gradescope submissions.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -359,13 +454,29 @@ async fn generate_combined_slo_report(
         .ok_or_else(|| anyhow::anyhow!("No content in OpenAI response"))
 }
 
+/// Resolves `names` to the project files whose code goes into SLO feedback,
+/// skipping names that don't resolve and instructor-provided files: those
+/// named in `provided` or marked with
+/// [`PROVIDED_MARKER`](crate::util::PROVIDED_MARKER).
+pub fn slo_input_files(project: &Project, names: &[String], provided: &[String]) -> Vec<File> {
+    let provided: Vec<File> = provided
+        .iter()
+        .filter_map(|name| project.identify(name).ok())
+        .collect();
+
+    names
+        .iter()
+        .filter_map(|name| project.identify(name).ok())
+        .filter(|file| !file.is_provided() && !provided.contains(file))
+        .collect()
+}
+
 /// Generates SLO responses for a given project.
 ///
 /// # Arguments
 ///
-/// * `project` - The project for which to generate SLO responses.
-/// * `source_files` - A list of source files in the project.
-/// * `test_files` - A list of test files in the project.
+/// * `source_files` - Source files to review, from [`slo_input_files`].
+/// * `test_files` - Test files to review, from [`slo_input_files`].
 /// * `project_title` - The title of the project.
 /// * `project_description` - A description of the project.
 ///
@@ -374,9 +485,8 @@ async fn generate_combined_slo_report(
 /// A vector of tuples containing the SLO name and the result of the SLO
 /// response.
 async fn generate_slo_responses(
-    project: &Project,
-    source_files: &[String],
-    test_files: &[String],
+    source_files: &[File],
+    test_files: &[File],
     project_title: &str,
     project_description: &str,
     enabled_slos: &HashSet<String>,
@@ -432,20 +542,10 @@ async fn generate_slo_responses(
             continue;
         }
 
-        let relevant_files: Vec<File> = match slo_file_type {
-            SLOFileType::Source => source_files
-                .iter()
-                .filter_map(|x| project.identify(x).ok())
-                .collect(),
-            SLOFileType::Test => test_files
-                .iter()
-                .filter_map(|x| project.identify(x).ok())
-                .collect(),
-            SLOFileType::SourceAndTest => source_files
-                .iter()
-                .chain(test_files.iter())
-                .filter_map(|x| project.identify(x).ok())
-                .collect(),
+        let relevant_files: Vec<&File> = match slo_file_type {
+            SLOFileType::Source => source_files.iter().collect(),
+            SLOFileType::Test => test_files.iter().collect(),
+            SLOFileType::SourceAndTest => source_files.iter().chain(test_files).collect(),
         };
 
         let relevant_file_codes: Vec<String> = relevant_files
@@ -516,71 +616,211 @@ async fn generate_slo_responses(
     Ok(slo_responses)
 }
 
+/// One overview-table row, with the grade pre-rendered in the configured
+/// style.
+#[derive(Tabled)]
+struct OverviewRow<'a> {
+    /// Requirement ID.
+    #[tabled(rename = "Requirement")]
+    requirement: String,
+    /// Formatted grade.
+    #[tabled(rename = "Grade")]
+    grade:       String,
+    /// Reason for penalties, if any.
+    #[tabled(rename = "Reason")]
+    reason:      &'a str,
+}
+
+/// Row of the overview table in [`TableDetail::Summary`] mode.
+#[derive(Tabled)]
+struct SummaryRow {
+    /// Requirement ID.
+    #[tabled(rename = "Requirement")]
+    requirement: String,
+    /// Formatted grade.
+    #[tabled(rename = "Grade")]
+    grade:       String,
+}
+
+/// Narrowest wrap width for overview table cells.
+const MIN_CELL_WIDTH: usize = 24;
+
+/// Characters taken by the borders and padding of a three-column table.
+const TABLE_CHROME_WIDTH: usize = 10;
+
+/// Terminal width from the `COLUMNS` environment variable, if set.
+fn terminal_width() -> Option<usize> {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns > 0)
+}
+
+/// Requirement name as shown in the overview table.
+fn overview_requirement(result: &GradeResult) -> String {
+    if result.extra_credit {
+        format!("{} (extra credit)", result.requirement)
+    } else {
+        result.requirement.clone()
+    }
+}
+
+/// Renders the "Grading Overview" table shown by [`show_result`], using the
+/// detail level, grade style, and width from `config`.
+pub fn overview_table(results: &[GradeResult], config: &GradescopeConfig) -> String {
+    let style = &config.grade_style;
+    let total = total_grade(results).format(style.clone());
+
+    let mut table = match config.table_detail {
+        TableDetail::Summary => Table::new(results.iter().map(|r| SummaryRow {
+            requirement: overview_requirement(r),
+            grade:       r.grade.format(style.clone()),
+        })),
+        TableDetail::Full => Table::new(results.iter().map(|r| OverviewRow {
+            requirement: overview_requirement(r),
+            grade:       r.grade.format(style.clone()),
+            reason:      &r.reason,
+        })),
+    };
+    table
+        .with(Panel::header("Grading Overview"))
+        .with(Panel::footer(format!("Total: {total}")));
+
+    match config.table_width.or_else(terminal_width) {
+        Some(width) if config.table_detail == TableDetail::Full => {
+            // Names and grades keep the default wrap; reasons take whatever
+            // width is left so long explanations use the whole terminal.
+            let widest = |cells: Vec<String>| {
+                cells
+                    .iter()
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    .min(MIN_CELL_WIDTH)
+            };
+            let names = widest(results.iter().map(overview_requirement).collect());
+            let grades = widest(
+                results
+                    .iter()
+                    .map(|r| r.grade.format(style.clone()))
+                    .collect(),
+            );
+            let reason_width = width
+                .saturating_sub(names.max(11) + grades.max(5) + TABLE_CHROME_WIDTH)
+                .max(MIN_CELL_WIDTH);
+            table
+                .with(
+                    Modify::new(Rows::new(1..).intersect(Columns::new(..2)))
+                        .with(Width::wrap(MIN_CELL_WIDTH).keep_words(true)),
+                )
+                .with(
+                    Modify::new(Rows::new(1..).intersect(Columns::single(2)))
+                        .with(Width::wrap(reason_width).keep_words(true)),
+                );
+        }
+        _ => {
+            table.with(
+                Modify::new(Rows::new(1..)).with(Width::wrap(MIN_CELL_WIDTH).keep_words(true)),
+            );
+        }
+    }
+
+    table
+        .with(
+            Modify::new(Rows::first())
+                .with(Alignment::center())
+                .with(Alignment::center_vertical()),
+        )
+        .with(
+            Modify::new(Rows::last())
+                .with(Alignment::center())
+                .with(Alignment::center_vertical()),
+        )
+        .with(Style::modern())
+        .to_string()
+}
+
 /// Print grade results to stderr and optionally emit a Gradescope JSON
 /// artifact.
 ///
 /// * `results`: collection of requirement-level grades to render.
 /// * `config`: strongly typed configuration that replaces the legacy Rhai map.
-pub fn show_result(results: Vec<GradeResult>, config: GradescopeConfig) -> Result<()> {
+/// * `paths`: the graded project's paths, read for SLO feedback.
+pub fn show_result(
+    results: Vec<GradeResult>,
+    config: GradescopeConfig,
+    paths: &ProjectPaths,
+) -> Result<()> {
     let show_table = config.show_table;
     let gradescope_json = config.results_json;
     let gradescope_feedback = config.feedback;
     let gradescope_debug = config.debug;
     let pass_threshold = config.pass_threshold;
-    let source_files = config.source_files.clone();
-    let test_files = config.test_files.clone();
     let project_title = config.project_title.clone();
     let project_description = config.project_description.clone();
     let enabled_slos = config.enabled_slos.clone();
 
-    let (grade, out_of) = results
-        .iter()
-        .fold((0f64, 0f64), |acc, r| (acc.0 + r.grade_value(), acc.1 + r.out_of_value()));
+    let total = total_grade(&results);
+    let (grade, out_of) = (total.grade, total.out_of);
 
     if show_table {
-        eprintln!(
-            "{}",
-            Table::new(&results)
-                .with(Panel::header("Grading Overview"))
-                .with(Panel::footer(format!("Total: {grade:.2}/{out_of:.2}")))
-                .with(Modify::new(Rows::new(1..)).with(Width::wrap(24).keep_words(true)))
-                .with(
-                    Modify::new(Rows::first())
-                        .with(Alignment::center())
-                        .with(Alignment::center_vertical()),
-                )
-                .with(
-                    Modify::new(Rows::last())
-                        .with(Alignment::center())
-                        .with(Alignment::center_vertical()),
-                )
-                .with(Style::modern())
-        );
+        eprintln!("{}", overview_table(&results, &config));
+    }
+
+    if config.emit_score {
+        let predicted = PredictedScore {
+            event: "predicted_score".to_string(),
+            score: grade,
+            out_of,
+            passed: grade > pass_threshold * out_of,
+        };
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", serde_json::to_string(&predicted)?)?;
+        stdout.flush()?;
     }
 
     if gradescope_json {
-        let project = Project::new()?;
         let mut test_cases = vec![];
         for result in &results {
-            let feedback = if gradescope_feedback {
+            let mut feedback = if gradescope_feedback {
                 generate_single_feedback(result)?
             } else {
                 String::new()
             };
-
-            let test_case = GradescopeTestCase::builder()
-                .name(result.requirement.clone())
-                .name_format(GradescopeOutputFormat::Text)
-                .max_score(result.out_of_value())
-                .score(result.grade_value())
-                .status(if result.grade_value() > pass_threshold * result.out_of_value() {
-                    GradescopeStatus::Passed
+            // Structured reasons render as Markdown lists ahead of any feedback link.
+            if let Some(details) = &result.details {
+                let details = details.render(ReasonTarget::Markdown);
+                feedback = if feedback.is_empty() {
+                    details
                 } else {
-                    GradescopeStatus::Failed
-                })
-                .output(feedback)
-                .output_format(GradescopeOutputFormat::Md)
-                .build();
+                    format!("{details}\n\n{feedback}")
+                };
+            }
+
+            // Extra credit keeps its own out_of for pass/fail but contributes
+            // nothing to Gradescope's maximum.
+            let max_score = if result.extra_credit {
+                0.0
+            } else {
+                result.out_of_value()
+            };
+            let test_case =
+                GradescopeTestCase::builder()
+                    .name(result.requirement.clone())
+                    .name_format(GradescopeOutputFormat::Text)
+                    .max_score(max_score)
+                    .score(result.grade_value())
+                    .status(if result.grade_value() > pass_threshold * result.out_of_value() {
+                        GradescopeStatus::Passed
+                    } else {
+                        GradescopeStatus::Failed
+                    })
+                    .output(feedback)
+                    .output_format(GradescopeOutputFormat::Md)
+                    .maybe_extra_data(result.elapsed().map(
+                        |elapsed| serde_json::json!({ "elapsed_secs": elapsed.as_secs_f64() }),
+                    ))
+                    .build();
 
             test_cases.push(test_case);
         }
@@ -602,12 +842,16 @@ pub fn show_result(results: Vec<GradeResult>, config: GradescopeConfig) -> Resul
                 )
             })?;
 
+            let project = Project::from_paths(paths.clone())?;
+            let source_files =
+                slo_input_files(&project, &config.source_files, &config.provided_files);
+            let test_files = slo_input_files(&project, &config.test_files, &config.provided_files);
+
             let env_ref = &openai_env;
             let slo_responses = match tokio::runtime::Handle::try_current() {
                 Ok(handle) => block_in_place(|| {
                     handle.block_on(async {
                         generate_slo_responses(
-                            &project,
                             &source_files,
                             &test_files,
                             &project_title,
@@ -622,7 +866,6 @@ pub fn show_result(results: Vec<GradeResult>, config: GradescopeConfig) -> Resul
                     .context("Failed to create Tokio runtime for SLO feedback generation")?
                     .block_on(async {
                         generate_slo_responses(
-                            &project,
                             &source_files,
                             &test_files,
                             &project_title,
@@ -664,12 +907,14 @@ pub fn show_result(results: Vec<GradeResult>, config: GradescopeConfig) -> Resul
             .visibility(GradescopeVisibility::Visible)
             .build();
 
-        let mut file = fs::File::create(if gradescope_debug {
-            "./results.json"
-        } else {
-            "/autograder/results/results.json"
-        })?;
-        file.write_all(serde_json::to_string_pretty(&submission)?.as_bytes())?;
+        write_atomic(
+            if gradescope_debug {
+                "./results.json"
+            } else {
+                "/autograder/results/results.json"
+            },
+            serde_json::to_string_pretty(&submission)?,
+        )?;
     }
 
     Ok(())
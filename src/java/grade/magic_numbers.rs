@@ -0,0 +1,224 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use anyhow::Result;
+use bon::Builder;
+use tree_sitter::Node;
+
+use super::{
+    results::{GradeResult, timed},
+    static_analysis::{StaticCheck, StaticGrading, walk},
+};
+use crate::java::{Parser, Project};
+
+/// Numeric literal node kinds.
+const NUMBER_KINDS: &[&str] = &[
+    "decimal_integer_literal",
+    "hex_integer_literal",
+    "octal_integer_literal",
+    "binary_integer_literal",
+    "decimal_floating_point_literal",
+    "hex_floating_point_literal",
+];
+
+/// Declarations that name a constant when marked `final`.
+const DECLARATION_KINDS: &[&str] = &["field_declaration", "local_variable_declaration"];
+
+/// Numbers allowed inline when no others are designated.
+pub const DEFAULT_ALLOWED_NUMBERS: &[&str] = &["0", "1"];
+
+/// A numeric literal used inline rather than through a named constant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MagicNumber {
+    /// Enclosing method qualified by its type, e.g. `Circle.area`; field
+    /// initializers and initializer blocks report `Circle.<init>`.
+    pub method:  String,
+    /// 1-based line the literal is on.
+    pub line:    usize,
+    /// The literal as written, e.g. `3.14159` or `0xFF`.
+    pub literal: String,
+    /// Numeric value of the literal, used to match it against an allowlist.
+    pub value:   Option<f64>,
+}
+
+/// Finds every numeric literal in `parser`'s tree that is not part of a named
+/// constant.
+///
+/// Initializers of `final` fields and `final` local variables, interface
+/// constants, enum constant arguments, and annotation arguments count as named
+/// and are skipped. A negated literal such as `-1` is reported by its unsigned
+/// literal.
+pub fn magic_numbers(parser: &Parser) -> Result<Vec<MagicNumber>> {
+    let source = parser.code().as_bytes();
+    let mut found = Vec::new();
+    walk(parser, |node, scope| {
+        if is_named_context(node, source) {
+            return false;
+        }
+        if NUMBER_KINDS.contains(&node.kind())
+            && let Ok(literal) = node.utf8_text(source)
+        {
+            found.push(MagicNumber {
+                method:  scope.method_or("<init>"),
+                line:    node.start_position().row + 1,
+                literal: literal.to_string(),
+                value:   literal_value(literal),
+            });
+        }
+        true
+    })?;
+    Ok(found)
+}
+
+/// Returns true when literals under `node` belong to a named constant.
+fn is_named_context(node: Node, source: &[u8]) -> bool {
+    match node.kind() {
+        "constant_declaration"
+        | "enum_constant"
+        | "annotation"
+        | "marker_annotation"
+        | "annotation_type_element_declaration" => true,
+        kind if DECLARATION_KINDS.contains(&kind) => {
+            let mut cursor = node.walk();
+            node.named_children(&mut cursor)
+                .filter(|child| child.kind() == "modifiers")
+                .any(|modifiers| {
+                    modifiers
+                        .utf8_text(source)
+                        .is_ok_and(|text| text.split_whitespace().any(|word| word == "final"))
+                })
+        }
+        _ => false,
+    }
+}
+
+/// Parses a Java numeric literal into its value, ignoring underscores and
+/// type suffixes.
+fn literal_value(literal: &str) -> Option<f64> {
+    let text = literal.replace('_', "").to_ascii_lowercase();
+    let integer = text.trim_end_matches('l');
+    let parse_radix = |digits: &str, radix: u32| {
+        i64::from_str_radix(digits, radix)
+            .ok()
+            .map(|value| value as f64)
+    };
+
+    if let Some(digits) = integer.strip_prefix("0x") {
+        parse_radix(digits, 16)
+    } else if let Some(digits) = integer.strip_prefix("0b") {
+        parse_radix(digits, 2)
+    } else if integer.len() > 1
+        && integer.starts_with('0')
+        && integer.bytes().all(|b| b.is_ascii_digit())
+    {
+        parse_radix(&integer[1..], 8)
+    } else {
+        integer.trim_end_matches(['f', 'd']).parse().ok()
+    }
+}
+
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+/// A grader that penalizes magic numbers: numeric literals used inline instead
+/// of through a named constant.
+pub struct MagicNumberGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name:        String,
+    /// points to give if no magic numbers are found
+    #[builder(getter)]
+    pub out_of:          f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:         Project,
+    /// files to scan for magic numbers
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub files:           Vec<String>,
+    /// numbers allowed inline, compared by value so `2` also allows `2.0`
+    /// and `2L`. Optional, default is `["0", "1"]`
+    #[builder(
+        default = DEFAULT_ALLOWED_NUMBERS.iter().map(ToString::to_string).collect(),
+        with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+            iter.into_iter().map(Into::into).collect::<Vec<String>>()
+        }
+    )]
+    #[builder(getter)]
+    pub allowed_numbers: Vec<String>,
+    /// points deducted per magic number. Optional, default is 1
+    #[builder(default = 1.0)]
+    #[builder(getter)]
+    pub penalty:         f64,
+}
+
+impl MagicNumberGrader {
+    /// Builds and runs the configured magic-number grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move { self.grade_magic_numbers() }).await
+    }
+
+    /// Returns true when `number` is on the allowlist.
+    fn is_allowed(&self, number: &MagicNumber) -> bool {
+        self.allowed_numbers.iter().any(|allowed| {
+            allowed == &number.literal
+                || (number.value.is_some() && literal_value(allowed) == number.value)
+        })
+    }
+
+    /// Scans `files` for literals outside named constants and deducts
+    /// `penalty` for each one not on the allowlist.
+    pub fn grade_magic_numbers(&self) -> Result<GradeResult> {
+        StaticGrading {
+            grader:   "MagicNumberGrader",
+            req_name: &self.req_name,
+            out_of:   self.out_of,
+            penalty:  self.penalty,
+            project:  &self.project,
+            files:    &self.files,
+        }
+        .grade(self)
+    }
+}
+
+impl StaticCheck for MagicNumberGrader {
+    type Finding = MagicNumber;
+
+    fn find(&self, parser: &Parser) -> Result<Vec<MagicNumber>> {
+        Ok(magic_numbers(parser)?
+            .into_iter()
+            .filter(|number| !self.is_allowed(number))
+            .collect())
+    }
+
+    fn describe(&self, file: &str, number: &MagicNumber) -> String {
+        format!("{file}:{} in {}: {}", number.line, number.method, number.literal)
+    }
+
+    fn passed(&self) -> String {
+        "No magic numbers found".to_string()
+    }
+
+    fn summary(&self, count: usize) -> String {
+        format!("{count} magic number(s):")
+    }
+
+    fn advice(&self) -> String {
+        format!(
+            "Numeric literals other than {} should be given a name with a `static final` constant \
+             instead of being used inline.",
+            self.allowed_numbers.join(", ")
+        )
+    }
+}
+
+impl<S> MagicNumberGraderBuilder<S>
+where
+    S: magic_number_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
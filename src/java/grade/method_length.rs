@@ -0,0 +1,143 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use anyhow::Result;
+use bon::Builder;
+
+use super::{
+    results::{GradeResult, timed},
+    static_analysis::{StaticCheck, StaticGrading, walk},
+    syntax::METHOD_KINDS,
+};
+use crate::java::{Parser, Project};
+
+/// Line span of a single method or constructor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodLength {
+    /// Method name qualified by its enclosing type, e.g. `Main.process`.
+    pub name:       String,
+    /// 1-based line the declaration starts on.
+    pub start_line: usize,
+    /// 1-based line the declaration ends on.
+    pub end_line:   usize,
+}
+
+impl MethodLength {
+    /// Number of lines the declaration spans, from its signature to its
+    /// closing brace.
+    pub fn lines(&self) -> usize {
+        self.end_line - self.start_line + 1
+    }
+}
+
+/// Measures the line span of every method and constructor in `parser`'s
+/// tree.
+///
+/// The span runs from the first line of the declaration (annotations and
+/// modifiers included, Javadoc excluded) to its last, so blank and comment
+/// lines inside the body count. Methods of local and anonymous classes are
+/// reported on their own and also count towards the method around them.
+pub fn method_lengths(parser: &Parser) -> Result<Vec<MethodLength>> {
+    let mut methods = Vec::new();
+    walk(parser, |node, scope| {
+        if METHOD_KINDS.contains(&node.kind()) {
+            methods.push(MethodLength {
+                name:       scope.method_or("<unknown>"),
+                start_line: node.start_position().row + 1,
+                end_line:   node.end_position().row + 1,
+            });
+        }
+        true
+    })?;
+    Ok(methods)
+}
+
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+/// A grader that penalizes methods longer than a maximum number of lines, for
+/// "keep methods short" readability rubrics.
+pub struct MethodLengthGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name:  String,
+    /// points to give if no method is too long
+    #[builder(getter)]
+    pub out_of:    f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:   Project,
+    /// files whose methods are measured
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub files:     Vec<String>,
+    /// most lines a method may span without penalty. Optional, default is 30
+    #[builder(default = 30)]
+    #[builder(getter)]
+    pub max_lines: usize,
+    /// points deducted per offending method. Optional, default is 1
+    #[builder(default = 1.0)]
+    #[builder(getter)]
+    pub penalty:   f64,
+}
+
+impl MethodLengthGrader {
+    /// Builds and runs the configured method length grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move { self.grade_method_length() }).await
+    }
+
+    /// Measures every method in `files` and deducts `penalty` for each one
+    /// spanning more than `max_lines`.
+    pub fn grade_method_length(&self) -> Result<GradeResult> {
+        StaticGrading {
+            grader:   "MethodLengthGrader",
+            req_name: &self.req_name,
+            out_of:   self.out_of,
+            penalty:  self.penalty,
+            project:  &self.project,
+            files:    &self.files,
+        }
+        .grade(self)
+    }
+}
+
+impl StaticCheck for MethodLengthGrader {
+    type Finding = MethodLength;
+
+    fn find(&self, parser: &Parser) -> Result<Vec<MethodLength>> {
+        Ok(method_lengths(parser)?
+            .into_iter()
+            .filter(|m| m.lines() > self.max_lines)
+            .collect())
+    }
+
+    fn describe(&self, _file: &str, m: &MethodLength) -> String {
+        format!("{} (lines {}-{}): {} lines", m.name, m.start_line, m.end_line, m.lines())
+    }
+
+    fn passed(&self) -> String {
+        format!("Every method is at most {} lines long", self.max_lines)
+    }
+
+    fn summary(&self, count: usize) -> String {
+        format!("{count} method(s) longer than {} lines:", self.max_lines)
+    }
+
+    fn advice(&self) -> String {
+        "Long methods are harder to read and test; each method should do one thing, with helper \
+         methods for the steps. Lengths run from the method's signature to its closing brace."
+            .to_string()
+    }
+}
+
+impl<S> MethodLengthGraderBuilder<S>
+where
+    S: method_length_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
@@ -1,38 +1,105 @@
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
+/// Test assertion density grading.
+pub mod assertions;
+/// Cyclomatic complexity grading.
+pub mod complexity;
 /// Retrieval and source context helpers.
 pub mod context;
+/// JaCoCo code coverage grading.
+pub mod coverage;
+/// Print-debugging leftover detection.
+pub mod debug_print;
 /// Diagnostic helper data structures.
 pub mod diagnostics;
 /// Diff-based grading utilities.
 pub mod diff;
 /// Documentation grading helpers.
 pub mod docs;
+/// Field encapsulation grading.
+pub mod encapsulation;
 /// Feedback generation helpers.
 pub mod feedback;
+/// Output line count and format grading.
+pub mod format;
 /// Gradescope integration utilities.
 pub mod gradescope;
+/// Magic number detection.
+pub mod magic_numbers;
+/// Method length limits.
+pub mod method_length;
+/// Input/output oracle grading.
+pub mod oracle;
+/// Missing `@Override` detection.
+pub mod overrides;
+/// Regex-based output grading.
+pub mod pattern;
+/// Leftover `TODO` and stub detection.
+pub mod placeholders;
+/// JSON Lines grading progress output.
+pub mod progress;
 /// Tree-sitter query grading components.
 pub mod query;
 /// Shared grade result types.
 pub mod results;
+/// SARIF rendering for compiler diagnostics.
+pub mod sarif;
+/// Shared walker and grading loop for the static-analysis graders.
+mod static_analysis;
+/// Tree-sitter node helpers shared by the static-analysis graders.
+mod syntax;
 /// Unit, mutation, and hidden test graders.
 pub mod tests;
+/// Posting grade results to a webhook.
+pub mod webhook;
 
-pub use context::{build_active_retrieval_context, build_heuristic_context, get_source_context};
+pub use assertions::{
+    AssertionDensityGrader, DEFAULT_ASSERTION_PREFIXES, TestAssertions, test_assertions,
+};
+pub use complexity::{ComplexityGrader, MethodComplexity, method_complexities};
+pub use context::{
+    build_active_retrieval_context, build_failing_methods_context, build_heuristic_context,
+    get_source_context,
+};
+pub use coverage::{
+    ClassCoverage, CoverageGrader, CoverageMetric, UncoveredLines, parse_coverage_csv,
+    uncovered_lines,
+};
+pub use debug_print::{DEFAULT_OUTPUT_METHODS, DebugPrintGrader, PrintCall, print_calls};
 pub use diagnostics::{
-    DiagnosticSeverity, JavacDiagnostic, MutationDiagnostic, MutationTestResult,
+    DiagnosticSeverity, JavacDiagnostic, KnownSymbols, MutationDiagnostic, MutationTestResult,
+    suggest_unresolved_symbols,
 };
-pub use diff::{DiffCase, DiffGrader};
-pub use docs::DocsGrader;
-pub use feedback::{PromptRow, generate_feedback};
+pub use diff::{DiffCase, DiffGrader, Normalizer, interleave_stdin};
+pub use docs::{DocsGrader, PenaltyMode};
+pub use encapsulation::{EncapsulationGrader, FieldDecl, field_declarations};
+pub use feedback::{FEEDBACK_FILE, PromptRow, generate_feedback};
+pub use format::FormatConstraintGrader;
 pub use gradescope::{
     GradescopeLeaderboardEntry, GradescopeOutputFormat, GradescopeStatus, GradescopeSubmission,
-    GradescopeTestCase, GradescopeVisibility, show_result,
+    GradescopeTestCase, GradescopeVisibility, PredictedScore, SLO_KEYS, TableDetail,
+    overview_table, parse_slos, show_result, slo_input_files,
 };
+pub use magic_numbers::{DEFAULT_ALLOWED_NUMBERS, MagicNumber, MagicNumberGrader, magic_numbers};
+pub use method_length::{MethodLength, MethodLengthGrader, method_lengths};
+pub use oracle::{Oracle, OracleCheck, OracleGrader};
+pub use overrides::{MissingOverride, OverrideGrader, missing_overrides};
+pub use pattern::{RegexCase, RegexGrader};
+pub use placeholders::{
+    DEFAULT_COMMENT_PATTERNS, DEFAULT_THROW_PATTERNS, Placeholder, PlaceholderGrader,
+    PlaceholderKind, placeholders,
+};
+pub use progress::{ProgressEvent, ProgressWriter};
 pub use query::{Query, QueryConstraint, QueryError, QueryGrader};
-pub use results::{Grade, GradeResult};
-pub use tests::{ByHiddenTestGrader, ByUnitTestGrader, UnitTestGrader};
+pub use results::{
+    Grade, GradeResult, GradeStyle, LetterScale, Reason, ReasonTarget, timed, total_grade,
+};
+pub use sarif::{SarifLog, diagnostics_from_output, sarif_json, sarif_log};
+pub use tests::{
+    ByHiddenTestGrader, ByUnitTestGrader, DEFAULT_AVOID_CALLS_TO, PIT_OUTPUT_LOG, PitFailure,
+    UnitTestGrader, merge_avoid_calls_to, validate_pit_args,
+};
+pub use webhook::{ResultsWebhook, WebhookPayload, WebhookResult};
 
 pub use crate::types::LineRef;
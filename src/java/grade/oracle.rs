@@ -0,0 +1,217 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::{fmt, sync::Arc};
+
+use anyhow::{Result, bail};
+use bon::Builder;
+
+use super::{
+    diff::preview_trimmed,
+    results::{Grade, GradeResult, timed},
+};
+use crate::{config, java::Project};
+
+/// Check run by an [`Oracle`]: given a case's stdin and the program's output,
+/// returns `Err` with an explanation when the output violates the property.
+pub type OracleCheck = Arc<dyn Fn(&str, &str) -> Result<(), String> + Send + Sync + 'static>;
+
+/// A named property relating a program's output to its input, such as "the
+/// output is the input, sorted".
+#[derive(Clone)]
+pub struct Oracle {
+    /// Short description used in reasons, e.g. `sorted permutation of the
+    /// input`.
+    name:  String,
+    /// The property itself.
+    check: OracleCheck,
+}
+
+impl fmt::Debug for Oracle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Oracle").field("name", &self.name).finish()
+    }
+}
+
+impl Oracle {
+    /// Creates an oracle from a check that explains each violation.
+    pub fn new(
+        name: impl Into<String>,
+        check: impl Fn(&str, &str) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name:  name.into(),
+            check: Arc::new(check),
+        }
+    }
+
+    /// Creates an oracle from a yes/no predicate over `(input, output)`.
+    pub fn from_predicate(
+        name: impl Into<String>,
+        predicate: impl Fn(&str, &str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        let name = name.into();
+        let message = format!("output is not the {name}");
+        Self::new(name, move |input, output| {
+            if predicate(input, output) {
+                Ok(())
+            } else {
+                Err(message.clone())
+            }
+        })
+    }
+
+    /// Requires the output to list the input's numbers in ascending order,
+    /// with nothing added or dropped. Numbers may be separated by whitespace
+    /// or commas, on one line or several.
+    pub fn sorted_permutation() -> Self {
+        Self::new("sorted permutation of the input", |input, output| {
+            let input = parse_numbers(input)
+                .map_err(|token| format!("input token `{token}` is not a number"))?;
+            let output = parse_numbers(output)
+                .map_err(|token| format!("output token `{token}` is not a number"))?;
+
+            if let Some(pair) = output.windows(2).find(|pair| pair[0] > pair[1]) {
+                return Err(format!("output is not sorted: {} comes before {}", pair[0], pair[1]));
+            }
+
+            let mut expected = input;
+            expected.sort_by(f64::total_cmp);
+            if expected != output {
+                return Err(format!(
+                    "output is not a permutation of the input: expected {} number(s) {}, got {} \
+                     number(s) {}",
+                    expected.len(),
+                    join_numbers(&expected),
+                    output.len(),
+                    join_numbers(&output)
+                ));
+            }
+            Ok(())
+        })
+    }
+
+    /// Short description of the property.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Checks `output` against `input`.
+    pub fn check(&self, input: &str, output: &str) -> Result<(), String> {
+        (self.check)(input, output)
+    }
+}
+
+/// Parses whitespace- or comma-separated numbers, returning the first token
+/// that is not a number on failure.
+fn parse_numbers(text: &str) -> Result<Vec<f64>, String> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(|token| token.parse::<f64>().map_err(|_| token.to_string()))
+        .collect()
+}
+
+/// Renders numbers space-separated, as written (`3`, not `3.0`).
+fn join_numbers(numbers: &[f64]) -> String {
+    let shown: Vec<String> = numbers.iter().map(ToString::to_string).collect();
+    format!("[{}]", shown.join(" "))
+}
+
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+/// A grader that runs a file on each input and checks the output against an
+/// [`Oracle`] property instead of a fixed expected output. Any case that
+/// violates the property results in a `0` grade.
+pub struct OracleGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name: String,
+    /// points to give if every case satisfies the oracle
+    #[builder(getter)]
+    pub out_of:   f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:  Project,
+    /// Java file to run
+    #[builder(getter)]
+    pub file:     String,
+    /// stdin for each case; inputs may span several lines
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub inputs:   Vec<String>,
+    /// property every output must satisfy
+    #[builder(getter)]
+    pub oracle:   Oracle,
+}
+
+impl OracleGrader {
+    /// Builds and runs the configured oracle grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move {
+            if self.inputs.is_empty() {
+                bail!("OracleGrader requires at least one input");
+            }
+            self.grade_by_oracle().await
+        })
+        .await
+    }
+
+    /// Runs every case and grades on the first output that violates the
+    /// oracle.
+    pub async fn grade_by_oracle(&self) -> Result<GradeResult> {
+        let file = self.project.identify(&self.file)?;
+        if file.is_empty() {
+            return Ok(GradeResult::empty_file(&self.req_name, self.out_of, file.file_name()));
+        }
+
+        for (idx, input) in self.inputs.iter().enumerate() {
+            let case_num = idx + 1;
+            let output = match file.run_with_input(Some(input.clone())).await {
+                Ok(out) => out,
+                Err(e) => {
+                    return GradeResult::run_error(&self.req_name, self.out_of, Some(case_num), e);
+                }
+            };
+
+            let Err(violation) = self.oracle.check(input, &output) else {
+                continue;
+            };
+            // Show every line of multi-line input, not just the first.
+            let one_line = input.trim().lines().collect::<Vec<_>>().join("\\n");
+            let input_note = format!(" (input: `{}`)", preview_trimmed(&one_line));
+            return GradeResult::failure(
+                &self.req_name,
+                self.out_of,
+                config::java_prompts().system_message(),
+                format!("Case {case_num}{input_note}: {violation}"),
+                format!(
+                    "Running {}{input_note}, the output was expected to be the {}, but \
+                     {violation}.\n\nInput:\n```\n{}\n```\n\nActual output:\n```\n{}\n```",
+                    file.file_name(),
+                    self.oracle.name(),
+                    input.trim_end(),
+                    output.trim_end()
+                ),
+            );
+        }
+
+        Ok(GradeResult::builder()
+            .requirement(self.req_name.clone())
+            .grade(Grade::new(self.out_of, self.out_of))
+            .reason(format!("Every output was the {}", self.oracle.name()))
+            .maybe_prompt(None)
+            .build())
+    }
+}
+
+impl<S> OracleGraderBuilder<S>
+where
+    S: oracle_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
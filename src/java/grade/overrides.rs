@@ -0,0 +1,404 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use bon::Builder;
+use tree_sitter::Node;
+
+use super::{
+    results::{GradeResult, timed},
+    static_analysis::{StaticCheck, StaticGrading, walk},
+    syntax::{TYPE_KINDS, declared_name},
+};
+use crate::java::{Parser, Project};
+
+/// An overriding method that is not annotated `@Override`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingOverride {
+    /// Method name qualified by its enclosing type, e.g. `Dog.speak`.
+    pub name:       String,
+    /// 1-based line the method is declared on.
+    pub line:       usize,
+    /// The supertype method it overrides, e.g. `Animal.speak`.
+    pub overridden: String,
+}
+
+/// A method declared directly in a type body.
+#[derive(Debug, Clone)]
+struct MethodSig {
+    /// Simple method name.
+    name:        String,
+    /// Erased parameter types, e.g. `["List", "int[]"]`.
+    params:      Vec<String>,
+    /// 1-based line the method is declared on.
+    line:        usize,
+    /// Whether the method can take part in overriding, i.e. is neither
+    /// `static` nor `private`.
+    overridable: bool,
+    /// Whether the method carries `@Override`.
+    annotated:   bool,
+}
+
+/// A class, interface, enum, or record declared in the project.
+#[derive(Debug, Clone)]
+struct TypeDecl {
+    /// Simple name of the type.
+    name:        String,
+    /// Simple names of the extended and implemented types.
+    supertypes:  Vec<String>,
+    /// Names of the type's own type parameters, e.g. `["T"]`.
+    type_params: Vec<String>,
+    /// Methods declared directly in the type's body.
+    methods:     Vec<MethodSig>,
+}
+
+impl TypeDecl {
+    /// Returns true when this type declares a method that `method` overrides.
+    /// A parameter typed by one of this type's type parameters matches any
+    /// type, since its binding in the subtype is not resolved.
+    fn declares(&self, method: &MethodSig) -> bool {
+        self.methods.iter().any(|own| {
+            own.overridable
+                && own.name == method.name
+                && own.params.len() == method.params.len()
+                && own
+                    .params
+                    .iter()
+                    .zip(&method.params)
+                    .all(|(own, other)| own == other || self.type_params.contains(own))
+        })
+    }
+}
+
+/// Lists the methods in `parser`'s tree that override a method of a
+/// supertype declared in `project` but are not annotated `@Override`.
+///
+/// Supertypes are resolved by simple name within the project, so methods
+/// inherited from library types such as `Object` are not considered.
+pub fn missing_overrides(project: &Project, parser: &Parser) -> Result<Vec<MissingOverride>> {
+    let known = project_types(project)?;
+    local_missing_overrides(&known, parser)
+}
+
+/// Collects every type declared in `project`, keyed by simple name. When two
+/// types share a name, the first one found wins.
+fn project_types(project: &Project) -> Result<HashMap<String, TypeDecl>> {
+    let mut known = HashMap::new();
+    for file in project.files() {
+        for decl in type_declarations(file.parser())? {
+            known.entry(decl.name.clone()).or_insert(decl);
+        }
+    }
+    Ok(known)
+}
+
+/// Checks the types declared in `parser`'s tree against the `known` project
+/// types.
+fn local_missing_overrides(
+    known: &HashMap<String, TypeDecl>,
+    parser: &Parser,
+) -> Result<Vec<MissingOverride>> {
+    let mut missing = Vec::new();
+    for decl in type_declarations(parser)? {
+        for method in decl
+            .methods
+            .iter()
+            .filter(|method| method.overridable && !method.annotated)
+        {
+            if let Some(overridden) = find_overridden(known, &decl, method) {
+                missing.push(MissingOverride {
+                    name:       format!("{}.{}", decl.name, method.name),
+                    line:       method.line,
+                    overridden: format!("{overridden}.{}", method.name),
+                });
+            }
+        }
+    }
+    Ok(missing)
+}
+
+/// Walks the supertypes of `decl` breadth-first and returns the name of the
+/// first one that declares a method `method` overrides.
+fn find_overridden(
+    known: &HashMap<String, TypeDecl>,
+    decl: &TypeDecl,
+    method: &MethodSig,
+) -> Option<String> {
+    let mut seen = HashSet::from([decl.name.clone()]);
+    let mut queue: Vec<&str> = decl.supertypes.iter().map(String::as_str).collect();
+    while !queue.is_empty() {
+        let mut next = Vec::new();
+        for name in queue {
+            if !seen.insert(name.to_string()) {
+                continue;
+            }
+            let Some(parent) = known.get(name) else {
+                continue;
+            };
+            if parent.declares(method) {
+                return Some(parent.name.clone());
+            }
+            next.extend(parent.supertypes.iter().map(String::as_str));
+        }
+        queue = next;
+    }
+    None
+}
+
+/// Lists every type declared in `parser`'s tree, including nested ones.
+fn type_declarations(parser: &Parser) -> Result<Vec<TypeDecl>> {
+    let source = parser.code().as_bytes();
+    let mut types = Vec::new();
+    walk(parser, |node, scope| {
+        if TYPE_KINDS.contains(&node.kind())
+            && let Some(name) = &scope.owner
+        {
+            types.push(TypeDecl {
+                name:        name.clone(),
+                supertypes:  supertypes(node, source),
+                type_params: type_params(node, source),
+                methods:     methods(node, source),
+            });
+        }
+        true
+    })?;
+    Ok(types)
+}
+
+/// Erased simple names of the types a declaration extends or implements.
+fn supertypes(node: Node, source: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "superclass" => names.extend(child.named_child(0).map(|ty| erase(ty, source))),
+            "super_interfaces" | "extends_interfaces" => {
+                if let Some(list) = child.named_child(0) {
+                    let mut cursor = list.walk();
+                    names.extend(list.named_children(&mut cursor).map(|ty| erase(ty, source)));
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Names of a declaration's own type parameters.
+fn type_params(node: Node, source: &[u8]) -> Vec<String> {
+    let Some(params) = node.child_by_field_name("type_parameters") else {
+        return Vec::new();
+    };
+    let mut cursor = params.walk();
+    params
+        .named_children(&mut cursor)
+        .filter_map(|param| {
+            let mut cursor = param.walk();
+            param
+                .named_children(&mut cursor)
+                .find(|child| matches!(child.kind(), "type_identifier" | "identifier"))
+                .and_then(|name| name.utf8_text(source).ok().map(str::to_string))
+        })
+        .collect()
+}
+
+/// Methods declared directly in a type's body. Enum methods live in the
+/// `enum_body_declarations` after the constants.
+fn methods(node: Node, source: &[u8]) -> Vec<MethodSig> {
+    let Some(body) = node.child_by_field_name("body") else {
+        return Vec::new();
+    };
+    let mut members = Vec::new();
+    let mut cursor = body.walk();
+    for child in body.named_children(&mut cursor) {
+        if child.kind() == "enum_body_declarations" {
+            let mut cursor = child.walk();
+            members.extend(child.named_children(&mut cursor));
+        } else {
+            members.push(child);
+        }
+    }
+
+    members
+        .into_iter()
+        .filter(|member| member.kind() == "method_declaration")
+        .filter_map(|method| {
+            let (keywords, annotations) = modifiers(method, source);
+            Some(MethodSig {
+                name:        declared_name(method, source)?,
+                params:      parameter_types(method, source),
+                line:        method.start_position().row + 1,
+                overridable: !keywords.iter().any(|k| k == "static" || k == "private"),
+                annotated:   annotations.iter().any(|a| a == "Override"),
+            })
+        })
+        .collect()
+}
+
+/// Keyword modifiers and simple annotation names of a declaration.
+fn modifiers(node: Node, source: &[u8]) -> (Vec<String>, Vec<String>) {
+    let mut cursor = node.walk();
+    let Some(modifiers) = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "modifiers")
+    else {
+        return (Vec::new(), Vec::new());
+    };
+
+    let mut keywords = Vec::new();
+    let mut annotations = Vec::new();
+    let mut cursor = modifiers.walk();
+    for child in modifiers.children(&mut cursor) {
+        if !child.is_named() {
+            keywords.extend(child.utf8_text(source).ok().map(str::to_string));
+        } else if let Some(name) = child.child_by_field_name("name")
+            && let Ok(name) = name.utf8_text(source)
+        {
+            annotations.push(name.rsplit('.').next().unwrap_or(name).to_string());
+        }
+    }
+    (keywords, annotations)
+}
+
+/// Erased parameter types of a method; varargs and trailing dimensions
+/// (`int a[]`) become arrays.
+fn parameter_types(method: Node, source: &[u8]) -> Vec<String> {
+    let Some(params) = method.child_by_field_name("parameters") else {
+        return Vec::new();
+    };
+    let mut cursor = params.walk();
+    params
+        .named_children(&mut cursor)
+        .filter_map(|param| match param.kind() {
+            "formal_parameter" => param.child_by_field_name("type").map(|ty| {
+                let dims = param
+                    .child_by_field_name("dimensions")
+                    .and_then(|dims| dims.utf8_text(source).ok())
+                    .unwrap_or_default();
+                let dims: String = dims.chars().filter(|c| !c.is_whitespace()).collect();
+                format!("{}{dims}", erase(ty, source))
+            }),
+            "spread_parameter" => {
+                let mut cursor = param.walk();
+                param
+                    .named_children(&mut cursor)
+                    .find(|child| !matches!(child.kind(), "modifiers" | "variable_declarator"))
+                    .map(|ty| format!("{}[]", erase(ty, source)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Renders a type node without generic arguments, package qualifiers, or
+/// whitespace, so `java.util.List<String>` becomes `List`.
+fn erase(ty: Node, source: &[u8]) -> String {
+    let text = ty.utf8_text(source).unwrap_or_default();
+    let mut erased = String::new();
+    let mut depth = 0usize;
+    for ch in text.chars() {
+        match ch {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && !ch.is_whitespace() => erased.push(ch),
+            _ => {}
+        }
+    }
+    let dims = erased.find('[').unwrap_or(erased.len());
+    let (base, dims) = erased.split_at(dims);
+    format!("{}{dims}", base.rsplit('.').next().unwrap_or(base))
+}
+
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+/// A grader that flags methods overriding a supertype method declared in the
+/// project without an `@Override` annotation.
+pub struct OverrideGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name: String,
+    /// points to give if every overriding method is annotated
+    #[builder(getter)]
+    pub out_of:   f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:  Project,
+    /// files whose methods are checked
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub files:    Vec<String>,
+    /// points deducted per missing annotation. Optional, default is 1
+    #[builder(default = 1.0)]
+    #[builder(getter)]
+    pub penalty:  f64,
+}
+
+impl OverrideGrader {
+    /// Builds and runs the configured override grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move { self.grade_overrides() }).await
+    }
+
+    /// Checks the methods of `files` and deducts `penalty` for each
+    /// overriding method missing `@Override`.
+    pub fn grade_overrides(&self) -> Result<GradeResult> {
+        let check = OverrideCheck {
+            known: project_types(&self.project)?,
+        };
+        StaticGrading {
+            grader:   "OverrideGrader",
+            req_name: &self.req_name,
+            out_of:   self.out_of,
+            penalty:  self.penalty,
+            project:  &self.project,
+            files:    &self.files,
+        }
+        .grade(&check)
+    }
+}
+
+/// The project types an [`OverrideGrader`] resolves supertypes against.
+struct OverrideCheck {
+    /// Every type declared in the project, keyed by simple name.
+    known: HashMap<String, TypeDecl>,
+}
+
+impl StaticCheck for OverrideCheck {
+    type Finding = MissingOverride;
+
+    fn find(&self, parser: &Parser) -> Result<Vec<MissingOverride>> {
+        local_missing_overrides(&self.known, parser)
+    }
+
+    fn describe(&self, file: &str, method: &MissingOverride) -> String {
+        format!("{file}:{} {} overrides {}", method.line, method.name, method.overridden)
+    }
+
+    fn passed(&self) -> String {
+        "All overriding methods are annotated `@Override`".to_string()
+    }
+
+    fn summary(&self, count: usize) -> String {
+        format!("{count} overriding method(s) lack `@Override`:")
+    }
+
+    fn advice(&self) -> String {
+        "Methods that override a superclass or interface method should be annotated `@Override` so \
+         the compiler checks the signature; these are not."
+            .to_string()
+    }
+}
+
+impl<S> OverrideGraderBuilder<S>
+where
+    S: override_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
@@ -0,0 +1,157 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use anyhow::{Context, Result, bail};
+use bon::Builder;
+use regex::Regex;
+
+use super::{
+    diff::preview_trimmed,
+    results::{Grade, GradeResult, timed},
+};
+use crate::{config, java::Project};
+
+/// A single run of a regex grader: optional stdin and the pattern the output
+/// must satisfy.
+#[derive(Debug, Clone)]
+pub struct RegexCase {
+    /// Regular expression the output is checked against.
+    pub pattern: String,
+    /// Optional stdin supplied to the program.
+    pub input:   Option<String>,
+}
+
+#[derive(Clone, Default, Builder)]
+#[builder(on(String, into))]
+/// A grader that runs a file and checks its output against a regular
+/// expression per case, for when only part of the output matters. Any case
+/// that does not match results in a `0` grade.
+pub struct RegexGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name:   String,
+    /// points to give if all cases match
+    #[builder(getter)]
+    pub out_of:     f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:    Project,
+    /// Java file to run
+    #[builder(getter)]
+    pub file:       String,
+    /// Cases pairing a pattern with optional stdin.
+    #[builder(
+        default,
+        with = |iter: impl IntoIterator<
+            Item = (impl Into<String>, Option<impl Into<String>>)
+        >| iter
+            .into_iter()
+            .map(|(pattern, input)| RegexCase {
+                pattern: pattern.into(),
+                input:   input.map(Into::into),
+            })
+            .collect::<Vec<_>>()
+    )]
+    #[builder(getter)]
+    pub cases:      Vec<RegexCase>,
+    /// Require the whole trimmed output to match instead of any part of it.
+    #[builder(default)]
+    #[builder(getter)]
+    pub full_match: bool,
+}
+
+impl RegexGrader {
+    /// Builds and runs the configured regex grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move {
+            if self.cases.is_empty() {
+                bail!("RegexGrader requires at least one case");
+            }
+            self.grade_by_regex().await
+        })
+        .await
+    }
+
+    /// Runs every case and grades on the first output that does not match.
+    /// Patterns are compiled up front so a typo fails before anything runs.
+    pub async fn grade_by_regex(&self) -> Result<GradeResult> {
+        let patterns = self
+            .cases
+            .iter()
+            .enumerate()
+            .map(|(idx, case)| {
+                let pattern = if self.full_match {
+                    format!("^(?:{})$", case.pattern)
+                } else {
+                    case.pattern.clone()
+                };
+                Regex::new(&pattern).with_context(|| {
+                    format!("Invalid pattern for case {}: {}", idx + 1, case.pattern)
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let file = self.project.identify(&self.file)?;
+        if file.is_empty() {
+            return Ok(GradeResult::empty_file(&self.req_name, self.out_of, file.file_name()));
+        }
+
+        for (idx, (case, regex)) in self.cases.iter().zip(&patterns).enumerate() {
+            let case_num = idx + 1;
+            let output = match file.run_with_input(case.input.clone()).await {
+                Ok(out) => out,
+                Err(e) => {
+                    return GradeResult::run_error(&self.req_name, self.out_of, Some(case_num), e);
+                }
+            };
+
+            let output = output.trim();
+            if regex.is_match(output) {
+                continue;
+            }
+
+            let expectation = if self.full_match {
+                "match"
+            } else {
+                "contain a match for"
+            };
+            let input_note = match case.input.as_deref().filter(|input| !input.is_empty()) {
+                Some(input) => format!(" (input: `{}`)", preview_trimmed(input)),
+                None => String::new(),
+            };
+            return GradeResult::failure(
+                &self.req_name,
+                self.out_of,
+                config::java_prompts().system_message(),
+                format!(
+                    "Case {case_num}{input_note}: output did not {expectation} `{}`; got \"{}\"",
+                    case.pattern,
+                    preview_trimmed(output)
+                ),
+                format!(
+                    "Running {}{input_note}, the output was expected to {expectation} the regular \
+                     expression `{}`.\n\nActual output:\n```\n{output}\n```",
+                    file.file_name(),
+                    case.pattern
+                ),
+            );
+        }
+
+        Ok(GradeResult::builder()
+            .requirement(self.req_name.clone())
+            .grade(Grade::new(self.out_of, self.out_of))
+            .reason("Output matched every pattern")
+            .maybe_prompt(None)
+            .build())
+    }
+}
+
+impl<S> RegexGraderBuilder<S>
+where
+    S: regex_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
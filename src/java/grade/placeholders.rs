@@ -0,0 +1,227 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use anyhow::{Context, Result};
+use bon::Builder;
+use regex::Regex;
+
+use super::{
+    results::{GradeResult, timed},
+    static_analysis::{StaticCheck, StaticGrading, walk},
+};
+use crate::java::{Parser, Project};
+
+/// Comment patterns that mark unfinished code when none are configured.
+pub const DEFAULT_COMMENT_PATTERNS: &[&str] = &[r"\bTODO\b", r"\bFIXME\b"];
+
+/// `throw` statement patterns that mark a stub when none are configured.
+pub const DEFAULT_THROW_PATTERNS: &[&str] = &[
+    r"\bUnsupportedOperationException\b",
+    r"(?i)\bnot\s+(yet\s+)?implemented\b",
+];
+
+/// Where a placeholder was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceholderKind {
+    /// A line or block comment, e.g. `// TODO`.
+    Comment,
+    /// A `throw` statement, e.g. `throw new UnsupportedOperationException()`.
+    Throw,
+}
+
+/// Unfinished template code left in a submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    /// Enclosing method qualified by its type, e.g. `Stack.push`; outside a
+    /// method this is the type name alone, or `<file>` outside any type.
+    pub location: String,
+    /// 1-based line the placeholder is on.
+    pub line:     usize,
+    /// Whether a comment or a `throw` matched.
+    pub kind:     PlaceholderKind,
+    /// The matching line of the comment, or the whole `throw` statement, with
+    /// whitespace collapsed.
+    pub text:     String,
+}
+
+/// Finds every comment matching one of `comment_patterns` and every `throw`
+/// statement matching one of `throw_patterns` in `parser`'s tree.
+///
+/// Each matching line of a block comment is reported separately.
+pub fn placeholders(
+    parser: &Parser,
+    comment_patterns: &[Regex],
+    throw_patterns: &[Regex],
+) -> Result<Vec<Placeholder>> {
+    let source = parser.code().as_bytes();
+    let matches = |patterns: &[Regex], text: &str| patterns.iter().any(|re| re.is_match(text));
+    let mut found = Vec::new();
+    walk(parser, |node, scope| {
+        let location = || match (&scope.owner, &scope.method) {
+            (Some(owner), Some(method)) => format!("{owner}.{method}"),
+            (Some(name), None) | (None, Some(name)) => name.clone(),
+            (None, None) => "<file>".to_string(),
+        };
+
+        match node.kind() {
+            "line_comment" | "block_comment" => {
+                let Ok(text) = node.utf8_text(source) else {
+                    return false;
+                };
+                for (offset, line) in text.lines().enumerate() {
+                    if matches(comment_patterns, line) {
+                        found.push(Placeholder {
+                            location: location(),
+                            line:     node.start_position().row + offset + 1,
+                            kind:     PlaceholderKind::Comment,
+                            text:     line.trim().to_string(),
+                        });
+                    }
+                }
+                false
+            }
+            "throw_statement" => {
+                if let Ok(text) = node.utf8_text(source) {
+                    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                    if matches(throw_patterns, &text) {
+                        found.push(Placeholder {
+                            location: location(),
+                            line: node.start_position().row + 1,
+                            kind: PlaceholderKind::Throw,
+                            text,
+                        });
+                    }
+                }
+                true
+            }
+            _ => true,
+        }
+    })?;
+    Ok(found)
+}
+
+/// Compiles `patterns`, naming the offending one if it is invalid.
+fn compile_patterns(patterns: &[String], what: &str) -> Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern).with_context(|| format!("Invalid {what} pattern: {pattern}"))
+        })
+        .collect()
+}
+
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+/// A grader that penalizes placeholders left from a template: `TODO`
+/// comments and stub `throw`s such as `UnsupportedOperationException`.
+pub struct PlaceholderGrader {
+    /// name of requirement
+    #[builder(getter)]
+    pub req_name:         String,
+    /// points to give if no placeholders are found
+    #[builder(getter)]
+    pub out_of:           f64,
+    /// the project to grade
+    #[builder(getter)]
+    pub project:          Project,
+    /// files to scan for placeholders
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub files:            Vec<String>,
+    /// regexes matched against each line of every comment. Optional, default
+    /// matches `TODO` and `FIXME`
+    #[builder(
+        default = DEFAULT_COMMENT_PATTERNS.iter().map(ToString::to_string).collect(),
+        with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+            iter.into_iter().map(Into::into).collect::<Vec<String>>()
+        }
+    )]
+    #[builder(getter)]
+    pub comment_patterns: Vec<String>,
+    /// regexes matched against every `throw` statement. Optional, default
+    /// matches `UnsupportedOperationException` and "not implemented" messages
+    #[builder(
+        default = DEFAULT_THROW_PATTERNS.iter().map(ToString::to_string).collect(),
+        with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+            iter.into_iter().map(Into::into).collect::<Vec<String>>()
+        }
+    )]
+    #[builder(getter)]
+    pub throw_patterns:   Vec<String>,
+    /// points deducted per placeholder. Optional, default is 1
+    #[builder(default = 1.0)]
+    #[builder(getter)]
+    pub penalty:          f64,
+}
+
+impl PlaceholderGrader {
+    /// Builds and runs the configured placeholder grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move { self.grade_placeholders() }).await
+    }
+
+    /// Scans `files` for placeholder comments and stub `throw`s and deducts
+    /// `penalty` for each one. Patterns are compiled up front so a typo fails
+    /// before anything is scanned.
+    pub fn grade_placeholders(&self) -> Result<GradeResult> {
+        let check = PlaceholderCheck {
+            comment_patterns: compile_patterns(&self.comment_patterns, "comment")?,
+            throw_patterns:   compile_patterns(&self.throw_patterns, "throw")?,
+        };
+        StaticGrading {
+            grader:   "PlaceholderGrader",
+            req_name: &self.req_name,
+            out_of:   self.out_of,
+            penalty:  self.penalty,
+            project:  &self.project,
+            files:    &self.files,
+        }
+        .grade(&check)
+    }
+}
+
+/// The compiled patterns a [`PlaceholderGrader`] scans for.
+struct PlaceholderCheck {
+    /// Patterns matched against each line of every comment.
+    comment_patterns: Vec<Regex>,
+    /// Patterns matched against every `throw` statement.
+    throw_patterns:   Vec<Regex>,
+}
+
+impl StaticCheck for PlaceholderCheck {
+    type Finding = Placeholder;
+
+    fn find(&self, parser: &Parser) -> Result<Vec<Placeholder>> {
+        placeholders(parser, &self.comment_patterns, &self.throw_patterns)
+    }
+
+    fn describe(&self, file: &str, placeholder: &Placeholder) -> String {
+        format!("{file}:{} in {}: {}", placeholder.line, placeholder.location, placeholder.text)
+    }
+
+    fn passed(&self) -> String {
+        "No placeholders left in the submission".to_string()
+    }
+
+    fn summary(&self, count: usize) -> String {
+        format!("{count} placeholder(s) left unimplemented:")
+    }
+
+    fn advice(&self) -> String {
+        "These placeholders from the starter code mark parts of the assignment that still need to \
+         be implemented."
+            .to_string()
+    }
+}
+
+impl<S> PlaceholderGraderBuilder<S>
+where
+    S: placeholder_grader_builder::IsComplete,
+{
+    /// Build the grader and immediately execute it.
+    pub async fn run(self) -> Result<GradeResult> {
+        self.build().run().await
+    }
+}
@@ -0,0 +1,137 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use super::results::{GradeResult, total_grade};
+
+/// One line of JSON Lines grading progress.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// A single requirement finished grading.
+    Requirement {
+        /// Label of the submission being graded, if known.
+        submission:  Option<String>,
+        /// Requirement name.
+        requirement: String,
+        /// Points earned.
+        grade:       f64,
+        /// Points available.
+        out_of:      f64,
+        /// Plain-text reason shown to the student.
+        reason:      String,
+        /// Seconds since the Unix epoch when the requirement finished.
+        timestamp:   u64,
+    },
+    /// Every requirement of a submission finished grading.
+    Submission {
+        /// Label of the submission that was graded, if known.
+        submission:   Option<String>,
+        /// Number of requirements graded.
+        requirements: usize,
+        /// Total points earned.
+        grade:        f64,
+        /// Total points available.
+        out_of:       f64,
+        /// Seconds since the Unix epoch when the submission finished.
+        timestamp:    u64,
+    },
+}
+
+impl ProgressEvent {
+    /// Event for a finished requirement.
+    pub fn requirement(submission: Option<&str>, result: &GradeResult) -> Self {
+        Self::Requirement {
+            submission:  submission.map(str::to_string),
+            requirement: result.requirement.clone(),
+            grade:       result.grade_value(),
+            out_of:      result.out_of_value(),
+            reason:      result.reason().to_string(),
+            timestamp:   now(),
+        }
+    }
+
+    /// Event summarising a finished submission.
+    pub fn submission(submission: Option<&str>, results: &[GradeResult]) -> Self {
+        let total = total_grade(results);
+        Self::Submission {
+            submission:   submission.map(str::to_string),
+            requirements: results.len(),
+            grade:        total.grade,
+            out_of:       total.out_of,
+            timestamp:    now(),
+        }
+    }
+}
+
+/// Seconds since the Unix epoch, or 0 if the clock is before it.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Writes [`ProgressEvent`]s as JSON Lines while grading runs, one line per
+/// completed requirement or submission, so dashboards can follow along.
+///
+/// Every line is flushed as soon as it is written.
+pub struct ProgressWriter {
+    /// Destination for the JSON lines.
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl ProgressWriter {
+    /// Streams progress to `out`.
+    pub fn new(out: impl Write + Send + 'static) -> Self {
+        Self {
+            out: Mutex::new(Box::new(out)),
+        }
+    }
+
+    /// Streams progress to `path`, appending to any lines already there so
+    /// one file can collect a whole batch.
+    pub fn append(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open progress file {}", path.display()))?;
+        Ok(Self::new(file))
+    }
+
+    /// Writes `event` as a single line.
+    pub fn emit(&self, event: &ProgressEvent) -> Result<()> {
+        let mut line =
+            serde_json::to_string(event).context("Failed to serialize progress event")?;
+        line.push('\n');
+
+        let mut out = self
+            .out
+            .lock()
+            .map_err(|_| anyhow!("Progress writer lock poisoned"))?;
+        out.write_all(line.as_bytes())
+            .and_then(|()| out.flush())
+            .context("Failed to write progress event")
+    }
+
+    /// Records a finished requirement.
+    pub fn requirement(&self, submission: Option<&str>, result: &GradeResult) -> Result<()> {
+        self.emit(&ProgressEvent::requirement(submission, result))
+    }
+
+    /// Records a finished submission along with its totals.
+    pub fn submission(&self, submission: Option<&str>, results: &[GradeResult]) -> Result<()> {
+        self.emit(&ProgressEvent::submission(submission, results))
+    }
+}
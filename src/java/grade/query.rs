@@ -1,17 +1,21 @@
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
-use std::{fmt, sync::Arc};
+use std::{fmt, sync::Arc, time::Instant};
 
 use anyhow::{Context, Result, bail};
 use async_openai::types::chat::ChatCompletionRequestSystemMessageArgs;
 use bon::Builder;
 use snailquote::unescape;
+use tree_sitter::Node;
 
 use super::results::{Grade, GradeResult};
 use crate::{
     config,
-    java::{Parser, Project},
+    java::{
+        File, ImportDecl, Parser, Project, QueryCompileError, file::IMPORT_DECLARATION_QUERY,
+        queries::QueryLibrary,
+    },
 };
 
 /// Predicate invoked to keep query results that satisfy additional constraints.
@@ -115,11 +119,301 @@ pub enum QueryError {
          return nothing."
     )]
     NoMatchesFound(String),
+    /// No query with this name was loaded into the query library.
+    #[error("No query named `{name}` in {dir}; available: {available}")]
+    UnknownNamedQuery {
+        /// The requested query name.
+        name:      String,
+        /// Directory the library was loaded from.
+        dir:       String,
+        /// Comma-separated names that were loaded.
+        available: String,
+    },
     /// Unknown error.
     #[error("Unknown error: {0}")]
     Unknown(#[from] anyhow::Error),
 }
 
+impl QueryError {
+    /// Wraps a failure from running `q`. Compilation errors are reduced to
+    /// their position and message, since the query text is reported alongside.
+    fn during_execution(q: &str, e: anyhow::Error) -> Self {
+        let e = match e.downcast_ref::<QueryCompileError>() {
+            Some(compile) => format!(
+                "{} error at line {}, column {}: {}",
+                compile.kind, compile.line, compile.column, compile.message
+            ),
+            None => format!("{:#?}", e),
+        };
+        QueryError::DuringQueryExecution {
+            q: q.to_string(),
+            e,
+        }
+    }
+}
+
+/// Strips a leading `@` and any package qualifier from an annotation name, so
+/// `@java.lang.Deprecated` and `Deprecated` compare equal.
+fn simple_annotation_name(name: &str) -> &str {
+    let name = name.trim().trim_start_matches('@');
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Removes all whitespace so type names compare independent of formatting.
+fn strip_whitespace(value: &str) -> String {
+    value.split_whitespace().collect()
+}
+
+/// Extracts the declared parameter types from a `formal_parameters` capture
+/// such as `(final String name, Map<String, Integer> counts)`.
+///
+/// Modifiers and annotations are dropped, array dimensions written after the
+/// name are moved onto the type (`int a[]` reads `int[]`), and varargs keep
+/// their `...`.
+fn parameter_types(params: &str) -> Vec<String> {
+    // A parameter list alone is not a valid program, so wrap it in a method.
+    let Ok(parser) = Parser::new(format!("class UmmParams {{\nvoid m{params} {{}}\n}}")) else {
+        return Vec::new();
+    };
+    let Ok(root) = parser.root_node() else {
+        return Vec::new();
+    };
+    let source = parser.code().as_bytes();
+    let Some(list) = first_of_kind(root, "formal_parameters") else {
+        return Vec::new();
+    };
+
+    let text = |node: Node| node.utf8_text(source).unwrap_or_default().to_string();
+    let mut cursor = list.walk();
+    list.named_children(&mut cursor)
+        .filter_map(|param| match param.kind() {
+            "formal_parameter" => {
+                let ty = text(param.child_by_field_name("type")?);
+                let dims = param
+                    .child_by_field_name("dimensions")
+                    .map(|dims| strip_whitespace(&text(dims)))
+                    .unwrap_or_default();
+                Some(format!("{ty}{dims}"))
+            }
+            "spread_parameter" => {
+                let mut cursor = param.walk();
+                param
+                    .named_children(&mut cursor)
+                    .find(|child| !matches!(child.kind(), "modifiers" | "variable_declarator"))
+                    .map(|ty| format!("{}...", text(ty)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the first node of `kind` at or below `node`, in document order.
+fn first_of_kind<'tree>(node: Node<'tree>, kind: &str) -> Option<Node<'tree>> {
+    if node.kind() == kind {
+        return Some(node);
+    }
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .find_map(|child| first_of_kind(child, kind))
+}
+
+/// Returns true when the method declaration in `method_src` calls a method
+/// named `name`, either unqualified or through `this`.
+///
+/// This is a name-based heuristic: a call to an overload with the same name,
+/// or to a same-named method on another object, is not distinguished from
+/// true recursion, and mutual recursion through a helper is not detected.
+fn calls_itself(method_src: &str, name: &str) -> bool {
+    invocation_receivers(method_src, name)
+        .iter()
+        .any(|object| object.as_deref().is_none_or(|object| object == "this"))
+}
+
+/// Returns true when the method declaration in `method_src` calls a method
+/// named `callee` on any receiver, or on none.
+fn calls_method(method_src: &str, callee: &str) -> bool {
+    !invocation_receivers(method_src, callee).is_empty()
+}
+
+/// Returns true when the method declaration in `method_src` has a base case:
+/// a path that finishes without calling itself.
+///
+/// Counted as base cases are a `return` inside an `if` or `switch` that does
+/// not call the method, and an `if` or ternary where one branch calls the
+/// method and the other (including a missing `else`) does not. This is a
+/// heuristic: it does not check that the base case is reachable.
+fn has_base_case(method_src: &str, name: &str) -> bool {
+    // A lone method declaration is not a valid program, so wrap it in a class.
+    let Ok(parser) = Parser::new(format!("class UmmMethodCheck {{\n{method_src}\n}}")) else {
+        return false;
+    };
+    let Ok(root) = parser.root_node() else {
+        return false;
+    };
+    base_case_under(root, parser.code().as_bytes(), name, false)
+}
+
+/// Walks `node` looking for a base case; `guarded` is true once inside an
+/// `if` or `switch`.
+fn base_case_under(node: Node, source: &[u8], name: &str, guarded: bool) -> bool {
+    let calls = |node: Option<Node>| node.is_some_and(|node| node_calls_itself(node, source, name));
+    let found = match node.kind() {
+        "return_statement" => guarded && !calls(Some(node)),
+        "if_statement" | "ternary_expression" => {
+            let consequence = node.child_by_field_name("consequence");
+            let alternative = node.child_by_field_name("alternative");
+            let missing_else = node.kind() == "if_statement" && alternative.is_none();
+            (calls(consequence) || calls(alternative))
+                && (!calls(consequence) || missing_else || !calls(alternative))
+        }
+        _ => false,
+    };
+    if found {
+        return true;
+    }
+
+    let guarded = guarded || matches!(node.kind(), "if_statement" | "switch_block");
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .any(|child| base_case_under(child, source, name, guarded))
+}
+
+/// Returns true when `node` contains a call to a method named `name`, either
+/// unqualified or through `this`.
+fn node_calls_itself(node: Node, source: &[u8], name: &str) -> bool {
+    let text = |node: Option<Node>| node.and_then(|node| node.utf8_text(source).ok());
+    if node.kind() == "method_invocation"
+        && text(node.child_by_field_name("name")) == Some(name)
+        && text(node.child_by_field_name("object")).is_none_or(|object| object == "this")
+    {
+        return true;
+    }
+
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .any(|child| node_calls_itself(child, source, name))
+}
+
+/// Returns the receiver of every call to a method named `name` inside the
+/// method declaration in `method_src`; unqualified calls have no receiver.
+fn invocation_receivers(method_src: &str, name: &str) -> Vec<Option<String>> {
+    // A lone method declaration is not a valid program, so wrap it in a class.
+    let Ok(parser) = Parser::new(format!("class UmmMethodCheck {{\n{method_src}\n}}")) else {
+        return Vec::new();
+    };
+    let query = format!(include_str!("../queries/method_invocations_with_name.scm"), name);
+
+    parser
+        .query(&query)
+        .map(|calls| {
+            calls
+                .into_iter()
+                .map(|call| call.get("object").cloned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Literal node kinds that can appear in a hardcoded print.
+const LITERAL_KINDS: &[&str] = &[
+    "string_literal",
+    "character_literal",
+    "decimal_integer_literal",
+    "hex_integer_literal",
+    "octal_integer_literal",
+    "binary_integer_literal",
+    "decimal_floating_point_literal",
+    "hex_floating_point_literal",
+    "true",
+    "false",
+    "null_literal",
+];
+
+/// Returns true if `main_src`, a `main` method declaration, does nothing but
+/// print fixed text: every statement in its body is a `System.out`/
+/// `System.err` print whose arguments are all literals, or literals joined
+/// with `+`. Comments are ignored, and so is anything outside the body, such
+/// as annotations on `main`.
+fn prints_only_literals(main_src: &str) -> bool {
+    let Ok(parser) = Parser::new(format!("class UmmHardcodeCheck {{\n{main_src}\n}}")) else {
+        return false;
+    };
+    let source = parser.code().as_bytes();
+    let Some(body) = parser
+        .root_node()
+        .ok()
+        .and_then(|root| first_of_kind(root, "method_declaration"))
+        .and_then(|main| main.child_by_field_name("body"))
+    else {
+        return false;
+    };
+
+    let mut cursor = body.walk();
+    let statements: Vec<Node> = body
+        .named_children(&mut cursor)
+        .filter(|node| !matches!(node.kind(), "line_comment" | "block_comment"))
+        .collect();
+    !statements.is_empty()
+        && statements
+            .iter()
+            .all(|statement| is_literal_print(*statement, source))
+}
+
+/// Returns true if `statement` is a `System.out`/`System.err` print whose
+/// arguments are all literal.
+fn is_literal_print(statement: Node, source: &[u8]) -> bool {
+    let Some(call) = statement
+        .named_child(0)
+        .filter(|_| statement.kind() == "expression_statement")
+        .filter(|call| call.kind() == "method_invocation")
+    else {
+        return false;
+    };
+    let text = |field: &str| {
+        call.child_by_field_name(field)
+            .and_then(|node| node.utf8_text(source).ok())
+            .unwrap_or_default()
+    };
+    let stream: String = text("object").split_whitespace().collect();
+    if !matches!(stream.as_str(), "System.out" | "System.err")
+        || !matches!(text("name"), "print" | "println" | "printf")
+    {
+        return false;
+    }
+
+    let Some(args) = call.child_by_field_name("arguments") else {
+        return false;
+    };
+    let mut cursor = args.walk();
+    args.named_children(&mut cursor)
+        .filter(|arg| !matches!(arg.kind(), "line_comment" | "block_comment"))
+        .all(|arg| is_literal(arg, source))
+}
+
+/// Returns true if `node` is a literal, possibly parenthesized, negated, or
+/// joined to other literals with `+`.
+fn is_literal(node: Node, source: &[u8]) -> bool {
+    let mut cursor = node.walk();
+    let mut operands = node.named_children(&mut cursor);
+    match node.kind() {
+        kind if LITERAL_KINDS.contains(&kind) => true,
+        "parenthesized_expression" => operands.all(|child| is_literal(child, source)),
+        "unary_expression" => {
+            node.child_by_field_name("operator")
+                .and_then(|op| op.utf8_text(source).ok())
+                .is_some_and(|op| op == "-" || op == "+")
+                && operands.all(|child| is_literal(child, source))
+        }
+        "binary_expression" => {
+            node.child_by_field_name("operator")
+                .and_then(|op| op.utf8_text(source).ok())
+                .is_some_and(|op| op == "+")
+                && operands.all(|child| is_literal(child, source))
+        }
+        _ => false,
+    }
+}
+
 #[derive(Default, Clone)]
 /// An enum to represent the constraint of a query.
 pub enum QueryConstraint {
@@ -180,6 +474,19 @@ impl QueryGrader {
         Ok(self)
     }
 
+    /// Adds the query named `name` from a [`QueryLibrary`] loaded at runtime.
+    /// Follow it with [`QueryGrader::capture`] to pick the capture to keep.
+    pub fn named_query(self, library: &QueryLibrary, name: &str) -> Result<Self, QueryError> {
+        let query = library
+            .get(name)
+            .ok_or_else(|| QueryError::UnknownNamedQuery {
+                name:      name.to_string(),
+                dir:       library.dir().display().to_string(),
+                available: library.names().join(", "),
+            })?;
+        self.query(query.to_string())
+    }
+
     /// Adds a predicate that filters results from the most recent query.
     /// If no queries have been added, this will throw an error.
     pub fn capture(#[allow(unused_mut)] mut self, c: String) -> Result<Self, QueryError> {
@@ -248,6 +555,145 @@ impl QueryGrader {
         self
     }
 
+    /// Selects the modifiers of the class with the supplied name, keeping the
+    /// match only if `modifier` (e.g. `final`, `abstract`, `public`) is among
+    /// them.
+    ///
+    /// When no reason has been set, one is generated: a failing check lists
+    /// the modifiers the class is actually declared with, so it explains
+    /// itself, while a passing one just confirms the modifier.
+    pub fn class_has_modifier(mut self, class_name: String, modifier: String) -> Self {
+        let query = format!(include_str!("../queries/class_modifiers_with_name.scm"), class_name);
+
+        if self.reason.trim().is_empty() {
+            let declared = self
+                .project
+                .identify(&self.file)
+                .ok()
+                .and_then(|file| file.query(&query).ok())
+                .and_then(|matches| {
+                    matches
+                        .into_iter()
+                        .find_map(|m| m.get("modifiers").cloned())
+                })
+                .map(|modifiers| modifiers.split_whitespace().collect::<Vec<_>>().join(" "));
+
+            self.reason = match declared {
+                Some(declared) if declared.split_whitespace().any(|token| token == modifier) => {
+                    format!("Class `{class_name}` is declared `{modifier}`.")
+                }
+                Some(declared) => format!(
+                    "Class `{class_name}` must be declared `{modifier}` (declared modifiers: \
+                     `{declared}`)."
+                ),
+                None => format!(
+                    "Class `{class_name}` must be declared `{modifier}` (declared with no \
+                     modifiers)."
+                ),
+            };
+        }
+
+        self.queries.push(Query {
+            query,
+            capture: "modifiers".to_string(),
+            filter: Some(Arc::new(move |found: &str| {
+                found.split_whitespace().any(|token| token == modifier)
+            })),
+        });
+        self
+    }
+
+    /// Keeps the annotations on the class, interface, enum, record, method,
+    /// or constructor named `target_name` that match `annotation`, e.g.
+    /// `FunctionalInterface` or `@Deprecated`. A qualified annotation such as
+    /// `@java.lang.Deprecated` matches by its simple name.
+    ///
+    /// When no reason has been set, one is generated that lists the
+    /// annotations `target_name` actually carries.
+    pub fn has_annotation(mut self, target_name: String, annotation: String) -> Self {
+        let query = format!(include_str!("../queries/annotations_with_target.scm"), target_name);
+        let wanted = simple_annotation_name(&annotation).to_string();
+
+        if self.reason.trim().is_empty() {
+            let mut found: Vec<String> = Vec::new();
+            for name in self
+                .project
+                .identify(&self.file)
+                .ok()
+                .and_then(|file| file.query(&query).ok())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|m| m.get("annotation"))
+            {
+                let name = format!("@{name}");
+                if !found.contains(&name) {
+                    found.push(name);
+                }
+            }
+
+            let found = if found.is_empty() {
+                "no annotations found".to_string()
+            } else {
+                format!("annotations found: `{}`", found.join("`, `"))
+            };
+            self.reason = format!("`{target_name}` must be annotated `@{wanted}` ({found}).");
+        }
+
+        self.queries.push(Query {
+            query,
+            capture: "annotation".to_string(),
+            filter: Some(Arc::new(move |found: &str| simple_annotation_name(found) == wanted)),
+        });
+        self
+    }
+
+    /// Selects constructor parameter lists whose types match `param_types` in
+    /// order, e.g. `["String", "int"]` for `Person(String name, int age)`.
+    /// Whitespace inside types is ignored, as are `final` and annotations.
+    ///
+    /// When no reason has been set, one is generated that lists the
+    /// constructors actually declared in the file.
+    pub fn constructor_with_params(mut self, param_types: Vec<String>) -> Self {
+        let query = include_str!("../queries/constructor_parameters.scm").to_string();
+        let expected: Vec<String> = param_types.iter().map(|t| strip_whitespace(t)).collect();
+
+        if self.reason.trim().is_empty() {
+            let declared: Vec<String> = self
+                .project
+                .identify(&self.file)
+                .ok()
+                .and_then(|file| file.query(&query).ok())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|m| m.get("parameters"))
+                .map(|params| format!("({})", parameter_types(params).join(", ")))
+                .collect();
+
+            let declared = if declared.is_empty() {
+                "none".to_string()
+            } else {
+                declared.join(", ")
+            };
+            self.reason = format!(
+                "Expected a constructor taking ({}) in `{}` (declared constructors: {declared}).",
+                param_types.join(", "),
+                self.file
+            );
+        }
+
+        self.queries.push(Query {
+            query,
+            capture: "parameters".to_string(),
+            filter: Some(Arc::new(move |found: &str| {
+                parameter_types(found)
+                    .iter()
+                    .map(|t| strip_whitespace(t))
+                    .eq(expected.iter().cloned())
+            })),
+        });
+        self
+    }
+
     /// Selects local variable declaration statements
     pub fn local_variables(mut self) -> Self {
         self.queries.push(Query {
@@ -308,6 +754,71 @@ impl QueryGrader {
         self
     }
 
+    /// Selects `switch` statements and expressions, in either the classic
+    /// `case ...:` form or the arrow `case ... ->` form.
+    ///
+    /// When no reason has been set, one is generated that reports which form
+    /// the file actually uses.
+    pub fn uses_switch(mut self) -> Self {
+        if self.reason.trim().is_empty() {
+            self.reason = format!(
+                "`{}` must use a `switch` (found: {}).",
+                self.file,
+                self.switch_forms_found()
+            );
+        }
+        self.queries.push(Query {
+            query:   String::from("((switch_expression) @switch)"),
+            capture: "switch".to_string(),
+            filter:  None,
+        });
+        self
+    }
+
+    /// Selects `switch` statements and expressions written in the arrow
+    /// `case ... ->` form only.
+    ///
+    /// When no reason has been set, one is generated that reports which form
+    /// the file actually uses.
+    pub fn uses_switch_expression(mut self) -> Self {
+        if self.reason.trim().is_empty() {
+            self.reason = format!(
+                "`{}` must use an arrow-form `switch` (`case ... ->`) (found: {}).",
+                self.file,
+                self.switch_forms_found()
+            );
+        }
+        self.queries.push(Query {
+            query:   String::from(
+                "((switch_expression body: (switch_block (switch_rule))) @switch)",
+            ),
+            capture: "switch".to_string(),
+            filter:  None,
+        });
+        self
+    }
+
+    /// Describes which `switch` forms the target file contains, for generated
+    /// reasons.
+    fn switch_forms_found(&self) -> &'static str {
+        let has = |query: &str| {
+            self.project
+                .identify(&self.file)
+                .ok()
+                .and_then(|file| file.query(query).ok())
+                .is_some_and(|matches| !matches.is_empty())
+        };
+        let classic = has("((switch_block_statement_group) @group)");
+        let arrow = has("((switch_rule) @rule)");
+
+        match (classic, arrow) {
+            (true, true) => "both classic `case ...:` and arrow `case ... ->` switches",
+            (true, false) => "only a classic `case ...:` switch",
+            (false, true) => "only an arrow `case ... ->` switch",
+            (false, false) => "no switch",
+        }
+    }
+
     /// Selects method invocations
     pub fn method_invocations(mut self) -> Self {
         self.queries.push(Query {
@@ -351,7 +862,240 @@ impl QueryGrader {
         self
     }
 
+    /// Selects the method named `method_name` if its body calls itself
+    /// (directly or via `this.`), so the default constraint requires
+    /// recursion.
+    ///
+    /// Detection is by name only: overloads and same-named methods on other
+    /// objects count as recursive calls, and mutual recursion is missed.
+    pub fn uses_recursion(mut self, method_name: String) -> Self {
+        if self.reason.trim().is_empty() {
+            self.reason = format!("Method `{method_name}` must be implemented recursively.");
+        }
+        self.push_recursive_method_query(method_name);
+        self
+    }
+
+    /// Requires that the method named `method_name` does not call itself,
+    /// setting the constraint to [`QueryConstraint::MustNotMatch`].
+    ///
+    /// Shares the name-based limitations of [`QueryGrader::uses_recursion`].
+    /// A file without the method fails, since there is nothing to inspect.
+    pub fn must_not_use_recursion(mut self, method_name: String) -> Self {
+        if self.reason.trim().is_empty() {
+            self.reason = format!("Method `{method_name}` must not use recursion.");
+        }
+        self.constraint = QueryConstraint::MustNotMatch;
+        self.push_recursive_method_query(method_name);
+        self
+    }
+
+    /// Selects the method named `caller` if its body calls a method named
+    /// `callee`, so the default constraint requires the call, e.g. a sort that
+    /// must use `swap`.
+    ///
+    /// The call is matched by name on any receiver, including calls made
+    /// inside lambdas in `caller`. When no reason has been set, one is
+    /// generated that says whether `caller` exists and whether it makes the
+    /// call.
+    pub fn method_calls_method(mut self, caller: String, callee: String) -> Self {
+        let query = format!(include_str!("../queries/method_body_with_name.scm"), caller);
+
+        if self.reason.trim().is_empty() {
+            let bodies = self
+                .project
+                .identify(&self.file)
+                .ok()
+                .and_then(|file| file.query(&query).ok())
+                .map(|matches| {
+                    matches
+                        .into_iter()
+                        .filter_map(|m| m.get("body").cloned())
+                        .collect::<Vec<_>>()
+                });
+
+            self.reason = match bodies {
+                Some(bodies) if bodies.is_empty() => format!(
+                    "Method `{caller}` must call `{callee}`, but no method named `{caller}` was \
+                     found."
+                ),
+                Some(bodies) if !bodies.iter().any(|body| calls_method(body, &callee)) => {
+                    format!("Method `{caller}` must call `{callee}`, but it does not.")
+                }
+                _ => format!("Method `{caller}` must call `{callee}`."),
+            };
+        }
+
+        self.queries.push(Query {
+            query,
+            capture: "body".to_string(),
+            filter: Some(Arc::new(move |body: &str| calls_method(body, &callee))),
+        });
+        self
+    }
+
+    /// Selects the method named `method_name` unless it calls itself without
+    /// a base case, so the default constraint flags likely infinite
+    /// recursion. A method that does not recurse passes; pair this with
+    /// [`QueryGrader::uses_recursion`] to also require recursion.
+    ///
+    /// A base case is a `return` inside an `if` or `switch` that does not
+    /// recurse, or an `if` or ternary with one branch that recurses and one
+    /// that does not (a missing `else` counts as the latter). When no reason
+    /// has been set, one is generated that says whether the method exists and
+    /// whether a base case was found.
+    pub fn recursion_has_base_case(mut self, method_name: String) -> Self {
+        let query = format!(include_str!("../queries/method_body_with_name.scm"), method_name);
+
+        if self.reason.trim().is_empty() {
+            let bodies = self
+                .project
+                .identify(&self.file)
+                .ok()
+                .and_then(|file| file.query(&query).ok())
+                .map(|matches| {
+                    matches
+                        .into_iter()
+                        .filter_map(|m| m.get("body").cloned())
+                        .collect::<Vec<_>>()
+                });
+            let lacks_base_case = |body: &String| {
+                calls_itself(body, &method_name) && !has_base_case(body, &method_name)
+            };
+
+            self.reason = match bodies {
+                Some(bodies) if bodies.is_empty() => format!(
+                    "Recursive method `{method_name}` must have a base case, but no method named \
+                     `{method_name}` was found."
+                ),
+                Some(bodies) if bodies.iter().any(lacks_base_case) => format!(
+                    "Recursive method `{method_name}` must have a base case that returns without \
+                     calling `{method_name}`, but none was found, so it may recurse forever."
+                ),
+                _ => format!("Recursive method `{method_name}` must have a base case."),
+            };
+        }
+
+        self.queries.push(Query {
+            query,
+            capture: "body".to_string(),
+            filter: Some(Arc::new(move |body: &str| {
+                !calls_itself(body, &method_name) || has_base_case(body, &method_name)
+            })),
+        });
+        self
+    }
+
+    /// Flags a `main` method that only prints literal text, a common sign that
+    /// the expected output was hardcoded rather than computed. Sets the
+    /// constraint to [`QueryConstraint::MustNotMatch`].
+    ///
+    /// This is a heuristic meant as a warning: pair it with a small or zero
+    /// `out_of` and review flagged submissions by hand. Any statement in
+    /// `main` other than a print, or a print of anything but literals, clears
+    /// the flag. A file without `main`, such as a library class, passes.
+    pub fn must_not_hardcode_output(mut self) -> Self {
+        if self.reason.trim().is_empty() {
+            self.reason = "Warning: `main` only prints fixed text, so the output may be hardcoded \
+                           instead of computed."
+                .to_string();
+        }
+        self.constraint = QueryConstraint::MustNotMatch;
+        // Also matching the program itself keeps a file without `main` from
+        // being reported as a query that found nothing to inspect.
+        self.queries.push(Query {
+            query:   format!(
+                "[{} ((program) @program)]",
+                include_str!("../queries/main_method.scm")
+            ),
+            capture: "body".to_string(),
+            filter:  Some(Arc::new(prints_only_literals)),
+        });
+        self
+    }
+
+    /// Requires that the file does not import anything `pattern` names, setting
+    /// the constraint to [`QueryConstraint::MustNotMatch`]. See
+    /// [`ImportDecl::matches`] for how patterns and `.*` imports compare.
+    ///
+    /// When no reason has been set, one is generated that lists each
+    /// violating import with its line.
+    pub fn must_not_import(mut self, pattern: String) -> Self {
+        if self.reason.trim().is_empty() {
+            let found = self.imports_found(|file| file.forbidden_imports(&pattern));
+            self.reason = format!("`{}` must not import `{pattern}`{found}.", self.file);
+        }
+        self.constraint = QueryConstraint::MustNotMatch;
+        self.push_import_query(move |import| import.matches(&pattern));
+        self
+    }
+
+    /// Requires that the file imports only from the JDK (see
+    /// [`STDLIB_PACKAGES`](crate::java::STDLIB_PACKAGES)), setting the
+    /// constraint to [`QueryConstraint::MustNotMatch`].
+    ///
+    /// When no reason has been set, one is generated that lists each
+    /// violating import with its line.
+    pub fn only_stdlib_imports(mut self) -> Self {
+        if self.reason.trim().is_empty() {
+            let found = self.imports_found(File::non_stdlib_imports);
+            self.reason =
+                format!("`{}` may only import from the Java standard library{found}.", self.file);
+        }
+        self.constraint = QueryConstraint::MustNotMatch;
+        self.push_import_query(|import| !import.is_stdlib());
+        self
+    }
+
+    /// Describes the target file's violating imports for generated reasons,
+    /// e.g. ` (found: `import java.util.Scanner;` on line 1)`, or nothing
+    /// when there are none.
+    fn imports_found(&self, violations: impl Fn(&File) -> Result<Vec<ImportDecl>>) -> String {
+        let found = self
+            .project
+            .identify(&self.file)
+            .ok()
+            .and_then(|file| violations(&file).ok())
+            .unwrap_or_default();
+        if found.is_empty() {
+            return String::new();
+        }
+        let listed: Vec<String> = found.iter().map(ToString::to_string).collect();
+        format!(" (found: {})", listed.join(", "))
+    }
+
+    /// Adds a query selecting import declarations, kept only when
+    /// `violates` holds for them.
+    fn push_import_query(
+        &mut self,
+        violates: impl Fn(&ImportDecl) -> bool + Send + Sync + 'static,
+    ) {
+        // Also matching the program itself keeps a file without imports from
+        // being reported as a query that found nothing to inspect.
+        self.queries.push(Query {
+            query:   format!("[{IMPORT_DECLARATION_QUERY} ((program) @program)]"),
+            capture: "import".to_string(),
+            filter:  Some(Arc::new(move |text: &str| {
+                ImportDecl::parse(text, 0).is_some_and(|import| violates(&import))
+            })),
+        });
+    }
+
+    /// Adds a query selecting the named method, kept only when it calls
+    /// itself.
+    fn push_recursive_method_query(&mut self, method_name: String) {
+        self.queries.push(Query {
+            query:   format!(include_str!("../queries/method_body_with_name.scm"), method_name),
+            capture: "body".to_string(),
+            filter:  Some(Arc::new(move |body: &str| calls_itself(body, &method_name))),
+        });
+    }
+
     /// Runs the configured queries and returns the captured results.
+    ///
+    /// Filters are plain Rust predicates and parsing uses per-thread parsers,
+    /// so graders can run concurrently without any shared lock.
+    ///
     /// TODO: Make it so that it doesn't parse a new piece of code, just filters
     /// out the irrelevant line ranges. This performs better but more
     /// importantly is more accurate.
@@ -389,12 +1133,7 @@ impl QueryGrader {
 
                 captured
             }
-            Err(e) => {
-                return Err(QueryError::DuringQueryExecution {
-                    q: first_query.clone(),
-                    e: format!("{:#?}", e),
-                });
-            }
+            Err(e) => return Err(QueryError::during_execution(&first_query, e)),
         };
 
         for (index, query) in self.queries.iter().enumerate().skip(1) {
@@ -417,13 +1156,9 @@ impl QueryGrader {
                     .context(format!("Failed to create parser for query: `{}`", query_src))
                     .map_err(QueryError::Unknown)?;
 
-                let raw =
-                    parser
-                        .query(&query_src)
-                        .map_err(|e| QueryError::DuringQueryExecution {
-                            q: query_src.clone(),
-                            e: format!("{:#?}", e),
-                        })?;
+                let raw = parser
+                    .query(&query_src)
+                    .map_err(|e| QueryError::during_execution(&query_src, e))?;
 
                 let mut captured: Vec<String> = raw
                     .iter()
@@ -446,6 +1181,21 @@ impl QueryGrader {
     /// Grades the file according to the supplied queries, captures, and
     /// constraints.
     pub fn grade_by_query(self) -> Result<GradeResult> {
+        if let Ok(file) = self.project.identify(&self.file) {
+            if file.is_empty() {
+                return Ok(GradeResult::empty_file(&self.req_name, self.out_of, file.file_name()));
+            }
+            if file.parser().has_errors() {
+                let errors = file.parser().syntax_errors();
+                return Ok(GradeResult::syntax_errors(
+                    &self.req_name,
+                    self.out_of,
+                    file.file_name(),
+                    &errors,
+                ));
+            }
+        }
+
         let reason = if self.reason.trim().is_empty() {
             eprintln!(
                 "Warning: No reason provided for query grading. Feedback to student will not be \
@@ -592,10 +1342,11 @@ impl QueryGrader {
 impl QueryGrader {
     /// Builds and runs the query grader.
     pub fn run(self) -> Result<GradeResult> {
+        let started = Instant::now();
         if self.queries.is_empty() {
             bail!("QueryGrader requires at least one query");
         }
-        self.grade_by_query()
+        Ok(self.grade_by_query()?.with_elapsed(started.elapsed()))
     }
 }
 
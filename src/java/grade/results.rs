@@ -1,14 +1,25 @@
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
 
 use anyhow::{Context, Result};
-use async_openai::types::chat::ChatCompletionRequestMessage;
+use async_openai::types::chat::{
+    ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs,
+    ChatCompletionRequestUserMessageArgs,
+};
 use bon::Builder;
 use serde::{Deserialize, Serialize};
 use tabled::Tabled;
 
+use crate::{
+    config,
+    java::{JavaFileError, SyntaxError},
+};
+
 #[derive(Clone, Default, Builder, Serialize, Deserialize)]
 /// A struct representing a grade
 pub struct Grade {
@@ -41,12 +52,197 @@ impl Grade {
     }
 }
 
+/// A letter grade scale: `(minimum percentage, letter)` cutoffs checked from
+/// highest to lowest, with `fallback` used below the last cutoff.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LetterScale {
+    /// Cutoffs as `(minimum percentage, letter)` pairs.
+    cutoffs:  Vec<(f64, String)>,
+    /// Letter assigned when no cutoff is met.
+    fallback: String,
+}
+
+impl LetterScale {
+    /// Creates a scale from `(minimum percentage, letter)` cutoffs and the
+    /// letter used below all of them. Cutoffs may be given in any order.
+    pub fn new(
+        cutoffs: impl IntoIterator<Item = (f64, impl Into<String>)>,
+        fallback: impl Into<String>,
+    ) -> Self {
+        let mut cutoffs: Vec<(f64, String)> = cutoffs
+            .into_iter()
+            .map(|(min, letter)| (min, letter.into()))
+            .collect();
+        cutoffs.sort_by(|a, b| b.0.total_cmp(&a.0));
+        Self {
+            cutoffs,
+            fallback: fallback.into(),
+        }
+    }
+
+    /// Returns the letter for `percent`. Cutoffs are inclusive, so `90.0`
+    /// earns an `A` on the default scale.
+    pub fn letter(&self, percent: f64) -> &str {
+        self.cutoffs
+            .iter()
+            // Tolerate float noise such as 0.7 * 100.0 == 70.00000000000001.
+            .find(|(min, _)| percent + 1e-9 >= *min)
+            .map(|(_, letter)| letter.as_str())
+            .unwrap_or(self.fallback.as_str())
+    }
+}
+
+impl Default for LetterScale {
+    /// The usual 90/80/70/60 scale.
+    fn default() -> Self {
+        Self::new([(90.0, "A"), (80.0, "B"), (70.0, "C"), (60.0, "D")], "F")
+    }
+}
+
+/// How a [`Grade`] is rendered in tables and totals.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum GradeStyle {
+    /// `grade/out_of`, e.g. `8.00/10.00`.
+    #[default]
+    Fraction,
+    /// Percentage of `out_of`, e.g. `80.00%`.
+    Percent,
+    /// Letter grade from the given scale, e.g. `B (80.00%)`.
+    Letter(LetterScale),
+}
+
+impl Grade {
+    /// Returns the grade as a percentage of `out_of`; a zero `out_of` yields
+    /// `0.0`.
+    pub fn percent(&self) -> f64 {
+        if self.out_of == 0.0 {
+            0.0
+        } else {
+            self.grade / self.out_of * 100.0
+        }
+    }
+
+    /// Renders the grade in the given style.
+    pub fn format(&self, style: GradeStyle) -> String {
+        match style {
+            GradeStyle::Fraction => self.to_string(),
+            GradeStyle::Percent => format!("{:.2}%", self.percent()),
+            GradeStyle::Letter(scale) => {
+                format!("{} ({:.2}%)", scale.letter(self.percent()), self.percent())
+            }
+        }
+    }
+}
+
 impl Display for Grade {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:.2}/{:.2}", self.grade, self.out_of)
     }
 }
 
+/// Where a [`Reason`] is going to be displayed.
+///
+/// The targets differ in more than styling: points often carry raw program
+/// output or diffs, whose `-`, `+`, `*`, and `#` lines a Markdown renderer
+/// would turn into nested lists, emphasis, or headings. Markdown therefore
+/// fences those lines, while the terminal shows them as they are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReasonTarget {
+    /// Plain text for the terminal overview table.
+    Terminal,
+    /// Markdown for Gradescope output.
+    Markdown,
+}
+
+/// A grading explanation built from an optional headline and bullet points.
+///
+/// A lone point without a headline renders as just that point, so single-issue
+/// reasons read the same as a plain string.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Reason {
+    /// Headline shown above the points.
+    summary: Option<String>,
+    /// Individual issues, one bullet each; may span several lines.
+    points:  Vec<String>,
+}
+
+impl Reason {
+    /// Creates an empty reason.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the headline shown above the points.
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Appends a bullet point.
+    pub fn point(mut self, point: impl Into<String>) -> Self {
+        self.push(point);
+        self
+    }
+
+    /// Appends a bullet point in place.
+    pub fn push(&mut self, point: impl Into<String>) {
+        self.points.push(point.into());
+    }
+
+    /// Returns true if the reason has neither a headline nor points.
+    pub fn is_empty(&self) -> bool {
+        self.summary.is_none() && self.points.is_empty()
+    }
+
+    /// Renders the reason for `target`.
+    ///
+    /// Both targets use `- ` bullets with continuation lines indented under
+    /// their bullet. Markdown additionally bolds the headline, separates it
+    /// from the list with a blank line so renderers start a new block, and
+    /// fences continuation lines so their contents render verbatim.
+    pub fn render(&self, target: ReasonTarget) -> String {
+        if self.summary.is_none() && self.points.len() == 1 {
+            return self.points[0].clone();
+        }
+
+        let mut lines = Vec::new();
+        if let Some(summary) = &self.summary {
+            match target {
+                ReasonTarget::Terminal => lines.push(summary.clone()),
+                ReasonTarget::Markdown => {
+                    lines.push(format!("**{summary}**"));
+                    if !self.points.is_empty() {
+                        lines.push(String::new());
+                    }
+                }
+            }
+        }
+        for point in &self.points {
+            let mut point_lines = point.lines();
+            lines.push(format!("- {}", point_lines.next().unwrap_or_default()));
+            let rest: Vec<&str> = point_lines.collect();
+            if rest.is_empty() {
+                continue;
+            }
+            if target == ReasonTarget::Markdown {
+                lines.push(String::new());
+                lines.push("  ```".to_string());
+            }
+            lines.extend(rest.iter().map(|line| format!("  {line}")));
+            if target == ReasonTarget::Markdown {
+                lines.push("  ```".to_string());
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(ReasonTarget::Terminal))
+    }
+}
+
 #[derive(Tabled, Clone, Default, Builder, Serialize, Deserialize)]
 #[builder(on(String, into))]
 /// A struct to store grading results and display them
@@ -54,20 +250,38 @@ pub struct GradeResult {
     #[tabled(rename = "Requirement")]
     /// * `requirement`: refers to Requirement ID
     #[builder(getter)]
-    pub(crate) requirement: String,
+    pub(crate) requirement:  String,
     #[tabled(rename = "Grade")]
     /// * `grade`: grade received for above Requirement
     #[builder(default)]
     #[builder(getter)]
-    pub(crate) grade:       Grade,
+    pub(crate) grade:        Grade,
     #[tabled(rename = "Reason")]
     /// * `reason`: the reason for penalties applied, if any
     #[builder(getter)]
-    pub(crate) reason:      String,
+    pub(crate) reason:       String,
+    #[tabled(skip)]
+    /// * `details`: structured form of `reason`, used for Markdown output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(getter)]
+    pub(crate) details:      Option<Reason>,
     #[tabled(skip)]
     /// * `prompt`: the prompt for the AI TA
     #[builder(getter)]
-    pub(crate) prompt:      Option<Vec<ChatCompletionRequestMessage>>,
+    pub(crate) prompt:       Option<Vec<ChatCompletionRequestMessage>>,
+    #[tabled(skip)]
+    /// * `extra_credit`: whether the grade counts on top of the total instead
+    ///   of towards it
+    #[serde(default)]
+    #[builder(default)]
+    #[builder(getter)]
+    pub(crate) extra_credit: bool,
+    #[tabled(skip)]
+    /// * `elapsed`: wall-clock time the grader took, from the start of its
+    ///   `run` to the result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(getter)]
+    pub(crate) elapsed:      Option<Duration>,
 }
 
 impl GradeResult {
@@ -90,4 +304,177 @@ impl GradeResult {
     pub fn reason(&self) -> &str {
         self.reason.as_str()
     }
+
+    /// Returns the reason rendered for `target`, falling back to the plain
+    /// reason string when no structured [`Reason`] was recorded.
+    pub fn reason_as(&self, target: ReasonTarget) -> String {
+        match &self.details {
+            Some(details) => details.render(target),
+            None => self.reason.clone(),
+        }
+    }
+
+    /// Returns the feedback prompt messages, if any were produced.
+    pub fn prompt(&self) -> Option<&[ChatCompletionRequestMessage]> {
+        self.prompt.as_deref()
+    }
+
+    /// A zero grade for a requirement whose target file is empty or contains
+    /// no code, so students see why instead of a bare zero.
+    pub fn empty_file(requirement: impl Into<String>, out_of: f64, file_name: &str) -> Self {
+        GradeResult::builder()
+            .requirement(requirement.into())
+            .grade(Grade::new(0.0, out_of))
+            .reason(format!("`{file_name}` is empty or contains no code."))
+            .maybe_prompt(None)
+            .build()
+    }
+
+    /// A zero grade for a requirement whose target file does not parse, so
+    /// students see "fix the syntax" rather than a misleading query failure.
+    pub fn syntax_errors(
+        requirement: impl Into<String>,
+        out_of: f64,
+        file_name: &str,
+        errors: &[SyntaxError],
+    ) -> Self {
+        let mut reason = format!("`{file_name}` has syntax errors, so it could not be graded");
+        if let Some(first) = errors.first() {
+            reason.push_str(&format!(" ({first}"));
+            if errors.len() > 1 {
+                reason.push_str(&format!(", and {} more", errors.len() - 1));
+            }
+            reason.push(')');
+        }
+        reason.push('.');
+
+        GradeResult::builder()
+            .requirement(requirement.into())
+            .grade(Grade::new(0.0, out_of))
+            .reason(reason)
+            .maybe_prompt(None)
+            .build()
+    }
+
+    /// A zero grade with `reason`, prompting for help with `body` after the
+    /// instructor's `system_message`.
+    pub fn failure(
+        requirement: impl Into<String>,
+        out_of: f64,
+        system_message: &str,
+        reason: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Result<Self> {
+        let messages: Vec<ChatCompletionRequestMessage> = vec![
+            ChatCompletionRequestSystemMessageArgs::default()
+                .content(system_message.to_string())
+                .name("Instructor".to_string())
+                .build()?
+                .into(),
+            ChatCompletionRequestUserMessageArgs::default()
+                .content(body.into())
+                .name("Student".to_string())
+                .build()?
+                .into(),
+        ];
+
+        Ok(GradeResult::builder()
+            .requirement(requirement.into())
+            .grade(Grade::new(0.0, out_of))
+            .reason(reason.into())
+            .maybe_prompt(Some(messages))
+            .build())
+    }
+
+    /// A zero grade for a Java file that failed to compile or run, explaining
+    /// `error`. `case` numbers the failing run for graders that run the file
+    /// more than once.
+    pub fn run_error(
+        requirement: impl Into<String>,
+        out_of: f64,
+        case: Option<usize>,
+        error: JavaFileError,
+    ) -> Result<Self> {
+        let for_case = case.map(|n| format!(" for case {n}")).unwrap_or_default();
+        let (reason, body) = match error {
+            JavaFileError::AtRuntime { output, .. } => (
+                format!("Error running file{for_case}."),
+                format!("Error while running -\n```\n{output}\n```"),
+            ),
+            JavaFileError::DuringCompilation { stacktrace, .. } => (
+                "Error compiling file.".to_string(),
+                format!("Error while compiling -\n```\n{stacktrace}\n```"),
+            ),
+            e @ JavaFileError::NoMainMethod { .. } => {
+                ("File has no main method to run.".to_string(), e.to_string())
+            }
+            JavaFileError::TimedOut {
+                partial_output,
+                timeout,
+            } => {
+                let seconds = timeout.as_secs_f64();
+                let run = case.map_or_else(|| "File".to_string(), |n| format!("Case {n}"));
+                (
+                    format!("{run} did not finish within {seconds}s."),
+                    format!(
+                        "The program was stopped after {seconds}s, possibly because of an \
+                         infinite loop or because it is waiting for more input. Output before it \
+                         was stopped -\n```\n{partial_output}\n```"
+                    ),
+                )
+            }
+            e => (
+                format!("Unknown error while running file{for_case}."),
+                format!("Unknown error -\n```\n{e:?}\n```"),
+            ),
+        };
+
+        let prompts = config::java_prompts();
+        Self::failure(requirement, out_of, prompts.system_message(), reason, body)
+    }
+
+    /// Returns true if this requirement is extra credit.
+    pub fn is_extra_credit(&self) -> bool {
+        self.extra_credit
+    }
+
+    /// Returns how long the grader took, if it was timed.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed
+    }
+
+    /// Records how long the grader took.
+    pub fn with_elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = Some(elapsed);
+        self
+    }
+
+    /// Marks this requirement as extra credit (or not). Extra-credit grades
+    /// add to the achieved total without adding to its `out_of`, so totals
+    /// may exceed 100%.
+    pub fn with_extra_credit(mut self, extra_credit: bool) -> Self {
+        self.extra_credit = extra_credit;
+        self
+    }
+}
+
+/// Runs a grader's `grade` future, recording the wall-clock time it took on
+/// the result.
+pub async fn timed(grade: impl Future<Output = Result<GradeResult>>) -> Result<GradeResult> {
+    let started = Instant::now();
+    let result = grade.await?;
+    Ok(result.with_elapsed(started.elapsed()))
+}
+
+/// Sums `results` into an overall grade. Extra-credit requirements add to the
+/// achieved grade but not to `out_of`.
+pub fn total_grade(results: &[GradeResult]) -> Grade {
+    results.iter().fold(Grade::new(0.0, 0.0), |total, r| {
+        let out_of = if r.extra_credit {
+            0.0
+        } else {
+            r.out_of_value()
+        };
+        Grade::new(total.grade + r.grade_value(), total.out_of + out_of)
+    })
 }
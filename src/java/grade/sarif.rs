@@ -0,0 +1,172 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::diagnostics::JavacDiagnostic;
+use crate::java::parsers::parser;
+
+/// JSON schema advertised by emitted SARIF logs.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF version emitted by this module.
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// Top-level SARIF log document.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SarifLog {
+    /// Schema URI for editors and validators.
+    #[serde(rename = "$schema")]
+    pub schema:  String,
+    /// SARIF format version.
+    pub version: String,
+    /// One run per tool invocation.
+    pub runs:    Vec<SarifRun>,
+}
+
+/// A single analysis run.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SarifRun {
+    /// Tool that produced the results.
+    pub tool:    SarifTool,
+    /// Diagnostics reported by the tool.
+    pub results: Vec<SarifResult>,
+}
+
+/// Wrapper around the tool's driver description.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SarifTool {
+    /// Primary tool component.
+    pub driver: SarifDriver,
+}
+
+/// Describes the tool component that produced the results.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SarifDriver {
+    /// Tool name, e.g. `javac`.
+    pub name: String,
+}
+
+/// One diagnostic in SARIF form.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    /// Stable identifier for the kind of diagnostic.
+    pub rule_id:   String,
+    /// `error` or `warning`.
+    pub level:     String,
+    /// Human-readable diagnostic text.
+    pub message:   SarifMessage,
+    /// Where the diagnostic points.
+    pub locations: Vec<SarifLocation>,
+}
+
+/// Plain-text SARIF message.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SarifMessage {
+    /// Message body.
+    pub text: String,
+}
+
+/// Location wrapper for a result.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    /// File and region the result refers to.
+    pub physical_location: SarifPhysicalLocation,
+}
+
+/// File plus region inside it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    /// File the result refers to.
+    pub artifact_location: SarifArtifactLocation,
+    /// Line range inside the file.
+    pub region:            SarifRegion,
+}
+
+/// Relative URI of a source file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SarifArtifactLocation {
+    /// Forward-slash path relative to the project root.
+    pub uri: String,
+}
+
+/// Line range inside a file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    /// 1-based line the diagnostic starts on.
+    pub start_line: u32,
+}
+
+impl From<&JavacDiagnostic> for SarifResult {
+    fn from(diag: &JavacDiagnostic) -> Self {
+        let level = if diag.severity().is_error() {
+            "error"
+        } else {
+            "warning"
+        };
+        // `parse_diag` prefixes error messages; SARIF carries that in `level`.
+        let text = diag
+            .message()
+            .strip_prefix("Error: ")
+            .unwrap_or(diag.message())
+            .trim()
+            .to_string();
+
+        SarifResult {
+            rule_id:   format!("javac.{level}"),
+            level:     level.to_string(),
+            message:   SarifMessage { text },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: sarif_uri(&diag.path().display().to_string()),
+                    },
+                    region:            SarifRegion {
+                        start_line: diag.line_number(),
+                    },
+                },
+            }],
+        }
+    }
+}
+
+/// Normalizes a diagnostic path into a relative, forward-slash URI.
+fn sarif_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.trim_start_matches("./").to_string()
+}
+
+/// Builds a SARIF log with a single run for `tool` covering `diags`.
+pub fn sarif_log(tool: &str, diags: &[JavacDiagnostic]) -> SarifLog {
+    SarifLog {
+        schema:  SARIF_SCHEMA.to_string(),
+        version: SARIF_VERSION.to_string(),
+        runs:    vec![SarifRun {
+            tool:    SarifTool {
+                driver: SarifDriver {
+                    name: tool.to_string(),
+                },
+            },
+            results: diags.iter().map(SarifResult::from).collect(),
+        }],
+    }
+}
+
+/// Renders `diags` as pretty-printed SARIF JSON.
+pub fn sarif_json(tool: &str, diags: &[JavacDiagnostic]) -> Result<String> {
+    serde_json::to_string_pretty(&sarif_log(tool, diags)).context("Failed to serialize SARIF log")
+}
+
+/// Collects the diagnostics `javac` printed in `output`, skipping the
+/// context lines that follow each one.
+pub fn diagnostics_from_output(output: &str) -> Vec<JavacDiagnostic> {
+    output
+        .lines()
+        .filter_map(|line| parser::parse_diag(line).ok())
+        .collect()
+}
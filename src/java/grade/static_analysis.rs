@@ -0,0 +1,203 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::borrow::Cow;
+
+use anyhow::{Result, bail};
+use async_openai::types::chat::{
+    ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs,
+    ChatCompletionRequestUserMessageArgs,
+};
+use tree_sitter::Node;
+
+use super::{
+    results::{Grade, GradeResult, Reason, ReasonTarget},
+    syntax::{METHOD_KINDS, TYPE_KINDS, declared_name},
+};
+use crate::{
+    config,
+    java::{Parser, Project},
+};
+
+/// The declarations enclosing a node.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Scope {
+    /// Name of the innermost enclosing class, interface, enum, or record.
+    pub owner:  Option<String>,
+    /// Name of the innermost enclosing method or constructor. Entering a
+    /// nested type declaration clears it.
+    pub method: Option<String>,
+}
+
+impl Scope {
+    /// Qualifies `name` by the enclosing type, e.g. `Main.process`.
+    pub fn qualify(&self, name: &str) -> String {
+        match &self.owner {
+            Some(owner) => format!("{owner}.{name}"),
+            None => name.to_string(),
+        }
+    }
+
+    /// The enclosing method qualified by its type, with `fallback` standing in
+    /// outside any method, e.g. `Main.<init>` for a field initializer.
+    pub fn method_or(&self, fallback: &str) -> String {
+        self.qualify(self.method.as_deref().unwrap_or(fallback))
+    }
+
+    /// The scope inside `node`: a type declaration replaces the owner and
+    /// clears the method, and a method declaration replaces the method.
+    fn enter(&self, node: Node, source: &[u8]) -> Cow<'_, Self> {
+        if TYPE_KINDS.contains(&node.kind()) {
+            Cow::Owned(Self {
+                owner:  declared_name(node, source),
+                method: None,
+            })
+        } else if METHOD_KINDS.contains(&node.kind()) {
+            Cow::Owned(Self {
+                owner:  self.owner.clone(),
+                method: declared_name(node, source),
+            })
+        } else {
+            Cow::Borrowed(self)
+        }
+    }
+}
+
+/// Visits every named node of `parser`'s tree in source order, along with the
+/// declarations enclosing it. A declaration's scope includes the declaration
+/// itself. `visit` returns whether to descend into the node's children.
+pub(super) fn walk<'tree>(
+    parser: &'tree Parser,
+    mut visit: impl FnMut(Node<'tree>, &Scope) -> bool,
+) -> Result<()> {
+    let source = parser.code().as_bytes();
+    walk_node(parser.root_node()?, source, &Scope::default(), &mut visit);
+    Ok(())
+}
+
+/// Visits `node` and, if `visit` asks for it, its named children.
+fn walk_node<'tree, F>(node: Node<'tree>, source: &[u8], scope: &Scope, visit: &mut F)
+where
+    F: FnMut(Node<'tree>, &Scope) -> bool,
+{
+    let scope = scope.enter(node, source);
+    if !visit(node, &scope) {
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        walk_node(child, source, &scope, visit);
+    }
+}
+
+/// A rule a static-analysis grader checks in the syntax tree of each file.
+pub(super) trait StaticCheck {
+    /// A single violation of the rule.
+    type Finding;
+
+    /// Lists the violations in one file's tree.
+    fn find(&self, parser: &Parser) -> Result<Vec<Self::Finding>>;
+
+    /// Describes `finding`, found in `file`, as one point of the reason.
+    fn describe(&self, file: &str, finding: &Self::Finding) -> String;
+
+    /// Reason given when nothing is found.
+    fn passed(&self) -> String;
+
+    /// Headline above the `count` findings.
+    fn summary(&self, count: usize) -> String;
+
+    /// Explains the rule to the student ahead of the findings in the prompt.
+    fn advice(&self) -> String;
+}
+
+/// Settings every static-analysis grader shares.
+pub(super) struct StaticGrading<'a> {
+    /// Name of the grader, for error messages, e.g. `ComplexityGrader`.
+    pub grader:   &'static str,
+    /// Name of the requirement.
+    pub req_name: &'a str,
+    /// Points to give if nothing is found.
+    pub out_of:   f64,
+    /// Points deducted per finding.
+    pub penalty:  f64,
+    /// The project to grade.
+    pub project:  &'a Project,
+    /// Files to check.
+    pub files:    &'a [String],
+}
+
+impl StaticGrading<'_> {
+    /// Checks every file against `check` and deducts `penalty` per finding.
+    /// An empty file or one with syntax errors fails the requirement outright.
+    pub fn grade(&self, check: &impl StaticCheck) -> Result<GradeResult> {
+        if self.files.is_empty() {
+            bail!("{} requires at least one file", self.grader);
+        }
+
+        let mut findings = Vec::new();
+        for name in self.files {
+            let file = self.project.identify(name)?;
+            if file.is_empty() {
+                return Ok(GradeResult::empty_file(self.req_name, self.out_of, file.file_name()));
+            }
+            if file.parser().has_errors() {
+                let errors = file.parser().syntax_errors();
+                return Ok(GradeResult::syntax_errors(
+                    self.req_name,
+                    self.out_of,
+                    file.file_name(),
+                    &errors,
+                ));
+            }
+            findings.extend(
+                check
+                    .find(file.parser())?
+                    .into_iter()
+                    .map(|finding| (file.file_name().to_string(), finding)),
+            );
+        }
+
+        if findings.is_empty() {
+            return Ok(GradeResult::builder()
+                .requirement(self.req_name)
+                .grade(Grade::new(self.out_of, self.out_of))
+                .reason(check.passed())
+                .maybe_prompt(None)
+                .build());
+        }
+
+        let details = findings.iter().fold(
+            Reason::new().summary(check.summary(findings.len())),
+            |reason, (file, finding)| reason.point(check.describe(file, finding)),
+        );
+        let reason = details.render(ReasonTarget::Terminal);
+        let grade = (self.out_of - self.penalty * findings.len() as f64).max(0.0);
+
+        Ok(GradeResult::builder()
+            .requirement(self.req_name)
+            .grade(Grade::new(grade, self.out_of))
+            .reason(reason.clone())
+            .details(details)
+            .maybe_prompt(Some(prompt(&check.advice(), &reason)?))
+            .build())
+    }
+}
+
+/// Builds a prompt that explains the rule and lists what broke it.
+fn prompt(advice: &str, reason: &str) -> Result<Vec<ChatCompletionRequestMessage>> {
+    let prompts = config::java_prompts();
+    Ok(vec![
+        ChatCompletionRequestSystemMessageArgs::default()
+            .content(prompts.system_message().to_string())
+            .name("Instructor".to_string())
+            .build()?
+            .into(),
+        ChatCompletionRequestUserMessageArgs::default()
+            .content(format!("{advice}\n\n{reason}"))
+            .name("Student".to_string())
+            .build()?
+            .into(),
+    ])
+}
@@ -0,0 +1,27 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use tree_sitter::Node;
+
+/// Declarations whose bodies hold a method's code.
+pub(super) const METHOD_KINDS: &[&str] = &[
+    "method_declaration",
+    "constructor_declaration",
+    "compact_constructor_declaration",
+];
+
+/// Declarations whose name qualifies the methods inside them.
+pub(super) const TYPE_KINDS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+];
+
+/// Returns the text of `node`'s `name` field, if any.
+pub(super) fn declared_name(node: Node, source: &[u8]) -> Option<String> {
+    node.child_by_field_name("name")?
+        .utf8_text(source)
+        .ok()
+        .map(str::to_string)
+}
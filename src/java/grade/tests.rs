@@ -1,7 +1,12 @@
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
-use std::{collections::HashSet, ffi::OsString, path::PathBuf, time::Duration};
+use std::{
+    collections::HashSet,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use anyhow::{Context, Result, anyhow, bail};
 use async_openai::types::chat::{
@@ -13,21 +18,117 @@ use tabled::tables::ExtendedTable;
 use tokio::fs as async_fs;
 
 use super::{
+    context::build_failing_methods_context,
     diagnostics::MutationDiagnostic,
-    results::{Grade, GradeResult},
+    results::{Grade, GradeResult, Reason, ReasonTarget, timed},
 };
 use crate::{
     config,
     java::{
-        File, JavaFileError, Project, ProjectPaths,
+        File, FileType, JavaFileError, Project, ProjectPaths,
         parsers::parser,
         util::{classpath, java_path},
     },
     process::{self, StdinSource},
-    retrieval::build_context_message,
+    retrieval::build_context_message_with,
     types::LineRef,
 };
 
+/// File in the report directory that keeps PIT's full output after a failed
+/// run.
+pub const PIT_OUTPUT_LOG: &str = "pit-output.log";
+
+/// File name of PIT's CSV mutation report.
+const MUTATION_REPORT_CSV: &str = "mutations.csv";
+
+/// Common reasons a PIT run fails, recognised from its combined output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PitFailure {
+    /// The test suite fails before any mutation; PIT needs a green suite.
+    TestsNotGreen,
+    /// PIT found no tests to run, or no supported test library.
+    NoTestsFound,
+    /// Nothing in the target classes could be mutated.
+    NoMutations,
+    /// A class PIT needed was missing from the classpath.
+    ClasspathProblem,
+    /// Classes were compiled for a newer Java than the one running PIT.
+    JavaVersionMismatch,
+    /// A test or mutant ran out of memory or timed out.
+    ResourceLimit,
+    /// None of the above.
+    Unknown,
+}
+
+impl PitFailure {
+    /// Recognises the failure from PIT's combined stdout and stderr. Earlier
+    /// checks win, since one root cause often triggers later symptoms.
+    pub fn classify(output: &str) -> Self {
+        let has = |needles: &[&str]| needles.iter().any(|needle| output.contains(needle));
+
+        if has(&[
+            "did not pass without mutation",
+            "Mutation testing requires a green suite",
+        ]) {
+            Self::TestsNotGreen
+        } else if has(&[
+            "UnsupportedClassVersionError",
+            "has been compiled by a more recent version",
+        ]) {
+            Self::JavaVersionMismatch
+        } else if has(&[
+            "No test library found",
+            "No tests found",
+            "no tests were found",
+        ]) {
+            Self::NoTestsFound
+        } else if has(&["No mutations found"]) {
+            Self::NoMutations
+        } else if has(&[
+            "ClassNotFoundException",
+            "NoClassDefFoundError",
+            "Could not find or load main class",
+        ]) {
+            Self::ClasspathProblem
+        } else if has(&["OutOfMemoryError", "MEMORY_ERROR", "TIMED_OUT"]) {
+            Self::ResourceLimit
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// One-line explanation shown as the requirement's reason.
+    pub fn reason(self) -> &'static str {
+        match self {
+            Self::TestsNotGreen => {
+                "Mutation tests could not run because some tests fail on the unmodified code; make \
+                 every test pass first."
+            }
+            Self::NoTestsFound => {
+                "Mutation tests could not run because PIT found no tests; check that the test \
+                 class has `@Test` methods and compiles."
+            }
+            Self::NoMutations => {
+                "Mutation tests could not run because PIT found nothing to mutate in the target \
+                 classes."
+            }
+            Self::ClasspathProblem => {
+                "Mutation tests could not run because a class was missing from the classpath; \
+                 check package names and that every file compiles."
+            }
+            Self::JavaVersionMismatch => {
+                "Mutation tests could not run because classes were compiled for a newer Java \
+                 version than the one running PIT."
+            }
+            Self::ResourceLimit => {
+                "Mutation tests could not finish because a test ran out of memory or time; look \
+                 for infinite loops or unbounded recursion."
+            }
+            Self::Unknown => "Something went wrong while running mutation tests, skipping.",
+        }
+    }
+}
+
 /// Aggregated result of running a single test file.
 struct TestRunOutcome {
     /// Number of tests that passed inside the file.
@@ -49,6 +150,84 @@ pub struct MutationInputs {
     excluded_methods: Vec<String>,
     /// Classes whose calls should be avoided during mutation.
     avoid_calls_to:   Vec<String>,
+    /// Additional PIT arguments appended to the generated command line.
+    extra_args:       Vec<String>,
+}
+
+/// PIT flags that umm sets itself and custom arguments may not override.
+const RESERVED_PIT_FLAGS: &[&str] = &[
+    "--class-path",
+    "--reportDir",
+    "--targetClasses",
+    "--targetTests",
+    "--sourceDirs",
+    "--timestampedReports",
+    "--outputFormats",
+    "--excludedMethods",
+    "--avoidCallsTo",
+];
+
+/// Packages and classes whose calls PIT leaves unmutated by default: logging
+/// frameworks and console streams, where mutants only change diagnostics.
+pub const DEFAULT_AVOID_CALLS_TO: &[&str] = &[
+    "java.util.logging",
+    "org.apache.log4j",
+    "org.apache.logging.log4j",
+    "org.slf4j",
+    "org.apache.commons.logging",
+    "java.io.PrintStream",
+];
+
+/// Merges `defaults` with `extra` for `--avoidCallsTo`, keeping first-seen
+/// order and dropping blanks and duplicates.
+pub fn merge_avoid_calls_to(defaults: &[String], extra: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    defaults
+        .iter()
+        .chain(extra)
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty() && seen.insert(*entry))
+        .map(str::to_string)
+        .collect()
+}
+
+/// PIT flags with umm defaults that custom arguments replace when present.
+const OVERRIDABLE_PIT_FLAGS: &[&str] = &["--mutators", "--threads", "--failWhenNoMutations"];
+
+/// Returns the flag name of a PIT argument, dropping any `=value` suffix.
+fn pit_flag_name(arg: &str) -> &str {
+    arg.split_once('=').map_or(arg, |(flag, _)| flag)
+}
+
+/// Checks custom PIT arguments for obvious mistakes before they reach PIT.
+///
+/// Arguments must start with a `--flag`, every flag must be spelled with two
+/// dashes, flags may appear at most once, and flags umm manages itself (report
+/// location, targets, source dirs, output formats) are rejected.
+pub fn validate_pit_args(args: &[String]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for (idx, arg) in args.iter().enumerate() {
+        if arg.trim().is_empty() {
+            bail!("PIT argument {idx} is empty");
+        }
+        if let Some(flag) = arg.strip_prefix("--") {
+            let name = pit_flag_name(arg);
+            if flag.is_empty() || name == "--" {
+                bail!("PIT argument `{arg}` is missing a flag name");
+            }
+            if RESERVED_PIT_FLAGS.contains(&name) {
+                bail!("PIT flag `{name}` is managed by umm and cannot be set via custom arguments");
+            }
+            if !seen.insert(name.to_string()) {
+                bail!("PIT flag `{name}` is given more than once");
+            }
+        } else if arg.starts_with('-') && arg.len() > 1 && !arg[1..].starts_with(char::is_numeric) {
+            bail!("PIT flags take two dashes; did you mean `-{arg}`?");
+        } else if idx == 0 {
+            bail!("PIT argument `{arg}` is a value without a preceding `--flag`");
+        }
+    }
+    Ok(())
 }
 
 impl MutationInputs {
@@ -64,9 +243,16 @@ impl MutationInputs {
             target_classes:   target_classes.into(),
             excluded_methods: excluded_methods.into(),
             avoid_calls_to:   avoid_calls_to.into(),
+            extra_args:       Vec::new(),
         }
     }
 
+    /// Appends custom PIT arguments to the generated command line.
+    pub fn with_extra_args(mut self, extra_args: impl Into<Vec<String>>) -> Self {
+        self.extra_args = extra_args.into();
+        self
+    }
+
     /// Borrow target tests.
     pub fn target_tests(&self) -> &[String] {
         &self.target_tests
@@ -86,7 +272,181 @@ impl MutationInputs {
     pub fn avoid_calls_to(&self) -> &[String] {
         &self.avoid_calls_to
     }
+
+    /// Borrow custom PIT arguments.
+    pub fn extra_args(&self) -> &[String] {
+        &self.extra_args
+    }
+}
+/// Extra classpath entries resolved for a single test-grader run.
+struct ClasspathOverlay {
+    /// Local jar paths appended to the project's classpath.
+    entries:      Vec<PathBuf>,
+    /// Directory holding jars downloaded for this run, removed afterwards.
+    download_dir: Option<PathBuf>,
+}
+
+impl ClasspathOverlay {
+    /// Largest jar accepted from an overlay URL.
+    const MAX_JAR_BYTES: usize = 64 * 1024 * 1024;
+
+    /// Resolves overlay entries into local jars, downloading any `http(s)` URLs
+    /// into a scratch directory under `.umm/`. Relative paths are resolved
+    /// against the project root, not the working directory.
+    async fn resolve(paths: &ProjectPaths, overlay: &[String]) -> Result<Self> {
+        let mut resolved = Self {
+            entries:      Vec::new(),
+            download_dir: None,
+        };
+        if overlay.is_empty() {
+            return Ok(resolved);
+        }
+
+        for (index, entry) in overlay.iter().enumerate() {
+            if !(entry.starts_with("http://") || entry.starts_with("https://")) {
+                let path = paths.root_dir().join(entry);
+                if !path.exists() {
+                    resolved.cleanup().await;
+                    bail!("Classpath overlay entry does not exist: {}", path.display());
+                }
+                resolved.entries.push(path);
+                continue;
+            }
+
+            let dir = match &resolved.download_dir {
+                Some(dir) => dir.clone(),
+                None => {
+                    let dir = paths
+                        .umm_dir()
+                        .join("classpath-overlay")
+                        .join(uuid::Uuid::new_v4().to_string());
+                    async_fs::create_dir_all(&dir).await.with_context(|| {
+                        format!("Failed to create overlay directory {}", dir.display())
+                    })?;
+                    resolved.download_dir = Some(dir.clone());
+                    dir
+                }
+            };
+
+            let bytes = match download_limited(
+                entry,
+                Self::MAX_JAR_BYTES,
+                DEFAULT_DOWNLOAD_TIMEOUT,
+                "Classpath overlay",
+            )
+            .await
+            {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    resolved.cleanup().await;
+                    return Err(err);
+                }
+            };
+            let path = dir.join(Self::jar_file_name(entry, index));
+            if let Err(err) = async_fs::write(&path, &bytes).await {
+                resolved.cleanup().await;
+                return Err(err).context(format!("Failed to write overlay jar {}", path.display()));
+            }
+            resolved.entries.push(path);
+        }
+
+        Ok(resolved)
+    }
+
+    /// Picks a file name for a downloaded jar, falling back to a positional
+    /// name when the URL does not end in one.
+    fn jar_file_name(url: &str, index: usize) -> String {
+        let last = url
+            .split(['?', '#'])
+            .next()
+            .and_then(|path| path.rsplit('/').next())
+            .unwrap_or_default();
+        if last.ends_with(".jar") {
+            last.to_string()
+        } else {
+            format!("overlay-{index}.jar")
+        }
+    }
+
+    /// Returns `project` with the overlay appended to its classpath.
+    fn apply(&self, project: Project) -> Project {
+        if self.entries.is_empty() {
+            return project;
+        }
+        let paths = project
+            .paths()
+            .clone()
+            .with_extra_classpath(self.entries.iter().cloned());
+        project.with_paths(paths)
+    }
+
+    /// Removes any jars downloaded for this run.
+    async fn cleanup(&self) {
+        if let Some(dir) = &self.download_dir {
+            let _ = async_fs::remove_dir_all(dir).await;
+        }
+    }
 }
+
+/// Default time allowed for a single download, including reading the body.
+pub(super) const DEFAULT_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Downloads `url`, refusing bodies larger than `limit` bytes or taking longer
+/// than `timeout`. `label` names the download in error messages.
+///
+/// The body is streamed so an oversized response without a `Content-Length`
+/// is cut off once it crosses `limit` instead of being buffered in full.
+pub(super) async fn download_limited(
+    url: &str,
+    limit: usize,
+    timeout: Duration,
+    label: &str,
+) -> Result<Vec<u8>> {
+    let download = async {
+        let mut response = config::http_client()
+            .get(url)
+            .send()
+            .await
+            .context(format!("Failed to download {url}"))?
+            .error_for_status()
+            .context(format!("{label} download returned error status: {url}"))?;
+
+        if let Some(len) = response.content_length()
+            && len as usize > limit
+        {
+            bail!("{label} download exceeds allowed size ({len} bytes > {limit} bytes)");
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .context(format!("Failed to read response body: {url}"))?
+        {
+            if body.len() + chunk.len() > limit {
+                bail!("{label} download exceeds allowed size (more than {limit} bytes)");
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    };
+
+    match tokio::time::timeout(timeout, download).await {
+        Ok(result) => result,
+        Err(_) => bail!("{label} download timed out after {}s: {url}", timeout.as_secs_f64()),
+    }
+}
+
+/// Checks on the test methods themselves, made before any test runs.
+struct TestExpectations<'a> {
+    /// Test names that must be present.
+    expected_tests:    &'a [String],
+    /// Whether tests beyond `expected_tests` are allowed.
+    allow_extra_tests: bool,
+    /// Minimum number of distinct test methods, if any.
+    min_test_methods:  Option<usize>,
+}
+
 #[derive(Clone, Default, Builder)]
 #[builder(on(String, into))]
 /// Grades by running tests, and reports how many tests pass.
@@ -98,7 +458,7 @@ pub struct ByUnitTestGrader {
         iter.into_iter().map(Into::into).collect::<Vec<String>>()
     })]
     #[builder(getter)]
-    test_files:     Vec<String>,
+    test_files:        Vec<String>,
     /// A list of test names that should be found. Grade returned is 0 if any
     /// are not found.
     #[builder(default)]
@@ -106,16 +466,38 @@ pub struct ByUnitTestGrader {
         iter.into_iter().map(Into::into).collect::<Vec<String>>()
     })]
     #[builder(getter)]
-    expected_tests: Vec<String>,
+    expected_tests:    Vec<String>,
+    /// When set, tests beyond `expected_tests` are allowed instead of being
+    /// reported as unexpected; the requirement becomes "at least these tests
+    /// exist".
+    #[builder(default)]
+    #[builder(getter)]
+    allow_extra_tests: bool,
+    /// Minimum number of distinct test methods the test files must declare,
+    /// whether or not they pass. Grade returned is 0 if there are fewer.
+    #[builder(getter)]
+    min_test_methods:  Option<usize>,
     /// A reference to the project the test files belong to.
     #[builder(getter)]
-    project:        Project,
+    project:           Project,
     /// Maximum possible grade.
     #[builder(getter)]
-    out_of:         f64,
+    out_of:            f64,
     /// Display name for requirement to use while displaying grade result
     #[builder(getter)]
-    req_name:       String,
+    req_name:          String,
+    /// Extra jars (local paths or URLs) added to the classpath for this run
+    /// only. URLs are downloaded before the tests run and removed afterwards.
+    #[builder(default)]
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    classpath_overlay: Vec<String>,
+    /// Overrides the global active-retrieval toggle when building feedback
+    /// context for failing tests. `None` uses the global setting.
+    #[builder(getter)]
+    active_retrieval:  Option<bool>,
 }
 
 impl ByUnitTestGrader {
@@ -126,23 +508,69 @@ impl ByUnitTestGrader {
         let ByUnitTestGrader {
             test_files,
             expected_tests,
+            allow_extra_tests,
+            min_test_methods,
             project,
             out_of,
             req_name,
+            classpath_overlay,
+            active_retrieval,
         } = self;
 
+        let overlay = ClasspathOverlay::resolve(project.paths(), &classpath_overlay)
+            .await
+            .context("While preparing the classpath overlay")?;
+        let project = overlay.apply(project);
+
+        let expectations = TestExpectations {
+            expected_tests: &expected_tests,
+            allow_extra_tests,
+            min_test_methods,
+        };
+        let outcome = Self::grade_project(
+            project,
+            &test_files,
+            &expectations,
+            out_of,
+            req_name,
+            active_retrieval,
+        )
+        .await;
+        overlay.cleanup().await;
+        outcome
+    }
+
+    /// Runs the requested test files against `project` and scores the result.
+    async fn grade_project(
+        project: Project,
+        test_files: &[String],
+        expectations: &TestExpectations<'_>,
+        out_of: f64,
+        req_name: String,
+        active_retrieval: Option<bool>,
+    ) -> Result<GradeResult> {
         let prompts = config::java_prompts();
-        let files = Self::resolve_test_files(&project, &test_files)
+        let files = Self::resolve_test_files(&project, test_files)
             .context("While resolving test files for execution")?;
 
-        let mut reasons = Self::expected_mismatches(&files, &expected_tests);
+        let mut mismatches = Self::expected_mismatches(
+            &files,
+            expectations.expected_tests,
+            expectations.allow_extra_tests,
+        );
+        if let Some(min) = expectations.min_test_methods {
+            mismatches.extend(Self::too_few_tests(&files, min));
+        }
         let system_prompt = prompts.system_message().to_string();
         let system_message = Self::build_system_message(system_prompt.clone())
             .context("Failed to build initial system message")?;
 
-        if !reasons.is_empty() {
-            reasons.push("Tests will not be run until above is fixed.".into());
-            let reasons_body = reasons.join("\n");
+        if !mismatches.is_empty() {
+            let reason = mismatches.into_iter().fold(
+                Reason::new().summary("Tests will not be run until the following is fixed:"),
+                Reason::point,
+            );
+            let reasons_body = reason.render(ReasonTarget::Terminal);
             let user_message = Self::build_user_message(reasons_body.clone())
                 .context("Failed to build expected-test failure message")?;
 
@@ -150,6 +578,7 @@ impl ByUnitTestGrader {
                 .requirement(req_name)
                 .grade(Grade::new(0.0, out_of))
                 .reason(reasons_body)
+                .details(reason)
                 .maybe_prompt(Some(vec![system_message, user_message]))
                 .build());
         }
@@ -159,7 +588,7 @@ impl ByUnitTestGrader {
         let mut messages = vec![system_message];
 
         for file in &files {
-            let outcome = Self::run_tests_for_file(&project, file)
+            let outcome = Self::run_tests_for_file(&project, file, active_retrieval)
                 .await
                 .with_context(|| format!("While executing tests in {}", file.proper_name()))?;
             total_passed += outcome.tests_passed;
@@ -194,8 +623,13 @@ impl ByUnitTestGrader {
     }
 
     /// Compares expected test names against discovered tests and reports
-    /// mismatches.
-    fn expected_mismatches(files: &[File], expected_tests: &[String]) -> Vec<String> {
+    /// mismatches. Unexpected tests are only reported when `allow_extra` is
+    /// false.
+    fn expected_mismatches(
+        files: &[File],
+        expected_tests: &[String],
+        allow_extra: bool,
+    ) -> Vec<String> {
         let mut reasons = Vec::new();
         if expected_tests.is_empty() {
             return reasons;
@@ -239,10 +673,14 @@ impl ByUnitTestGrader {
             };
 
             if missing {
-                reasons.push(format!("- {method_name} not found."));
+                reasons.push(format!("{method_name} not found."));
             }
         }
 
+        if allow_extra {
+            return reasons;
+        }
+
         for actual in &actual_tests {
             let method_name = actual
                 .split_once('#')
@@ -251,13 +689,21 @@ impl ByUnitTestGrader {
             let expected_match =
                 expected_full.contains(actual.as_str()) || expected_methods.contains(method_name);
             if !expected_match {
-                reasons.push(format!("- Unexpected test called {method_name}"));
+                reasons.push(format!("Unexpected test called {method_name}"));
             }
         }
 
         reasons
     }
 
+    /// Reports when `files` declare fewer than `min` distinct test methods.
+    fn too_few_tests(files: &[File], min: usize) -> Option<String> {
+        let found: HashSet<String> = files.iter().flat_map(File::test_methods).collect();
+        (found.len() < min).then(|| {
+            format!("Found {} test method(s), but at least {min} are required.", found.len())
+        })
+    }
+
     /// Builds a user message suitable for inclusion in prompts, truncating if
     /// required.
     fn build_user_message(mut content: String) -> Result<ChatCompletionRequestMessage> {
@@ -329,15 +775,75 @@ impl ByUnitTestGrader {
         (updated, diags)
     }
 
+    /// Collects `(file name, method name)` pairs for stack frames that point
+    /// into the student's (non-test) project files.
+    fn failing_frame_methods(project: &Project, stacktrace: &str) -> Vec<(String, String)> {
+        let mut frames = Vec::new();
+        for line in stacktrace.lines() {
+            if line.contains("Test run finished after") {
+                break;
+            }
+
+            let Ok(diag) = parser::junit_stacktrace_line_ref(line) else {
+                continue;
+            };
+            let Some(method) = Self::stack_frame_method(line) else {
+                continue;
+            };
+            let is_student_code = project
+                .identify(diag.file_name())
+                .is_ok_and(|file| *file.kind() != FileType::Test);
+            let frame = (diag.file_name, method);
+            if is_student_code && !frames.contains(&frame) {
+                frames.push(frame);
+            }
+        }
+        frames
+    }
+
+    /// Extracts the method name from a frame like
+    /// `pkg.Main.sumTo(Main.java:34)`. Constructors and lambdas are skipped
+    /// since they have no named body to retrieve.
+    fn stack_frame_method(line: &str) -> Option<String> {
+        let (call, _) = line.trim().split_once('(')?;
+        let call = call.trim_start_matches("at ").trim();
+        let (_, method) = call.rsplit_once('.')?;
+        let is_named = !method.is_empty()
+            && !method.starts_with("lambda$")
+            && method
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+        is_named.then(|| method.to_string())
+    }
+
     /// Replaces escaped characters emitted by JUnit with literal values.
     fn unescape_stacktrace_line(line: &str) -> String {
         line.replace("\\\\", "\\").replace("\\\"", "\"")
     }
 
     /// Parses the JUnit summary table to collect total and passing test counts.
-    fn parse_summary_counts(summary: &str) -> (f64, f64) {
+    ///
+    /// JUnit counts every dynamic test registered by a `@TestFactory` under
+    /// "tests found", so those are already included in the total. A factory
+    /// that throws before producing any tests only shows up as a failed
+    /// container, listed under its own name among the failures; each of
+    /// `factories` (`Class#method` strings) listed there is counted as one
+    /// failing test so the failure is not silently dropped from the total.
+    /// Other failed containers, such as a class whose `@BeforeAll` throws,
+    /// are left out since their tests are already found. Likewise every
+    /// `@RepeatedTest` repetition is its own test, and callers raise the total
+    /// to [`File::expected_test_runs`] so repetitions that never ran still
+    /// count.
+    fn parse_summary_counts(summary: &str, factories: &[String]) -> (f64, f64) {
+        let factory_headers: Vec<String> = factories
+            .iter()
+            .filter_map(|factory| factory.rsplit_once('#'))
+            .map(|(_, method)| format!(":{method}()"))
+            .collect();
+
         let mut passed = 0.0;
         let mut total = 0.0;
+        let mut failed_factories = 0.0;
         for line in summary.lines() {
             if let Ok(value) = parser::num_tests_passed(line) {
                 passed = value as f64;
@@ -345,16 +851,30 @@ impl ByUnitTestGrader {
             if let Ok(value) = parser::num_tests_found(line) {
                 total = value as f64;
             }
+            let line = line.trim();
+            if line.starts_with("JUnit Jupiter:")
+                && factory_headers
+                    .iter()
+                    .any(|header| line.ends_with(header.as_str()))
+            {
+                failed_factories += 1.0;
+            }
         }
-        (passed, total)
+        (passed, total + failed_factories)
     }
 
     /// Runs the given test file and returns aggregated output and prompt
     /// messages.
-    async fn run_tests_for_file(project: &Project, file: &File) -> Result<TestRunOutcome> {
+    async fn run_tests_for_file(
+        project: &Project,
+        file: &File,
+        active_retrieval: Option<bool>,
+    ) -> Result<TestRunOutcome> {
         match file.test(Vec::new(), Some(project)).await {
             Ok(output) => {
-                let (tests_passed, tests_total) = Self::parse_summary_counts(&output);
+                let (tests_passed, tests_total) =
+                    Self::parse_summary_counts(&output, &file.test_factories());
+                let tests_total = tests_total.max(file.expected_test_runs() as f64);
                 Ok(TestRunOutcome {
                     tests_passed,
                     tests_total,
@@ -375,11 +895,18 @@ impl ByUnitTestGrader {
                     ))
                     .context("Failed to build failed-tests message")?,
                 );
+                let frames = Self::failing_frame_methods(project, &test_results);
+                if let Some(message) = build_failing_methods_context(project, &frames)
+                    .context("Failed to build context for methods in failing stack frames")?
+                {
+                    messages.push(message);
+                }
                 messages.push(
-                    build_context_message(
+                    build_context_message_with(
                         project,
                         Some(grader_output.clone()),
                         Self::filter_known_diags(project, diags),
+                        active_retrieval,
                     )
                     .with_context(|| {
                         format!(
@@ -388,13 +915,34 @@ impl ByUnitTestGrader {
                         )
                     })?,
                 );
-                let (tests_passed, tests_total) = Self::parse_summary_counts(&test_results);
+                let (tests_passed, tests_total) =
+                    Self::parse_summary_counts(&test_results, &file.test_factories());
+                let tests_total = tests_total.max(file.expected_test_runs() as f64);
                 Ok(TestRunOutcome {
                     tests_passed,
                     tests_total,
                     messages,
                 })
             }
+            Err(err @ JavaFileError::NoMainMethod { .. }) => Err(err.into()),
+            Err(JavaFileError::TimedOut {
+                partial_output,
+                timeout,
+            }) => {
+                let body = format!(
+                    "Tests did not finish within {}s. Output before they were stopped \
+                     -\n```\n{}\n```",
+                    timeout.as_secs_f64(),
+                    partial_output
+                );
+                let message =
+                    Self::build_user_message(body).context("Failed to build timeout message")?;
+                Ok(TestRunOutcome {
+                    tests_passed: 0.0,
+                    tests_total:  0.0,
+                    messages:     vec![message],
+                })
+            }
             Err(JavaFileError::Unknown(err)) => {
                 let body = format!("Unknown error -\n```\n{:#?}\n```", err);
                 let message = Self::build_user_message(body)
@@ -413,13 +961,18 @@ impl ByUnitTestGrader {
                         .context("Failed to build compiler-error message")?,
                 );
                 messages.push(
-                    build_context_message(project, None, Self::filter_known_diags(project, diags))
-                        .with_context(|| {
-                            format!(
-                                "Failed to build retrieval context for compiler errors in {}",
-                                file.proper_name()
-                            )
-                        })?,
+                    build_context_message_with(
+                        project,
+                        Some(stacktrace),
+                        Self::filter_known_diags(project, diags),
+                        active_retrieval,
+                    )
+                    .with_context(|| {
+                        format!(
+                            "Failed to build retrieval context for compiler errors in {}",
+                            file.proper_name()
+                        )
+                    })?,
                 );
                 Ok(TestRunOutcome {
                     tests_passed: 0.0,
@@ -435,13 +988,18 @@ impl ByUnitTestGrader {
                         .context("Failed to build runtime-error message")?,
                 );
                 messages.push(
-                    build_context_message(project, None, Self::filter_known_diags(project, diags))
-                        .with_context(|| {
-                            format!(
-                                "Failed to build retrieval context for runtime errors in {}",
-                                file.proper_name()
-                            )
-                        })?,
+                    build_context_message_with(
+                        project,
+                        Some(output),
+                        Self::filter_known_diags(project, diags),
+                        active_retrieval,
+                    )
+                    .with_context(|| {
+                        format!(
+                            "Failed to build retrieval context for runtime errors in {}",
+                            file.proper_name()
+                        )
+                    })?,
                 );
                 Ok(TestRunOutcome {
                     tests_passed: 0.0,
@@ -456,10 +1014,13 @@ impl ByUnitTestGrader {
 impl ByUnitTestGrader {
     /// Builds and runs the unit-test grader.
     pub async fn run(self) -> Result<GradeResult> {
-        if self.test_files.is_empty() {
-            bail!("ByUnitTestGrader requires at least one test file");
-        }
-        self.grade_by_tests().await
+        timed(async move {
+            if self.test_files.is_empty() {
+                bail!("ByUnitTestGrader requires at least one test file");
+            }
+            self.grade_by_tests().await
+        })
+        .await
     }
 }
 
@@ -479,45 +1040,76 @@ where
 pub struct UnitTestGrader {
     /// Project to mutate.
     #[builder(getter)]
-    pub project:          Project,
+    pub project:                Project,
     /// Name of the requirement.
     #[builder(getter)]
-    pub req_name:         String,
+    pub req_name:               String,
     /// Maximum possible grade.
     #[builder(getter)]
-    pub out_of:           f64,
+    pub out_of:                 f64,
     /// List of test classes to run.
     #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
         iter.into_iter().map(Into::into).collect::<Vec<String>>()
     })]
     #[builder(getter)]
-    pub target_test:      Vec<String>,
+    pub target_test:            Vec<String>,
     /// List of classes to mutate.
     #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
         iter.into_iter().map(Into::into).collect::<Vec<String>>()
     })]
     #[builder(getter)]
-    pub target_class:     Vec<String>,
+    pub target_class:           Vec<String>,
     /// List of methods to exclude from mutation.
     #[builder(default)]
     #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
         iter.into_iter().map(Into::into).collect::<Vec<String>>()
     })]
     #[builder(getter)]
-    pub excluded_methods: Vec<String>,
+    pub excluded_methods:       Vec<String>,
     /// List of classes to avoid mutating.
     #[builder(default)]
     #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
         iter.into_iter().map(Into::into).collect::<Vec<String>>()
     })]
     #[builder(getter)]
-    pub avoid_calls_to:   Vec<String>,
+    pub avoid_calls_to:         Vec<String>,
+    /// Calls avoided in every run, merged ahead of `avoid_calls_to`. Optional,
+    /// default is [`DEFAULT_AVOID_CALLS_TO`]; pass an empty list to disable.
+    #[builder(
+        default = DEFAULT_AVOID_CALLS_TO.iter().map(|s| s.to_string()).collect(),
+        with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+            iter.into_iter().map(Into::into).collect::<Vec<String>>()
+        }
+    )]
+    #[builder(getter)]
+    pub default_avoid_calls_to: Vec<String>,
+    /// Additional PIT arguments (e.g. `--mutableCodePaths`, `--mutators`)
+    /// appended to the generated command line.
+    #[builder(default)]
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub pit_args:               Vec<String>,
+    /// Overrides the global active-retrieval toggle when building feedback
+    /// context for surviving mutations. `None` uses the global setting.
+    #[builder(getter)]
+    pub active_retrieval:       Option<bool>,
 }
 
 impl UnitTestGrader {
-    /// Location where PIT writes the CSV summary for surviving mutations.
-    pub(crate) fn mutation_report_path(project: &Project) -> PathBuf {
-        project.paths().report_dir().join("mutations.csv")
+    /// Fresh directory for one PIT run: a `pit-<id>` directory under the
+    /// project's report directory, so concurrent runs never share a
+    /// `mutations.csv`. A relative report directory is taken relative to the
+    /// project root, where PIT runs, rather than the current directory.
+    pub fn mutation_run_dir(project: &Project) -> PathBuf {
+        let report_dir = project.paths().report_dir();
+        let report_dir = if report_dir.is_relative() {
+            project.paths().root_dir().join(report_dir)
+        } else {
+            report_dir.to_path_buf()
+        };
+        report_dir.join(format!("pit-{}", Uuid::new_v4()))
     }
 
     /// Runs mutation tests using ![Pitest](http://pitest.org/) to grade unit tests written by students.
@@ -531,8 +1123,10 @@ impl UnitTestGrader {
             .context("Failed to interpret mutation grader configuration")?;
         let project = self.project.clone();
 
+        let run_dir = Self::mutation_run_dir(&project);
+
         let result = async {
-            let args = Self::build_mutation_args(&project, &inputs)
+            let args = Self::build_mutation_args(&project, &inputs, &run_dir)
                 .context("Failed to assemble mutation testing arguments")?;
 
             let collected = Self::run_mutation_command(&project, &args)
@@ -540,7 +1134,7 @@ impl UnitTestGrader {
                 .context("Failed to execute PIT mutation coverage report")?;
 
             let prompts = config::java_prompts();
-            let report_path = Self::mutation_report_path(&project);
+            let report_path = run_dir.join(MUTATION_REPORT_CSV);
 
             if collected.status.success() {
                 if !report_path.exists() {
@@ -550,6 +1144,7 @@ impl UnitTestGrader {
                         report_path.display()
                     );
                     return Self::handle_failure(
+                        &run_dir,
                         &prompts,
                         collected,
                         inputs,
@@ -558,14 +1153,36 @@ impl UnitTestGrader {
                     );
                 }
 
-                Self::handle_success(&project, &prompts, inputs.clone(), req_name.clone(), out_of)
-                    .await
-                    .or_else(|err| {
-                        eprintln!("Mutation report handling failed: {err}");
-                        Self::handle_failure(&prompts, collected, inputs, req_name.clone(), out_of)
-                    })
+                Self::handle_success(
+                    &project,
+                    &report_path,
+                    &prompts,
+                    inputs.clone(),
+                    req_name.clone(),
+                    out_of,
+                    self.active_retrieval,
+                )
+                .await
+                .or_else(|err| {
+                    eprintln!("Mutation report handling failed: {err}");
+                    Self::handle_failure(
+                        &run_dir,
+                        &prompts,
+                        collected,
+                        inputs,
+                        req_name.clone(),
+                        out_of,
+                    )
+                })
             } else {
-                Self::handle_failure(&prompts, collected, inputs, req_name.clone(), out_of)
+                Self::handle_failure(
+                    &run_dir,
+                    &prompts,
+                    collected,
+                    inputs,
+                    req_name.clone(),
+                    out_of,
+                )
             }
         }
         .await;
@@ -580,20 +1197,47 @@ impl UnitTestGrader {
         }
     }
 
-    /// Normalizes configured mutation grader inputs into owned collections.
+    /// `target_test` plus every other top-level class declaring tests in the
+    /// same files, so PIT also runs tests kept in secondary classes.
+    fn expanded_target_tests(&self) -> Vec<String> {
+        let mut tests = self.target_test.clone();
+        for name in &self.target_test {
+            let Ok(file) = self.project.identify(name) else {
+                continue;
+            };
+            for test in file.test_methods() {
+                let Some((owner, _)) = test.split_once('#') else {
+                    continue;
+                };
+                if !tests.iter().any(|t| t == owner) {
+                    tests.push(owner.to_string());
+                }
+            }
+        }
+        tests
+    }
+
+    /// Normalizes configured mutation grader inputs into owned collections,
+    /// merging the default and user-supplied `--avoidCallsTo` entries.
     fn normalize_inputs(&self) -> Result<MutationInputs> {
         Ok(MutationInputs {
-            target_tests:     self.target_test.clone(),
+            target_tests:     self.expanded_target_tests(),
             target_classes:   self.target_class.clone(),
             excluded_methods: self.excluded_methods.clone(),
-            avoid_calls_to:   self.avoid_calls_to.clone(),
+            avoid_calls_to:   merge_avoid_calls_to(
+                &self.default_avoid_calls_to,
+                &self.avoid_calls_to,
+            ),
+            extra_args:       self.pit_args.clone(),
         })
     }
 
-    /// Builds the argument list used to invoke PIT mutation testing.
+    /// Builds the argument list used to invoke PIT mutation testing, with
+    /// reports written to `report_dir` (see [`Self::mutation_run_dir`]).
     pub fn build_mutation_args(
         project: &Project,
         inputs: &MutationInputs,
+        report_dir: &Path,
     ) -> Result<Vec<OsString>> {
         let class_path = classpath(project.paths())
             .context("Failed to construct classpath for mutation grader")?;
@@ -603,13 +1247,10 @@ impl UnitTestGrader {
         ]
         .join(",");
 
-        // Ensure report directory is absolute and exists so PIT always writes
-        // under the project root (avoids sandbox cwd surprises).
-        let report_dir = project.paths().report_dir();
         std::fs::create_dir_all(report_dir)
             .context(format!("Failed to create {}", report_dir.display()))?;
 
-        Ok(vec![
+        let mut args: Vec<OsString> = vec![
             "--class-path".into(),
             class_path.into(),
             "org.pitest.mutationtest.commandline.MutationCoverageReport".into(),
@@ -639,7 +1280,32 @@ impl UnitTestGrader {
             inputs.excluded_methods.join(",").into(),
             "--avoidCallsTo".into(),
             inputs.avoid_calls_to.join(",").into(),
-        ])
+        ];
+
+        validate_pit_args(&inputs.extra_args).context("Invalid custom PIT arguments")?;
+        let overridden: HashSet<&str> = inputs
+            .extra_args
+            .iter()
+            .map(|arg| pit_flag_name(arg))
+            .filter(|flag| OVERRIDABLE_PIT_FLAGS.contains(flag))
+            .collect();
+        if !overridden.is_empty() {
+            // Defaults are emitted as `--flag value` pairs; drop any pair the
+            // custom arguments replace.
+            let mut kept = Vec::with_capacity(args.len());
+            let mut iter = args.into_iter();
+            while let Some(arg) = iter.next() {
+                if arg.to_str().is_some_and(|a| overridden.contains(a)) {
+                    let _ = iter.next();
+                } else {
+                    kept.push(arg);
+                }
+            }
+            args = kept;
+        }
+        args.extend(inputs.extra_args.iter().map(OsString::from));
+
+        Ok(args)
     }
 
     /// Executes PIT mutation testing and returns the collected process output.
@@ -664,12 +1330,14 @@ impl UnitTestGrader {
     /// prompts.
     async fn handle_success(
         project: &Project,
+        report_path: &Path,
         prompts: &crate::java::JavaPrompts,
         inputs: MutationInputs,
         req_name: String,
         out_of: f64,
+        active_retrieval: Option<bool>,
     ) -> Result<GradeResult> {
-        let surviving = Self::load_surviving_mutations(project)
+        let surviving = Self::load_surviving_mutations(report_path)
             .await
             .context("While loading mutation report")?;
         let penalty = surviving.len() as f64 * 4.0;
@@ -677,8 +1345,14 @@ impl UnitTestGrader {
         eprintln!("Ran mutation tests for {} -", inputs.target_tests.join(", "));
         eprintln!("Problematic mutation test failures printed above.");
 
-        let prompt = Self::build_mutation_success_prompt(project, prompts, &inputs, &surviving)
-            .context("Failed to build mutation failure prompt")?;
+        let prompt = Self::build_mutation_success_prompt(
+            project,
+            prompts,
+            &inputs,
+            &surviving,
+            active_retrieval,
+        )
+        .context("Failed to build mutation failure prompt")?;
 
         let grade_value = (out_of - penalty).max(0.0);
 
@@ -690,8 +1364,11 @@ impl UnitTestGrader {
             .build())
     }
 
-    /// Processes a failed PIT run by capturing stderr/stdout into prompts.
+    /// Processes a failed PIT run by classifying the failure, saving the raw
+    /// output to [`PIT_OUTPUT_LOG`] in the run's report directory, and
+    /// capturing stderr/stdout into prompts.
     fn handle_failure(
+        run_dir: &Path,
         prompts: &crate::java::JavaPrompts,
         collected: process::Collected,
         inputs: MutationInputs,
@@ -703,44 +1380,44 @@ impl UnitTestGrader {
         // Decode output lossily to avoid bubbling up IO/UTF-8 errors from PIT.
         let mut output = String::from_utf8_lossy(&stderr).to_string();
         output.push_str(&String::from_utf8_lossy(&stdout));
+        let failure = PitFailure::classify(&output);
 
         // Keep the console output deterministic while still surfacing a clue
         // that mutation testing failed. Full stderr/stdout is preserved inside
         // the prompt for downstream consumption.
         if !output.is_empty() {
-            eprintln!("Mutation tests failed; details captured in prompt.");
+            let log_path = run_dir.join(PIT_OUTPUT_LOG);
+            match crate::util::write_atomic(&log_path, &output) {
+                Ok(()) => eprintln!(
+                    "Mutation tests failed ({failure:?}); full output saved to {}.",
+                    log_path.display()
+                ),
+                Err(e) => eprintln!(
+                    "Mutation tests failed ({failure:?}); could not save output to {}: {e}",
+                    log_path.display()
+                ),
+            }
         }
         if output.len() > config::PROMPT_TRUNCATE {
             output.truncate(config::PROMPT_TRUNCATE);
             output.push_str("...[TRUNCATED]");
         }
 
-        let prompt = Self::build_mutation_failure_prompt(prompts, &inputs, output)
+        let prompt = Self::build_mutation_failure_prompt(prompts, &inputs, failure, output)
             .context("Failed to build mutation failure prompt")?;
 
         Ok(GradeResult::builder()
             .requirement(req_name)
             .grade(Grade::new(0.0, out_of))
-            .reason("Something went wrong while running mutation tests, skipping.")
+            .reason(failure.reason())
             .maybe_prompt(prompt)
             .build())
     }
 
-    /// Loads the mutation CSV report and extracts surviving mutations.
-    async fn load_surviving_mutations(project: &Project) -> Result<Vec<MutationDiagnostic>> {
-        let csv_path = Self::mutation_report_path(project);
-
-        let reports_dir = csv_path
-            .parent()
-            .ok_or_else(|| anyhow!("Failed to derive parent for {}", csv_path.display()))?;
-
-        async_fs::create_dir_all(reports_dir)
-            .await
-            .with_context(|| {
-                format!("Failed to create reports directory {}", reports_dir.display())
-            })?;
-
-        let csv_bytes = async_fs::read(&csv_path)
+    /// Loads the mutation CSV report at `csv_path` and extracts surviving
+    /// mutations.
+    async fn load_surviving_mutations(csv_path: &Path) -> Result<Vec<MutationDiagnostic>> {
+        let csv_bytes = async_fs::read(csv_path)
             .await
             .with_context(|| format!("Could not read {}", csv_path.display()))?;
         let csv_contents =
@@ -783,17 +1460,23 @@ impl UnitTestGrader {
         prompts: &crate::java::JavaPrompts,
         inputs: &MutationInputs,
         surviving: &[MutationDiagnostic],
+        active_retrieval: Option<bool>,
     ) -> Result<Option<Vec<ChatCompletionRequestMessage>>> {
         if surviving.is_empty() {
             return Ok(None);
         }
 
-        let context = build_context_message(project, None, surviving.to_vec())
-            .context("Failed to build retrieval context for surviving mutations")?;
-
         let mut feedback = ExtendedTable::new(surviving.to_vec()).to_string();
         eprintln!("{feedback}");
 
+        let context = build_context_message_with(
+            project,
+            Some(feedback.clone()),
+            surviving.to_vec(),
+            active_retrieval,
+        )
+        .context("Failed to build retrieval context for surviving mutations")?;
+
         if feedback.len() > config::PROMPT_TRUNCATE {
             feedback.truncate(config::PROMPT_TRUNCATE);
             feedback.push_str("...[TRUNCATED]");
@@ -825,6 +1508,7 @@ impl UnitTestGrader {
     fn build_mutation_failure_prompt(
         prompts: &crate::java::JavaPrompts,
         inputs: &MutationInputs,
+        failure: PitFailure,
         output: String,
     ) -> Result<Option<Vec<ChatCompletionRequestMessage>>> {
         if output.is_empty() {
@@ -840,6 +1524,15 @@ impl UnitTestGrader {
             ByUnitTestGrader::build_user_message(output)
                 .context("Failed to build mutation stderr/stdout message")?,
         );
+        if failure != PitFailure::Unknown {
+            messages.push(
+                ByUnitTestGrader::build_system_message(format!(
+                    "Likely cause: {}",
+                    failure.reason()
+                ))
+                .context("Failed to build mutation failure cause message")?,
+            );
+        }
         messages.push(
             ByUnitTestGrader::build_system_message(format!(
                 include_str!("../prompts/mutation_testing_2.md"),
@@ -856,13 +1549,16 @@ impl UnitTestGrader {
 impl UnitTestGrader {
     /// Builds and runs the mutation grader.
     pub async fn run(self) -> Result<GradeResult> {
-        if self.target_test.is_empty() {
-            bail!("UnitTestGrader requires at least one target test class");
-        }
-        if self.target_class.is_empty() {
-            bail!("UnitTestGrader requires at least one target class");
-        }
-        self.grade_unit_tests().await
+        timed(async move {
+            if self.target_test.is_empty() {
+                bail!("UnitTestGrader requires at least one target test class");
+            }
+            if self.target_class.is_empty() {
+                bail!("UnitTestGrader requires at least one target class");
+            }
+            self.grade_unit_tests().await
+        })
+        .await
     }
 }
 
@@ -883,63 +1579,62 @@ where
 pub struct ByHiddenTestGrader {
     /// URL to download test source from.
     #[builder(getter)]
-    pub url:             String,
+    pub url:                   String,
     /// name of hidden test class.
     #[builder(getter)]
-    pub test_class_name: String,
+    pub test_class_name:       String,
     /// points to give if all tests pass.
     #[builder(getter)]
-    pub out_of:          f64,
+    pub out_of:                f64,
     /// name of requirement.
     #[builder(getter)]
-    pub req_name:        String,
+    pub req_name:              String,
+    /// the project the hidden tests run against.
+    #[builder(getter)]
+    pub project:               Project,
+    /// Extra jars (local paths or URLs) added to the classpath for this run
+    /// only.
+    #[builder(default)]
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    pub classpath_overlay:     Vec<String>,
+    /// Seconds allowed for downloading the test source (default 60).
+    #[builder(getter)]
+    pub download_timeout_secs: Option<f64>,
+    /// Largest test source accepted, in bytes (default 5 MiB).
+    #[builder(getter)]
+    pub max_download_bytes:    Option<usize>,
+    /// Overrides the global active-retrieval toggle when building feedback
+    /// context for failing tests. `None` uses the global setting.
+    #[builder(getter)]
+    pub active_retrieval:      Option<bool>,
 }
 
 impl ByHiddenTestGrader {
+    /// Largest hidden test source accepted when no limit is configured.
+    const MAX_HIDDEN_TEST_BYTES: usize = 5 * 1024 * 1024;
+
     /// Grades using hidden tests. Test file is downloaded, ran, and then
     /// cleaned up before returning.
     pub async fn grade_by_hidden_tests(&self) -> Result<GradeResult> {
-        const MAX_HIDDEN_TEST_BYTES: usize = 5 * 1024 * 1024;
-
         let url = self.url.clone();
         let test_class_name = self.test_class_name.clone();
         let out_of = self.out_of;
         let req_name = self.req_name.clone();
 
-        let client = config::http_client();
-        let response = client
-            .get(&url)
-            .timeout(Duration::from_secs(60))
-            .send()
-            .await
-            .context(format!("Failed to download {url}"))?
-            .error_for_status()
-            .context(format!("Hidden test download returned error status: {url}"))?;
-
-        if let Some(len) = response.content_length()
-            && len as usize > MAX_HIDDEN_TEST_BYTES
-        {
-            anyhow::bail!(
-                "Hidden test download exceeds allowed size ({} bytes > {} bytes)",
-                len,
-                MAX_HIDDEN_TEST_BYTES
-            );
-        }
-
-        let test_source = response
-            .bytes()
-            .await
-            .context(format!("Failed to get response as bytes: {url}"))?;
-
-        if test_source.len() > MAX_HIDDEN_TEST_BYTES {
-            anyhow::bail!(
-                "Hidden test download exceeds allowed size ({} bytes > {} bytes)",
-                test_source.len(),
-                MAX_HIDDEN_TEST_BYTES
-            );
-        }
+        let timeout = match self.download_timeout_secs {
+            Some(secs) => Duration::try_from_secs_f64(secs)
+                .map_err(|_| anyhow!("Invalid hidden test download timeout: {secs}s"))?,
+            None => DEFAULT_DOWNLOAD_TIMEOUT,
+        };
+        let limit = self
+            .max_download_bytes
+            .unwrap_or(Self::MAX_HIDDEN_TEST_BYTES);
+        let test_source = download_limited(&url, limit, timeout, "Hidden test").await?;
 
-        let root_paths = ProjectPaths::default();
+        let root_paths = self.project.paths().clone();
         let path = root_paths
             .root_dir()
             .join(format!("{test_class_name}.java"));
@@ -963,7 +1658,8 @@ impl ByHiddenTestGrader {
             return Err(err);
         }
 
-        let project = match Project::new() {
+        // Rediscover the project so the downloaded test is part of it.
+        let project = match Project::from_paths(root_paths) {
             Ok(a) => a,
             Err(e) => {
                 let _ = async_fs::remove_file(&path).await;
@@ -975,9 +1671,13 @@ impl ByHiddenTestGrader {
         let grader = ByUnitTestGrader {
             test_files: vec![test_class_name],
             expected_tests: Vec::new(),
+            allow_extra_tests: false,
+            min_test_methods: None,
             project,
             out_of,
             req_name,
+            classpath_overlay: self.classpath_overlay.clone(),
+            active_retrieval: self.active_retrieval,
         };
 
         let out = match grader.grade_by_tests().await {
@@ -1000,7 +1700,7 @@ impl ByHiddenTestGrader {
 impl ByHiddenTestGrader {
     /// Builds and runs the hidden-test grader.
     pub async fn run(self) -> Result<GradeResult> {
-        self.grade_by_hidden_tests().await
+        timed(self.grade_by_hidden_tests()).await
     }
 }
 
@@ -0,0 +1,135 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use bon::Builder;
+use reqwest::header::AUTHORIZATION;
+use serde::{Deserialize, Serialize};
+
+use super::results::{GradeResult, total_grade};
+use crate::config;
+
+/// A single requirement as reported to a results webhook.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WebhookResult {
+    /// Requirement name.
+    pub requirement:  String,
+    /// Points awarded.
+    pub grade:        f64,
+    /// Points possible.
+    pub out_of:       f64,
+    /// Reason for any penalty.
+    pub reason:       String,
+    /// Whether the requirement counts on top of the total.
+    pub extra_credit: bool,
+    /// Seconds the grader took, when it was timed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elapsed_secs: Option<f64>,
+}
+
+/// JSON body POSTed to a results webhook: the total followed by every
+/// requirement.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WebhookPayload {
+    /// Total points awarded.
+    pub grade:   f64,
+    /// Total points possible, excluding extra credit.
+    pub out_of:  f64,
+    /// Per-requirement results, in grading order.
+    pub results: Vec<WebhookResult>,
+}
+
+impl WebhookPayload {
+    /// Summarizes `results` for posting.
+    pub fn new(results: &[GradeResult]) -> Self {
+        let total = total_grade(results);
+        Self {
+            grade:   total.grade,
+            out_of:  total.out_of,
+            results: results
+                .iter()
+                .map(|result| WebhookResult {
+                    requirement:  result.requirement.clone(),
+                    grade:        result.grade_value(),
+                    out_of:       result.out_of_value(),
+                    reason:       result.reason().to_string(),
+                    extra_credit: result.is_extra_credit(),
+                    elapsed_secs: result.elapsed().map(|elapsed| elapsed.as_secs_f64()),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Posts grade results as JSON to an LMS or other HTTP endpoint.
+#[derive(Debug, Clone, Builder)]
+#[builder(on(String, into))]
+pub struct ResultsWebhook {
+    /// URL the results are POSTed to.
+    #[builder(getter)]
+    pub url:          String,
+    /// Value sent in the `Authorization` header, e.g. `Bearer <token>`.
+    #[builder(getter)]
+    pub auth_header:  Option<String>,
+    /// Attempts made before giving up. Optional, default is 3
+    #[builder(default = 3)]
+    #[builder(getter)]
+    pub attempts:     u32,
+    /// Seconds each attempt may take. Optional, default is 10
+    #[builder(default = 10.0)]
+    #[builder(getter)]
+    pub timeout_secs: f64,
+}
+
+impl ResultsWebhook {
+    /// Posts `results`, retrying failed attempts with a growing delay.
+    ///
+    /// Failures are logged to stderr rather than returned so a flaky endpoint
+    /// never fails the grading run; the return value reports whether the
+    /// results were delivered.
+    pub async fn send(&self, results: &[GradeResult]) -> bool {
+        let payload = WebhookPayload::new(results);
+        let attempts = self.attempts.max(1);
+        for attempt in 1..=attempts {
+            match self.post(&payload).await {
+                Ok(()) => return true,
+                Err(e) => {
+                    eprintln!(
+                        "Results webhook attempt {attempt}/{attempts} to {} failed: {e:#}",
+                        self.url
+                    );
+                    if attempt < attempts {
+                        tokio::time::sleep(Duration::from_millis(250 * u64::from(attempt))).await;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Makes a single POST of `payload`, failing on transport errors and
+    /// non-success statuses.
+    pub async fn post(&self, payload: &WebhookPayload) -> Result<()> {
+        if !self.timeout_secs.is_finite() || self.timeout_secs <= 0.0 {
+            bail!("Webhook timeout must be a positive number of seconds");
+        }
+
+        let mut request = config::http_client()
+            .post(&self.url)
+            .timeout(Duration::from_secs_f64(self.timeout_secs))
+            .json(payload);
+        if let Some(auth) = &self.auth_header {
+            request = request.header(AUTHORIZATION, auth);
+        }
+
+        request
+            .send()
+            .await
+            .context("Failed to reach results webhook")?
+            .error_for_status()
+            .context("Results webhook returned error status")?;
+        Ok(())
+    }
+}
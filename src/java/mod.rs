@@ -21,7 +21,9 @@ pub mod queries;
 pub mod util;
 
 pub use config::{JavaConfig, JavaPrompts};
-pub use file::{File, FileType, JavaFileError};
-pub use parser::Parser;
+pub use file::{
+    File, FileMetrics, FileType, ImportDecl, JavaFileError, LineEnding, RunOutput, STDLIB_PACKAGES,
+};
+pub use parser::{Parser, QueryCompileError, SyntaxError};
 pub use paths::ProjectPaths;
-pub use project::Project;
+pub use project::{ParseCache, Project, ProjectMetrics, SUBMISSION_URL_BASE};
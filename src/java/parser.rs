@@ -1,12 +1,103 @@
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
-use std::fmt::Formatter;
+use std::{cell::RefCell, fmt::Formatter};
 
 use anyhow::{Context, Result, anyhow};
-use tree_sitter::{Query, QueryCursor, StreamingIterator, Tree};
+use tree_sitter::{Node, Query, QueryCursor, StreamingIterator, Tree};
 
 use crate::Dict;
+
+/// A tree-sitter query that failed to compile against the Java grammar.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error(
+    "Failed to compile tree-sitter query ({kind} at line {line}, column {column}): \
+     {message}\nQuery:\n{query}"
+)]
+pub struct QueryCompileError {
+    /// The query text that was rejected.
+    pub query:   String,
+    /// 1-based line of the offending token within the query.
+    pub line:    usize,
+    /// 1-based column of the offending token within the query.
+    pub column:  usize,
+    /// Category of the failure, e.g. `Syntax` or `NodeType`.
+    pub kind:    String,
+    /// tree-sitter's description of the failure.
+    pub message: String,
+}
+
+impl QueryCompileError {
+    /// Wraps tree-sitter's error together with the query it came from.
+    fn new(query: &str, err: tree_sitter::QueryError) -> Self {
+        Self {
+            query:   query.to_string(),
+            line:    err.row + 1,
+            column:  err.column + 1,
+            kind:    format!("{:?}", err.kind),
+            message: err.message,
+        }
+    }
+}
+
+/// A spot where tree-sitter had to recover from invalid Java, either by
+/// skipping unexpected text or by inserting a missing token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    /// 1-based line where the problem starts.
+    pub line:    usize,
+    /// 1-based column where the problem starts.
+    pub column:  usize,
+    /// True when a token was missing rather than unexpected.
+    pub missing: bool,
+    /// The missing token's kind, or the unexpected source text.
+    pub text:    String,
+}
+
+impl std::fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let what = if self.missing {
+            "missing"
+        } else {
+            "unexpected"
+        };
+        write!(f, "line {}, column {}: {what} `{}`", self.line, self.column, self.text)
+    }
+}
+
+/// Collects the ERROR and MISSING nodes under `node`, outermost first.
+fn collect_syntax_errors(node: Node, source: &[u8], errors: &mut Vec<SyntaxError>) {
+    if node.is_error() || node.is_missing() {
+        let position = node.start_position();
+        let text = if node.is_missing() {
+            node.kind().to_string()
+        } else {
+            let text = node.utf8_text(source).unwrap_or_default();
+            let first_line = text.trim().lines().next().unwrap_or_default();
+            first_line.chars().take(40).collect()
+        };
+        errors.push(SyntaxError {
+            line: position.row + 1,
+            column: position.column + 1,
+            missing: node.is_missing(),
+            text,
+        });
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.has_error() {
+            collect_syntax_errors(child, source, errors);
+        }
+    }
+}
+
+/// Compiles `q` against `lang`, keeping the query text in the error.
+fn compile_query(lang: &tree_sitter::Language, q: &str) -> Result<Query, QueryCompileError> {
+    Query::new(lang, q).map_err(|e| QueryCompileError::new(q, e))
+}
+
 #[derive(Clone)]
 /// A struct that wraps a tree-sitter parser object and source code
 pub struct Parser {
@@ -23,6 +114,34 @@ fn java_language() -> tree_sitter::Language {
     tree_sitter_java::LANGUAGE.into()
 }
 
+thread_local! {
+    /// Per-thread tree-sitter parser with the Java grammar already loaded, so
+    /// parsing many files does not pay for `set_language` each time.
+    static JAVA_PARSER: RefCell<Option<tree_sitter::Parser>> = const { RefCell::new(None) };
+}
+
+/// Parses `code` with this thread's pooled parser, creating it on first use.
+fn parse_java(code: &str) -> Result<Tree> {
+    JAVA_PARSER.with(|cell| {
+        let mut parser = match cell.borrow_mut().take() {
+            Some(parser) => parser,
+            None => {
+                let mut parser = tree_sitter::Parser::new();
+                parser
+                    .set_language(&java_language())
+                    .with_context(|| "Failed to load Java grammar")?;
+                parser
+            }
+        };
+
+        // Clear any state left over from an earlier (possibly aborted) parse.
+        parser.reset();
+        let tree = parser.parse(code, None);
+        *cell.borrow_mut() = Some(parser);
+        tree.ok_or_else(|| anyhow!("Error parsing Java code"))
+    })
+}
+
 impl Default for Parser {
     fn default() -> Self {
         // Fall back to the fallible constructor but keep Default for callers
@@ -43,38 +162,37 @@ impl Parser {
     /// * `source_code`: the source code to be parsed
     /// * `lang`: the tree-sitter grammar to use
     pub fn new(source_code: String) -> Result<Self> {
-        let mut parser = tree_sitter::Parser::new();
-        let language = java_language();
-
-        parser
-            .set_language(&language)
-            .with_context(|| "Failed to load Java grammar")?;
-        let tree = parser
-            .parse(source_code.as_str(), None)
-            .ok_or_else(|| anyhow!("Error parsing Java code"))?;
+        let tree = parse_java(&source_code)?;
 
         Ok(Self {
             code:  source_code,
             _tree: Some(tree),
-            lang:  language,
+            lang:  java_language(),
         })
     }
 
+    /// Compiles `q` against the Java grammar without running it, so malformed
+    /// queries can be rejected before any file is graded.
+    pub fn validate_query(q: &str) -> Result<(), QueryCompileError> {
+        compile_query(&java_language(), q).map(|_| ())
+    }
+
     /// A getter for parser's source code
     pub fn code(&self) -> &str {
         self.code.as_str()
     }
 
+    /// Returns the parse tree's root node.
+    pub fn root_node(&self) -> Result<Node<'_>> {
+        self._tree
+            .as_ref()
+            .map(Tree::root_node)
+            .context("Treesitter could not parse code")
+    }
+
     /// A setter for parser's source code
     pub fn set_code(&mut self, code: String) -> Result<()> {
-        let mut parser = tree_sitter::Parser::new();
-        parser
-            .set_language(&self.lang)
-            .with_context(|| "Failed to load Java grammar")?;
-
-        let tree = parser
-            .parse(code.as_str(), None)
-            .ok_or_else(|| anyhow!("Error parsing Java code"))?;
+        let tree = parse_java(&code)?;
 
         self.code = code;
         self._tree = Some(tree);
@@ -82,10 +200,35 @@ impl Parser {
         Ok(())
     }
 
+    /// Returns true when the code does not parse cleanly. tree-sitter still
+    /// builds a tree for invalid code, so queries over it can quietly match
+    /// nothing; check this first to tell "won't compile" apart from "not
+    /// there".
+    pub fn has_errors(&self) -> bool {
+        self._tree
+            .as_ref()
+            .is_some_and(|tree| tree.root_node().has_error())
+    }
+
+    /// Lists where the code fails to parse, in source order.
+    pub fn syntax_errors(&self) -> Vec<SyntaxError> {
+        let mut errors = Vec::new();
+        if let Some(tree) = &self._tree {
+            let root = tree.root_node();
+            if root.has_error() {
+                collect_syntax_errors(root, self.code.as_bytes(), &mut errors);
+            }
+        }
+        errors
+    }
+
     /// Applies a tree sitter query and returns the result as a collection of
     /// HashMaps
     ///
     /// * `q`: the tree-sitter query to be applied
+    ///
+    /// A query that does not compile yields a [`QueryCompileError`] (wrapped
+    /// in the returned `anyhow::Error`) rather than a panic.
     pub fn query(&self, q: &str) -> Result<Vec<Dict>> {
         let mut results = vec![];
         let tree = self
@@ -93,8 +236,7 @@ impl Parser {
             .as_ref()
             .context("Treesitter could not parse code")?;
 
-        let query = Query::new(&self.lang, q)
-            .with_context(|| format!("Failed to compile tree-sitter query: {q}"))?;
+        let query = compile_query(&self.lang, q)?;
         let mut cursor = QueryCursor::new();
         let mut matches = cursor.matches(&query, tree.root_node(), self.code.as_bytes());
         let mut capture_indices = Vec::new();
@@ -145,8 +287,7 @@ impl Parser {
             .as_ref()
             .context("Treesitter could not parse code")?;
 
-        let query = Query::new(&self.lang, q)
-            .with_context(|| format!("Failed to compile tree-sitter query: {q}"))?;
+        let query = compile_query(&self.lang, q)?;
         let capture_index = query
             .capture_index_for_name(capture_name)
             .ok_or_else(|| anyhow!("Capture name {capture_name} not present in query"))?;
@@ -168,4 +309,47 @@ impl Parser {
 
         Ok(results)
     }
+
+    /// Applies a tree sitter query like [`Parser::query`], pairing each match
+    /// with the 1-based starting line of its `capture_name` capture. Matches
+    /// without that capture are skipped.
+    pub fn query_match_positions(&self, q: &str, capture_name: &str) -> Result<Vec<(Dict, usize)>> {
+        let tree = self
+            ._tree
+            .as_ref()
+            .context("Treesitter could not parse code")?;
+
+        let query = compile_query(&self.lang, q)?;
+        let line_index = query
+            .capture_index_for_name(capture_name)
+            .ok_or_else(|| anyhow!("Capture name {capture_name} not present in query"))?;
+
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&query, tree.root_node(), self.code.as_bytes());
+        let mut results = Vec::new();
+
+        while let Some(m) = matches.next() {
+            let Some(line) = m
+                .captures
+                .iter()
+                .find(|c| c.index == line_index)
+                .map(|c| c.node.start_position().row + 1)
+            else {
+                continue;
+            };
+
+            let mut result = Dict::new();
+            for capture in m.captures {
+                let name = query.capture_names()[capture.index as usize];
+                let text = capture
+                    .node
+                    .utf8_text(self.code.as_bytes())
+                    .context("Cannot map capture to source text")?;
+                result.insert(name.to_string(), text.to_string());
+            }
+            results.push((result, line));
+        }
+
+        Ok(results)
+    }
 }
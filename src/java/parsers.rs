@@ -21,6 +21,10 @@ peg::parser! {
         rule found_tests()
             = " tests found"
 
+        /// matches the keyword "containers failed"
+        rule failed_containers()
+            = " containers failed"
+
         /// parses and returns the number of tests passed
         pub rule num_tests_passed() -> u32
             = "[" whitespace()? l:number() successful_tests() whitespace()? "]" { l }
@@ -29,6 +33,11 @@ peg::parser! {
         pub rule num_tests_found() -> u32
             = "[" whitespace()? l:number() found_tests() whitespace()? "]" { l }
 
+        /// parses and returns the number of containers (test classes or
+        /// `@TestFactory` methods) that failed
+        pub rule num_containers_failed() -> u32
+            = "[" whitespace()? l:number() failed_containers() whitespace()? "]" { l }
+
         /// matches any path separator, hopefully cross-platform
         rule path_separator() =
             whitespace()?
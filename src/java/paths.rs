@@ -6,23 +6,30 @@ use std::path::{Path, PathBuf};
 use bon::builder;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 /// Represents standard workspace paths for a Java project.
 pub struct ProjectPaths {
     /// Root directory of the project workspace.
-    root_dir:   PathBuf,
+    root_dir:          PathBuf,
     /// `src/` directory containing production sources.
-    source_dir: PathBuf,
+    source_dir:        PathBuf,
     /// `target/` build output directory.
-    build_dir:  PathBuf,
+    build_dir:         PathBuf,
     /// `test/` directory containing student tests.
-    test_dir:   PathBuf,
+    test_dir:          PathBuf,
     /// `lib/` directory holding downloaded jars.
-    lib_dir:    PathBuf,
+    lib_dir:           PathBuf,
     /// `.umm/` metadata directory maintained by the tool.
-    umm_dir:    PathBuf,
+    umm_dir:           PathBuf,
     /// `test_reports/` directory where graders write reports (e.g., PIT).
-    report_dir: PathBuf,
+    report_dir:        PathBuf,
+    /// Extra jars appended to the classpath (e.g., a grader's overlay).
+    #[serde(default)]
+    extra_classpath:   Vec<PathBuf>,
+    /// Instructor-provided jars or class directories whose classes are used
+    /// as-is instead of being compiled from source.
+    #[serde(default)]
+    reference_classes: Vec<PathBuf>,
 }
 
 impl ProjectPaths {
@@ -82,6 +89,34 @@ impl ProjectPaths {
         self
     }
 
+    /// Extra classpath entries appended after the `lib` jars.
+    pub fn extra_classpath(&self) -> &[PathBuf] {
+        &self.extra_classpath
+    }
+
+    /// Returns a copy of these paths with additional classpath entries.
+    pub fn with_extra_classpath(mut self, entries: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.extra_classpath.extend(entries);
+        self
+    }
+
+    /// Precompiled reference classes placed on the classpath right after the
+    /// build directory.
+    pub fn reference_classes(&self) -> &[PathBuf] {
+        &self.reference_classes
+    }
+
+    /// Returns a copy of these paths that compiles and runs against the given
+    /// reference jars or class directories.
+    ///
+    /// Only sources whose classes the reference does not provide are
+    /// compiled; the rest are taken from the reference as-is, so their
+    /// sources never need to be present and are not recompiled if they are.
+    pub fn with_reference_classes(mut self, entries: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.reference_classes.extend(entries);
+        self
+    }
+
     /// Directory for umm artefacts.
     pub fn umm_dir(&self) -> &Path {
         self.umm_dir.as_path()
@@ -91,6 +126,32 @@ impl ProjectPaths {
     pub fn report_dir(&self) -> &Path {
         self.report_dir.as_path()
     }
+
+    /// Returns these paths moved onto another checkout of the project rooted
+    /// at `root`, such as a git worktree.
+    ///
+    /// Directories under the current root keep their place relative to it;
+    /// `lib`, extra classpath entries, and reference classes keep pointing at
+    /// this checkout (made absolute), since jars are rarely committed.
+    pub fn rebased_onto(&self, root: &Path) -> Self {
+        let rebase = |dir: &Path| match dir.strip_prefix(&self.root_dir) {
+            Ok(rel) => root.join(rel),
+            Err(_) => dir.to_path_buf(),
+        };
+        let absolute = |dir: &Path| std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf());
+
+        Self {
+            root_dir:          root.to_path_buf(),
+            source_dir:        rebase(&self.source_dir),
+            build_dir:         rebase(&self.build_dir),
+            test_dir:          rebase(&self.test_dir),
+            lib_dir:           absolute(&self.lib_dir),
+            umm_dir:           rebase(&self.umm_dir),
+            report_dir:        rebase(&self.report_dir),
+            extra_classpath:   self.extra_classpath.iter().map(|p| absolute(p)).collect(),
+            reference_classes: self.reference_classes.iter().map(|p| absolute(p)).collect(),
+        }
+    }
 }
 
 impl Default for ProjectPaths {
@@ -126,6 +187,8 @@ impl ProjectPaths {
             lib_dir,
             umm_dir,
             report_dir,
+            extra_classpath: Vec::new(),
+            reference_classes: Vec::new(),
         }
     }
 }
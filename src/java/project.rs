@@ -1,22 +1,93 @@
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
-use anyhow::{Context, Result, anyhow};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use anyhow::{Context, Result, anyhow, bail};
 use async_openai::types::chat::{
     ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs,
 };
 use bon::{builder, vec};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::{runtime::Runtime, task::block_in_place};
+use tree_sitter::Node;
+use uuid::Uuid;
 
-use super::{file::File, paths::ProjectPaths};
+use super::{
+    file::{File, FileMetrics, FileType, JavaFileError},
+    parser::Parser,
+    paths::ProjectPaths,
+};
 use crate::{
+    archive::Unzipped,
+    config,
+    git::Worktree,
     java::grade::{
-        LineRef,
+        KnownSymbols, LineRef,
         context::{build_active_retrieval_context, build_heuristic_context},
+        suggest_unresolved_symbols,
     },
     retrieval::{HeuristicConfig, RetrievalFormatter},
     util::find_files,
 };
+/// Where submissions uploaded by [`Project::serve_project_code`] can be
+/// viewed, followed by the submission's id.
+pub const SUBMISSION_URL_BASE: &str = "https://feedback.dhruvdh.com/submissions/";
+
+/// Schema for the `submissions` table.
+#[derive(Serialize)]
+struct SubmissionRow {
+    /// UUID of the submission, also its URL slug.
+    id:      String,
+    /// Course identifier.
+    course:  String,
+    /// Academic term identifier.
+    term:    String,
+    /// Source code keyed by file name.
+    content: BTreeMap<String, String>,
+}
+
+/// Node kinds that declare a named type.
+const TYPE_DECLARATION_KINDS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "record_declaration",
+    "annotation_type_declaration",
+];
+
+/// Walks `node`, collecting the names of declared types and methods.
+fn collect_declared_names(
+    node: Node,
+    source: &[u8],
+    types: &mut Vec<String>,
+    methods: &mut Vec<String>,
+) {
+    let declared = || {
+        node.child_by_field_name("name")
+            .and_then(|name| name.utf8_text(source).ok())
+            .map(str::to_string)
+    };
+    if TYPE_DECLARATION_KINDS.contains(&node.kind()) {
+        types.extend(declared());
+    } else if node.kind() == "method_declaration" {
+        methods.extend(declared());
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_declared_names(child, source, types, methods);
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 /// Struct representing a Java project.
 /// Any index `i` in any collection in this struct always refers to the same
@@ -33,6 +104,21 @@ pub struct Project {
     paths: ProjectPaths,
 }
 
+/// Size metrics for a whole project: one entry per file plus totals.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectMetrics {
+    /// Per-file metrics, sorted by proper name.
+    pub files:      Vec<FileMetrics>,
+    /// Lines of code across all files.
+    pub loc:        usize,
+    /// Methods across all files.
+    pub methods:    usize,
+    /// Top-level classes across all files.
+    pub classes:    usize,
+    /// Top-level interfaces across all files.
+    pub interfaces: usize,
+}
+
 impl Project {
     /// Initializes a Project by discovering Java files in the workspace root
     /// and preparing metadata for later operations.
@@ -42,6 +128,18 @@ impl Project {
 
     /// Core implementation that discovers files for the provided paths.
     pub fn from_paths(paths: ProjectPaths) -> Result<Self> {
+        Self::discover(paths, None)
+    }
+
+    /// Like [`Project::from_paths`], but reuses files from `cache` whose path,
+    /// modification time, and size are unchanged since they were last parsed.
+    pub fn from_paths_cached(paths: ProjectPaths, cache: &ParseCache) -> Result<Self> {
+        Self::discover(paths, Some(cache))
+    }
+
+    /// Discovers and loads every Java file under `paths`, going through
+    /// `cache` when one is supplied.
+    fn discover(paths: ProjectPaths, cache: Option<&ParseCache>) -> Result<Self> {
         // TODO: When a typed Project builder lands, surface custom workspace layouts
         // instead of hard-coding defaults.
         let mut files = vec![];
@@ -53,8 +151,11 @@ impl Project {
 
         for path in found_files {
             let display_path = path.display().to_string();
-            let file = File::new(path, paths.clone())
-                .with_context(|| format!("Failed to load {}", display_path))?;
+            let file = match cache {
+                Some(cache) => cache.load(path, &paths),
+                None => File::new(path, paths.clone()),
+            }
+            .with_context(|| format!("Failed to load {}", display_path))?;
             names.push(file.proper_name());
             files.push(file);
         }
@@ -66,6 +167,54 @@ impl Project {
         })
     }
 
+    /// Discovers the project as of `git_ref` (a commit, branch, or tag) by
+    /// checking it out into a temporary worktree of the repository at
+    /// `paths.root_dir()`.
+    ///
+    /// The project root may be a subdirectory of the repository, in which case
+    /// the same subdirectory of the worktree is used. Paths are moved onto it
+    /// with [`ProjectPaths::rebased_onto`], so builds stay isolated while `lib`
+    /// keeps pointing at the original checkout. The returned [`Worktree`] must
+    /// outlive the project, and removes the checkout when dropped.
+    pub async fn from_git_ref(paths: &ProjectPaths, git_ref: &str) -> Result<(Self, Worktree)> {
+        let worktree = Worktree::checkout(paths.root_dir(), git_ref).await?;
+        let rebased = paths.rebased_onto(&worktree.project_dir());
+
+        let project = Self::from_paths(rebased)
+            .with_context(|| format!("Could not load the project at `{git_ref}`"))?;
+        Ok((project, worktree))
+    }
+
+    /// Discovers the project in the zip archive at `zip`, extracted into a
+    /// temporary directory. When everything in the archive sits under one
+    /// folder, as in zips downloaded from GitHub, that folder is the project
+    /// root.
+    ///
+    /// Submissions rarely include jars, so `lib` and extra classpath entries
+    /// come from the current workspace, as in [`Project::new`]. The returned
+    /// [`Unzipped`] must outlive the project, and removes the extracted files
+    /// when dropped.
+    pub async fn from_zip(zip: impl AsRef<Path>) -> Result<(Self, Unzipped)> {
+        let zip = zip.as_ref();
+        let unzipped = Unzipped::extract(zip).await?;
+        let workspace = ProjectPaths::default();
+
+        let paths = ProjectPaths::from_parts(
+            unzipped.root().to_path_buf(),
+            None,
+            None,
+            None,
+            Some(workspace.lib_dir().to_path_buf()),
+            None,
+            None,
+        )
+        .with_extra_classpath(workspace.extra_classpath().to_vec());
+
+        let project = Self::from_paths(paths)
+            .with_context(|| format!("Could not load the project in {}", zip.display()))?;
+        Ok((project, unzipped))
+    }
+
     /// Return a copy of this project with updated workspace paths.
     ///
     /// File metadata and cached names are recomputed against the provided paths
@@ -92,15 +241,52 @@ impl Project {
     ///
     /// * `name`: partial/fully formed name of the Java file to look for.
     pub fn identify(&self, name: &str) -> Result<File> {
-        let Some(index) = self.match_index(name) else {
+        let Some(index) = self.match_index(name)? else {
             return Err(anyhow!("Could not find {} in the project", name));
         };
         Ok(self.files[index].clone())
     }
 
+    /// Finds the method `method_name` declared by the class
+    /// `class_proper_name`, returning the declaring file and the method's
+    /// source.
+    ///
+    /// The file is resolved with [`Project::identify`], then the search is
+    /// scoped to the class declaration of that name, so a same-named method on
+    /// another top-level class in the file is skipped. Interfaces, enums, and
+    /// records fall back to searching the whole file. With overloads, the
+    /// first declaration in source order is returned.
+    pub fn find_method(
+        &self,
+        class_proper_name: &str,
+        method_name: &str,
+    ) -> Option<(File, String)> {
+        let file = self.identify(class_proper_name).ok()?;
+        let class_name = class_proper_name
+            .rsplit('.')
+            .next()
+            .unwrap_or(class_proper_name);
+
+        let class_query = format!(include_str!("queries/class_with_name.scm"), class_name);
+        let scope = file
+            .query(&class_query)
+            .ok()
+            .and_then(|matches| matches.into_iter().find_map(|m| m.get("body").cloned()))
+            .unwrap_or_else(|| file.code().to_string());
+
+        let method_query = format!(include_str!("queries/method_body_with_name.scm"), method_name);
+        let body = Parser::new(scope)
+            .ok()?
+            .query(&method_query)
+            .ok()?
+            .into_iter()
+            .find_map(|m| m.get("body").cloned())?;
+        Some((file, body))
+    }
+
     /// Returns true if project contains a file with the given name.
     pub fn contains(&self, name: &str) -> bool {
-        self.match_index(name).is_some()
+        self.match_index(name).is_ok_and(|index| index.is_some())
     }
 
     /// Returns the workspace paths associated with this project.
@@ -113,10 +299,191 @@ impl Project {
         self.files.as_ref()
     }
 
-    /// Prints project struct as a json
+    /// Groups the proper names of the project's files by their kind, each list
+    /// sorted. Kinds with no files are left out.
+    pub fn files_by_type(&self) -> BTreeMap<FileType, Vec<String>> {
+        let mut grouped: BTreeMap<FileType, Vec<String>> = BTreeMap::new();
+        for file in &self.files {
+            grouped
+                .entry(file.kind().clone())
+                .or_default()
+                .push(file.proper_name());
+        }
+        for names in grouped.values_mut() {
+            names.sort();
+        }
+        grouped
+    }
+
+    /// Maps each test file's proper name to the test methods it declares
+    /// (`Class#method`), in source order.
+    pub fn test_methods(&self) -> BTreeMap<String, Vec<String>> {
+        self.files
+            .iter()
+            .filter(|file| matches!(file.kind(), FileType::Test))
+            .map(|file| (file.proper_name(), file.test_methods()))
+            .collect()
+    }
+
+    /// Returns a stable SHA-256 hex digest of the project's source code.
+    ///
+    /// Files are hashed in order of their proper names, so the digest does not
+    /// depend on discovery order or on where the submission lives on disk.
+    pub fn fingerprint(&self) -> String {
+        self.digest_sources(false)
+    }
+
+    /// Like [`Project::fingerprint`], but hashes the syntax tree's tokens
+    /// rather than the raw text, so that submissions differing only in
+    /// formatting share a fingerprint while `return x` and `returnx` do not.
+    pub fn normalized_fingerprint(&self) -> String {
+        self.digest_sources(true)
+    }
+
+    /// Hashes each file's name and source, optionally as separated tokens.
+    fn digest_sources(&self, tokenize: bool) -> String {
+        let mut files: Vec<&File> = self.files.iter().collect();
+        files.sort_by_key(|file| file.proper_name());
+
+        let mut hasher = Sha256::new();
+        for file in files {
+            hasher.update(file.proper_name().as_bytes());
+            hasher.update(b"\0");
+            if tokenize {
+                for token in leaf_tokens(file.parser()) {
+                    hasher.update(token.as_bytes());
+                    hasher.update(b"\x1f");
+                }
+            } else {
+                hasher.update(file.code().as_bytes());
+            }
+            hasher.update(b"\0");
+        }
+
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Computes per-file and aggregate size metrics.
+    pub fn metrics(&self) -> Result<ProjectMetrics> {
+        let mut files = self
+            .files
+            .iter()
+            .map(File::metrics)
+            .collect::<Result<Vec<_>>>()?;
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut metrics = ProjectMetrics::default();
+        for file in &files {
+            metrics.loc += file.loc;
+            metrics.methods += file.methods;
+            metrics.classes += file.classes;
+            metrics.interfaces += file.interfaces;
+        }
+        metrics.files = files;
+        Ok(metrics)
+    }
+
+    /// Lines of code across the project, excluding blank and comment-only
+    /// lines.
+    pub fn total_loc(&self) -> Result<usize> {
+        Ok(self.metrics()?.loc)
+    }
+
+    /// Uploads the source of every file to the `submissions` table and
+    /// returns the URL where it can be viewed.
+    ///
+    /// Requires `SUPABASE_URL` and `SUPABASE_ANON_KEY`.
+    pub async fn serve_project_code_async(&self) -> Result<String> {
+        let client = config::postgrest_client().ok_or_else(|| {
+            anyhow!("SUPABASE_URL and SUPABASE_ANON_KEY must be set to serve project code.")
+        })?;
+
+        let id = Uuid::new_v4().to_string();
+        let row = SubmissionRow {
+            id:      id.clone(),
+            course:  config::course(),
+            term:    config::term(),
+            content: self
+                .files
+                .iter()
+                .map(|file| (file.file_name().to_string(), file.code().to_string()))
+                .collect(),
+        };
+        let body = serde_json::to_string(&row).context("Failed to serialize submission")?;
+
+        client
+            .from("submissions")
+            .insert(body)
+            .execute()
+            .await
+            .and_then(|response| response.error_for_status())
+            .context("Failed to upload project code to Supabase")?;
+
+        Ok(format!("{SUBMISSION_URL_BASE}{id}"))
+    }
+
+    /// Blocking form of
+    /// [`serve_project_code_async`](Self::serve_project_code_async)
+    /// that works both inside and outside a Tokio runtime.
+    pub fn serve_project_code(&self) -> Result<String> {
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => block_in_place(|| handle.block_on(self.serve_project_code_async())),
+            Err(_) => Runtime::new()
+                .context("Failed to create Tokio runtime for Supabase call")?
+                .block_on(self.serve_project_code_async()),
+        }
+    }
+
+    /// Type and method names declared anywhere in the project, for suggesting
+    /// fixes to misspelled symbols.
+    pub fn known_symbols(&self) -> KnownSymbols {
+        let mut types = Vec::new();
+        let mut methods = Vec::new();
+        for file in &self.files {
+            if let Ok(root) = file.parser().root_node() {
+                collect_declared_names(root, file.code().as_bytes(), &mut types, &mut methods);
+            }
+        }
+        KnownSymbols::new(types, methods)
+    }
+
+    /// Compiles the file `name` like [`File::check`], adding "did you mean"
+    /// hints to "cannot find symbol" errors whose symbol looks like a typo of
+    /// a name in [`known_symbols`](Self::known_symbols).
+    pub async fn check_file(&self, name: &str) -> Result<String, JavaFileError> {
+        let file = self.identify(name)?;
+        match file.check().await {
+            Err(JavaFileError::DuringCompilation {
+                stacktrace,
+                mut diags,
+            }) => {
+                let stacktrace =
+                    suggest_unresolved_symbols(&stacktrace, &mut diags, &self.known_symbols());
+                Err(JavaFileError::DuringCompilation { stacktrace, diags })
+            }
+            other => other,
+        }
+    }
+
+    /// Prints project struct as a json, with its [`metrics`](Self::metrics)
+    /// under a `metrics` key.
     pub fn info(&self) -> Result<()> {
+        /// Project fields plus metrics, serialized side by side.
+        #[derive(Serialize)]
+        struct Info<'a> {
+            /// The project itself.
+            #[serde(flatten)]
+            project: &'a Project,
+            /// Size metrics for the project.
+            metrics: ProjectMetrics,
+        }
+
+        let info = Info {
+            project: self,
+            metrics: self.metrics()?,
+        };
         // Keep the same shape but use pretty JSON so humans can read it more easily.
-        println!("{}", serde_json::to_string_pretty(&self)?);
+        println!("{}", serde_json::to_string_pretty(&info)?);
         Ok(())
     }
 
@@ -124,26 +491,50 @@ impl Project {
     /// methods.
     pub fn describe(&self) -> String {
         let mut lines = vec!["<project>"];
+        lines.extend(self.outline_files().map(File::description));
+        lines.push("</project>".to_string());
+        lines.join("\n")
+    }
 
-        for file in self.files.iter() {
-            // Hidden-test fixtures generated by ByHiddenTestGrader carry
-            // "Hidden" in their proper name. Skip them so we don't include
-            // instructor-only assets in the synthesized outline.
-            if file.proper_name().contains("Hidden") {
-                continue;
-            }
-            lines.push(file.description());
+    /// Renders the [`describe`](Self::describe) summary as Markdown, with a
+    /// heading per file followed by its outline.
+    pub fn describe_markdown(&self) -> String {
+        let mut sections = vec!["# Project outline".to_string()];
+        for file in self.outline_files() {
+            sections.push(format!(
+                "## `{}` ({})\n\n`{}`\n\n```xml\n{}\n```",
+                file.proper_name(),
+                file.kind().as_str(),
+                file.path().display(),
+                file.description()
+            ));
         }
+        sections.join("\n\n")
+    }
 
-        lines.push("</project>".to_string());
-        lines.join("\n")
+    /// Files included in project outlines.
+    fn outline_files(&self) -> impl Iterator<Item = &File> {
+        // Hidden-test fixtures generated by ByHiddenTestGrader carry
+        // "Hidden" in their proper name. Skip them so we don't include
+        // instructor-only assets in the synthesized outline.
+        self.files
+            .iter()
+            .filter(|file| !file.proper_name().contains("Hidden"))
     }
 }
 
 impl Project {
     /// Attempts to locate the index of a file that matches the provided name.
-    fn match_index(&self, name: &str) -> Option<usize> {
-        self.names
+    ///
+    /// Fails for an empty name, and for a path that only matches by suffix
+    /// when it is the suffix of more than one file.
+    fn match_index(&self, name: &str) -> Result<Option<usize>> {
+        if name.trim().is_empty() {
+            bail!("Cannot identify a file from an empty name");
+        }
+
+        let by_name = self
+            .names
             .iter()
             .position(|n| n == name)
             .or_else(|| self.files.iter().position(|file| file.file_name() == name))
@@ -156,17 +547,68 @@ impl Project {
                 self.files
                     .iter()
                     .position(|file| file.simple_name() == name)
+            });
+        if by_name.is_some() {
+            return Ok(by_name);
+        }
+
+        if let Some(index) = self.path_index(name)? {
+            return Ok(Some(index));
+        }
+        Ok(self
+            .files
+            .iter()
+            .position(|file| file.proper_name() == name))
+    }
+
+    /// Finds the file `name` points at, whichever of the two is absolute or
+    /// relative. Relative names are tried against both the current directory
+    /// and the project root.
+    ///
+    /// Only when `name` does not exist on disk do we fall back to a
+    /// component-wise suffix match, which must pick out exactly one file.
+    fn path_index(&self, name: &str) -> Result<Option<usize>> {
+        let candidate = Path::new(name);
+        let resolved: Vec<PathBuf> = [
+            candidate.canonicalize(),
+            self.paths.root_dir().join(candidate).canonicalize(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        let exact = self.files.iter().position(|file| {
+            file.path() == candidate
+                || file
+                    .path()
+                    .canonicalize()
+                    .is_ok_and(|stored| resolved.contains(&stored))
+        });
+        if exact.is_some() || !resolved.is_empty() {
+            return Ok(exact);
+        }
+
+        let suffix_matches: Vec<usize> = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| {
+                file.path().ends_with(candidate) || candidate.ends_with(file.path())
             })
-            .or_else(|| {
-                self.files
-                    .iter()
-                    .position(|file| file.path().display().to_string() == name)
-            })
-            .or_else(|| {
-                self.files
+            .map(|(index, _)| index)
+            .collect();
+        match suffix_matches.as_slice() {
+            [] => Ok(None),
+            [index] => Ok(Some(*index)),
+            several => bail!(
+                "{name} is ambiguous; it could be any of: {}",
+                several
                     .iter()
-                    .position(|file| file.proper_name() == name)
-            })
+                    .map(|&index| self.files[index].path().display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
     }
 }
 
@@ -212,11 +654,44 @@ impl RetrievalFormatter for Project {
         build_heuristic_context(line_refs, self.clone(), cfg)
     }
 
-    fn active_retrieval(&self, grader_output: String) -> Result<ChatCompletionRequestMessage> {
-        build_active_retrieval_context(self, grader_output)
+    fn active_retrieval(
+        &self,
+        grader_output: String,
+        active_retrieval: Option<bool>,
+    ) -> Result<ChatCompletionRequestMessage> {
+        build_active_retrieval_context(self, grader_output, active_retrieval)
     }
 }
 
+/// Source text of every leaf of `parser`'s syntax tree, in order. Whitespace
+/// between tokens is dropped, while whitespace inside a token, such as a
+/// string literal, is kept.
+fn leaf_tokens(parser: &Parser) -> Vec<&str> {
+    let Ok(root) = parser.root_node() else {
+        return Vec::new();
+    };
+    let source = parser.code();
+    let mut tokens = Vec::new();
+    let mut cursor = root.walk();
+    'walk: loop {
+        let node = cursor.node();
+        if node.child_count() > 0 && cursor.goto_first_child() {
+            continue;
+        }
+        if let Some(token) = source.get(node.byte_range())
+            && !token.is_empty()
+        {
+            tokens.push(token);
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                break 'walk;
+            }
+        }
+    }
+    tokens
+}
+
 /// Builder-style entry point for constructing a Project with optional custom
 /// paths.
 #[builder(finish_fn = build)]
@@ -224,3 +699,88 @@ pub fn project(paths: Option<ProjectPaths>) -> Result<Project> {
     let paths = paths.unwrap_or_default();
     Project::from_paths(paths)
 }
+
+/// Digest of a file's contents, identifying one version of it for
+/// [`ParseCache`], or `None` if it cannot be read (in which case the file is
+/// always reparsed).
+fn content_hash(path: &Path) -> Option<Vec<u8>> {
+    let source = std::fs::read(path).ok()?;
+    Some(Sha256::digest(&source).to_vec())
+}
+
+/// Parsed files shared across discoveries, for batch grading many submissions
+/// built from the same template.
+///
+/// Entries are keyed by path and invalidated whenever the file's contents
+/// change, whatever its modification time says. Pass the same cache to
+/// [`Project::from_paths_cached`] for every discovery that should share it.
+#[derive(Debug, Default)]
+pub struct ParseCache {
+    /// Previously parsed files and the hash of the contents they were parsed
+    /// from.
+    entries: Mutex<HashMap<PathBuf, (Vec<u8>, File)>>,
+    /// Number of files parsed (rather than reused) through this cache.
+    parses:  AtomicUsize,
+}
+
+impl ParseCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of files this cache has had to parse so far.
+    pub fn parses(&self) -> usize {
+        self.parses.load(Ordering::Relaxed)
+    }
+
+    /// Number of files currently cached.
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.len())
+            .unwrap_or(0)
+    }
+
+    /// Returns true if nothing has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops every cached file.
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+
+    /// Returns the cached file for `path` if it is unchanged on disk,
+    /// otherwise parses it and remembers the result.
+    fn load(&self, path: PathBuf, paths: &ProjectPaths) -> Result<File> {
+        let hash = content_hash(&path);
+
+        let cached = hash.as_ref().and_then(|hash| {
+            let entries = self.entries.lock().ok()?;
+            let (cached_hash, file) = entries.get(&path)?;
+            (cached_hash == hash).then(|| file.clone())
+        });
+        if let Some(file) = cached {
+            return Ok(file.with_paths(paths.clone()));
+        }
+
+        let file = File::new(path.clone(), paths.clone())?;
+        self.parses.fetch_add(1, Ordering::Relaxed);
+
+        if let Ok(mut entries) = self.entries.lock() {
+            match hash {
+                Some(hash) => {
+                    entries.insert(path, (hash, file.clone()));
+                }
+                None => {
+                    entries.remove(&path);
+                }
+            }
+        }
+        Ok(file)
+    }
+}
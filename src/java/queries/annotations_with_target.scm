@@ -0,0 +1,47 @@
+(class_declaration
+    (modifiers
+        [(marker_annotation name: (_) @annotation)
+         (annotation name: (_) @annotation)])
+    name: (_) @name
+    (#eq? @name {0:?})
+)
+
+(interface_declaration
+    (modifiers
+        [(marker_annotation name: (_) @annotation)
+         (annotation name: (_) @annotation)])
+    name: (_) @name
+    (#eq? @name {0:?})
+)
+
+(enum_declaration
+    (modifiers
+        [(marker_annotation name: (_) @annotation)
+         (annotation name: (_) @annotation)])
+    name: (_) @name
+    (#eq? @name {0:?})
+)
+
+(record_declaration
+    (modifiers
+        [(marker_annotation name: (_) @annotation)
+         (annotation name: (_) @annotation)])
+    name: (_) @name
+    (#eq? @name {0:?})
+)
+
+(method_declaration
+    (modifiers
+        [(marker_annotation name: (_) @annotation)
+         (annotation name: (_) @annotation)])
+    name: (_) @name
+    (#eq? @name {0:?})
+)
+
+(constructor_declaration
+    (modifiers
+        [(marker_annotation name: (_) @annotation)
+         (annotation name: (_) @annotation)])
+    name: (_) @name
+    (#eq? @name {0:?})
+)
@@ -0,0 +1,6 @@
+(
+    class_declaration
+    (modifiers) @modifiers
+    name: (_) @name
+    (#eq? @name {:?})
+)
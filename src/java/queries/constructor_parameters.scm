@@ -0,0 +1,4 @@
+(
+    constructor_declaration
+    parameters: (formal_parameters) @parameters
+)
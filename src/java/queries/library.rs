@@ -0,0 +1,71 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::java::Parser;
+
+/// A set of tree-sitter queries read from `.scm` files in a directory, keyed
+/// by file stem (`loops.scm` is referenced as `loops`).
+///
+/// Every query is compiled against the Java grammar when the library is
+/// loaded, so a typo surfaces once with the offending file name instead of
+/// during grading.
+#[derive(Debug, Clone, Default)]
+pub struct QueryLibrary {
+    /// Directory the queries were read from.
+    dir:     PathBuf,
+    /// Query source keyed by name.
+    queries: BTreeMap<String, String>,
+}
+
+impl QueryLibrary {
+    /// Loads and validates every `.scm` file directly inside `dir`.
+    pub fn load(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        let entries = std::fs::read_dir(&dir)
+            .with_context(|| format!("Could not read query directory {}", dir.display()))?;
+
+        let mut queries = BTreeMap::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("scm") {
+                continue;
+            }
+
+            let name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .ok_or_else(|| anyhow!("Query file name is not valid UTF-8: {}", path.display()))?
+                .to_string();
+            let source = std::fs::read_to_string(&path)
+                .with_context(|| format!("Could not read query file {}", path.display()))?;
+            Parser::validate_query(&source)
+                .with_context(|| format!("Query file {} failed to compile", path.display()))?;
+
+            queries.insert(name, source);
+        }
+
+        Ok(Self { dir, queries })
+    }
+
+    /// Directory the queries were read from.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the query named `name`, if one was loaded.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.queries.get(name).map(String::as_str)
+    }
+
+    /// Names of all loaded queries, sorted.
+    pub fn names(&self) -> Vec<String> {
+        self.queries.keys().cloned().collect()
+    }
+}
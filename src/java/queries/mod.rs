@@ -17,10 +17,21 @@ pub const CLASSNAME_QUERY: &str = include_str!("class_name.scm");
 /// * `name`: name of the interface
 pub const INTERFACENAME_QUERY: &str = include_str!("interface_name.scm");
 
-/// Tree-sitter query that returns name of the JUnit `@Test` annotated methods
+/// Tree-sitter query that returns name of the JUnit `@Test` and `@TestFactory`
+/// annotated methods
 /// * `name`: name of the test method
 pub const TEST_ANNOTATION_QUERY: &str = include_str!("test_annotation.scm");
 
+/// Tree-sitter query that returns name of the JUnit `@TestFactory` annotated
+/// methods
+/// * `name`: name of the factory method
+pub const TEST_FACTORY_QUERY: &str = include_str!("test_factory.scm");
+
+/// Tree-sitter query that returns JUnit `@RepeatedTest` methods
+/// * `name`: name of the test method
+/// * `arguments`: the annotation's argument list, e.g. `(5)`
+pub const REPEATED_TEST_QUERY: &str = include_str!("repeated_test.scm");
+
 /// Tree-sitter query to check the existence of a main method.
 pub const MAIN_METHOD_QUERY: &str = include_str!("main_method.scm");
 
@@ -67,3 +78,14 @@ pub const INTERFACE_METHODS_QUERY: &str = include_str!("interface_methods.scm");
 /// Tree-sitter query that returns method call identifiers
 /// * `name`: method call identifier
 pub const METHOD_CALL_QUERY: &str = include_str!("method_invocation.scm");
+
+/// Tree-sitter query that returns every top-level class and interface
+/// * `name`: name of the type
+/// * `class`: entire declaration, when the type is a class
+/// * `interface`: entire declaration, when the type is an interface
+pub const TOP_LEVEL_TYPES_QUERY: &str = include_str!("top_level_types.scm");
+
+/// Course-specific queries loaded from `.scm` files at runtime.
+mod library;
+
+pub use library::QueryLibrary;
@@ -0,0 +1,10 @@
+(method_declaration
+	(modifiers
+        (annotation
+            name: (_) @annotation
+            arguments: (_) @arguments
+        )
+    )
+    name: (_) @name
+    (#eq? @annotation "RepeatedTest")
+)
\ No newline at end of file
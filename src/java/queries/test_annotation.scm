@@ -14,5 +14,5 @@
     	name: (_) @annotation)
     )
     name: (_) @name
-    (#eq? @annotation "Test")
+    (#any-of? @annotation "Test" "TestFactory")
 )
\ No newline at end of file
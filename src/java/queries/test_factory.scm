@@ -0,0 +1,8 @@
+(method_declaration
+	(modifiers
+	(marker_annotation
+    	name: (_) @annotation)
+    )
+    name: (_) @name
+    (#eq? @annotation "TestFactory")
+)
\ No newline at end of file
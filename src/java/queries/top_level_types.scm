@@ -0,0 +1,7 @@
+(program
+  (class_declaration
+    name: (identifier) @name) @class)
+
+(program
+  (interface_declaration
+    name: (identifier) @name) @interface)
@@ -1,10 +1,22 @@
-use std::{collections::HashSet, ffi::OsString, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::SystemTime,
+};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use which::which;
 
-use super::ProjectPaths;
-use crate::util::find_files;
+use super::{Parser, ProjectPaths, queries::PACKAGE_QUERY};
+use crate::{
+    process::{self, StdinSource},
+    util::find_files,
+};
 
 /// Finds and returns the path to the javac binary.
 pub fn javac_path() -> Result<OsString> {
@@ -20,8 +32,103 @@ pub fn java_path() -> Result<OsString> {
         .context("Cannot find a Java runtime on path (java)")
 }
 
+/// Finds and returns the path to the jar binary.
+pub fn jar_path() -> Result<OsString> {
+    which("jar")
+        .map(PathBuf::into_os_string)
+        .context("Cannot find the Java archive tool on path (jar)")
+}
+
+/// Every directory a cached path string was scanned from, with its
+/// modification time; a missing directory stamps as `None`.
+type DirStamp = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Computed path strings keyed by the paths they were built for.
+type PathCache = LazyLock<Mutex<HashMap<ProjectPaths, (DirStamp, String)>>>;
+
+/// Classpaths computed so far, reused until the `lib` directory changes.
+static CLASSPATH_CACHE: PathCache = LazyLock::new(Default::default);
+
+/// Sourcepaths computed so far, reused until a source root changes.
+static SOURCEPATH_CACHE: PathCache = LazyLock::new(Default::default);
+
+/// Number of classpath and sourcepath directory scans performed.
+static PATH_SCANS: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of times [`classpath`] or [`sourcepath`] had to scan the
+/// filesystem instead of reusing a cached result.
+pub fn path_scans() -> usize {
+    PATH_SCANS.load(Ordering::Relaxed)
+}
+
+/// Stamps `dirs` and every directory below them by modification time, the
+/// same directories [`find_files`] walks. Adding, removing, or renaming an
+/// entry updates its parent directory's time, so a file added anywhere in the
+/// tree invalidates the cache.
+fn dir_stamp(dirs: &[&Path]) -> DirStamp {
+    let mut stamp = Vec::new();
+    let mut pending: Vec<PathBuf> = dirs.iter().map(|dir| dir.to_path_buf()).collect();
+
+    while let Some(dir) = pending.pop() {
+        let modified = std::fs::metadata(&dir)
+            .and_then(|meta| meta.modified())
+            .ok();
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            pending.extend(
+                entries
+                    .flatten()
+                    .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+                    .map(|entry| entry.path()),
+            );
+        }
+        stamp.push((dir, modified));
+    }
+
+    stamp.sort();
+    stamp
+}
+
+/// Returns the cached string for `paths` if `stamp` still matches, otherwise
+/// runs `scan` and remembers its result.
+fn cached_scan(
+    cache: &PathCache,
+    paths: &ProjectPaths,
+    stamp: DirStamp,
+    scan: impl FnOnce() -> Result<String>,
+) -> Result<String> {
+    if let Ok(entries) = cache.lock()
+        && let Some((cached_stamp, value)) = entries.get(paths)
+        && *cached_stamp == stamp
+    {
+        return Ok(value.clone());
+    }
+
+    let value = scan()?;
+    PATH_SCANS.fetch_add(1, Ordering::Relaxed);
+    if let Ok(mut entries) = cache.lock() {
+        entries.insert(paths.clone(), (stamp, value.clone()));
+    }
+    Ok(value)
+}
+
 /// Find class and jar files to populate the classpath.
+///
+/// The result is memoized per set of paths and reused until the contents of
+/// the `lib` directory or any directory below it change, so repeated compiles
+/// and runs do not re-glob it.
 pub fn classpath(paths: &ProjectPaths) -> Result<String> {
+    for entry in paths.reference_classes() {
+        if !entry.exists() {
+            bail!("Reference classes entry does not exist: {}", entry.display());
+        }
+    }
+
+    let stamp = dir_stamp(&[paths.lib_dir()]);
+    cached_scan(&CLASSPATH_CACHE, paths, stamp, || scan_classpath(paths))
+}
+
+/// Builds the classpath for `paths` from scratch.
+fn scan_classpath(paths: &ProjectPaths) -> Result<String> {
     // Order matters for classpath resolution; build the list deterministically
     // and deduplicate while preserving the first occurrence.
     let mut entries: Vec<String> = Vec::new();
@@ -29,17 +136,33 @@ pub fn classpath(paths: &ProjectPaths) -> Result<String> {
     // 1) Compiled classes.
     entries.push(paths.build_dir().display().to_string());
 
-    // 2) Project-local jars and wildcards under `lib/`.
+    // 2) Precompiled reference classes; student builds above shadow them.
+    entries.extend(
+        paths
+            .reference_classes()
+            .iter()
+            .map(|entry| entry.display().to_string()),
+    );
+
+    // 3) Project-local jars and wildcards under `lib/`.
     entries.push(paths.lib_dir().display().to_string());
     entries.push(paths.lib_dir().join("*").display().to_string());
 
-    // 3) Jar discovery under lib (keep the scan shallow to avoid long walks).
+    // 4) Jar discovery under lib (keep the scan shallow to avoid long walks).
     entries.extend(
         find_files("jar", 2, paths.lib_dir())?
             .iter()
             .map(|p| p.as_path().display().to_string()),
     );
 
+    // 5) Per-run extras (e.g., a test grader's classpath overlay).
+    entries.extend(
+        paths
+            .extra_classpath()
+            .iter()
+            .map(|p| p.display().to_string()),
+    );
+
     // Deduplicate without disturbing order.
     let mut seen = HashSet::new();
     entries.retain(|entry| seen.insert(entry.clone()));
@@ -48,7 +171,16 @@ pub fn classpath(paths: &ProjectPaths) -> Result<String> {
 }
 
 /// Find java files in source/test directories to populate the sourcepath.
+///
+/// Like [`classpath`], the result is memoized and reused until a directory
+/// anywhere under the project root, source, or test directory changes.
 pub fn sourcepath(paths: &ProjectPaths) -> Result<String> {
+    let stamp = dir_stamp(&[paths.root_dir(), paths.source_dir(), paths.test_dir()]);
+    cached_scan(&SOURCEPATH_CACHE, paths, stamp, || scan_sourcepath(paths))
+}
+
+/// Builds the sourcepath for `paths` from scratch.
+fn scan_sourcepath(paths: &ProjectPaths) -> Result<String> {
     let mut entries: Vec<String> = vec![
         paths.source_dir().display().to_string(),
         paths.test_dir().display().to_string(),
@@ -64,3 +196,88 @@ pub fn sourcepath(paths: &ProjectPaths) -> Result<String> {
 
     Ok(entries.join(paths.separator()))
 }
+
+/// Binary names, e.g. `pkg.Greeter`, of the top-level classes the reference
+/// jars and class directories provide.
+pub async fn reference_class_names(paths: &ProjectPaths) -> Result<HashSet<String>> {
+    let mut names = HashSet::new();
+    for entry in paths.reference_classes() {
+        let class_files = if entry.is_dir() {
+            find_files("class", 1, entry)?
+                .iter()
+                .filter_map(|path| path.strip_prefix(entry).ok())
+                .map(|path| {
+                    path.components()
+                        .map(|part| part.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/")
+                })
+                .collect()
+        } else {
+            jar_entries(entry).await?
+        };
+        names.extend(class_files.iter().filter_map(|file| binary_name(file)));
+    }
+    Ok(names)
+}
+
+/// Lists the entries of the jar at `jar`.
+async fn jar_entries(jar: &Path) -> Result<Vec<String>> {
+    let args = vec![OsString::from("tf"), jar.as_os_str().to_os_string()];
+    let collected = process::run_collect(jar_path()?, &args, StdinSource::Null, None, &[], None)
+        .await
+        .context("Failed to run jar to list reference classes")?;
+
+    if !collected.status.success() {
+        bail!(
+            "Could not list reference classes in {}:\n{}",
+            jar.display(),
+            String::from_utf8_lossy(&collected.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&collected.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Binary name of the top-level class stored at `entry`, a `/`-separated
+/// path inside a jar or class directory. Nested classes, module and package
+/// descriptors, and anything that is not a class file have none.
+fn binary_name(entry: &str) -> Option<String> {
+    let stem = entry.strip_suffix(".class")?;
+    let simple = stem.rsplit('/').next().unwrap_or(stem);
+    if stem.starts_with("META-INF/") || simple.contains('$') || simple.contains('-') {
+        return None;
+    }
+    Some(stem.replace('/', "."))
+}
+
+/// Sources to compile when the project has reference classes: every `.java`
+/// file under the project root whose top-level class the reference classes
+/// do not already provide. The reference versions of the rest are used
+/// as-is, even where their sources are shipped alongside.
+pub async fn student_sources(paths: &ProjectPaths) -> Result<Vec<PathBuf>> {
+    let provided = reference_class_names(paths).await?;
+
+    let mut sources = Vec::new();
+    for path in find_files("java", 4, paths.root_dir())? {
+        let code = std::fs::read_to_string(&path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        let package = Parser::new(code)?
+            .query(PACKAGE_QUERY)?
+            .first()
+            .and_then(|found| found.get("name").cloned());
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let name = match package {
+            Some(package) => format!("{package}.{stem}"),
+            None => stem.to_string(),
+        };
+        if !provided.contains(&name) {
+            sources.push(path);
+        }
+    }
+    Ok(sources)
+}
@@ -0,0 +1,351 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+//! Kotlin file representation and operations.
+
+use std::{
+    ffi::OsString,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{
+    parser::Parser,
+    paths::ProjectPaths,
+    queries::{
+        CLASS_DECLARATION_QUERY, FUNCTION_DECLARATION_QUERY, IMPORT_QUERY, MAIN_FUNCTION_QUERY,
+        PACKAGE_QUERY,
+    },
+    util::{kotlin_path, kotlinc_path},
+};
+use crate::{
+    Dict, config,
+    process::{self, StdinSource},
+};
+
+/// Errors specific to Kotlin file operations.
+#[derive(Error, Debug)]
+pub enum KotlinFileError {
+    /// An error while compiling the file with `kotlinc`.
+    #[error("Something went wrong while compiling the Kotlin file:\n{stacktrace}")]
+    DuringCompilation {
+        /// kotlinc output
+        stacktrace: String,
+    },
+    /// An error while running the compiled file.
+    #[error("Something went wrong while running the Kotlin file:\n{output}")]
+    AtRuntime {
+        /// Program output, including the stacktrace
+        output: String,
+    },
+    /// Unknown error
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Classification of Kotlin source files.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum FileType {
+    /// File declaring classes, objects, or functions without an entry point.
+    #[default]
+    Class,
+    /// File with a top-level `fun main`.
+    ClassWithMain,
+    /// Test file (`*Test.kt`).
+    Test,
+}
+
+impl Display for FileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileType::Class => write!(f, "Class"),
+            FileType::ClassWithMain => write!(f, "ClassWithMain"),
+            FileType::Test => write!(f, "Test"),
+        }
+    }
+}
+
+/// Represents a Kotlin source file with parsed metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct File {
+    /// Path to the Kotlin file.
+    path:         PathBuf,
+    /// Filesystem name including the `.kt` extension.
+    file_name:    String,
+    /// File name without the extension.
+    name:         String,
+    /// Package the file belongs to, if declared.
+    package_name: Option<String>,
+    /// JVM class `kotlinc` generates for top-level declarations (e.g.
+    /// `MainKt`).
+    jvm_class:    String,
+    /// Classification of the file.
+    kind:         FileType,
+    /// Discovered import paths.
+    imports:      Vec<String>,
+    /// Discovered class and object names.
+    classes:      Vec<String>,
+    /// Discovered function names.
+    functions:    Vec<String>,
+    /// Concise description of the file.
+    description:  String,
+    #[serde(skip)]
+    /// The parser for this file.
+    parser:       Parser,
+    /// Workspace paths associated with this file.
+    paths:        ProjectPaths,
+}
+
+impl File {
+    /// Creates a new File from a path.
+    pub fn new(path: impl Into<PathBuf>, paths: ProjectPaths) -> Result<Self> {
+        let path = path.into();
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| anyhow!("Invalid file path: {:?}", path))?
+            .to_string_lossy()
+            .to_string();
+        let name = file_name
+            .strip_suffix(".kt")
+            .unwrap_or(&file_name)
+            .to_string();
+
+        let code = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read Kotlin file: {:?}", path))?;
+        let parser = Parser::new(code)?;
+
+        let package_name = parser
+            .query(PACKAGE_QUERY)?
+            .into_iter()
+            .find_map(|d| d.get("name").cloned());
+        let imports = Self::captures(&parser, IMPORT_QUERY, "path")?;
+        let classes = Self::captures(&parser, CLASS_DECLARATION_QUERY, "name")?;
+        let functions = Self::captures(&parser, FUNCTION_DECLARATION_QUERY, "name")?;
+
+        let kind = if name.ends_with("Test") {
+            FileType::Test
+        } else if !parser.query(MAIN_FUNCTION_QUERY)?.is_empty() {
+            FileType::ClassWithMain
+        } else {
+            FileType::Class
+        };
+
+        let jvm_class = Self::jvm_class_name(&name, package_name.as_deref());
+        let description = Self::build_description(&file_name, &kind, &classes, &functions);
+
+        Ok(Self {
+            path,
+            file_name,
+            name,
+            package_name,
+            jvm_class,
+            kind,
+            imports,
+            classes,
+            functions,
+            description,
+            parser,
+            paths,
+        })
+    }
+
+    /// Collects every `capture` produced by `query`.
+    fn captures(parser: &Parser, query: &str, capture: &str) -> Result<Vec<String>> {
+        Ok(parser
+            .query(query)?
+            .into_iter()
+            .filter_map(|d| d.get(capture).cloned())
+            .collect())
+    }
+
+    /// Name of the facade class `kotlinc` emits for a file's top-level
+    /// declarations: `main.kt` in package `app` becomes `app.MainKt`.
+    fn jvm_class_name(name: &str, package_name: Option<&str>) -> String {
+        let mut chars = name.chars();
+        let facade = match chars.next() {
+            Some(first) => format!("{}{}Kt", first.to_uppercase(), chars.as_str()),
+            None => "Kt".to_string(),
+        };
+
+        match package_name {
+            Some(pkg) => format!("{pkg}.{facade}"),
+            None => facade,
+        }
+    }
+
+    /// Builds a concise description of the file.
+    fn build_description(
+        file_name: &str,
+        kind: &FileType,
+        classes: &[String],
+        functions: &[String],
+    ) -> String {
+        let mut desc = format!("{} ({})", file_name, kind);
+
+        if !classes.is_empty() {
+            desc.push_str(&format!(" - classes: {}", classes.join(", ")));
+        }
+
+        if !functions.is_empty() {
+            let top_funcs: Vec<_> = functions.iter().take(5).cloned().collect();
+            desc.push_str(&format!(" - functions: {}", top_funcs.join(", ")));
+            if functions.len() > 5 {
+                desc.push_str(&format!(" (+{} more)", functions.len() - 5));
+            }
+        }
+
+        desc
+    }
+
+    /// Compiles the file into the build directory with `kotlinc`.
+    ///
+    /// Files under the source directory are compiled together with their
+    /// siblings, since top-level declarations are visible across files.
+    pub async fn check(&self) -> Result<String, KotlinFileError> {
+        let kotlinc = kotlinc_path().map_err(KotlinFileError::Unknown)?;
+        let sources = if self.path.starts_with(self.paths.source_dir()) {
+            self.paths.source_dir()
+        } else {
+            self.path.as_path()
+        };
+        let args = vec![
+            OsString::from("-cp"),
+            self.paths.build_dir().as_os_str().to_os_string(),
+            OsString::from("-d"),
+            self.paths.build_dir().as_os_str().to_os_string(),
+            sources.as_os_str().to_os_string(),
+        ];
+
+        let collected = process::run_collect(
+            kotlinc,
+            &args,
+            StdinSource::Null,
+            None,
+            &[],
+            Some(config::javac_timeout()),
+        )
+        .await
+        .map_err(KotlinFileError::Unknown)?;
+
+        let output = format!(
+            "{}{}",
+            String::from_utf8_lossy(&collected.stdout),
+            String::from_utf8_lossy(&collected.stderr)
+        );
+
+        if collected.status.success() {
+            Ok(output)
+        } else {
+            Err(KotlinFileError::DuringCompilation { stacktrace: output })
+        }
+    }
+
+    /// Compiles and runs a file with a top-level `fun main`, feeding `input`
+    /// to stdin when provided.
+    pub async fn run(&self, input: Option<String>) -> Result<String, KotlinFileError> {
+        if self.kind != FileType::ClassWithMain {
+            return Err(KotlinFileError::DuringCompilation {
+                stacktrace: "The file you wish to run does not have a main function.".into(),
+            });
+        }
+
+        self.check().await?;
+
+        let kotlin = kotlin_path().map_err(KotlinFileError::Unknown)?;
+        let args = vec![
+            OsString::from("-cp"),
+            self.paths.build_dir().as_os_str().to_os_string(),
+            OsString::from(self.jvm_class.clone()),
+        ];
+        let stdin = match input {
+            Some(mut value) => {
+                value.push('\n');
+                StdinSource::Bytes(value.into_bytes())
+            }
+            None => StdinSource::Null,
+        };
+
+        let collected =
+            process::run_collect(kotlin, &args, stdin, None, &[], Some(config::java_timeout()))
+                .await
+                .map_err(KotlinFileError::Unknown)?;
+
+        let stdout = String::from_utf8_lossy(&collected.stdout).to_string();
+        if collected.status.success() {
+            Ok(stdout)
+        } else {
+            Err(KotlinFileError::AtRuntime {
+                output: format!("{stdout}{}", String::from_utf8_lossy(&collected.stderr)),
+            })
+        }
+    }
+
+    /// Returns the file's path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the file name.
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Returns the file name without its extension.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the declared package, if any.
+    pub fn package_name(&self) -> Option<&str> {
+        self.package_name.as_deref()
+    }
+
+    /// Returns the JVM class holding the file's top-level declarations.
+    pub fn jvm_class(&self) -> &str {
+        &self.jvm_class
+    }
+
+    /// Returns the file type.
+    pub fn kind(&self) -> &FileType {
+        &self.kind
+    }
+
+    /// Returns the imports.
+    pub fn imports(&self) -> &[String] {
+        &self.imports
+    }
+
+    /// Returns the class and object names.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// Returns the function names.
+    pub fn functions(&self) -> &[String] {
+        &self.functions
+    }
+
+    /// Returns the description.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns a reference to the parser.
+    pub fn parser(&self) -> &Parser {
+        &self.parser
+    }
+
+    /// Returns the source code.
+    pub fn code(&self) -> &str {
+        self.parser.code()
+    }
+
+    /// Executes a tree-sitter query on this file.
+    pub fn query(&self, q: &str) -> Result<Vec<Dict>> {
+        self.parser.query(q)
+    }
+}
@@ -0,0 +1,17 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+//! Kotlin-specific grading utilities.
+
+/// Diff-based grading utilities.
+pub mod diff;
+/// Tree-sitter query grading components.
+pub mod query;
+
+pub use diff::DiffGrader;
+pub use query::QueryGrader;
+
+pub use crate::{
+    java::grade::{DiffCase, Grade, GradeResult, Reason, ReasonTarget},
+    python::grade::{Query, QueryConstraint},
+};
@@ -0,0 +1,235 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+//! Tree-sitter query grading for Kotlin.
+
+use anyhow::{Result, bail};
+use bon::Builder;
+
+use super::{Grade, GradeResult, Reason, ReasonTarget};
+use crate::{
+    java::grade::timed,
+    kotlin::Project,
+    python::grade::query::{Query, QueryConstraint, QueryError},
+};
+
+/// A grader that uses tree-sitter queries to validate code structure.
+#[derive(Default, Clone, Builder)]
+#[builder(on(String, into))]
+pub struct QueryGrader {
+    /// The project being graded.
+    #[builder(getter)]
+    project:    Project,
+    /// Name of the file to query.
+    #[builder(getter)]
+    file:       String,
+    /// Queries to run.
+    #[builder(default)]
+    #[builder(getter)]
+    queries:    Vec<Query>,
+    /// Constraint to apply.
+    #[builder(default)]
+    #[builder(getter)]
+    constraint: QueryConstraint,
+    /// Reason to report on failure.
+    #[builder(default)]
+    #[builder(getter)]
+    reason:     String,
+    /// Requirement name.
+    #[builder(getter)]
+    req_name:   String,
+    /// Total points available.
+    #[builder(getter)]
+    out_of:     f64,
+}
+
+impl QueryGrader {
+    /// Adds a query to run.
+    pub fn query(mut self, q: String) -> Result<Self, QueryError> {
+        if self.file.is_empty() {
+            return Err(QueryError::NoFileSelected);
+        }
+        self.queries.push(Query::new().set_query(q));
+        Ok(self)
+    }
+
+    /// Sets the capture for the last query.
+    pub fn capture(mut self, c: String) -> Result<Self, QueryError> {
+        if self.queries.is_empty() {
+            return Err(QueryError::NoQueriesAdded);
+        }
+        if let Some(last) = self.queries.last_mut() {
+            *last = std::mem::take(last).set_capture(c);
+        }
+        Ok(self)
+    }
+
+    /// Adds a filter to the last query.
+    pub fn filter<F>(mut self, f: F) -> Result<Self, QueryError>
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        if self.queries.is_empty() {
+            return Err(QueryError::NoQueriesAdded);
+        }
+        if let Some(last) = self.queries.last_mut() {
+            *last = std::mem::take(last).set_filter_fn(f);
+        }
+        Ok(self)
+    }
+
+    /// Convenience: query for a function with a specific name.
+    pub fn function_with_name(mut self, name: String) -> Self {
+        let query =
+            format!(r#"(function_declaration (simple_identifier) @name (#eq? @name "{}"))"#, name);
+        self.queries.push(
+            Query::new()
+                .set_query(query)
+                .set_capture("name".to_string()),
+        );
+        self
+    }
+
+    /// Convenience: query for a class with a specific name.
+    pub fn class_with_name(mut self, name: String) -> Self {
+        let query =
+            format!(r#"(class_declaration (type_identifier) @name (#eq? @name "{}"))"#, name);
+        self.queries.push(
+            Query::new()
+                .set_query(query)
+                .set_capture("name".to_string()),
+        );
+        self
+    }
+
+    /// Convenience: query for any function declaration.
+    pub fn has_function(mut self) -> Self {
+        let query = "(function_declaration (simple_identifier) @name)".to_string();
+        self.queries.push(
+            Query::new()
+                .set_query(query)
+                .set_capture("name".to_string()),
+        );
+        self
+    }
+
+    /// Convenience: query for any class declaration.
+    pub fn has_class(mut self) -> Self {
+        let query = "(class_declaration (type_identifier) @name)".to_string();
+        self.queries.push(
+            Query::new()
+                .set_query(query)
+                .set_capture("name".to_string()),
+        );
+        self
+    }
+
+    /// Convenience: check for for loop usage.
+    pub fn uses_for_loop(mut self) -> Self {
+        let query = "(for_statement) @loop".to_string();
+        self.queries.push(
+            Query::new()
+                .set_query(query)
+                .set_capture("loop".to_string()),
+        );
+        self
+    }
+
+    /// Convenience: check for while loop usage.
+    pub fn uses_while_loop(mut self) -> Self {
+        let query = "(while_statement) @loop".to_string();
+        self.queries.push(
+            Query::new()
+                .set_query(query)
+                .set_capture("loop".to_string()),
+        );
+        self
+    }
+
+    /// Convenience: check for `when` expression usage.
+    pub fn uses_when(mut self) -> Self {
+        let query = "(when_expression) @when".to_string();
+        self.queries.push(
+            Query::new()
+                .set_query(query)
+                .set_capture("when".to_string()),
+        );
+        self
+    }
+
+    /// Builds and runs the grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(self.grade_by_query()).await
+    }
+
+    /// Performs the query grading.
+    async fn grade_by_query(self) -> Result<GradeResult> {
+        if self.queries.is_empty() {
+            bail!("QueryGrader requires at least one query");
+        }
+
+        let file = self.project.identify(&self.file)?;
+        let mut all_passed = true;
+        let mut failures = Reason::new();
+
+        for (idx, query) in self.queries.iter().enumerate() {
+            let query_str = query.query()?;
+            let results = file.query(&query_str)?;
+
+            // Apply filter if present
+            let filtered_results: Vec<_> = if let Some(filter) = query.filter() {
+                let capture_name = query.capture();
+                results
+                    .into_iter()
+                    .filter(|r| {
+                        if let Some(val) = r.get(&capture_name) {
+                            filter(val)
+                        } else {
+                            true
+                        }
+                    })
+                    .collect()
+            } else {
+                results
+            };
+
+            let match_count = filtered_results.len();
+            let passed = match &self.constraint {
+                QueryConstraint::MustMatchAtLeastOnce => match_count >= 1,
+                QueryConstraint::MustMatchExactlyNTimes(n) => match_count == *n,
+                QueryConstraint::MustNotMatch => match_count == 0,
+            };
+
+            if !passed {
+                all_passed = false;
+                let constraint_desc = match &self.constraint {
+                    QueryConstraint::MustMatchAtLeastOnce => "at least 1 match".to_string(),
+                    QueryConstraint::MustMatchExactlyNTimes(n) => format!("exactly {} matches", n),
+                    QueryConstraint::MustNotMatch => "no matches".to_string(),
+                };
+                failures.push(format!(
+                    "Query {}: Expected {}, found {} matches",
+                    idx + 1,
+                    constraint_desc,
+                    match_count
+                ));
+            }
+        }
+
+        let grade = if all_passed { self.out_of } else { 0.0 };
+        let (reason, details) = if all_passed {
+            ("All queries passed".to_string(), None)
+        } else if !self.reason.is_empty() {
+            (self.reason.clone(), None)
+        } else {
+            (failures.render(ReasonTarget::Terminal), Some(failures))
+        };
+
+        Ok(GradeResult::builder()
+            .requirement(self.req_name)
+            .grade(Grade::new(grade, self.out_of))
+            .reason(reason)
+            .maybe_details(details)
+            .build())
+    }
+}
@@ -0,0 +1,28 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+//! Kotlin project support for the umm autograder.
+//!
+//! A deliberately small surface mirroring the Java and Python modules: file
+//! discovery, tree-sitter parsing, and the query and diff graders. Kotlin
+//! sources are compiled with `kotlinc` and run with the `kotlin` launcher.
+
+/// File type definitions and helpers.
+pub mod file;
+/// Kotlin-specific grading utilities.
+pub mod grade;
+/// Tree-sitter parser wrapper.
+pub mod parser;
+/// Project path configuration helpers.
+pub mod paths;
+/// Kotlin project discovery and operations.
+pub mod project;
+/// Tree-sitter query strings used by Kotlin analysis.
+pub mod queries;
+/// Kotlin toolchain helpers.
+pub mod util;
+
+pub use file::{File, FileType, KotlinFileError};
+pub use parser::Parser;
+pub use paths::ProjectPaths;
+pub use project::Project;
@@ -0,0 +1,93 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+//! Kotlin-specific workspace path configuration.
+
+use std::path::{Path, PathBuf};
+
+use bon::builder;
+use serde::{Deserialize, Serialize};
+
+/// Represents standard workspace paths for a Kotlin project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectPaths {
+    /// Root directory of the project workspace.
+    root_dir:   PathBuf,
+    /// `src/` directory containing production sources.
+    source_dir: PathBuf,
+    /// `target/` build output directory for `kotlinc`.
+    build_dir:  PathBuf,
+    /// `.umm/` metadata directory maintained by the tool.
+    umm_dir:    PathBuf,
+}
+
+impl ProjectPaths {
+    /// Creates a new set of workspace paths rooted at `root_dir`.
+    pub fn new(root_dir: PathBuf) -> Self {
+        Self::build_with_defaults(root_dir, None, None, None)
+    }
+
+    /// Returns the platform specific separator character for classpaths.
+    pub fn separator(&self) -> &'static str {
+        if cfg!(windows) { ";" } else { ":" }
+    }
+
+    /// Root directory for the project.
+    pub fn root_dir(&self) -> &Path {
+        self.root_dir.as_path()
+    }
+
+    /// Source directory for the project.
+    pub fn source_dir(&self) -> &Path {
+        self.source_dir.as_path()
+    }
+
+    /// Build directory for the project.
+    pub fn build_dir(&self) -> &Path {
+        self.build_dir.as_path()
+    }
+
+    /// Directory for umm artefacts.
+    pub fn umm_dir(&self) -> &Path {
+        self.umm_dir.as_path()
+    }
+}
+
+impl Default for ProjectPaths {
+    fn default() -> Self {
+        Self::new(PathBuf::from("."))
+    }
+}
+
+impl ProjectPaths {
+    /// Centralized constructor that applies standard defaults when overrides
+    /// are absent.
+    fn build_with_defaults(
+        root_dir: PathBuf,
+        source_dir: Option<PathBuf>,
+        build_dir: Option<PathBuf>,
+        umm_dir: Option<PathBuf>,
+    ) -> Self {
+        let source_dir = source_dir.unwrap_or_else(|| root_dir.join("src"));
+        let build_dir = build_dir.unwrap_or_else(|| root_dir.join("target"));
+        let umm_dir = umm_dir.unwrap_or_else(|| root_dir.join(".umm"));
+
+        Self {
+            root_dir,
+            source_dir,
+            build_dir,
+            umm_dir,
+        }
+    }
+}
+
+/// Builder-friendly constructor for `ProjectPaths` with optional overrides.
+#[builder(finish_fn = build)]
+pub fn project_paths(
+    #[builder(into)] root_dir: PathBuf,
+    source_dir: Option<PathBuf>,
+    build_dir: Option<PathBuf>,
+    umm_dir: Option<PathBuf>,
+) -> ProjectPaths {
+    ProjectPaths::build_with_defaults(root_dir, source_dir, build_dir, umm_dir)
+}
@@ -0,0 +1,95 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+//! Kotlin project discovery and management.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use super::{file::File, paths::ProjectPaths};
+use crate::util::find_files;
+
+/// Represents a Kotlin project with discovered files and metadata.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Project {
+    /// Collection of Kotlin files in this project.
+    files: Vec<File>,
+    /// Workspace paths associated with this project.
+    paths: ProjectPaths,
+}
+
+impl Project {
+    /// Creates a new project by discovering files in the current directory.
+    pub fn new() -> Result<Self> {
+        Self::from_paths(ProjectPaths::default())
+    }
+
+    /// Creates a new project from a root directory.
+    pub fn from_root(root: impl Into<PathBuf>) -> Result<Self> {
+        Self::from_paths(ProjectPaths::new(root.into()))
+    }
+
+    /// Creates a new project from explicit paths.
+    pub fn from_paths(paths: ProjectPaths) -> Result<Self> {
+        let found = find_files("kt", 15, paths.root_dir()).with_context(|| {
+            format!("Could not discover Kotlin files under {}", paths.root_dir().display())
+        })?;
+
+        let mut files = Vec::new();
+        for path in found {
+            let display_path = path.display().to_string();
+            match File::new(&path, paths.clone()) {
+                Ok(file) => files.push(file),
+                Err(e) => {
+                    tracing::warn!("Skipping file {} due to error: {}", display_path, e);
+                }
+            }
+        }
+
+        Ok(Self { files, paths })
+    }
+
+    /// Identifies a file by its simple name (`Main`), file name (`Main.kt`),
+    /// JVM class (`MainKt`), or path.
+    pub fn identify(&self, name: &str) -> Result<File> {
+        let stripped = name.strip_suffix(".kt").unwrap_or(name);
+        let by_path = Path::new(name);
+
+        self.files
+            .iter()
+            .find(|file| {
+                file.name() == stripped
+                    || file.file_name() == name
+                    || file.jvm_class() == name
+                    || file.path() == by_path
+            })
+            .cloned()
+            .ok_or_else(|| anyhow!("Could not find {} in the project", name))
+    }
+
+    /// Returns true if the project contains a file matching `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.identify(name).is_ok()
+    }
+
+    /// Returns the workspace paths.
+    pub fn paths(&self) -> &ProjectPaths {
+        &self.paths
+    }
+
+    /// Returns the discovered files.
+    pub fn files(&self) -> &[File] {
+        &self.files
+    }
+
+    /// Returns a short, line-per-file description of the project.
+    pub fn describe(&self) -> String {
+        self.files
+            .iter()
+            .map(File::description)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
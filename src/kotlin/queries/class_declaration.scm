@@ -0,0 +1,6 @@
+[
+  (class_declaration
+    (type_identifier) @name)
+  (object_declaration
+    (type_identifier) @name)
+]
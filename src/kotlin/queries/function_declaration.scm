@@ -0,0 +1,2 @@
+(function_declaration
+  (simple_identifier) @name)
@@ -0,0 +1,2 @@
+(import_header
+  (identifier) @path)
@@ -0,0 +1,4 @@
+(source_file
+  (function_declaration
+    (simple_identifier) @name
+    (#eq? @name "main")))
@@ -0,0 +1,21 @@
+//! Tree-sitter query strings used by the Kotlin analyzers and graders.
+
+/// Tree-sitter query that returns class and object declarations.
+/// * `name`: class or object name
+pub const CLASS_DECLARATION_QUERY: &str = include_str!("class_declaration.scm");
+
+/// Tree-sitter query that returns function declarations, including methods.
+/// * `name`: function name
+pub const FUNCTION_DECLARATION_QUERY: &str = include_str!("function_declaration.scm");
+
+/// Tree-sitter query that matches a top-level `fun main`.
+/// * `name`: always `main`
+pub const MAIN_FUNCTION_QUERY: &str = include_str!("main_function.scm");
+
+/// Tree-sitter query that returns import headers.
+/// * `path`: dotted import path
+pub const IMPORT_QUERY: &str = include_str!("import.scm");
+
+/// Tree-sitter query that returns the package header.
+/// * `name`: dotted package name
+pub const PACKAGE_QUERY: &str = include_str!("package.scm");
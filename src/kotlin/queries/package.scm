@@ -0,0 +1,2 @@
+(package_header
+  (identifier) @name)
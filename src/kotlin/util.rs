@@ -0,0 +1,23 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+//! Kotlin toolchain helpers.
+
+use std::{ffi::OsString, path::PathBuf};
+
+use anyhow::{Context, Result};
+use which::which;
+
+/// Finds and returns the path to the kotlinc binary.
+pub fn kotlinc_path() -> Result<OsString> {
+    which("kotlinc")
+        .map(PathBuf::into_os_string)
+        .context("Cannot find a Kotlin compiler on path (kotlinc)")
+}
+
+/// Finds and returns the path to the kotlin launcher.
+pub fn kotlin_path() -> Result<OsString> {
+    which("kotlin")
+        .map(PathBuf::into_os_string)
+        .context("Cannot find a Kotlin runtime on path (kotlin)")
+}
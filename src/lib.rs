@@ -9,12 +9,20 @@
 #![feature(stmt_expr_attributes)]
 #![feature(iter_collect_into)]
 
+/// Zip archive helpers for grading submissions handed in as archives.
+pub mod archive;
 /// Shared, runtime-initialized configuration (prompts, services, env)
 pub mod config;
+/// Git worktree helpers for grading a specific commit or branch.
+pub mod git;
 // For all things related to grading see `java::grade` module.
 /// For discovering Java projects, analyzing them, and generating/executing
 /// build tasks
 pub mod java;
+/// For discovering Kotlin projects, analyzing them, and grading
+pub mod kotlin;
+/// For grading directories that mix Java and Python sources
+pub mod polyglot;
 /// Async process helpers shared across modules.
 pub mod process;
 /// For discovering Python projects, analyzing them, and grading
@@ -27,6 +35,8 @@ pub mod scripting;
 pub mod types;
 /// Utility functions for convenience
 pub mod util;
+/// Polling file watcher behind `--watch`.
+pub mod watch;
 
 /// Defined for convenience
 type Dict = std::collections::HashMap<String, String>;
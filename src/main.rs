@@ -14,20 +14,28 @@
 //!
 //! Once you are done, just type `cargo install --git=https://github.com/DhruvDh/umm.git` and it should compile and install it on your system.
 
-use anyhow::Result;
+use std::{ffi::OsString, ops::ControlFlow, path::PathBuf, time::Duration};
+
+use anyhow::{Context, Result, bail};
 use bpaf::*;
 use dotenvy::dotenv;
 use self_update::cargo_crate_version;
 use tracing::{Level, metadata::LevelFilter};
 use tracing_subscriber::{fmt, prelude::*, util::SubscriberInitExt};
 use umm::{
-    java::Project as JavaProject,
+    config,
+    git::Worktree,
+    java::{
+        FileType, JavaFileError, Project as JavaProject, ProjectPaths as JavaProjectPaths,
+        grade::{ProgressWriter, diagnostics_from_output, sarif_json},
+    },
     process,
     python::{
         Project as PythonProject,
         util::{black_format_command, ruff_lint_command},
     },
-    scripting,
+    scripting::{self, RunContext},
+    watch::Watcher,
 };
 
 /// Updates binary based on github releases
@@ -51,18 +59,26 @@ fn update() -> Result<()> {
 /// Java-specific subcommands.
 #[derive(Debug, Clone)]
 enum JavaCmd {
-    /// Run a file
-    Run(String),
-    /// Check a file
-    Check(String),
-    /// Test a file
-    Test(String, Vec<String>),
-    /// Check a file's documentation
-    DocCheck(String),
-    /// Grade a file
-    Grade(String),
+    /// Run a file, optionally feeding a file's contents to stdin
+    Run(String, Option<PathBuf>),
+    /// Check a file, optionally printing diagnostics as SARIF and re-checking
+    /// on every change
+    Check(String, bool, bool),
+    /// Test a file, optionally re-running only the tests that failed last
+    /// time and re-testing on every change
+    Test(String, Vec<String>, bool, bool),
+    /// Check a file's documentation, optionally printing diagnostics as SARIF
+    DocCheck(String, bool),
+    /// Grade a file, optionally at a git commit/branch, stopping at the
+    /// first failing requirement, streaming progress as JSON Lines, and
+    /// exporting feedback prompts to a directory instead of the database
+    Grade(String, Option<String>, bool, Option<PathBuf>, Option<PathBuf>),
     /// Print information about the project
     Info,
+    /// Print an outline of the project, optionally as Markdown
+    Outline(bool),
+    /// List the test methods each test file declares, optionally as JSON
+    ListTests(bool),
 }
 
 /// Python-specific subcommands.
@@ -78,8 +94,10 @@ enum PythonCmd {
     Lint(Vec<String>),
     /// Format using black (targets optional)
     Format(Vec<String>),
-    /// Grade using a Rune script
-    Grade(String),
+    /// Grade using a Rune script, optionally stopping at the first failing
+    /// requirement, streaming progress as JSON Lines, and exporting feedback
+    /// prompts to a directory instead of the database
+    Grade(String, bool, Option<PathBuf>, Option<PathBuf>),
     /// Print information about the project
     Info,
 }
@@ -109,6 +127,80 @@ fn options() -> Cmd {
         positional("FILENAME").help("Name of file")
     }
 
+    /// parses the flag that switches diagnostics output to SARIF
+    fn sarif() -> impl Parser<bool> {
+        long("sarif")
+            .help("Print diagnostics as a SARIF 2.1.0 log")
+            .switch()
+    }
+
+    /// parses the file whose contents are piped to the program's stdin
+    fn stdin_file() -> impl Parser<Option<PathBuf>> {
+        long("stdin-file")
+            .help("Feed the contents of PATH to the program's standard input")
+            .argument::<PathBuf>("PATH")
+            .optional()
+    }
+
+    /// parses the git ref to grade instead of the working tree
+    fn git_ref() -> impl Parser<Option<String>> {
+        long("ref")
+            .help("Grade the project as of this git commit, branch, or tag")
+            .argument::<String>("REF")
+            .optional()
+    }
+
+    /// parses the flag that stops grading at the first failing requirement
+    fn fail_fast() -> impl Parser<bool> {
+        long("fail-fast")
+            .help("Stop grading at the first requirement that loses points")
+            .switch()
+    }
+
+    /// parses the file grading progress is streamed to
+    fn progress() -> impl Parser<Option<PathBuf>> {
+        long("progress")
+            .help("Append one JSON line per graded requirement and submission to FILE")
+            .argument::<PathBuf>("FILE")
+            .optional()
+    }
+
+    /// parses the directory feedback prompts are exported to
+    fn export_prompts() -> impl Parser<Option<PathBuf>> {
+        long("export-prompts")
+            .help("Write feedback prompts as JSON files in DIR instead of posting them")
+            .argument::<PathBuf>("DIR")
+            .optional()
+    }
+
+    /// parses the flag that re-runs only the previously failing tests
+    fn failed_only() -> impl Parser<bool> {
+        long("failed-only")
+            .help("Re-run only the tests that failed in the last run of this file")
+            .switch()
+    }
+
+    /// parses the flag that re-runs the command whenever a `.java` file changes
+    fn watch() -> impl Parser<bool> {
+        long("watch")
+            .help("Re-run whenever a .java file in the project changes")
+            .switch()
+    }
+
+    /// parses the flag that switches the outline to Markdown
+    fn markdown() -> impl Parser<bool> {
+        long("markdown")
+            .help("Render the outline as Markdown with a heading per file")
+            .switch()
+    }
+
+    /// parses the flag that switches the test listing to JSON
+    fn json() -> impl Parser<bool> {
+        long("json")
+            .help("Print the tests as a JSON object keyed by test file")
+            .switch()
+    }
+
     /// parses zero or more file/dir targets
     fn fs() -> impl Parser<Vec<String>> {
         f().many()
@@ -120,43 +212,56 @@ fn options() -> Cmd {
     }
 
     // Java commands
-    let java_run = construct!(JavaCmd::Run(f()))
+    let java_run = construct!(JavaCmd::Run(f(), stdin_file()))
         .to_options()
         .command("run")
         .help("Run a java file with a main method");
 
-    let java_check = construct!(JavaCmd::Check(f()))
+    let java_check = construct!(JavaCmd::Check(f(), sarif(), watch()))
         .to_options()
         .command("check")
         .help("Check for syntax errors");
 
-    let java_test = construct!(JavaCmd::Test(f(), t()))
+    let java_test = construct!(JavaCmd::Test(f(), t(), failed_only(), watch()))
         .to_options()
         .command("test")
         .help("Run JUnit tests");
 
-    let java_doc_check = construct!(JavaCmd::DocCheck(f()))
+    let java_doc_check = construct!(JavaCmd::DocCheck(f(), sarif()))
         .to_options()
         .command("doc-check")
         .help("Check a file for missing javadoc");
 
-    let java_grade = construct!(JavaCmd::Grade(g()))
-        .to_options()
-        .command("grade")
-        .help("Grade your work");
+    let java_grade =
+        construct!(JavaCmd::Grade(g(), git_ref(), fail_fast(), progress(), export_prompts()))
+            .to_options()
+            .command("grade")
+            .help("Grade your work");
 
     let java_info = pure(JavaCmd::Info)
         .to_options()
         .command("info")
         .help("Prints a JSON description of the project as parsed");
 
+    let java_outline = construct!(JavaCmd::Outline(markdown()))
+        .to_options()
+        .command("outline")
+        .help("Print an outline of the project's files, fields, and methods");
+
+    let java_list_tests = construct!(JavaCmd::ListTests(json()))
+        .to_options()
+        .command("list-tests")
+        .help("List the test methods found in each test file");
+
     let java = construct!([
         java_run,
         java_check,
         java_test,
         java_doc_check,
         java_grade,
-        java_info
+        java_info,
+        java_outline,
+        java_list_tests
     ])
     .to_options()
     .command("java")
@@ -189,7 +294,7 @@ fn options() -> Cmd {
         .command("format")
         .help("Run black format (defaults to project root when no paths are given)");
 
-    let python_grade = construct!(PythonCmd::Grade(g()))
+    let python_grade = construct!(PythonCmd::Grade(g(), fail_fast(), progress(), export_prompts()))
         .to_options()
         .command("grade")
         .help("Grade your work using a Rune script");
@@ -223,6 +328,54 @@ fn options() -> Cmd {
     cmd.to_options().descr("Build tool for novices").run()
 }
 
+/// Makes `run` stream grading progress to `path`, labelling the submission
+/// with the current directory's name.
+fn stream_progress(run: RunContext, path: Option<PathBuf>) -> Result<RunContext> {
+    let Some(path) = path else {
+        return Ok(run);
+    };
+    let submission = std::env::current_dir()?
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned());
+    Ok(run.with_progress(ProgressWriter::append(&path)?, submission))
+}
+
+/// Exports feedback prompts to `dir`, resolved against the current directory,
+/// instead of posting them.
+fn export_prompts_to(dir: Option<PathBuf>) -> Result<()> {
+    if let Some(dir) = dir {
+        config::set_prompt_export_dir(Some(std::env::current_dir()?.join(dir)));
+    }
+    Ok(())
+}
+
+/// Re-runs the current command, minus `--watch`, in a child process whenever a
+/// `.java` file under the project root changes. Runs until interrupted.
+async fn watch_java() -> Result<()> {
+    let exe = std::env::current_exe().context("Could not locate the umm executable")?;
+    let args: Vec<OsString> = std::env::args_os()
+        .skip(1)
+        .filter(|arg| arg != "--watch")
+        .collect();
+    let root = JavaProject::new()?.paths().root_dir().to_path_buf();
+    let (exe, args) = (&exe, &args);
+
+    Watcher::new(root, "java", Duration::from_millis(500))?
+        .run(move || async move {
+            let status = tokio::process::Command::new(exe)
+                .args(args)
+                .status()
+                .await
+                .context("Could not re-run umm")?;
+            if !status.success() {
+                eprintln!("Exited with {status}.");
+            }
+            eprintln!("Watching for changes to .java files; press Ctrl+C to stop.");
+            Ok(ControlFlow::Continue(()))
+        })
+        .await
+}
+
 #[tokio::main]
 async fn main() {
     if let Err(err) = run_cli().await {
@@ -249,30 +402,68 @@ async fn run_cli() -> Result<()> {
 
     match cmd {
         Cmd::Java(java_cmd) => match java_cmd {
-            JavaCmd::Run(f) => {
-                let file = JavaProject::new()?.identify(f.as_str())?;
-                match file.run(None).await {
+            JavaCmd::Run(f, stdin_file) => {
+                let project = JavaProject::new()?;
+                let file = project.identify(f.as_str())?;
+                let input = match stdin_file {
+                    Some(path) => {
+                        let contents = std::fs::read_to_string(&path).with_context(|| {
+                            format!("Could not read stdin file {}", path.display())
+                        })?;
+                        // `File::run` terminates the input with its own newline.
+                        let trimmed = contents.strip_suffix('\n').unwrap_or(&contents);
+                        Some(trimmed.strip_suffix('\r').unwrap_or(trimmed).to_string())
+                    }
+                    None => None,
+                };
+                match file.run(input).await {
                     Ok(out) => println!("{out}"),
+                    Err(e @ JavaFileError::NoMainMethod { .. }) => {
+                        eprintln!("{e}");
+                        let runnable: Vec<String> = project
+                            .files()
+                            .iter()
+                            .filter(|file| *file.kind() == FileType::ClassWithMain)
+                            .map(|file| file.proper_name())
+                            .collect();
+                        if !runnable.is_empty() {
+                            eprintln!("Files with a main method: {}", runnable.join(", "));
+                            eprintln!("Try `umm java run {}`", runnable[0]);
+                        }
+                        std::process::exit(1);
+                    }
                     Err(e) => {
                         eprintln!("{:#?}", e);
                         std::process::exit(1);
                     }
                 }
             }
-            JavaCmd::Check(f) => {
-                let file = JavaProject::new()?.identify(f.as_str())?;
-                match file.check().await {
+            JavaCmd::Check(_, _, true) | JavaCmd::Test(.., true) => watch_java().await?,
+            JavaCmd::Check(f, sarif, false) => {
+                match JavaProject::new()?.check_file(f.as_str()).await {
+                    Ok(out) if sarif => {
+                        println!("{}", sarif_json("javac", &diagnostics_from_output(&out))?)
+                    }
                     Ok(out) => println!("{out}"),
+                    Err(JavaFileError::DuringCompilation { diags, .. }) if sarif => {
+                        println!("{}", sarif_json("javac", &diags)?);
+                        std::process::exit(1);
+                    }
                     Err(e) => {
                         eprintln!("{:#?}", e);
                         std::process::exit(1);
                     }
                 }
             }
-            JavaCmd::Test(f, t) => {
+            JavaCmd::Test(f, t, failed_only, false) => {
+                if failed_only && !t.is_empty() {
+                    bail!("--failed-only cannot be combined with explicit test names");
+                }
                 let project = JavaProject::new()?;
                 let file = project.identify(f.as_str())?;
-                let result = if t.is_empty() {
+                let result = if failed_only {
+                    file.test_failed_only(Some(&project)).await
+                } else if t.is_empty() {
                     file.test(Vec::<&str>::new(), Some(&project)).await
                 } else {
                     let test_refs: Vec<&str> = t.iter().map(String::as_str).collect();
@@ -287,15 +478,52 @@ async fn run_cli() -> Result<()> {
                     }
                 }
             }
-            JavaCmd::DocCheck(f) => {
+            JavaCmd::DocCheck(f, sarif) => {
                 let file = JavaProject::new()?.identify(f.as_str())?;
                 let out = file.doc_check().await?;
-                println!("{out}");
+                if sarif {
+                    println!("{}", sarif_json("javac", &diagnostics_from_output(&out))?);
+                } else {
+                    println!("{out}");
+                }
+            }
+            JavaCmd::Grade(g, None, fail_fast, progress, export) => {
+                config::set_fail_fast(fail_fast);
+                let run = stream_progress(RunContext::default(), progress)?;
+                export_prompts_to(export)?;
+                scripting::run_file(&g, run).await?;
             }
-            JavaCmd::Grade(g) => {
-                scripting::run_file(&g).await?;
+            JavaCmd::Grade(g, Some(git_ref), fail_fast, progress, export) => {
+                config::set_fail_fast(fail_fast);
+                export_prompts_to(export)?;
+                let paths = JavaProjectPaths::default();
+                let worktree = Worktree::checkout(paths.root_dir(), &git_ref).await?;
+
+                // Scripts discover the project through these paths; the
+                // working directory stays where it is.
+                let run = RunContext::new(paths.rebased_onto(&worktree.project_dir()));
+                let run = stream_progress(run, progress)?;
+                scripting::run_file(&g, run).await?;
             }
             JavaCmd::Info => JavaProject::new()?.info()?,
+            JavaCmd::Outline(markdown) => {
+                let project = JavaProject::new()?;
+                if markdown {
+                    println!("{}", project.describe_markdown());
+                } else {
+                    println!("{}", project.describe());
+                }
+            }
+            JavaCmd::ListTests(json) => {
+                let tests = JavaProject::new()?.test_methods();
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&tests)?);
+                } else {
+                    for test in tests.values().flatten() {
+                        println!("{test}");
+                    }
+                }
+            }
         },
         Cmd::Python(python_cmd) => match python_cmd {
             PythonCmd::Run(f) => {
@@ -390,8 +618,11 @@ async fn run_cli() -> Result<()> {
                     std::process::exit(collected.status.code().unwrap_or(1));
                 }
             }
-            PythonCmd::Grade(g) => {
-                scripting::run_file(&g).await?;
+            PythonCmd::Grade(g, fail_fast, progress, export) => {
+                config::set_fail_fast(fail_fast);
+                let run = stream_progress(RunContext::default(), progress)?;
+                export_prompts_to(export)?;
+                scripting::run_file(&g, run).await?;
             }
             PythonCmd::Info => {
                 PythonProject::new()?.info();
@@ -0,0 +1,85 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use crate::{java, python};
+
+/// A directory holding sources in more than one language, such as a Java
+/// program alongside a Python analysis script. Each language is discovered
+/// with its own project type so language-specific graders work unchanged;
+/// both produce the same [`GradeResult`](crate::java::grade::GradeResult), so
+/// their results can be reported together.
+#[derive(Clone)]
+pub struct Project {
+    /// Directory both projects were discovered from.
+    root:   PathBuf,
+    /// The Java project, if any `.java` files were found.
+    java:   Option<java::Project>,
+    /// The Python project, if any `.py` files were found.
+    python: Option<python::Project>,
+}
+
+impl Project {
+    /// Discovers the projects in the current directory.
+    pub fn new() -> Result<Self> {
+        let cwd = std::env::current_dir().context("Failed to get current directory")?;
+        Self::from_root(cwd)
+    }
+
+    /// Discovers the Java and Python projects under `root`, using each
+    /// language's default layout. Fails if neither language has any files.
+    pub fn from_root(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+
+        let java = java::Project::from_paths(java::ProjectPaths::from_parts(
+            root.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ))
+        .context("Failed to discover the Java project")?;
+        let python = python::Project::from_root(root.clone())
+            .context("Failed to discover the Python project")?;
+
+        let java = (!java.files().is_empty()).then_some(java);
+        let python = (python.file_count() > 0).then_some(python);
+        if java.is_none() && python.is_none() {
+            bail!("No Java or Python files found under {}", root.display());
+        }
+
+        Ok(Self { root, java, python })
+    }
+
+    /// Directory the projects were discovered from.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The Java project, if the directory has Java files.
+    pub fn java(&self) -> Option<&java::Project> {
+        self.java.as_ref()
+    }
+
+    /// The Python project, if the directory has Python files.
+    pub fn python(&self) -> Option<&python::Project> {
+        self.python.as_ref()
+    }
+
+    /// Names of the languages found, e.g. `["java", "python"]`.
+    pub fn languages(&self) -> Vec<&'static str> {
+        let mut languages = Vec::new();
+        if self.java.is_some() {
+            languages.push("java");
+        }
+        if self.python.is_some() {
+            languages.push("python");
+        }
+        languages
+    }
+}
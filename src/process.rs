@@ -5,6 +5,7 @@ use std::{
     ffi::{OsStr, OsString},
     path::Path,
     process::Stdio,
+    sync::{LazyLock, Mutex},
     time::Duration,
 };
 
@@ -12,9 +13,97 @@ use anyhow::{Context, Result};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt, BufReader},
     process::{Child, Command},
-    time::timeout,
+    sync::Notify,
+    time::{sleep, timeout},
 };
 
+use crate::config;
+
+/// Bounds how many child processes run at once across every grader, so
+/// concurrent graders cannot start more JVMs than the machine can handle.
+struct ProcessGovernor {
+    /// Processes currently running and the most allowed at once.
+    slots: Mutex<Slots>,
+    /// Wakes callers waiting for a slot when one frees up or the limit grows.
+    freed: Notify,
+}
+
+/// Slot accounting for [`ProcessGovernor`].
+struct Slots {
+    /// Processes currently holding a slot.
+    active: usize,
+    /// Maximum number of processes allowed to hold a slot.
+    limit:  usize,
+}
+
+/// Process-wide governor shared by every [`run_collect`] call.
+static GOVERNOR: LazyLock<ProcessGovernor> = LazyLock::new(|| ProcessGovernor {
+    slots: Mutex::new(Slots {
+        active: 0,
+        limit:  config::max_processes(),
+    }),
+    freed: Notify::new(),
+});
+
+/// Holds a governor slot until dropped.
+struct ProcessSlot;
+
+impl ProcessGovernor {
+    /// Waits until fewer than `limit` processes are running, then takes a slot.
+    async fn acquire(&self) -> ProcessSlot {
+        loop {
+            let freed = self.freed.notified();
+            tokio::pin!(freed);
+            freed.as_mut().enable();
+            {
+                let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+                if slots.active < slots.limit {
+                    slots.active += 1;
+                    return ProcessSlot;
+                }
+            }
+            freed.await;
+        }
+    }
+}
+
+impl Drop for ProcessSlot {
+    fn drop(&mut self) {
+        let mut slots = GOVERNOR.slots.lock().unwrap_or_else(|e| e.into_inner());
+        slots.active = slots.active.saturating_sub(1);
+        drop(slots);
+        GOVERNOR.freed.notify_waiters();
+    }
+}
+
+/// Sets how many child processes may run at once across all graders.
+/// Values below 1 are treated as 1. Processes already running are not
+/// interrupted; a lower limit applies as they finish.
+pub fn set_max_processes(limit: usize) {
+    let mut slots = GOVERNOR.slots.lock().unwrap_or_else(|e| e.into_inner());
+    slots.limit = limit.max(1);
+    drop(slots);
+    GOVERNOR.freed.notify_waiters();
+}
+
+/// Returns how many child processes may run at once.
+pub fn max_processes() -> usize {
+    GOVERNOR
+        .slots
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .limit
+}
+
+/// Returns how many child processes started by [`run_collect`] are running.
+pub fn active_processes() -> usize {
+    GOVERNOR
+        .slots
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .active
+}
+
 /// Drop guard that terminates a spawned child process if callers forget to
 /// await it.
 struct ChildDropGuard(Option<Child>);
@@ -46,6 +135,40 @@ impl Drop for ChildDropGuard {
     }
 }
 
+/// Error returned by [`run_collect`] when a process outlives its deadline.
+/// The process (and, on Unix, its process group) has been killed; whatever
+/// it wrote before then is kept so callers can show where it got stuck.
+#[derive(Debug, thiserror::Error)]
+#[error("subprocess timed out after {}s", .limit.as_secs_f64())]
+pub struct TimedOut {
+    /// The deadline that was exceeded.
+    pub limit:  Duration,
+    /// Contents written to stdout before the process was killed.
+    pub stdout: Vec<u8>,
+    /// Contents written to stderr before the process was killed.
+    pub stderr: Vec<u8>,
+}
+
+/// How long to wait for a killed process's pipes to close before giving up
+/// on its partial output.
+const DRAIN_GRACE: Duration = Duration::from_secs(1);
+
+/// Kills `child` and, when it leads its own process group, every process it
+/// started, so helpers cannot keep its pipes open.
+fn kill_tree(child: &mut Child, grouped: bool) {
+    #[cfg(unix)]
+    if grouped && let Some(pid) = child.id() {
+        let _ = std::process::Command::new("kill")
+            .args(["-KILL", "--", &format!("-{pid}")])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+    #[cfg(not(unix))]
+    let _ = grouped;
+    let _ = child.start_kill();
+}
+
 /// Captured result of a finished subprocess.
 #[derive(Debug)]
 pub struct Collected {
@@ -66,9 +189,23 @@ pub enum StdinSource {
     Null,
     /// Write the provided bytes, then close stdin.
     Bytes(Vec<u8>),
+    /// Write each chunk after waiting `delay`, then close stdin. For programs
+    /// that prompt between reads and expect input to arrive as a person would
+    /// type it.
+    Paced {
+        /// Chunks written in order, e.g. one per input line.
+        chunks: Vec<Vec<u8>>,
+        /// Pause before each chunk.
+        delay:  Duration,
+    },
 }
 
 /// Spawns a command, optionally feeds stdin, and collects stdout/stderr.
+///
+/// Waits for a slot from the process-wide limit (see [`set_max_processes`])
+/// before spawning; the wait does not count towards `deadline`. A process
+/// still running at the deadline is killed and [`TimedOut`] is returned with
+/// its partial output.
 pub async fn run_collect(
     program: impl AsRef<OsStr>,
     args: &[OsString],
@@ -77,6 +214,8 @@ pub async fn run_collect(
     env: &[(OsString, OsString)],
     deadline: Option<Duration>,
 ) -> Result<Collected> {
+    let _slot = GOVERNOR.acquire().await;
+
     let mut cmd = Command::new(program);
     cmd.args(args).stdout(Stdio::piped()).stderr(Stdio::piped());
 
@@ -87,11 +226,20 @@ pub async fn run_collect(
         StdinSource::Null => {
             cmd.stdin(Stdio::null());
         }
-        StdinSource::Bytes(_) => {
+        StdinSource::Bytes(_) | StdinSource::Paced { .. } => {
             cmd.stdin(Stdio::piped());
         }
     }
 
+    // Give the child its own process group so a timeout can kill anything it
+    // spawned. Not when it shares our terminal's stdin: a background group
+    // reading from the terminal would be stopped.
+    let grouped = cfg!(unix) && !matches!(stdin, StdinSource::Inherit);
+    #[cfg(unix)]
+    if grouped {
+        cmd.process_group(0);
+    }
+
     if let Some(dir) = cwd {
         cmd.current_dir(dir);
     }
@@ -101,16 +249,25 @@ pub async fn run_collect(
 
     let mut guard = ChildDropGuard::new(cmd.spawn().context("failed to spawn process")?);
     let stdin_payload = match stdin {
-        StdinSource::Bytes(bytes) => Some(bytes),
+        StdinSource::Bytes(bytes) => Some((vec![bytes], Duration::ZERO)),
+        StdinSource::Paced { chunks, delay } => Some((chunks, delay)),
         StdinSource::Inherit | StdinSource::Null => None,
     };
 
-    if let Some(bytes) = stdin_payload
+    if let Some((chunks, delay)) = stdin_payload
         && let Some(mut handle) = guard.child_mut()?.stdin.take()
     {
         tokio::spawn(async move {
-            if !bytes.is_empty() {
-                let _ = handle.write_all(&bytes).await;
+            for chunk in chunks {
+                if !delay.is_zero() {
+                    sleep(delay).await;
+                }
+                if chunk.is_empty() {
+                    continue;
+                }
+                if handle.write_all(&chunk).await.is_err() || handle.flush().await.is_err() {
+                    break;
+                }
             }
             let _ = handle.shutdown().await;
         });
@@ -147,27 +304,40 @@ pub async fn run_collect(
         Ok::<Vec<u8>, anyhow::Error>(buf)
     });
 
-    let wait_future = async move {
-        let mut guard = guard;
-        let status = guard
-            .child_mut()?
-            .wait()
-            .await
-            .context("failed to wait on process")?;
-        let stdout = out_task.await.context("stdout task join error")??;
-        let stderr = err_task.await.context("stderr task join error")??;
+    let wait_future = guard.child_mut()?.wait();
+    let waited = match deadline {
+        Some(limit) => timeout(limit, wait_future).await.ok(),
+        None => Some(wait_future.await),
+    };
+
+    let Some(status) = waited else {
+        let limit = deadline.unwrap_or_default();
+        let child = guard.child_mut()?;
+        kill_tree(child, grouped);
+        let _ = child.wait().await;
         guard.disarm();
-        Ok(Collected {
-            status,
-            stdout,
-            stderr,
-        })
+
+        let drain = |task: tokio::task::JoinHandle<Result<Vec<u8>>>| async move {
+            match timeout(DRAIN_GRACE, task).await {
+                Ok(Ok(Ok(buf))) => buf,
+                _ => Vec::new(),
+            }
+        };
+        return Err(TimedOut {
+            limit,
+            stdout: drain(out_task).await,
+            stderr: drain(err_task).await,
+        }
+        .into());
     };
 
-    match deadline {
-        Some(limit) => timeout(limit, wait_future)
-            .await
-            .context("subprocess timed out")?,
-        None => wait_future.await,
-    }
+    let status = status.context("failed to wait on process")?;
+    let stdout = out_task.await.context("stdout task join error")??;
+    let stderr = err_task.await.context("stderr task join error")??;
+    guard.disarm();
+    Ok(Collected {
+        status,
+        stdout,
+        stderr,
+    })
 }
@@ -37,6 +37,13 @@ impl Default for PythonPrompts {
 }
 
 impl PythonPrompts {
+    /// Replaces the system message prompt, e.g. with a course's own
+    /// instructor persona and policies.
+    pub fn with_system_message(mut self, message: impl Into<String>) -> Self {
+        self.system_message = message.into();
+        self
+    }
+
     /// Returns the system message prompt.
     pub fn system_message(&self) -> &str {
         &self.system_message
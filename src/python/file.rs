@@ -545,6 +545,12 @@ impl File {
         self.parser.code()
     }
 
+    /// Returns true if a comment marks the file as instructor-provided with
+    /// [`PROVIDED_MARKER`](crate::util::PROVIDED_MARKER).
+    pub fn is_provided(&self) -> bool {
+        crate::util::has_provided_marker(self.code())
+    }
+
     /// Executes a tree-sitter query on this file.
     pub fn query(&self, q: &str) -> Result<Vec<Dict>> {
         self.parser.query(q)
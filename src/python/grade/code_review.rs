@@ -18,7 +18,7 @@ use async_openai::{
 use bon::Builder;
 use serde::{Deserialize, Serialize};
 
-use super::results::{Grade, GradeResult};
+use super::results::{Grade, GradeResult, timed};
 use crate::{config, python::Project};
 
 /// Input information for a Python script (sample inputs for stdin).
@@ -56,6 +56,16 @@ pub struct CodeReviewGrader {
     })]
     #[builder(getter)]
     files:               Vec<String>,
+    /// Instructor-provided files left out of the review, in addition to
+    /// files marked with [`PROVIDED_MARKER`](crate::util::PROVIDED_MARKER).
+    #[builder(
+        default,
+        with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+            iter.into_iter().map(Into::into).collect::<Vec<String>>()
+        }
+    )]
+    #[builder(getter)]
+    provided_files:      Vec<String>,
     /// Path to assignment instructions (optional).
     #[builder(getter)]
     instructions_path:   Option<String>,
@@ -77,10 +87,13 @@ pub struct CodeReviewGrader {
 impl CodeReviewGrader {
     /// Builds and runs the grader.
     pub async fn run(self) -> Result<GradeResult> {
-        if self.files.is_empty() {
-            bail!("CodeReviewGrader requires at least one file to grade");
-        }
-        self.grade_with_llm().await
+        timed(async move {
+            if self.files.is_empty() {
+                bail!("CodeReviewGrader requires at least one file to grade");
+            }
+            self.grade_with_llm().await
+        })
+        .await
     }
 
     /// Performs LLM-based grading.
@@ -105,8 +118,20 @@ impl CodeReviewGrader {
         // Add file contents and execution results
         prompt_content.push_str("## Python Files\n\n");
 
+        let provided: Vec<_> = self
+            .provided_files
+            .iter()
+            .filter_map(|name| self.project.identify(name).ok())
+            .map(|file| file.path().to_path_buf())
+            .collect();
+        let mut reviewed = 0;
+
         for file_name in &self.files {
             let file = self.project.identify(file_name)?;
+            if file.is_provided() || provided.iter().any(|path| path == file.path()) {
+                continue;
+            }
+            reviewed += 1;
 
             prompt_content.push_str(&format!("### {}\n\n", file.file_name()));
             prompt_content.push_str(&format!("**Type:** {}\n", file.kind()));
@@ -139,6 +164,10 @@ impl CodeReviewGrader {
             }
         }
 
+        if reviewed == 0 {
+            bail!("CodeReviewGrader has no student files to review; every file is provided");
+        }
+
         // Build messages
         let mut messages: Vec<ChatCompletionRequestMessage> = vec![
             ChatCompletionRequestSystemMessageArgs::default()
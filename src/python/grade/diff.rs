@@ -3,15 +3,15 @@
 
 //! Diff-based grading utilities for Python.
 
-use anyhow::{Result, ensure};
+use anyhow::{Context, Result, ensure};
 use async_openai::types::chat::{
     ChatCompletionRequestSystemMessageArgs, ChatCompletionRequestUserMessageArgs,
 };
 use bon::Builder;
 use similar::{ChangeTag, TextDiff};
 
-use super::results::{Grade, GradeResult};
-use crate::{config, python::Project};
+use super::results::{Grade, GradeResult, Reason, ReasonTarget, timed};
+use crate::{config, python::Project, util::strip_ansi};
 
 /// Represents a single diff test case with optional stdin.
 #[derive(Debug, Clone)]
@@ -60,6 +60,15 @@ pub struct DiffGrader {
     #[builder(default = false)]
     #[builder(getter)]
     preserve_whitespace: bool,
+    /// Whether to strip ANSI escape sequences from program output before
+    /// comparison.
+    #[builder(default = false)]
+    #[builder(getter)]
+    strip_ansi:          bool,
+    /// Reference solution run on each case's input; its output replaces the
+    /// case's expected output.
+    #[builder(getter)]
+    reference_file:      Option<String>,
     /// Requirement name for reporting.
     #[builder(getter)]
     req_name:            String,
@@ -81,33 +90,62 @@ impl DiffGrader {
 
     /// Builds and runs the grader.
     pub async fn run(self) -> Result<GradeResult> {
-        ensure!(!self.cases.is_empty(), "DiffGrader requires at least one test case");
-        self.grade_by_diff().await
+        timed(async move {
+            ensure!(!self.cases.is_empty(), "DiffGrader requires at least one test case");
+            self.grade_by_diff().await
+        })
+        .await
     }
 
     /// Performs the diff grading.
     async fn grade_by_diff(&self) -> Result<GradeResult> {
         let file = self.project.identify(&self.file)?;
+        let reference = self
+            .reference_file
+            .as_deref()
+            .map(|name| self.project.identify(name))
+            .transpose()?;
         let prompts = config::python_prompts();
 
         let mut all_passed = true;
-        let mut reasons = Vec::new();
+        let mut reason = Reason::new();
         let mut messages = Vec::new();
 
         for (idx, case) in self.cases.iter().enumerate() {
             let case_num = idx + 1;
+            let expected = match &reference {
+                Some(reference) => {
+                    let output = reference.run(case.input.clone()).await.with_context(|| {
+                        format!(
+                            "Reference solution {} failed on case {case_num}",
+                            reference.file_name()
+                        )
+                    })?;
+                    if self.strip_ansi {
+                        strip_ansi(&output)
+                    } else {
+                        output
+                    }
+                }
+                None => case.expected.clone(),
+            };
 
             match file.run(case.input.clone()).await {
                 Ok(actual) => {
-                    let expected = self.normalize(&case.expected);
+                    let actual = if self.strip_ansi {
+                        strip_ansi(&actual)
+                    } else {
+                        actual
+                    };
+                    let expected_normalized = self.normalize(&expected);
                     let actual_normalized = self.normalize(&actual);
 
-                    if expected == actual_normalized {
-                        reasons.push(format!("Case {}: PASSED", case_num));
+                    if expected_normalized == actual_normalized {
+                        reason.push(format!("Case {case_num}: PASSED"));
                     } else {
                         all_passed = false;
-                        let diff = self.format_diff(&case.expected, &actual);
-                        reasons.push(format!("Case {}: FAILED\n{}", case_num, diff));
+                        let diff = self.format_diff(&expected, &actual);
+                        reason.push(format!("Case {case_num}: FAILED\n{diff}"));
 
                         messages.push(
                             ChatCompletionRequestUserMessageArgs::default()
@@ -118,7 +156,7 @@ impl DiffGrader {
                                         "Actual:\n```\n{}\n```\n\n",
                                         "Diff:\n```\n{}\n```"
                                     ),
-                                    case_num, case.expected, actual, diff
+                                    case_num, expected, actual, diff
                                 ))
                                 .name("Student".to_string())
                                 .build()?
@@ -129,7 +167,7 @@ impl DiffGrader {
                 Err(e) => {
                     all_passed = false;
                     let error_msg = format!("{}", e);
-                    reasons.push(format!("Case {}: ERROR\n{}", case_num, error_msg));
+                    reason.push(format!("Case {case_num}: ERROR\n{error_msg}"));
 
                     messages.push(
                         ChatCompletionRequestUserMessageArgs::default()
@@ -146,7 +184,6 @@ impl DiffGrader {
         }
 
         let grade = if all_passed { self.out_of } else { 0.0 };
-        let reason = reasons.join("\n\n");
 
         let prompt = if !messages.is_empty() {
             let mut full_messages = vec![
@@ -165,7 +202,8 @@ impl DiffGrader {
         Ok(GradeResult::builder()
             .requirement(self.req_name.clone())
             .grade(Grade::new(grade, self.out_of))
-            .reason(reason)
+            .reason(reason.render(ReasonTarget::Terminal))
+            .details(reason)
             .maybe_prompt(prompt)
             .build())
     }
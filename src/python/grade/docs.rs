@@ -10,7 +10,7 @@ use async_openai::types::chat::{
 use bon::Builder;
 use tree_sitter::Node;
 
-use super::results::{Grade, GradeResult};
+use super::results::{Grade, GradeResult, timed};
 use crate::{config, python::Project};
 
 /// A grader that checks Python docstrings and documentation quality.
@@ -41,10 +41,13 @@ pub struct DocsGrader {
 impl DocsGrader {
     /// Builds and runs the grader.
     pub async fn run(self) -> Result<GradeResult> {
-        if self.files.is_empty() {
-            bail!("DocsGrader requires at least one file to grade");
-        }
-        self.grade_docs().await
+        timed(async move {
+            if self.files.is_empty() {
+                bail!("DocsGrader requires at least one file to grade");
+            }
+            self.grade_docs().await
+        })
+        .await
     }
 
     /// Performs the documentation grading.
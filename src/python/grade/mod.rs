@@ -13,6 +13,8 @@ pub mod diagnostics;
 pub mod diff;
 /// Documentation grading helpers.
 pub mod docs;
+/// Property-based output grading.
+pub mod oracle;
 /// Tree-sitter query grading components.
 pub mod query;
 /// Shared grade result types.
@@ -23,6 +25,7 @@ pub mod tests;
 pub use code_review::CodeReviewGrader;
 pub use diff::{DiffCase, DiffGrader};
 pub use docs::DocsGrader;
+pub use oracle::{Oracle, OracleCheck, OracleGrader};
 pub use query::{Query, QueryConstraint, QueryGrader};
 pub use tests::TestGrader;
 
@@ -0,0 +1,99 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+//! Property-based output grading for Python.
+
+use anyhow::{Result, bail};
+use bon::Builder;
+
+use super::results::{Grade, GradeResult, timed};
+pub use crate::java::grade::{Oracle, OracleCheck};
+use crate::{config, python::Project};
+
+/// A grader that runs a file on each input and checks the output against an
+/// [`Oracle`] property instead of a fixed expected output.
+#[derive(Clone, Builder)]
+#[builder(on(String, into))]
+pub struct OracleGrader {
+    /// The project being graded.
+    #[builder(getter)]
+    project:  Project,
+    /// Name of the file to run.
+    #[builder(getter)]
+    file:     String,
+    /// Stdin for each case; inputs may span several lines.
+    #[builder(with = |iter: impl IntoIterator<Item = impl Into<String>>| {
+        iter.into_iter().map(Into::into).collect::<Vec<String>>()
+    })]
+    #[builder(getter)]
+    inputs:   Vec<String>,
+    /// Property every output must satisfy.
+    #[builder(getter)]
+    oracle:   Oracle,
+    /// Requirement name for reporting.
+    #[builder(getter)]
+    req_name: String,
+    /// Total points available.
+    #[builder(getter)]
+    out_of:   f64,
+}
+
+impl OracleGrader {
+    /// Builds and runs the grader.
+    pub async fn run(self) -> Result<GradeResult> {
+        timed(async move {
+            if self.inputs.is_empty() {
+                bail!("OracleGrader requires at least one input");
+            }
+            self.grade_by_oracle().await
+        })
+        .await
+    }
+
+    /// Runs every case and grades on the first output that violates the
+    /// oracle.
+    async fn grade_by_oracle(&self) -> Result<GradeResult> {
+        let file = self.project.identify(&self.file)?;
+
+        for (idx, input) in self.inputs.iter().enumerate() {
+            let case_num = idx + 1;
+            let output = match file.run(Some(input.clone())).await {
+                Ok(output) => output,
+                Err(e) => {
+                    return GradeResult::failure(
+                        &self.req_name,
+                        self.out_of,
+                        config::python_prompts().system_message(),
+                        format!("Case {case_num}: ERROR\n{e}"),
+                        format!("Test case {case_num} resulted in an error:\n```\n{e}\n```"),
+                    );
+                }
+            };
+
+            let Err(violation) = self.oracle.check(input, &output) else {
+                continue;
+            };
+            return GradeResult::failure(
+                &self.req_name,
+                self.out_of,
+                config::python_prompts().system_message(),
+                format!("Case {case_num}: {violation}"),
+                format!(
+                    "Running {}, the output was expected to be the {}, but \
+                     {violation}.\n\nInput:\n```\n{}\n```\n\nActual output:\n```\n{}\n```",
+                    file.file_name(),
+                    self.oracle.name(),
+                    input.trim_end(),
+                    output.trim_end()
+                ),
+            );
+        }
+
+        Ok(GradeResult::builder()
+            .requirement(self.req_name.clone())
+            .grade(Grade::new(self.out_of, self.out_of))
+            .reason(format!("Every output was the {}", self.oracle.name()))
+            .maybe_prompt(None)
+            .build())
+    }
+}
@@ -8,7 +8,7 @@ use std::{fmt, sync::Arc};
 use anyhow::{Result, bail};
 use bon::Builder;
 
-use super::results::{Grade, GradeResult};
+use super::results::{Grade, GradeResult, Reason, ReasonTarget, timed};
 use crate::python::Project;
 
 /// Predicate invoked to filter query results.
@@ -266,7 +266,7 @@ impl QueryGrader {
 
     /// Builds and runs the grader.
     pub async fn run(self) -> Result<GradeResult> {
-        self.grade_by_query().await
+        timed(self.grade_by_query()).await
     }
 
     /// Performs the query grading.
@@ -277,7 +277,7 @@ impl QueryGrader {
 
         let file = self.project.identify(&self.file)?;
         let mut all_passed = true;
-        let mut reasons = Vec::new();
+        let mut failures = Reason::new();
 
         for (idx, query) in self.queries.iter().enumerate() {
             let query_str = query.query()?;
@@ -314,7 +314,7 @@ impl QueryGrader {
                     QueryConstraint::MustMatchExactlyNTimes(n) => format!("exactly {} matches", n),
                     QueryConstraint::MustNotMatch => "no matches".to_string(),
                 };
-                reasons.push(format!(
+                failures.push(format!(
                     "Query {}: Expected {}, found {} matches",
                     idx + 1,
                     constraint_desc,
@@ -324,18 +324,19 @@ impl QueryGrader {
         }
 
         let grade = if all_passed { self.out_of } else { 0.0 };
-        let reason = if all_passed {
-            "All queries passed".to_string()
+        let (reason, details) = if all_passed {
+            ("All queries passed".to_string(), None)
         } else if !self.reason.is_empty() {
-            self.reason.clone()
+            (self.reason.clone(), None)
         } else {
-            reasons.join("\n")
+            (failures.render(ReasonTarget::Terminal), Some(failures))
         };
 
         Ok(GradeResult::builder()
             .requirement(self.req_name)
             .grade(Grade::new(grade, self.out_of))
             .reason(reason)
+            .maybe_details(details)
             .build())
     }
 }
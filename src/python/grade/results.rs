@@ -4,4 +4,4 @@
 //! Shared grade result types for Python (re-exports from Java for consistency).
 
 // Re-export from Java module to maintain API consistency
-pub use crate::java::grade::results::{Grade, GradeResult};
+pub use crate::java::grade::results::{Grade, GradeResult, Reason, ReasonTarget, timed};
@@ -11,7 +11,7 @@ use async_openai::types::chat::{
 };
 use bon::Builder;
 
-use super::results::{Grade, GradeResult};
+use super::results::{Grade, GradeResult, timed};
 use crate::{
     config,
     process::{self, StdinSource},
@@ -43,10 +43,13 @@ pub struct TestGrader {
 impl TestGrader {
     /// Builds and runs the grader.
     pub async fn run(self) -> Result<GradeResult> {
-        if self.test_files.is_empty() {
-            bail!("TestGrader requires at least one test file");
-        }
-        self.grade_by_tests().await
+        timed(async move {
+            if self.test_files.is_empty() {
+                bail!("TestGrader requires at least one test file");
+            }
+            self.grade_by_tests().await
+        })
+        .await
     }
 
     /// Performs the test grading.
@@ -16,9 +16,11 @@ pub enum RetrievalMode {
     /// Invoke the active-retrieval service and optionally fall back.
     Active {
         /// Combined stdout/stderr from the grader run.
-        grader_output: String,
+        grader_output:    String,
+        /// The grader's override of the global active-retrieval toggle.
+        active_retrieval: Option<bool>,
         /// Mode to use when the retrieval service fails.
-        fallback:      Box<RetrievalMode>,
+        fallback:         Box<RetrievalMode>,
     },
 }
 
@@ -32,27 +34,43 @@ impl RetrievalMode {
     }
 }
 
+/// Default cap on the merged line references rendered into one heuristic
+/// context message.
+pub const DEFAULT_MAX_LINE_REFS: usize = 6;
+
 /// Parameters controlling heuristic snippet selection.
+///
+/// Each diagnostic line becomes a window from `start_offset` lines before it
+/// to `num_lines` lines after it (test files look back `num_lines` instead).
+/// Overlapping windows in the same file are merged, and only then are the
+/// first `max_line_refs` windows rendered, student files ahead of tests. Wider
+/// windows therefore merge more readily, letting nearby diagnostics share one
+/// slot under the cap.
 #[derive(Debug, Clone, Copy)]
 pub struct HeuristicConfig {
     /// Number of lines to include before the diagnostic line.
-    pub start_offset:    usize,
+    pub start_offset:         usize,
     /// Number of lines to include after the diagnostic line.
-    pub num_lines:       usize,
-    /// Maximum number of merged line references to include.
-    pub max_line_refs:   usize,
+    pub num_lines:            usize,
+    /// Maximum number of merged line references to include; references past
+    /// the cap are dropped. Defaults to [`DEFAULT_MAX_LINE_REFS`].
+    pub max_line_refs:        usize,
     /// Ratio of file length that triggers full-file rendering instead of a
     /// snippet.
-    pub full_file_ratio: f32,
+    pub full_file_ratio:      f32,
+    /// Whether to prepend the imports and field declarations of each
+    /// referenced file, space permitting.
+    pub include_declarations: bool,
 }
 
 impl Default for HeuristicConfig {
     fn default() -> Self {
         Self {
-            start_offset:    3,
-            num_lines:       6,
-            max_line_refs:   6,
-            full_file_ratio: 0.6,
+            start_offset:         3,
+            num_lines:            6,
+            max_line_refs:        DEFAULT_MAX_LINE_REFS,
+            full_file_ratio:      0.6,
+            include_declarations: false,
         }
     }
 }
@@ -97,6 +115,16 @@ impl HeuristicConfig {
     pub fn set_full_file_ratio(&mut self, value: f32) {
         self.full_file_ratio = value;
     }
+
+    /// Returns whether imports and field declarations are prepended.
+    pub fn include_declarations(&self) -> bool {
+        self.include_declarations
+    }
+
+    /// Updates whether imports and field declarations are prepended.
+    pub fn set_include_declarations(&mut self, value: bool) {
+        self.include_declarations = value;
+    }
 }
 
 /// Trait implemented by language-specific project types to format retrieval
@@ -122,7 +150,12 @@ pub trait RetrievalFormatter {
     ) -> Result<ChatCompletionRequestMessage>;
 
     /// Resolves active retrieval using the provided grader output.
-    fn active_retrieval(&self, grader_output: String) -> Result<ChatCompletionRequestMessage>;
+    /// `active_retrieval` overrides the global toggle; `None` defers to it.
+    fn active_retrieval(
+        &self,
+        grader_output: String,
+        active_retrieval: Option<bool>,
+    ) -> Result<ChatCompletionRequestMessage>;
 }
 
 /// Assembles retrieval messages based on the requested mode.
@@ -139,8 +172,9 @@ pub fn build_messages<F: RetrievalFormatter>(
         }
         RetrievalMode::Active {
             grader_output,
+            active_retrieval,
             fallback,
-        } => match formatter.active_retrieval(grader_output) {
+        } => match formatter.active_retrieval(grader_output, active_retrieval) {
             Ok(message) => Ok(vec![message]),
             Err(err) => {
                 eprintln!("Active retrieval failed: {err:?}. Falling back to heuristic context.");
@@ -169,6 +203,26 @@ where
         .with_context(|| format!("{} retrieval produced no messages", formatter.language()))
 }
 
+/// Chooses how to assemble context for a single message.
+///
+/// Active retrieval needs grader output to work from. `active_retrieval`
+/// overrides the global toggle for this call; `None` defers to it.
+pub fn select_retrieval_mode(
+    cfg: HeuristicConfig,
+    grader_output: Option<String>,
+    active_retrieval: Option<bool>,
+) -> RetrievalMode {
+    let active = active_retrieval.unwrap_or_else(crate::config::active_retrieval_enabled);
+    match grader_output {
+        Some(output) if active => RetrievalMode::Active {
+            grader_output: output,
+            active_retrieval,
+            fallback: Box::new(RetrievalMode::Heuristic(cfg)),
+        },
+        Some(_) | None => RetrievalMode::Heuristic(cfg),
+    }
+}
+
 /// Builds a single context message using the language defaults and the global
 /// config.
 pub fn build_context_message<F, T>(
@@ -180,14 +234,44 @@ where
     F: RetrievalFormatter,
     T: Into<LineRef>,
 {
-    let cfg = formatter.heuristic_defaults();
-    let mode = match grader_output {
-        Some(output) if crate::config::active_retrieval_enabled() => RetrievalMode::Active {
-            grader_output: output,
-            fallback:      Box::new(RetrievalMode::Heuristic(cfg)),
-        },
-        Some(_) | None => RetrievalMode::Heuristic(cfg),
-    };
+    build_context_message_with(formatter, grader_output, diags, None)
+}
 
+/// Builds a single context message like [`build_context_message`], letting the
+/// caller override the global active-retrieval toggle.
+pub fn build_context_message_with<F, T>(
+    formatter: &F,
+    grader_output: Option<String>,
+    diags: Vec<T>,
+    active_retrieval: Option<bool>,
+) -> Result<ChatCompletionRequestMessage>
+where
+    F: RetrievalFormatter,
+    T: Into<LineRef>,
+{
+    build_context_message_with_config(
+        formatter,
+        grader_output,
+        diags,
+        formatter.heuristic_defaults(),
+        active_retrieval,
+    )
+}
+
+/// Builds a single context message like [`build_context_message_with`], using
+/// `cfg` instead of the language defaults for heuristic snippets, e.g. to cap
+/// `max_line_refs` for one grader.
+pub fn build_context_message_with_config<F, T>(
+    formatter: &F,
+    grader_output: Option<String>,
+    diags: Vec<T>,
+    cfg: HeuristicConfig,
+    active_retrieval: Option<bool>,
+) -> Result<ChatCompletionRequestMessage>
+where
+    F: RetrievalFormatter,
+    T: Into<LineRef>,
+{
+    let mode = select_retrieval_mode(cfg, grader_output, active_retrieval);
     build_single_message(formatter, mode, diags)
 }
@@ -1,16 +1,176 @@
 #![warn(missing_docs)]
 #![deny(missing_docs)]
 
-use std::{result::Result as StdResult, sync::Arc};
+use std::{
+    result::Result as StdResult,
+    sync::{Arc, Mutex},
+};
 
 use ::rune::{
     Context, Diagnostics, FromValue, Source, Sources, Vm, prepare,
     termcolor::{ColorChoice, StandardStream},
 };
-use anyhow::{Context as AnyhowContext, Result};
+use anyhow::{Context as AnyhowContext, Result, bail};
+
+use crate::java::{
+    ProjectPaths,
+    grade::{GradeResult, ProgressWriter, gradescope::GradescopeConfig, show_result},
+};
 
 pub mod rune;
 
+/// JSON Lines progress sink for a grading run.
+struct Progress {
+    /// Where progress lines are written.
+    writer:     ProgressWriter,
+    /// Label reported for the submission being graded.
+    submission: Option<String>,
+    /// Results recorded so far, for the closing submission summary.
+    results:    Vec<GradeResult>,
+}
+
+/// Settings for one run of a grading script, visible to every grader the
+/// script invokes.
+#[derive(Default)]
+pub struct RunContext {
+    /// Paths of the Java project the script discovers as "the current
+    /// project".
+    project_paths:  ProjectPaths,
+    /// First failing result recorded while fail-fast mode is enabled.
+    fail_fast_stop: Mutex<Option<GradeResult>>,
+    /// Where this run's progress is streamed, if anywhere.
+    progress:       Mutex<Option<Progress>>,
+}
+
+impl RunContext {
+    /// A run whose scripts discover the project at `project_paths` instead of
+    /// the working directory, e.g. a checkout of a deadline commit.
+    ///
+    /// The process working directory is never changed, so other runs are not
+    /// affected.
+    pub fn new(project_paths: ProjectPaths) -> Self {
+        Self {
+            project_paths,
+            ..Self::default()
+        }
+    }
+
+    /// Streams a JSON line to `writer` for every requirement this run grades,
+    /// labelled with `submission`, plus a summary line when the script
+    /// finishes.
+    pub fn with_progress(self, writer: ProgressWriter, submission: Option<String>) -> Self {
+        Self {
+            progress: Mutex::new(Some(Progress {
+                writer,
+                submission,
+                results: Vec::new(),
+            })),
+            ..self
+        }
+    }
+
+    /// Streams the submission summary and clears the recorded results.
+    fn finish_progress(&self) {
+        let Ok(mut progress) = self.progress.lock() else {
+            return;
+        };
+        let Some(progress) = progress.as_mut() else {
+            return;
+        };
+
+        let results = std::mem::take(&mut progress.results);
+        if let Err(e) = progress
+            .writer
+            .submission(progress.submission.as_deref(), &results)
+        {
+            eprintln!("Warning: could not write grading progress: {e:#}");
+        }
+    }
+
+    /// Takes the failing result that halted the script, if any.
+    fn take_fail_fast_stop(&self) -> Option<GradeResult> {
+        self.fail_fast_stop
+            .lock()
+            .ok()
+            .and_then(|mut stop| stop.take())
+    }
+}
+
+tokio::task_local! {
+    /// The run the executing script belongs to, set by [`run_file`] for as
+    /// long as the script runs.
+    static RUN: Arc<RunContext>;
+}
+
+/// Paths of the current Java project: those of the running script's
+/// [`RunContext`], or the working directory's outside a run.
+pub(crate) fn project_paths() -> ProjectPaths {
+    RUN.try_with(|run| run.project_paths.clone())
+        .unwrap_or_default()
+}
+
+/// Streams a finished requirement to the running script's progress sink, if
+/// one is set. Write failures are reported but never interrupt grading.
+pub(crate) fn record_progress(result: &GradeResult) {
+    let _ = RUN.try_with(|run| {
+        let Ok(mut progress) = run.progress.lock() else {
+            return;
+        };
+        let Some(progress) = progress.as_mut() else {
+            return;
+        };
+
+        if let Err(e) = progress
+            .writer
+            .requirement(progress.submission.as_deref(), result)
+        {
+            eprintln!("Warning: could not write grading progress: {e:#}");
+        }
+        progress.results.push(result.clone());
+    });
+}
+
+/// Checks a grader's result against the fail-fast policy.
+///
+/// When fail-fast mode is enabled and `result` lost points, the result is
+/// recorded and an error is returned so the script halts before any remaining
+/// graders run.
+pub(crate) fn check_fail_fast(result: &GradeResult) -> Result<()> {
+    if !crate::config::fail_fast_enabled() || result.grade_value() >= result.out_of_value() {
+        return Ok(());
+    }
+
+    let requirement = result.requirement.clone();
+    let _ = RUN.try_with(|run| {
+        if let Ok(mut stop) = run.fail_fast_stop.lock() {
+            stop.get_or_insert_with(|| result.clone());
+        }
+    });
+    bail!("fail-fast: stopping after failing requirement `{requirement}`")
+}
+
+/// Refuses to start another grader once fail-fast mode has halted grading.
+pub(crate) fn ensure_not_halted() -> Result<()> {
+    let halted = RUN
+        .try_with(|run| run.fail_fast_stop.lock().is_ok_and(|stop| stop.is_some()))
+        .unwrap_or(false);
+    if halted {
+        bail!("fail-fast: grading already halted by a failing requirement");
+    }
+    Ok(())
+}
+
+/// Reports the failing requirement that halted a fail-fast grading run.
+fn report_fail_fast_stop(failed: GradeResult, paths: &ProjectPaths) -> Result<()> {
+    eprintln!(
+        "Fail-fast: `{}` scored {:.2}/{:.2}; remaining graders were skipped.",
+        failed.requirement,
+        failed.grade_value(),
+        failed.out_of_value()
+    );
+    show_result(vec![failed], GradescopeConfig::default(), paths)
+}
+
 /// Builds the Rune context with the default standard library.
 pub fn build_context() -> Result<Context> {
     let mut context = Context::with_default_modules()
@@ -22,9 +182,9 @@ pub fn build_context() -> Result<Context> {
     Ok(context)
 }
 
-/// Executes the Rune script located at `path`, invoking its top-level
-/// `main` function asynchronously.
-pub async fn run_file(path: &str) -> Result<()> {
+/// Executes the Rune script located at `path` as part of `run`, invoking its
+/// top-level `main` function asynchronously.
+pub async fn run_file(path: &str, run: RunContext) -> Result<()> {
     let mut sources = Sources::new();
     let source = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read Rune script: {path}"))?;
@@ -57,11 +217,20 @@ pub async fn run_file(path: &str) -> Result<()> {
 
     // `async_complete` returns a `VmResult<Value>`; convert it to a plain `Value`
     // so host-side error reporting stays in `anyhow`.
-    let value = exec
-        .async_complete()
+    let run = Arc::new(run);
+    let value = RUN
+        .scope(Arc::clone(&run), exec.async_complete())
         .await
-        .into_result()
-        .context("Rune script failed during async execution")?;
+        .into_result();
+    run.finish_progress();
+
+    // A fail-fast stop surfaces as a script error; report the failing
+    // requirement instead of the error it was propagated as.
+    if let Some(failed) = run.take_fail_fast_stop() {
+        return report_fail_fast_stop(failed, &run.project_paths);
+    }
+
+    let value = value.context("Rune script failed during async execution")?;
 
     let outcome: StdResult<(), ::rune::support::Error> =
         <StdResult<(), ::rune::support::Error> as FromValue>::from_value(value)
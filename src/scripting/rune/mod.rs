@@ -2,13 +2,15 @@
 
 use rune::{Context, compile::ContextError};
 
-/// Collection of `umm` Rune submodules (java, gradescope, config, retrieval).
+/// Collection of `umm` Rune submodules (java, python, polyglot, gradescope,
+/// config, retrieval).
 pub mod modules;
 
 /// Install all `umm` Rune modules into the provided context.
 pub fn install_all_modules(context: &mut Context) -> Result<(), ContextError> {
     context.install(modules::java::module()?)?;
     context.install(modules::python::module()?)?;
+    context.install(modules::polyglot::module()?)?;
     context.install(modules::gradescope::module()?)?;
     context.install(modules::config::module()?)?;
     context.install(modules::retrieval::module()?)?;
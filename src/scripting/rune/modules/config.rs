@@ -1,4 +1,7 @@
-use rune::{ContextError, Module};
+use rune::{
+    ContextError, Module,
+    support::{Error as RuneError, Result as RuneResult},
+};
 
 /// Enable or disable active retrieval globally.
 pub fn set_active_retrieval(enabled: bool) {
@@ -10,6 +13,61 @@ pub fn active_retrieval_enabled() -> bool {
     crate::config::active_retrieval_enabled()
 }
 
+/// Enable or disable fail-fast grading, which stops at the first failing
+/// requirement.
+pub fn set_fail_fast(enabled: bool) {
+    crate::config::set_fail_fast(enabled);
+}
+
+/// Check whether fail-fast grading is enabled.
+pub fn fail_fast_enabled() -> bool {
+    crate::config::fail_fast_enabled()
+}
+
+/// Limit how many child processes (JVMs, test runners, and so on) all
+/// graders may run at once.
+pub fn set_max_processes(limit: usize) {
+    crate::process::set_max_processes(limit);
+}
+
+/// Write feedback prompts as JSON files in `dir` instead of posting them.
+pub fn set_prompt_export_dir(dir: String) {
+    crate::config::set_prompt_export_dir(Some(dir.into()));
+}
+
+/// Use `message` as the system message of every feedback prompt, in place of
+/// the built-in one.
+pub fn set_system_message(message: String) {
+    crate::config::set_system_message(Some(message));
+}
+
+/// Read the system message of every feedback prompt from the file at `path`.
+pub fn load_system_message(path: String) -> RuneResult<()> {
+    crate::config::load_system_message(path).map_err(|e| RuneError::msg(e.to_string()))
+}
+
+/// Course identifier, from `UMM_COURSE`.
+pub fn course() -> String {
+    crate::config::course()
+}
+
+/// Academic term identifier, from `UMM_TERM`.
+pub fn term() -> String {
+    crate::config::term()
+}
+
+/// Read an allowlisted environment variable (`UMM_*` or `CI`); unset
+/// variables yield `None` and other names are an error.
+pub fn env(name: String) -> RuneResult<Option<String>> {
+    crate::config::script_env(&name).map_err(|e| RuneError::msg(e.to_string()))
+}
+
+/// Read an allowlisted environment variable, falling back to `default` when
+/// it is unset.
+pub fn env_or(name: String, default: String) -> RuneResult<String> {
+    Ok(env(name)?.unwrap_or(default))
+}
+
 /// Install the `umm::config` Rune module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("umm", ["config"])?;
@@ -20,5 +78,25 @@ pub fn module() -> Result<Module, ContextError> {
     module
         .function("active_retrieval_enabled", active_retrieval_enabled)
         .build()?;
+    module.function("set_fail_fast", set_fail_fast).build()?;
+    module
+        .function("fail_fast_enabled", fail_fast_enabled)
+        .build()?;
+    module
+        .function("set_max_processes", set_max_processes)
+        .build()?;
+    module
+        .function("set_prompt_export_dir", set_prompt_export_dir)
+        .build()?;
+    module
+        .function("set_system_message", set_system_message)
+        .build()?;
+    module
+        .function("load_system_message", load_system_message)
+        .build()?;
+    module.function("course", course).build()?;
+    module.function("term", term).build()?;
+    module.function("env", env).build()?;
+    module.function("env_or", env_or).build()?;
     Ok(module)
 }
@@ -6,7 +6,10 @@ use rune::{
 };
 
 use crate::{
-    java::grade::{self, gradescope::GradescopeConfig as InnerGradescopeConfig},
+    java::grade::{
+        self, GradeStyle, LetterScale, TableDetail,
+        gradescope::GradescopeConfig as InnerGradescopeConfig,
+    },
     scripting::rune::modules::java::GradeResult,
 };
 
@@ -119,6 +122,8 @@ pub struct GradescopeConfigBuilder {
     source_files:        Vec<String>,
     /// Test files included in summaries.
     test_files:          Vec<String>,
+    /// Instructor-provided files left out of summaries.
+    provided_files:      Vec<String>,
     /// Gradescope project title.
     project_title:       Option<String>,
     /// Gradescope project description.
@@ -135,15 +140,31 @@ pub struct GradescopeConfigBuilder {
     debug:               Option<bool>,
     /// Enabled SLO identifiers.
     enabled_slos:        HashSet<String>,
+    /// How grades are rendered in the overview table.
+    grade_style:         Option<GradeStyle>,
+    /// Which columns the overview table shows.
+    table_detail:        Option<TableDetail>,
+    /// Width the overview table should fit in.
+    table_width:         Option<usize>,
+    /// Print the predicted score before SLO feedback.
+    emit_score:          Option<bool>,
 }
 
 impl GradescopeConfig {
+    /// Enabled SLO keys, sorted.
+    pub fn enabled_slos(&self) -> Vec<String> {
+        let mut slos: Vec<String> = self.inner.enabled_slos.iter().cloned().collect();
+        slos.sort();
+        slos
+    }
+
     #[rune::function(path = GradescopeConfig::builder)]
     /// Start a Gradescope configuration builder.
     pub fn builder() -> GradescopeConfigBuilder {
         GradescopeConfigBuilder {
             source_files:        Vec::new(),
             test_files:          Vec::new(),
+            provided_files:      Vec::new(),
             project_title:       None,
             project_description: None,
             pass_threshold:      None,
@@ -152,6 +173,10 @@ impl GradescopeConfig {
             feedback:            None,
             debug:               None,
             enabled_slos:        HashSet::new(),
+            grade_style:         None,
+            table_detail:        None,
+            table_width:         None,
+            emit_score:          None,
         }
     }
 }
@@ -167,6 +192,11 @@ impl GradescopeConfigBuilder {
         self.test_files = files;
         self
     }
+    /// Set instructor-provided files to leave out of summaries.
+    pub fn provided_files(mut self, files: Vec<String>) -> Self {
+        self.provided_files = files;
+        self
+    }
     /// Set project title.
     pub fn project_title(mut self, title: String) -> Self {
         self.project_title = Some(title);
@@ -202,11 +232,61 @@ impl GradescopeConfigBuilder {
         self.debug = Some(value);
         self
     }
+    /// Toggle the `predicted_score` JSON line printed before SLO feedback.
+    pub fn emit_score(mut self, value: bool) -> Self {
+        self.emit_score = Some(value);
+        self
+    }
     /// Enable specific SLO identifiers.
     pub fn enabled_slos(mut self, slos: Vec<String>) -> Self {
         self.enabled_slos = slos.into_iter().collect();
         self
     }
+    /// Enable SLOs by name (`["logic", "testing"]`), rejecting unknown names.
+    pub fn slos(mut self, names: Vec<String>) -> RuneResult<Self> {
+        self.enabled_slos = grade::parse_slos(names).map_err(host_err)?;
+        Ok(self)
+    }
+    /// Render grades as `"fraction"`, `"percent"`, or `"letter"` (default
+    /// 90/80/70/60 scale).
+    pub fn grade_style(mut self, style: String) -> RuneResult<Self> {
+        self.grade_style = Some(match style.as_str() {
+            "fraction" => GradeStyle::Fraction,
+            "percent" => GradeStyle::Percent,
+            "letter" => GradeStyle::Letter(LetterScale::default()),
+            other => {
+                return Err(host_err(format!(
+                    "Unknown grade style `{other}`; expected fraction, percent, or letter"
+                )));
+            }
+        });
+        Ok(self)
+    }
+    /// Show `"summary"` (requirement and grade only) or `"full"` (with
+    /// reasons) rows in the overview table.
+    pub fn table_detail(mut self, detail: String) -> RuneResult<Self> {
+        self.table_detail = Some(match detail.as_str() {
+            "summary" => TableDetail::Summary,
+            "full" => TableDetail::Full,
+            other => {
+                return Err(host_err(format!(
+                    "Unknown table detail `{other}`; expected summary or full"
+                )));
+            }
+        });
+        Ok(self)
+    }
+    /// Fit the overview table's reasons to `width` columns instead of the
+    /// detected terminal width.
+    pub fn table_width(mut self, width: usize) -> Self {
+        self.table_width = Some(width);
+        self
+    }
+    /// Render letter grades using `(minimum percentage, letter)` cutoffs.
+    pub fn letter_scale(mut self, cutoffs: Vec<(f64, String)>, fallback: String) -> Self {
+        self.grade_style = Some(GradeStyle::Letter(LetterScale::new(cutoffs, fallback)));
+        self
+    }
 
     /// Finalize the configuration.
     pub fn build(self) -> GradescopeConfig {
@@ -214,6 +294,7 @@ impl GradescopeConfigBuilder {
         let inner = InnerGradescopeConfig {
             source_files:        self.source_files,
             test_files:          self.test_files,
+            provided_files:      self.provided_files,
             project_title:       self.project_title.unwrap_or(defaults.project_title),
             project_description: self
                 .project_description
@@ -228,6 +309,10 @@ impl GradescopeConfigBuilder {
             } else {
                 self.enabled_slos
             },
+            grade_style:         self.grade_style.unwrap_or(defaults.grade_style),
+            table_detail:        self.table_detail.unwrap_or(defaults.table_detail),
+            table_width:         self.table_width.or(defaults.table_width),
+            emit_score:          self.emit_score.unwrap_or(defaults.emit_score),
         };
         GradescopeConfig { inner }
     }
@@ -237,7 +322,7 @@ impl GradescopeConfigBuilder {
 pub fn show_result(results: Vec<GradeResult>) -> RuneResult<()> {
     let config = InnerGradescopeConfig::default();
     let inner_results: Vec<_> = results.into_iter().map(|r| r.into_inner()).collect();
-    grade::show_result(inner_results, config).map_err(host_err)
+    grade::show_result(inner_results, config, &crate::scripting::project_paths()).map_err(host_err)
 }
 
 /// Render results with an explicit Gradescope configuration.
@@ -246,7 +331,8 @@ pub fn show_result_with_config(
     config: GradescopeConfig,
 ) -> RuneResult<()> {
     let inner_results: Vec<_> = results.into_iter().map(|r| r.into_inner()).collect();
-    grade::show_result(inner_results, config.inner).map_err(host_err)
+    grade::show_result(inner_results, config.inner, &crate::scripting::project_paths())
+        .map_err(host_err)
 }
 
 /// Alias for `show_result`.
@@ -262,6 +348,31 @@ pub fn show_results_with_config(
     show_result_with_config(results, config)
 }
 
+/// Write feedback links for `results` to `path`, or to `FEEDBACK` when `path`
+/// is `None`.
+pub fn generate_feedback(results: Vec<GradeResult>, path: Option<String>) -> RuneResult<()> {
+    let inner_results: Vec<_> = results.into_iter().map(|r| r.into_inner()).collect();
+    grade::generate_feedback(inner_results, path.as_deref().map(std::path::Path::new))
+        .map_err(host_err)
+}
+
+/// POST `results` as JSON to `url`, with `auth` sent as the `Authorization`
+/// header. Failures are retried and logged but never fail the script; returns
+/// whether the results were delivered.
+pub async fn post_results_webhook(
+    results: Vec<GradeResult>,
+    url: String,
+    auth: Option<String>,
+) -> bool {
+    let inner_results: Vec<_> = results.into_iter().map(|r| r.into_inner()).collect();
+    grade::ResultsWebhook::builder()
+        .url(url)
+        .maybe_auth_header(auth)
+        .build()
+        .send(&inner_results)
+        .await
+}
+
 /// Install the `umm::gradescope` Rune module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("umm", ["gradescope"])?;
@@ -273,6 +384,7 @@ pub fn module() -> Result<Module, ContextError> {
 
     module.function_meta(GradescopeConfig::builder)?;
     module.associated_function("source_files", GradescopeConfigBuilder::source_files)?;
+    module.associated_function("provided_files", GradescopeConfigBuilder::provided_files)?;
     module.associated_function("test_files", GradescopeConfigBuilder::test_files)?;
     module.associated_function("project_title", GradescopeConfigBuilder::project_title)?;
     module
@@ -282,7 +394,14 @@ pub fn module() -> Result<Module, ContextError> {
     module.associated_function("results_json", GradescopeConfigBuilder::results_json)?;
     module.associated_function("feedback", GradescopeConfigBuilder::feedback)?;
     module.associated_function("debug", GradescopeConfigBuilder::debug)?;
+    module.associated_function("emit_score", GradescopeConfigBuilder::emit_score)?;
     module.associated_function("enabled_slos", GradescopeConfigBuilder::enabled_slos)?;
+    module.associated_function("slos", GradescopeConfigBuilder::slos)?;
+    module.associated_function("enabled_slos", GradescopeConfig::enabled_slos)?;
+    module.associated_function("grade_style", GradescopeConfigBuilder::grade_style)?;
+    module.associated_function("letter_scale", GradescopeConfigBuilder::letter_scale)?;
+    module.associated_function("table_detail", GradescopeConfigBuilder::table_detail)?;
+    module.associated_function("table_width", GradescopeConfigBuilder::table_width)?;
     module.associated_function("build", GradescopeConfigBuilder::build)?;
 
     module.function("show_result", show_result).build()?;
@@ -293,6 +412,12 @@ pub fn module() -> Result<Module, ContextError> {
     module
         .function("show_results_with_config", show_results_with_config)
         .build()?;
+    module
+        .function("generate_feedback", generate_feedback)
+        .build()?;
+    module
+        .function("post_results_webhook", post_results_webhook)
+        .build()?;
 
     module.function_meta(GradescopeOutputFormat::text)?;
     module.function_meta(GradescopeOutputFormat::html)?;
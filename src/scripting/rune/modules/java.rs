@@ -1,21 +1,29 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
 
 use rune::{
     Any, ContextError, Module, Ref,
+    runtime::Function,
     support::{Error as RuneError, Result as RuneResult},
 };
 use serde_json;
 
 use crate::{
+    archive::Unzipped,
+    git::Worktree,
     java::grade::{self, GradeResult as InnerGradeResult},
-    scripting::rune::modules::gradescope::GradescopeConfig as RuneGradescopeConfig,
+    scripting::rune::modules::{
+        gradescope::GradescopeConfig as RuneGradescopeConfig, retrieval::HeuristicConfig,
+    },
 };
 
 /// Free constructor: discover the current Java project.
 #[rune::function(path = new_project)]
 pub fn new_project() -> RuneResult<Project> {
     Ok(Project {
-        inner: crate::java::Project::new().map_err(host_err)?,
+        inner:     crate::java::Project::from_paths(crate::scripting::project_paths())
+            .map_err(host_err)?,
+        _worktree: None,
+        _unzipped: None,
     })
 }
 
@@ -23,23 +31,56 @@ pub fn new_project() -> RuneResult<Project> {
 #[rune::function(path = new_project_from_paths)]
 pub fn new_project_from_paths(paths: ProjectPaths) -> RuneResult<Project> {
     Ok(Project {
-        inner: crate::java::Project::from_paths(paths.inner).map_err(host_err)?,
+        inner:     crate::java::Project::from_paths(paths.inner).map_err(host_err)?,
+        _worktree: None,
+        _unzipped: None,
+    })
+}
+
+/// Free constructor: discover the current project as of `git_ref` (commit,
+/// branch, or tag), checked out into a temporary worktree.
+#[rune::function(path = new_project_at_ref)]
+pub async fn new_project_at_ref(git_ref: String) -> RuneResult<Project> {
+    let (inner, worktree) =
+        crate::java::Project::from_git_ref(&crate::scripting::project_paths(), &git_ref)
+            .await
+            .map_err(|e| host_err(format!("{e:#}")))?;
+    Ok(Project {
+        inner,
+        _worktree: Some(Arc::new(worktree)),
+        _unzipped: None,
+    })
+}
+
+/// Free constructor: discover the project in a zip archive, extracted into
+/// a temporary directory.
+#[rune::function(path = new_project_from_zip)]
+pub async fn new_project_from_zip(path: String) -> RuneResult<Project> {
+    let (inner, unzipped) = crate::java::Project::from_zip(&path)
+        .await
+        .map_err(|e| host_err(format!("{e:#}")))?;
+    Ok(Project {
+        inner,
+        _worktree: None,
+        _unzipped: Some(Arc::new(unzipped)),
     })
 }
 
 /// Free constructor: start a paths builder for fine-grained overrides.
 /// Defaults mirror `ProjectPaths::from_parts` defaults; callers set only what
-/// they need (e.g., `.lib_dir("...").build()?`).
+/// they need (e.g., `.lib_dir("...").build()?`). The root defaults to the
+/// current project's, so a script graded at a ref stays in its checkout.
 #[rune::function(path = new_project_paths)]
 pub fn new_project_paths() -> ProjectPathsBuilder {
     ProjectPathsBuilder {
-        root_dir:   None,
-        source_dir: None,
-        build_dir:  None,
-        test_dir:   None,
-        lib_dir:    None,
-        umm_dir:    None,
-        report_dir: None,
+        root_dir:          None,
+        source_dir:        None,
+        build_dir:         None,
+        test_dir:          None,
+        lib_dir:           None,
+        umm_dir:           None,
+        report_dir:        None,
+        reference_classes: Vec::new(),
     }
 }
 
@@ -47,11 +88,13 @@ pub fn new_project_paths() -> ProjectPathsBuilder {
 #[rune::function(path = new_docs_grader)]
 pub fn new_docs_grader() -> DocsGraderBuilder {
     DocsGraderBuilder {
-        project:  None,
-        files:    Vec::new(),
-        req_name: None,
-        out_of:   None,
-        penalty:  None,
+        project:          None,
+        files:            Vec::new(),
+        req_name:         None,
+        out_of:           None,
+        penalty:          None,
+        penalty_mode:     None,
+        active_retrieval: None,
     }
 }
 
@@ -59,11 +102,15 @@ pub fn new_docs_grader() -> DocsGraderBuilder {
 #[rune::function(path = new_by_unit_test_grader)]
 pub fn new_by_unit_test_grader() -> ByUnitTestGraderBuilder {
     ByUnitTestGraderBuilder {
-        test_files:     Vec::new(),
-        expected_tests: Vec::new(),
-        project:        None,
-        out_of:         None,
-        req_name:       None,
+        test_files:        Vec::new(),
+        expected_tests:    Vec::new(),
+        allow_extra_tests: false,
+        min_test_methods:  None,
+        project:           None,
+        out_of:            None,
+        req_name:          None,
+        classpath_overlay: Vec::new(),
+        active_retrieval:  None,
     }
 }
 
@@ -71,13 +118,45 @@ pub fn new_by_unit_test_grader() -> ByUnitTestGraderBuilder {
 #[rune::function(path = new_unit_test_grader)]
 pub fn new_unit_test_grader() -> UnitTestGraderBuilder {
     UnitTestGraderBuilder {
-        req_name:         None,
-        out_of:           None,
-        project:          None,
-        target_test:      Vec::new(),
-        target_class:     Vec::new(),
-        excluded_methods: Vec::new(),
-        avoid_calls_to:   Vec::new(),
+        req_name:               None,
+        out_of:                 None,
+        project:                None,
+        target_test:            Vec::new(),
+        target_class:           Vec::new(),
+        excluded_methods:       Vec::new(),
+        avoid_calls_to:         Vec::new(),
+        default_avoid_calls_to: None,
+        pit_args:               Vec::new(),
+        active_retrieval:       None,
+    }
+}
+
+/// Free constructor: alias for `new_by_unit_test_grader`, mirroring the
+/// Python module's `new_test_grader`.
+#[rune::function(path = new_test_grader)]
+pub fn new_test_grader() -> ByUnitTestGraderBuilder {
+    new_by_unit_test_grader()
+}
+
+/// Free constructor: alias for `new_unit_test_grader` with a name that says
+/// what the grader measures.
+#[rune::function(path = new_mutation_grader)]
+pub fn new_mutation_grader() -> UnitTestGraderBuilder {
+    new_unit_test_grader()
+}
+
+/// Free constructor: start building a JaCoCo coverage grader.
+#[rune::function(path = new_coverage_grader)]
+pub fn new_coverage_grader() -> CoverageGraderBuilder {
+    CoverageGraderBuilder {
+        req_name:       None,
+        out_of:         None,
+        project:        None,
+        target_classes: Vec::new(),
+        test_files:     Vec::new(),
+        min_coverage:   None,
+        metric:         None,
+        retrieval:      None,
     }
 }
 
@@ -85,10 +164,15 @@ pub fn new_unit_test_grader() -> UnitTestGraderBuilder {
 #[rune::function(path = new_by_hidden_test_grader)]
 pub fn new_by_hidden_test_grader() -> ByHiddenTestGraderBuilder {
     ByHiddenTestGraderBuilder {
-        url:             None,
-        test_class_name: None,
-        out_of:          None,
-        req_name:        None,
+        url:                   None,
+        test_class_name:       None,
+        out_of:                None,
+        req_name:              None,
+        project:               None,
+        classpath_overlay:     Vec::new(),
+        download_timeout_secs: None,
+        max_download_bytes:    None,
+        active_retrieval:      None,
     }
 }
 
@@ -103,6 +187,201 @@ pub fn new_diff_grader() -> DiffGraderBuilder {
         cases:               Vec::new(),
         ignore_case:         false,
         preserve_whitespace: false,
+        strip_ansi:          false,
+        normalizers:         Vec::new(),
+        case_separator:      None,
+        case_label:          None,
+        line_ending:         None,
+        stdout_only:         false,
+        reference_file:      None,
+        timeout:             None,
+        args:                Vec::new(),
+        input_delay:         None,
+        active_retrieval:    None,
+    }
+}
+
+/// Free constructor: build a result for a requirement the script grades
+/// itself, e.g. by combining several checks. `grade` and `out_of` must be
+/// finite, and `out_of` must not be negative.
+#[rune::function(path = new_grade_result)]
+pub fn new_grade_result(
+    requirement: String,
+    grade: f64,
+    out_of: f64,
+    reason: String,
+) -> RuneResult<GradeResult> {
+    if !grade.is_finite() || !out_of.is_finite() || out_of < 0.0 {
+        return Err(host_err(format!(
+            "Invalid grade {grade}/{out_of} for `{requirement}`: both must be finite and out_of \
+             must not be negative"
+        )));
+    }
+
+    Ok(InnerGradeResult::builder()
+        .requirement(requirement)
+        .grade(grade::Grade::new(grade, out_of))
+        .reason(reason)
+        .build()
+        .into())
+}
+
+/// Free constructor: start building a regex output grader.
+#[rune::function(path = new_regex_grader)]
+pub fn new_regex_grader() -> RegexGraderBuilder {
+    RegexGraderBuilder {
+        req_name:   None,
+        out_of:     None,
+        project:    None,
+        file:       None,
+        cases:      Vec::new(),
+        full_match: false,
+    }
+}
+
+/// Free constructor: start building an output format constraint grader.
+#[rune::function(path = new_format_constraint_grader)]
+pub fn new_format_constraint_grader() -> FormatConstraintGraderBuilder {
+    FormatConstraintGraderBuilder {
+        req_name:     None,
+        out_of:       None,
+        project:      None,
+        file:         None,
+        input:        None,
+        line_count:   None,
+        line_pattern: None,
+    }
+}
+
+/// Free constructor: start building an input/output oracle grader.
+#[rune::function(path = new_oracle_grader)]
+pub fn new_oracle_grader() -> OracleGraderBuilder {
+    OracleGraderBuilder {
+        req_name: None,
+        out_of:   None,
+        project:  None,
+        file:     None,
+        inputs:   Vec::new(),
+        oracle:   None,
+    }
+}
+
+/// Free constructor: start building a cyclomatic complexity grader.
+#[rune::function(path = new_complexity_grader)]
+pub fn new_complexity_grader() -> ComplexityGraderBuilder {
+    ComplexityGraderBuilder {
+        req_name:       None,
+        out_of:         None,
+        project:        None,
+        files:          Vec::new(),
+        max_complexity: None,
+        penalty:        None,
+    }
+}
+
+/// Free constructor: start building a method length grader.
+#[rune::function(path = new_method_length_grader)]
+pub fn new_method_length_grader() -> MethodLengthGraderBuilder {
+    MethodLengthGraderBuilder {
+        req_name:  None,
+        out_of:    None,
+        project:   None,
+        files:     Vec::new(),
+        max_lines: None,
+        penalty:   None,
+    }
+}
+
+/// Free constructor: start building a magic-number grader.
+#[rune::function(path = new_magic_number_grader)]
+pub fn new_magic_number_grader() -> MagicNumberGraderBuilder {
+    MagicNumberGraderBuilder {
+        req_name:        None,
+        out_of:          None,
+        project:         None,
+        files:           Vec::new(),
+        allowed_numbers: grade::DEFAULT_ALLOWED_NUMBERS
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        penalty:         None,
+    }
+}
+
+/// Free constructor: start building a placeholder grader.
+#[rune::function(path = new_placeholder_grader)]
+pub fn new_placeholder_grader() -> PlaceholderGraderBuilder {
+    PlaceholderGraderBuilder {
+        req_name:         None,
+        out_of:           None,
+        project:          None,
+        files:            Vec::new(),
+        comment_patterns: grade::DEFAULT_COMMENT_PATTERNS
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        throw_patterns:   grade::DEFAULT_THROW_PATTERNS
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        penalty:          None,
+    }
+}
+
+/// Free constructor: start building a debug-print grader.
+#[rune::function(path = new_debug_print_grader)]
+pub fn new_debug_print_grader() -> DebugPrintGraderBuilder {
+    DebugPrintGraderBuilder {
+        req_name:        None,
+        out_of:          None,
+        project:         None,
+        files:           Vec::new(),
+        allowed_methods: grade::DEFAULT_OUTPUT_METHODS
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        penalty:         None,
+    }
+}
+
+/// Free constructor: start building a missing `@Override` grader.
+#[rune::function(path = new_override_grader)]
+pub fn new_override_grader() -> OverrideGraderBuilder {
+    OverrideGraderBuilder {
+        req_name: None,
+        out_of:   None,
+        project:  None,
+        files:    Vec::new(),
+        penalty:  None,
+    }
+}
+
+/// Free constructor: start building a field encapsulation grader.
+#[rune::function(path = new_encapsulation_grader)]
+pub fn new_encapsulation_grader() -> EncapsulationGraderBuilder {
+    EncapsulationGraderBuilder {
+        req_name:        None,
+        out_of:          None,
+        project:         None,
+        files:           Vec::new(),
+        allow_constants: None,
+        allow_final:     None,
+        allowed_fields:  Vec::new(),
+        penalty:         None,
+    }
+}
+
+/// Free constructor: start building a test assertion density grader.
+#[rune::function(path = new_assertion_density_grader)]
+pub fn new_assertion_density_grader() -> AssertionDensityGraderBuilder {
+    AssertionDensityGraderBuilder {
+        req_name:           None,
+        out_of:             None,
+        project:            None,
+        files:              Vec::new(),
+        min_assertions:     None,
+        assertion_prefixes: None,
+        penalty:            None,
     }
 }
 
@@ -120,6 +399,14 @@ pub fn new_query_grader() -> QueryGraderBuilder {
     }
 }
 
+/// Free constructor: load and validate the `.scm` queries in `dir`.
+#[rune::function(path = load_queries)]
+pub fn load_queries(dir: String) -> RuneResult<QueryLibrary> {
+    crate::java::queries::QueryLibrary::load(dir)
+        .map(|inner| QueryLibrary { inner })
+        .map_err(|e| host_err(format!("{e:#}")))
+}
+
 // Convenience constructors live on ProjectPaths for Rune ergonomics.
 
 /// Map host errors into Rune errors with readable messages.
@@ -127,6 +414,14 @@ fn host_err<E: std::fmt::Display>(e: E) -> RuneError {
     RuneError::msg(e.to_string())
 }
 
+/// Records a finished grader's result as progress and applies the fail-fast
+/// policy to it.
+fn after_grade(result: InnerGradeResult) -> RuneResult<GradeResult> {
+    crate::scripting::record_progress(&result);
+    crate::scripting::check_fail_fast(&result).map_err(host_err)?;
+    Ok(GradeResult::from(result))
+}
+
 /// Helper to extract required builder fields without panicking.
 fn take_required<T>(opt: Option<T>, field: &str) -> RuneResult<T> {
     opt.ok_or_else(|| host_err(format!("Missing required field: {field}")))
@@ -137,7 +432,85 @@ fn take_required<T>(opt: Option<T>, field: &str) -> RuneResult<T> {
 #[rune(item = ::umm::java)]
 pub struct Project {
     /// Underlying Rust project instance.
-    inner: crate::java::Project,
+    inner:     crate::java::Project,
+    /// Worktree backing a project loaded at a git ref; kept alive with it.
+    _worktree: Option<Arc<Worktree>>,
+    /// Extracted archive backing a project loaded from a zip; kept alive with
+    /// it.
+    _unzipped: Option<Arc<Unzipped>>,
+}
+
+impl From<crate::java::Project> for Project {
+    fn from(inner: crate::java::Project) -> Self {
+        Self {
+            inner,
+            _worktree: None,
+            _unzipped: None,
+        }
+    }
+}
+
+impl Project {
+    /// Stable hex digest of the project's source code.
+    pub fn fingerprint(&self) -> String {
+        self.inner.fingerprint()
+    }
+
+    /// Hex digest of the project's source code, ignoring whitespace.
+    pub fn normalized_fingerprint(&self) -> String {
+        self.inner.normalized_fingerprint()
+    }
+
+    /// Proper names of the project's files keyed by kind (`interface`,
+    /// `class`, `class_with_main`, `test`); kinds with no files are absent.
+    pub fn files_by_type(&self) -> HashMap<String, Vec<String>> {
+        self.inner
+            .files_by_type()
+            .into_iter()
+            .map(|(kind, names)| (kind.as_str().to_string(), names))
+            .collect()
+    }
+
+    /// Source code of the file resolved from `name`.
+    pub fn open_file(&self, name: String) -> RuneResult<String> {
+        let file = self
+            .inner
+            .identify(&name)
+            .map_err(|e| host_err(format!("Cannot open `{name}`: {e}")))?;
+        Ok(file.parser().code().to_string())
+    }
+
+    /// Upload the project's source code and return the URL where it can be
+    /// viewed.
+    pub async fn serve_project_code(self) -> RuneResult<String> {
+        self.inner
+            .serve_project_code_async()
+            .await
+            .map_err(|e| host_err(format!("{e:#}")))
+    }
+}
+
+/// Queries loaded from `.scm` files at runtime, referenced by file stem.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct QueryLibrary {
+    /// Underlying Rust query library.
+    inner: crate::java::queries::QueryLibrary,
+}
+
+impl QueryLibrary {
+    /// Source of the query named `name`.
+    pub fn get(&self, name: String) -> RuneResult<String> {
+        self.inner
+            .get(&name)
+            .map(str::to_string)
+            .ok_or_else(|| host_err(format!("No query named `{name}` was loaded")))
+    }
+
+    /// Names of all loaded queries, sorted.
+    pub fn names(&self) -> Vec<String> {
+        self.inner.names()
+    }
 }
 
 /// Workspace path set bridged into Rune.
@@ -153,19 +526,21 @@ pub struct ProjectPaths {
 #[rune(item = ::umm::java)]
 pub struct ProjectPathsBuilder {
     /// Project root directory.
-    root_dir:   Option<PathBuf>,
+    root_dir:          Option<PathBuf>,
     /// Source directory (defaults to `root/src`).
-    source_dir: Option<PathBuf>,
+    source_dir:        Option<PathBuf>,
     /// Build output directory (defaults to `root/target`).
-    build_dir:  Option<PathBuf>,
+    build_dir:         Option<PathBuf>,
     /// Test sources directory (defaults to `root/test`).
-    test_dir:   Option<PathBuf>,
+    test_dir:          Option<PathBuf>,
     /// JAR library directory (defaults to `root/lib`).
-    lib_dir:    Option<PathBuf>,
+    lib_dir:           Option<PathBuf>,
     /// UMM metadata directory (defaults to `root/.umm`).
-    umm_dir:    Option<PathBuf>,
+    umm_dir:           Option<PathBuf>,
     /// Report directory (defaults to `root/test_reports`).
-    report_dir: Option<PathBuf>,
+    report_dir:        Option<PathBuf>,
+    /// Precompiled reference jars or class directories.
+    reference_classes: Vec<PathBuf>,
 }
 
 impl ProjectPathsBuilder {
@@ -204,10 +579,21 @@ impl ProjectPathsBuilder {
         self.report_dir = Some(PathBuf::from(path));
         self
     }
+    /// Compile and run against precompiled reference jars or class
+    /// directories.
+    pub fn reference_classes(mut self, entries: Vec<String>) -> Self {
+        self.reference_classes = entries.into_iter().map(PathBuf::from).collect();
+        self
+    }
 
-    /// Build a concrete `ProjectPaths`.
+    /// Build a concrete `ProjectPaths`. A relative root resolves against the
+    /// current project's root.
     pub fn build(self) -> RuneResult<ProjectPaths> {
-        let root = self.root_dir.unwrap_or_else(|| PathBuf::from("."));
+        let current = crate::scripting::project_paths().root_dir().to_path_buf();
+        let root = match self.root_dir {
+            Some(dir) => current.join(dir),
+            None => current,
+        };
 
         let paths = crate::java::paths::ProjectPaths::from_parts(
             root,
@@ -217,7 +603,8 @@ impl ProjectPathsBuilder {
             self.lib_dir,
             self.umm_dir,
             self.report_dir,
-        );
+        )
+        .with_reference_classes(self.reference_classes);
 
         Ok(ProjectPaths { inner: paths })
     }
@@ -261,6 +648,24 @@ impl GradeResult {
             .and_then(|msgs| serde_json::to_string_pretty(msgs).ok())
     }
 
+    /// Whether this requirement is extra credit.
+    pub fn is_extra_credit(&self) -> bool {
+        self.inner.is_extra_credit()
+    }
+
+    /// Seconds the grader took to run, if it was timed.
+    pub fn elapsed_secs(&self) -> Option<f64> {
+        self.inner.elapsed().map(|elapsed| elapsed.as_secs_f64())
+    }
+
+    /// Mark this requirement as extra credit, adding to the total without
+    /// raising its maximum.
+    pub fn extra_credit(self, extra_credit: bool) -> Self {
+        Self {
+            inner: self.inner.with_extra_credit(extra_credit),
+        }
+    }
+
     /// Consume the wrapper and return the underlying Rust result.
     pub(crate) fn into_inner(self) -> InnerGradeResult {
         self.inner
@@ -279,7 +684,11 @@ impl DiffCase {
     /// Create a new diff case with expected output and optional input.
     pub fn new(expected: String, input: Option<String>) -> Self {
         Self {
-            inner: grade::DiffCase { expected, input },
+            inner: grade::DiffCase {
+                expected,
+                input,
+                args: None,
+            },
         }
     }
 
@@ -299,19 +708,57 @@ pub struct DocsGrader;
 #[rune(item = ::umm::java)]
 pub struct DocsGraderBuilder {
     /// Project to grade.
-    project:  Option<Project>,
+    project:          Option<Project>,
     /// Source files to lint.
-    files:    Vec<String>,
+    files:            Vec<String>,
     /// Requirement name.
-    req_name: Option<String>,
+    req_name:         Option<String>,
     /// Maximum score.
-    out_of:   Option<f64>,
+    out_of:           Option<f64>,
     /// Penalty per violation.
-    penalty:  Option<f64>,
+    penalty:          Option<f64>,
+    /// How penalties accumulate across violations.
+    penalty_mode:     Option<PenaltyMode>,
+    /// Per-grader override of the global active-retrieval toggle.
+    active_retrieval: Option<bool>,
 }
 
 impl DocsGrader {}
 
+/// Penalty curve applied by the docs grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct PenaltyMode {
+    /// Wrapped Rust penalty mode.
+    inner: grade::PenaltyMode,
+}
+
+impl PenaltyMode {
+    #[rune::function(path = PenaltyMode::linear)]
+    /// Deduct the penalty once per violation.
+    pub fn linear() -> Self {
+        Self {
+            inner: grade::PenaltyMode::Linear,
+        }
+    }
+
+    #[rune::function(path = PenaltyMode::capped)]
+    /// Deduct the penalty once per violation, up to `max` in total.
+    pub fn capped(max: f64) -> Self {
+        Self {
+            inner: grade::PenaltyMode::Capped(max),
+        }
+    }
+
+    #[rune::function(path = PenaltyMode::logarithmic)]
+    /// Deduct `penalty * log2(1 + violations)` in total.
+    pub fn logarithmic() -> Self {
+        Self {
+            inner: grade::PenaltyMode::Logarithmic,
+        }
+    }
+}
+
 impl DocsGraderBuilder {
     /// Set the project to grade.
     pub fn project(mut self, project: Ref<Project>) -> Self {
@@ -343,21 +790,37 @@ impl DocsGraderBuilder {
         self
     }
 
+    /// Set how penalties accumulate across violations.
+    pub fn penalty_mode(mut self, mode: PenaltyMode) -> Self {
+        self.penalty_mode = Some(mode);
+        self
+    }
+
+    /// Enable or disable active retrieval for this grader, overriding the
+    /// global setting.
+    pub fn active_retrieval(mut self, enabled: bool) -> Self {
+        self.active_retrieval = Some(enabled);
+        self
+    }
+
     /// Build with bon defaults and run; bon enforces required fields.
     pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
         let builder = grade::DocsGrader::builder()
             .project(take_required(self.project, "project")?.inner)
             .files(self.files)
             .req_name(take_required(self.req_name, "req_name")?)
             .out_of(take_required(self.out_of, "out_of")?)
-            .penalty(self.penalty.unwrap_or(3.0));
+            .penalty(self.penalty.unwrap_or(3.0))
+            .maybe_penalty_mode(self.penalty_mode.map(|mode| mode.inner))
+            .maybe_active_retrieval(self.active_retrieval);
 
         builder
             .build()
             .run()
             .await
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
@@ -371,15 +834,23 @@ pub struct ByUnitTestGrader;
 #[rune(item = ::umm::java)]
 pub struct ByUnitTestGraderBuilder {
     /// Test files to run.
-    test_files:     Vec<String>,
+    test_files:        Vec<String>,
     /// Expected test names.
-    expected_tests: Vec<String>,
+    expected_tests:    Vec<String>,
+    /// Whether tests beyond `expected_tests` are allowed.
+    allow_extra_tests: bool,
+    /// Minimum number of distinct test methods.
+    min_test_methods:  Option<usize>,
     /// Project to grade.
-    project:        Option<Project>,
+    project:           Option<Project>,
     /// Maximum score.
-    out_of:         Option<f64>,
+    out_of:            Option<f64>,
     /// Requirement name.
-    req_name:       Option<String>,
+    req_name:          Option<String>,
+    /// Extra jars (paths or URLs) for this run's classpath.
+    classpath_overlay: Vec<String>,
+    /// Per-grader override of the global active-retrieval toggle.
+    active_retrieval:  Option<bool>,
 }
 
 impl ByUnitTestGraderBuilder {
@@ -395,6 +866,18 @@ impl ByUnitTestGraderBuilder {
         self
     }
 
+    /// Allow tests beyond the expected set instead of reporting them.
+    pub fn allow_extra_tests(mut self, allow: bool) -> Self {
+        self.allow_extra_tests = allow;
+        self
+    }
+
+    /// Require at least `min` distinct test methods, passing or not.
+    pub fn min_test_methods(mut self, min: usize) -> Self {
+        self.min_test_methods = Some(min);
+        self
+    }
+
     /// Attach the project.
     pub fn project(mut self, project: Ref<Project>) -> Self {
         self.project = Some(project.clone());
@@ -413,11 +896,29 @@ impl ByUnitTestGraderBuilder {
         self
     }
 
+    /// Add jars (paths or URLs) to the classpath for this run only.
+    pub fn classpath_overlay(mut self, jars: Vec<String>) -> Self {
+        self.classpath_overlay = jars;
+        self
+    }
+
+    /// Enable or disable active retrieval for this grader, overriding the
+    /// global setting.
+    pub fn active_retrieval(mut self, enabled: bool) -> Self {
+        self.active_retrieval = Some(enabled);
+        self
+    }
+
     /// Run the grader.
     pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
         let builder = grade::ByUnitTestGrader::builder()
             .test_files(self.test_files)
             .expected_tests(self.expected_tests)
+            .allow_extra_tests(self.allow_extra_tests)
+            .maybe_min_test_methods(self.min_test_methods)
+            .classpath_overlay(self.classpath_overlay)
+            .maybe_active_retrieval(self.active_retrieval)
             .project(take_required(self.project, "project")?.inner)
             .out_of(take_required(self.out_of, "out_of")?)
             .req_name(take_required(self.req_name, "req_name")?);
@@ -426,8 +927,8 @@ impl ByUnitTestGraderBuilder {
             .build()
             .run()
             .await
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
@@ -441,19 +942,25 @@ pub struct UnitTestGrader;
 #[rune(item = ::umm::java)]
 pub struct UnitTestGraderBuilder {
     /// Requirement name.
-    req_name:         Option<String>,
+    req_name:               Option<String>,
     /// Maximum score.
-    out_of:           Option<f64>,
+    out_of:                 Option<f64>,
     /// Project under test.
-    project:          Option<Project>,
+    project:                Option<Project>,
     /// Test classes to run.
-    target_test:      Vec<String>,
+    target_test:            Vec<String>,
     /// Classes under mutation.
-    target_class:     Vec<String>,
+    target_class:           Vec<String>,
     /// Methods excluded from mutation.
-    excluded_methods: Vec<String>,
+    excluded_methods:       Vec<String>,
     /// Classes to avoid calling.
-    avoid_calls_to:   Vec<String>,
+    avoid_calls_to:         Vec<String>,
+    /// Replacement for the default avoided calls, if set.
+    default_avoid_calls_to: Option<Vec<String>>,
+    /// Additional PIT arguments.
+    pit_args:               Vec<String>,
+    /// Per-grader override of the global active-retrieval toggle.
+    active_retrieval:       Option<bool>,
 }
 
 impl UnitTestGrader {}
@@ -489,19 +996,46 @@ impl UnitTestGraderBuilder {
         self.excluded_methods = methods;
         self
     }
-    /// Avoid calls to the specified classes.
+    /// Avoid calls to the specified classes, in addition to the defaults.
     pub fn avoid_calls_to(mut self, classes: Vec<String>) -> Self {
         self.avoid_calls_to = classes;
         self
     }
+    /// Replace the default avoided calls; an empty list disables them.
+    pub fn default_avoid_calls_to(mut self, classes: Vec<String>) -> Self {
+        self.default_avoid_calls_to = Some(classes);
+        self
+    }
+    /// Append custom PIT arguments to the generated command line.
+    pub fn pit_args(mut self, args: Vec<String>) -> Self {
+        self.pit_args = args;
+        self
+    }
+    /// Enable or disable active retrieval for this grader, overriding the
+    /// global setting.
+    pub fn active_retrieval(mut self, enabled: bool) -> Self {
+        self.active_retrieval = Some(enabled);
+        self
+    }
 
     /// Run the mutation-testing grader.
     pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        grade::validate_pit_args(&self.pit_args).map_err(host_err)?;
+        let default_avoid_calls_to = self.default_avoid_calls_to.unwrap_or_else(|| {
+            grade::DEFAULT_AVOID_CALLS_TO
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        });
         let builder = grade::UnitTestGrader::builder()
             .target_test(self.target_test)
             .target_class(self.target_class)
             .excluded_methods(self.excluded_methods)
             .avoid_calls_to(self.avoid_calls_to)
+            .default_avoid_calls_to(default_avoid_calls_to)
+            .pit_args(self.pit_args)
+            .maybe_active_retrieval(self.active_retrieval)
             .req_name(take_required(self.req_name, "req_name")?)
             .out_of(take_required(self.out_of, "out_of")?)
             .project(take_required(self.project, "project")?.inner);
@@ -510,99 +1044,1022 @@ impl UnitTestGraderBuilder {
             .build()
             .run()
             .await
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
-/// Namespace for hidden-test grader.
+/// Namespace for coverage grader.
 #[derive(Any, Clone)]
 #[rune(item = ::umm::java)]
-pub struct ByHiddenTestGrader;
+pub struct CoverageGrader;
 
-/// Builder for hidden-test grader.
+/// Builder for coverage grader.
 #[derive(Any, Clone)]
 #[rune(item = ::umm::java)]
-pub struct ByHiddenTestGraderBuilder {
-    /// URL to fetch hidden tests.
-    url:             Option<String>,
-    /// Name of hidden test class.
-    test_class_name: Option<String>,
-    /// Maximum score.
-    out_of:          Option<f64>,
+pub struct CoverageGraderBuilder {
     /// Requirement name.
-    req_name:        Option<String>,
+    req_name:       Option<String>,
+    /// Maximum score.
+    out_of:         Option<f64>,
+    /// Project under test.
+    project:        Option<Project>,
+    /// Classes whose coverage is measured.
+    target_classes: Vec<String>,
+    /// Test classes to run.
+    test_files:     Vec<String>,
+    /// Coverage below which the grade is zero.
+    min_coverage:   Option<f64>,
+    /// Counter name: `"line"`, `"branch"`, or `"instruction"`.
+    metric:         Option<String>,
+    /// Snippet windows for the uncovered-line context.
+    retrieval:      Option<HeuristicConfig>,
 }
 
-impl ByHiddenTestGrader {}
+impl CoverageGrader {}
 
-impl ByHiddenTestGraderBuilder {
-    /// Set URL to fetch hidden tests.
-    pub fn url(mut self, url: String) -> Self {
-        self.url = Some(url);
+impl CoverageGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
         self
     }
-    /// Set the test class name.
-    pub fn test_class_name(mut self, name: String) -> Self {
-        self.test_class_name = Some(name);
+    /// Set maximum score.
+    pub fn out_of(mut self, value: f64) -> Self {
+        self.out_of = Some(value);
         self
     }
-    /// Set maximum score.
-    pub fn out_of(mut self, out_of: f64) -> Self {
-        self.out_of = Some(out_of);
+    /// Set the project under test.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
         self
     }
-    /// Set requirement name.
-    pub fn req_name(mut self, name: String) -> Self {
-        self.req_name = Some(name);
+    /// Set the classes whose coverage is measured.
+    pub fn target_classes(mut self, classes: Vec<String>) -> Self {
+        self.target_classes = classes;
+        self
+    }
+    /// Set the test classes to run.
+    pub fn test_files(mut self, files: Vec<String>) -> Self {
+        self.test_files = files;
+        self
+    }
+    /// Set the coverage, from 0 to 1, below which the grade is zero.
+    pub fn min_coverage(mut self, min: f64) -> Self {
+        self.min_coverage = Some(min);
+        self
+    }
+    /// Measure `"line"` (default), `"branch"`, or `"instruction"` coverage.
+    pub fn metric(mut self, metric: String) -> Self {
+        self.metric = Some(metric);
+        self
+    }
+    /// Use `config` for the uncovered-line context instead of the global
+    /// retrieval settings.
+    pub fn retrieval(mut self, config: HeuristicConfig) -> Self {
+        self.retrieval = Some(config);
         self
     }
 
-    /// Run the hidden-test grader.
+    /// Run the coverage grader.
     pub async fn run(self) -> RuneResult<GradeResult> {
-        let builder = grade::ByHiddenTestGrader::builder()
-            .url(take_required(self.url, "url")?)
-            .test_class_name(take_required(self.test_class_name, "test_class_name")?)
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::CoverageGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
             .out_of(take_required(self.out_of, "out_of")?)
-            .req_name(take_required(self.req_name, "req_name")?);
+            .project(take_required(self.project, "project")?.inner)
+            .target_classes(self.target_classes)
+            .test_files(self.test_files)
+            .maybe_min_coverage(self.min_coverage)
+            .maybe_metric(
+                self.metric
+                    .map(|metric| metric.parse::<grade::CoverageMetric>())
+                    .transpose()
+                    .map_err(host_err)?,
+            )
+            .maybe_retrieval(self.retrieval.map(|config| config.inner));
 
         builder
             .build()
             .run()
             .await
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
-/// Namespace for diff-based grader.
+/// Namespace for hidden-test grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct ByHiddenTestGrader;
+
+/// Builder for hidden-test grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct ByHiddenTestGraderBuilder {
+    /// URL to fetch hidden tests.
+    url:                   Option<String>,
+    /// Name of hidden test class.
+    test_class_name:       Option<String>,
+    /// Maximum score.
+    out_of:                Option<f64>,
+    /// Requirement name.
+    req_name:              Option<String>,
+    /// Project the hidden tests run against.
+    project:               Option<Project>,
+    /// Extra jars (paths or URLs) for this run's classpath.
+    classpath_overlay:     Vec<String>,
+    /// Seconds allowed for downloading the test source.
+    download_timeout_secs: Option<f64>,
+    /// Largest test source accepted, in bytes.
+    max_download_bytes:    Option<u64>,
+    /// Per-grader override of the global active-retrieval toggle.
+    active_retrieval:      Option<bool>,
+}
+
+impl ByHiddenTestGrader {}
+
+impl ByHiddenTestGraderBuilder {
+    /// Set URL to fetch hidden tests.
+    pub fn url(mut self, url: String) -> Self {
+        self.url = Some(url);
+        self
+    }
+    /// Set the test class name.
+    pub fn test_class_name(mut self, name: String) -> Self {
+        self.test_class_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set the project the hidden tests run against.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Add jars (paths or URLs) to the classpath for this run only.
+    pub fn classpath_overlay(mut self, jars: Vec<String>) -> Self {
+        self.classpath_overlay = jars;
+        self
+    }
+    /// Set how long the test source download may take, in seconds.
+    pub fn download_timeout_secs(mut self, secs: f64) -> Self {
+        self.download_timeout_secs = Some(secs);
+        self
+    }
+    /// Set the largest test source accepted, in bytes.
+    pub fn max_download_bytes(mut self, bytes: u64) -> Self {
+        self.max_download_bytes = Some(bytes);
+        self
+    }
+    /// Enable or disable active retrieval for this grader, overriding the
+    /// global setting.
+    pub fn active_retrieval(mut self, enabled: bool) -> Self {
+        self.active_retrieval = Some(enabled);
+        self
+    }
+
+    /// Run the hidden-test grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::ByHiddenTestGrader::builder()
+            .url(take_required(self.url, "url")?)
+            .project(take_required(self.project, "project")?.inner)
+            .classpath_overlay(self.classpath_overlay)
+            .maybe_download_timeout_secs(self.download_timeout_secs)
+            .maybe_max_download_bytes(self.max_download_bytes.map(|bytes| bytes as usize))
+            .maybe_active_retrieval(self.active_retrieval)
+            .test_class_name(take_required(self.test_class_name, "test_class_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .req_name(take_required(self.req_name, "req_name")?);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for diff-based grader.
 #[derive(Any, Clone)]
 #[rune(item = ::umm::java)]
 pub struct DiffGrader;
 
-/// Builder for diff-based grader.
+/// Builder for diff-based grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct DiffGraderBuilder {
+    /// Requirement name.
+    req_name:            Option<String>,
+    /// Maximum score.
+    out_of:              Option<f64>,
+    /// Project to grade.
+    project:             Option<Project>,
+    /// File to execute.
+    file:                Option<String>,
+    /// Expected/actual cases.
+    cases:               Vec<grade::DiffCase>,
+    /// Whether to ignore case.
+    ignore_case:         bool,
+    /// Whether to preserve whitespace.
+    preserve_whitespace: bool,
+    /// Whether to strip ANSI escape sequences from program output.
+    strip_ansi:          bool,
+    /// Normalization step names applied after the flags, in order.
+    normalizers:         Vec<String>,
+    /// Text placed between failing cases in the prompt.
+    case_separator:      Option<String>,
+    /// Heading template placed above each failing case.
+    case_label:          Option<String>,
+    /// Line ending for stdin, `"lf"` or `"crlf"`.
+    line_ending:         Option<String>,
+    /// Whether to compare only stdout.
+    stdout_only:         bool,
+    /// Reference solution whose output becomes each case's expectation.
+    reference_file:      Option<String>,
+    /// Seconds each case may run before it is killed.
+    timeout:             Option<f64>,
+    /// Arguments passed to `main` for cases without their own.
+    args:                Vec<String>,
+    /// Seconds to wait before writing each input line.
+    input_delay:         Option<f64>,
+    /// Per-grader override of the global active-retrieval toggle.
+    active_retrieval:    Option<bool>,
+}
+
+impl DiffGrader {}
+
+impl DiffGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set file to run against.
+    pub fn file(mut self, file: String) -> Self {
+        self.file = Some(file);
+        self
+    }
+    /// Provide expected/actual cases.
+    pub fn cases(mut self, cases: Vec<(String, Option<String>)>) -> Self {
+        self.cases = cases.into_iter().map(grade::DiffCase::from).collect();
+        self
+    }
+    /// Set the arguments passed to `main` for every case without its own.
+    pub fn args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+        self
+    }
+    /// Add a case that runs `main` with `args` instead of the grader's.
+    pub fn expect_with_args(mut self, expected: String, args: Vec<String>) -> Self {
+        self.cases
+            .push(grade::DiffCase::new(expected).with_args(args));
+        self
+    }
+    /// Add a case whose stdin is `lines`, one per prompt the program reads.
+    pub fn expect_with_inputs(mut self, expected: String, lines: Vec<String>) -> Self {
+        self.cases
+            .push(grade::DiffCase::new(expected).with_inputs(lines));
+        self
+    }
+    /// Set how many seconds to wait before writing each line of input.
+    pub fn input_delay(mut self, seconds: f64) -> Self {
+        self.input_delay = Some(seconds);
+        self
+    }
+    /// Toggle case-insensitive comparison.
+    pub fn ignore_case(mut self, ignore: bool) -> Self {
+        self.ignore_case = ignore;
+        self
+    }
+    /// Preserve whitespace differences.
+    pub fn preserve_whitespace(mut self, preserve: bool) -> Self {
+        self.preserve_whitespace = preserve;
+        self
+    }
+    /// Strip ANSI escape sequences (e.g. colours) from program output.
+    pub fn strip_ansi(mut self, strip: bool) -> Self {
+        self.strip_ansi = strip;
+        self
+    }
+    /// Set normalization steps by name, e.g. `"collapse_whitespace"` or
+    /// `"sort_lines"`, applied in order after the flags.
+    pub fn normalizers(mut self, steps: Vec<String>) -> Self {
+        self.normalizers = steps;
+        self
+    }
+    /// Set the text placed between failing cases in the prompt.
+    pub fn case_separator(mut self, separator: String) -> Self {
+        self.case_separator = Some(separator);
+        self
+    }
+    /// Set the per-case heading; `{n}` becomes the case number and an empty
+    /// label disables headings.
+    pub fn case_label(mut self, label: String) -> Self {
+        self.case_label = Some(label);
+        self
+    }
+    /// Set the stdin line ending, `"lf"` (default) or `"crlf"`.
+    pub fn line_ending(mut self, ending: String) -> Self {
+        self.line_ending = Some(ending);
+        self
+    }
+    /// Compare only stdout, showing stderr in feedback instead.
+    pub fn stdout_only(mut self, stdout_only: bool) -> Self {
+        self.stdout_only = stdout_only;
+        self
+    }
+    /// Run a reference solution on each case's input and expect its output
+    /// instead of the case's expected string.
+    pub fn reference_file(mut self, file: String) -> Self {
+        self.reference_file = Some(file);
+        self
+    }
+    /// Add a case per stdin input, for use with `reference_file`.
+    pub fn inputs(mut self, inputs: Vec<String>) -> Self {
+        self.cases.extend(
+            inputs
+                .into_iter()
+                .map(|input| grade::DiffCase::new(String::new()).with_input(input)),
+        );
+        self
+    }
+    /// Set how many seconds each case may run before it is killed.
+    pub fn timeout(mut self, seconds: f64) -> Self {
+        self.timeout = Some(seconds);
+        self
+    }
+    /// Enable or disable active retrieval for this grader, overriding the
+    /// global setting.
+    pub fn active_retrieval(mut self, enabled: bool) -> Self {
+        self.active_retrieval = Some(enabled);
+        self
+    }
+
+    /// Run the diff grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::DiffGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .file(take_required(self.file, "file")?)
+            .cases(self.cases)
+            .args(self.args)
+            .ignore_case(self.ignore_case)
+            .preserve_whitespace(self.preserve_whitespace)
+            .strip_ansi(self.strip_ansi)
+            .normalizers(
+                self.normalizers
+                    .iter()
+                    .map(|step| step.parse::<grade::Normalizer>())
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .map_err(host_err)?,
+            )
+            .maybe_case_separator(self.case_separator)
+            .maybe_case_label(self.case_label)
+            .maybe_line_ending(
+                self.line_ending
+                    .map(|ending| ending.parse::<crate::java::LineEnding>())
+                    .transpose()
+                    .map_err(host_err)?,
+            )
+            .stdout_only(self.stdout_only)
+            .maybe_reference_file(self.reference_file)
+            .maybe_timeout(
+                self.timeout
+                    .map(Duration::try_from_secs_f64)
+                    .transpose()
+                    .map_err(host_err)?,
+            )
+            .maybe_input_delay(
+                self.input_delay
+                    .map(Duration::try_from_secs_f64)
+                    .transpose()
+                    .map_err(host_err)?,
+            )
+            .maybe_active_retrieval(self.active_retrieval);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for regex output grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct RegexGrader;
+
+/// Builder for regex output grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct RegexGraderBuilder {
+    /// Requirement name.
+    req_name:   Option<String>,
+    /// Maximum score.
+    out_of:     Option<f64>,
+    /// Project to grade.
+    project:    Option<Project>,
+    /// File to execute.
+    file:       Option<String>,
+    /// Pattern/input cases.
+    cases:      Vec<(String, Option<String>)>,
+    /// Whether the whole output must match.
+    full_match: bool,
+}
+
+impl RegexGrader {}
+
+impl RegexGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set file to run against.
+    pub fn file(mut self, file: String) -> Self {
+        self.file = Some(file);
+        self
+    }
+    /// Provide pattern/input cases.
+    pub fn cases(mut self, cases: Vec<(String, Option<String>)>) -> Self {
+        self.cases = cases;
+        self
+    }
+    /// Require the whole output to match rather than any part of it.
+    pub fn full_match(mut self, full: bool) -> Self {
+        self.full_match = full;
+        self
+    }
+
+    /// Run the regex grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::RegexGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .file(take_required(self.file, "file")?)
+            .cases(self.cases)
+            .full_match(self.full_match);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for output format constraint grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct FormatConstraintGrader;
+
+/// Builder for output format constraint grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct FormatConstraintGraderBuilder {
+    /// Requirement name.
+    req_name:     Option<String>,
+    /// Maximum score.
+    out_of:       Option<f64>,
+    /// Project to grade.
+    project:      Option<Project>,
+    /// File to execute.
+    file:         Option<String>,
+    /// Optional stdin.
+    input:        Option<String>,
+    /// Exact number of output lines.
+    line_count:   Option<usize>,
+    /// Pattern every output line must match.
+    line_pattern: Option<String>,
+}
+
+impl FormatConstraintGrader {}
+
+impl FormatConstraintGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set file to run against.
+    pub fn file(mut self, file: String) -> Self {
+        self.file = Some(file);
+        self
+    }
+    /// Provide stdin for the run.
+    pub fn input(mut self, input: String) -> Self {
+        self.input = Some(input);
+        self
+    }
+    /// Require exactly this many lines of output.
+    pub fn line_count(mut self, count: usize) -> Self {
+        self.line_count = Some(count);
+        self
+    }
+    /// Require every output line to match this pattern in full.
+    pub fn line_pattern(mut self, pattern: String) -> Self {
+        self.line_pattern = Some(pattern);
+        self
+    }
+
+    /// Run the format constraint grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::FormatConstraintGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .file(take_required(self.file, "file")?)
+            .maybe_input(self.input)
+            .maybe_line_count(self.line_count)
+            .maybe_line_pattern(self.line_pattern);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for input/output oracle grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct OracleGrader;
+
+/// Builder for input/output oracle grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct OracleGraderBuilder {
+    /// Requirement name.
+    req_name: Option<String>,
+    /// Maximum score.
+    out_of:   Option<f64>,
+    /// Project to grade.
+    project:  Option<Project>,
+    /// File to execute.
+    file:     Option<String>,
+    /// Stdin for each case.
+    inputs:   Vec<String>,
+    /// Property every output must satisfy.
+    oracle:   Option<grade::Oracle>,
+}
+
+impl OracleGrader {}
+
+impl OracleGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set file to run against.
+    pub fn file(mut self, file: String) -> Self {
+        self.file = Some(file);
+        self
+    }
+    /// Provide stdin for each case.
+    pub fn inputs(mut self, inputs: Vec<String>) -> Self {
+        self.inputs = inputs;
+        self
+    }
+    /// Require each output to be the input's numbers in ascending order.
+    pub fn sorted_permutation(mut self) -> Self {
+        self.oracle = Some(grade::Oracle::sorted_permutation());
+        self
+    }
+    /// Check each output with a Rune closure `|input, output| -> bool`;
+    /// `name` describes the property in reasons.
+    pub fn property(mut self, name: String, check: Function) -> RuneResult<Self> {
+        let check = check.into_sync().map_err(host_err)?;
+        self.oracle = Some(grade::Oracle::new(name.clone(), move |input, output| {
+            match check
+                .call::<bool>((input.to_string(), output.to_string()))
+                .into_result()
+            {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(format!("output is not the {name}")),
+                Err(e) => Err(format!("the oracle failed: {e}")),
+            }
+        }));
+        Ok(self)
+    }
+
+    /// Run the oracle grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::OracleGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .file(take_required(self.file, "file")?)
+            .inputs(self.inputs)
+            .oracle(take_required(self.oracle, "oracle")?);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for cyclomatic complexity grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct ComplexityGrader;
+
+/// Builder for cyclomatic complexity grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct ComplexityGraderBuilder {
+    /// Requirement name.
+    req_name:       Option<String>,
+    /// Maximum score.
+    out_of:         Option<f64>,
+    /// Project to grade.
+    project:        Option<Project>,
+    /// Files whose methods are measured.
+    files:          Vec<String>,
+    /// Highest complexity allowed without penalty.
+    max_complexity: Option<usize>,
+    /// Points deducted per offending method.
+    penalty:        Option<f64>,
+}
+
+impl ComplexityGrader {}
+
+impl ComplexityGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set the files whose methods are measured.
+    pub fn files(mut self, files: Vec<String>) -> Self {
+        self.files = files;
+        self
+    }
+    /// Set the highest complexity allowed without penalty.
+    pub fn max_complexity(mut self, max: usize) -> Self {
+        self.max_complexity = Some(max);
+        self
+    }
+    /// Set the points deducted per offending method.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        self.penalty = Some(penalty);
+        self
+    }
+
+    /// Run the complexity grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::ComplexityGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .files(self.files)
+            .maybe_max_complexity(self.max_complexity)
+            .maybe_penalty(self.penalty);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for method length grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct MethodLengthGrader;
+
+/// Builder for method length grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct MethodLengthGraderBuilder {
+    /// Requirement name.
+    req_name:  Option<String>,
+    /// Maximum score.
+    out_of:    Option<f64>,
+    /// Project to grade.
+    project:   Option<Project>,
+    /// Files whose methods are measured.
+    files:     Vec<String>,
+    /// Most lines a method may span without penalty.
+    max_lines: Option<usize>,
+    /// Points deducted per offending method.
+    penalty:   Option<f64>,
+}
+
+impl MethodLengthGrader {}
+
+impl MethodLengthGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set the files whose methods are measured.
+    pub fn files(mut self, files: Vec<String>) -> Self {
+        self.files = files;
+        self
+    }
+    /// Set the most lines a method may span without penalty.
+    pub fn max_lines(mut self, max: usize) -> Self {
+        self.max_lines = Some(max);
+        self
+    }
+    /// Set the points deducted per offending method.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        self.penalty = Some(penalty);
+        self
+    }
+
+    /// Run the method length grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::MethodLengthGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .files(self.files)
+            .maybe_max_lines(self.max_lines)
+            .maybe_penalty(self.penalty);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for debug-print grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct DebugPrintGrader;
+
+/// Builder for debug-print grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct DebugPrintGraderBuilder {
+    /// Requirement name.
+    req_name:        Option<String>,
+    /// Maximum score.
+    out_of:          Option<f64>,
+    /// Project to grade.
+    project:         Option<Project>,
+    /// Files scanned for prints.
+    files:           Vec<String>,
+    /// Methods allowed to print.
+    allowed_methods: Vec<String>,
+    /// Points deducted per stray print.
+    penalty:         Option<f64>,
+}
+
+impl DebugPrintGrader {}
+
+impl DebugPrintGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set the files scanned for prints.
+    pub fn files(mut self, files: Vec<String>) -> Self {
+        self.files = files;
+        self
+    }
+    /// Set the methods allowed to print, replacing the default `["main"]`.
+    pub fn allowed_methods(mut self, methods: Vec<String>) -> Self {
+        self.allowed_methods = methods;
+        self
+    }
+    /// Set the points deducted per stray print.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        self.penalty = Some(penalty);
+        self
+    }
+
+    /// Run the debug-print grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::DebugPrintGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .files(self.files)
+            .allowed_methods(self.allowed_methods)
+            .maybe_penalty(self.penalty);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for magic-number grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct MagicNumberGrader;
+
+/// Builder for magic-number grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct MagicNumberGraderBuilder {
+    /// Requirement name.
+    req_name:        Option<String>,
+    /// Maximum score.
+    out_of:          Option<f64>,
+    /// Project to grade.
+    project:         Option<Project>,
+    /// Files scanned for magic numbers.
+    files:           Vec<String>,
+    /// Numbers allowed inline.
+    allowed_numbers: Vec<String>,
+    /// Points deducted per magic number.
+    penalty:         Option<f64>,
+}
+
+impl MagicNumberGrader {}
+
+impl MagicNumberGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set the files scanned for magic numbers.
+    pub fn files(mut self, files: Vec<String>) -> Self {
+        self.files = files;
+        self
+    }
+    /// Set the numbers allowed inline, replacing the default `["0", "1"]`.
+    pub fn allowed_numbers(mut self, numbers: Vec<String>) -> Self {
+        self.allowed_numbers = numbers;
+        self
+    }
+    /// Set the points deducted per magic number.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        self.penalty = Some(penalty);
+        self
+    }
+
+    /// Run the magic-number grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::MagicNumberGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .files(self.files)
+            .allowed_numbers(self.allowed_numbers)
+            .maybe_penalty(self.penalty);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for placeholder grader.
 #[derive(Any, Clone)]
 #[rune(item = ::umm::java)]
-pub struct DiffGraderBuilder {
+pub struct PlaceholderGrader;
+
+/// Builder for placeholder grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct PlaceholderGraderBuilder {
     /// Requirement name.
-    req_name:            Option<String>,
+    req_name:         Option<String>,
     /// Maximum score.
-    out_of:              Option<f64>,
+    out_of:           Option<f64>,
     /// Project to grade.
-    project:             Option<Project>,
-    /// File to execute.
-    file:                Option<String>,
-    /// Expected/actual cases.
-    cases:               Vec<(String, Option<String>)>,
-    /// Whether to ignore case.
-    ignore_case:         bool,
-    /// Whether to preserve whitespace.
-    preserve_whitespace: bool,
+    project:          Option<Project>,
+    /// Files scanned for placeholders.
+    files:            Vec<String>,
+    /// Regexes matched against comment lines.
+    comment_patterns: Vec<String>,
+    /// Regexes matched against `throw` statements.
+    throw_patterns:   Vec<String>,
+    /// Points deducted per placeholder.
+    penalty:          Option<f64>,
 }
 
-impl DiffGrader {}
+impl PlaceholderGrader {}
 
-impl DiffGraderBuilder {
+impl PlaceholderGraderBuilder {
     /// Set requirement name.
     pub fn req_name(mut self, name: String) -> Self {
         self.req_name = Some(name);
@@ -618,44 +2075,298 @@ impl DiffGraderBuilder {
         self.project = Some(project.clone());
         self
     }
-    /// Set file to run against.
-    pub fn file(mut self, file: String) -> Self {
-        self.file = Some(file);
+    /// Set the files scanned for placeholders.
+    pub fn files(mut self, files: Vec<String>) -> Self {
+        self.files = files;
         self
     }
-    /// Provide expected/actual cases.
-    pub fn cases(mut self, cases: Vec<(String, Option<String>)>) -> Self {
-        self.cases = cases;
+    /// Set the comment regexes, replacing the default `TODO`/`FIXME`.
+    pub fn comment_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.comment_patterns = patterns;
         self
     }
-    /// Toggle case-insensitive comparison.
-    pub fn ignore_case(mut self, ignore: bool) -> Self {
-        self.ignore_case = ignore;
+    /// Set the `throw` regexes, replacing the default stub patterns.
+    pub fn throw_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.throw_patterns = patterns;
         self
     }
-    /// Preserve whitespace differences.
-    pub fn preserve_whitespace(mut self, preserve: bool) -> Self {
-        self.preserve_whitespace = preserve;
+    /// Set the points deducted per placeholder.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        self.penalty = Some(penalty);
         self
     }
 
-    /// Run the diff grader.
+    /// Run the placeholder grader.
     pub async fn run(self) -> RuneResult<GradeResult> {
-        let builder = grade::DiffGrader::builder()
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::PlaceholderGrader::builder()
             .req_name(take_required(self.req_name, "req_name")?)
             .out_of(take_required(self.out_of, "out_of")?)
             .project(take_required(self.project, "project")?.inner)
-            .file(take_required(self.file, "file")?)
-            .cases(self.cases)
-            .ignore_case(self.ignore_case)
-            .preserve_whitespace(self.preserve_whitespace);
+            .files(self.files)
+            .comment_patterns(self.comment_patterns)
+            .throw_patterns(self.throw_patterns)
+            .maybe_penalty(self.penalty);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for field encapsulation grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct EncapsulationGrader;
+
+/// Builder for field encapsulation grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct EncapsulationGraderBuilder {
+    /// Requirement name.
+    req_name:        Option<String>,
+    /// Maximum score.
+    out_of:          Option<f64>,
+    /// Project to grade.
+    project:         Option<Project>,
+    /// Files whose fields are checked.
+    files:           Vec<String>,
+    /// Whether non-private `static final` constants are allowed.
+    allow_constants: Option<bool>,
+    /// Whether non-private `final` fields are allowed.
+    allow_final:     Option<bool>,
+    /// Fields exempt from the check.
+    allowed_fields:  Vec<String>,
+    /// Points deducted per exposed field.
+    penalty:         Option<f64>,
+}
+
+impl EncapsulationGrader {}
+
+impl EncapsulationGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set the files whose fields are checked.
+    pub fn files(mut self, files: Vec<String>) -> Self {
+        self.files = files;
+        self
+    }
+    /// Allow or flag non-private `static final` constants.
+    pub fn allow_constants(mut self, allow: bool) -> Self {
+        self.allow_constants = Some(allow);
+        self
+    }
+    /// Allow or flag non-private `final` fields.
+    pub fn allow_final(mut self, allow: bool) -> Self {
+        self.allow_final = Some(allow);
+        self
+    }
+    /// Set the fields exempt from the check.
+    pub fn allowed_fields(mut self, fields: Vec<String>) -> Self {
+        self.allowed_fields = fields;
+        self
+    }
+    /// Set the points deducted per exposed field.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        self.penalty = Some(penalty);
+        self
+    }
+
+    /// Run the encapsulation grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::EncapsulationGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .files(self.files)
+            .maybe_allow_constants(self.allow_constants)
+            .maybe_allow_final(self.allow_final)
+            .allowed_fields(self.allowed_fields)
+            .maybe_penalty(self.penalty);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for missing `@Override` grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct OverrideGrader;
+
+/// Builder for missing `@Override` grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct OverrideGraderBuilder {
+    /// Requirement name.
+    req_name: Option<String>,
+    /// Maximum score.
+    out_of:   Option<f64>,
+    /// Project to grade.
+    project:  Option<Project>,
+    /// Files whose methods are checked.
+    files:    Vec<String>,
+    /// Points deducted per missing annotation.
+    penalty:  Option<f64>,
+}
+
+impl OverrideGrader {}
+
+impl OverrideGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set the files whose methods are checked.
+    pub fn files(mut self, files: Vec<String>) -> Self {
+        self.files = files;
+        self
+    }
+    /// Set the points deducted per missing annotation.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        self.penalty = Some(penalty);
+        self
+    }
+
+    /// Run the override grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::OverrideGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .files(self.files)
+            .maybe_penalty(self.penalty);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for test assertion density grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct AssertionDensityGrader;
+
+/// Builder for test assertion density grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::java)]
+pub struct AssertionDensityGraderBuilder {
+    /// Requirement name.
+    req_name:           Option<String>,
+    /// Maximum score.
+    out_of:             Option<f64>,
+    /// Project to grade.
+    project:            Option<Project>,
+    /// Test files whose tests are checked.
+    files:              Vec<String>,
+    /// Minimum assertions per test.
+    min_assertions:     Option<usize>,
+    /// Method-name prefixes counted as assertions.
+    assertion_prefixes: Option<Vec<String>>,
+    /// Points deducted per test below the minimum.
+    penalty:            Option<f64>,
+}
+
+impl AssertionDensityGrader {}
+
+impl AssertionDensityGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set the test files whose tests are checked.
+    pub fn files(mut self, files: Vec<String>) -> Self {
+        self.files = files;
+        self
+    }
+    /// Set the minimum assertions each test must make.
+    pub fn min_assertions(mut self, min: usize) -> Self {
+        self.min_assertions = Some(min);
+        self
+    }
+    /// Set the method-name prefixes counted as assertions.
+    pub fn assertion_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.assertion_prefixes = Some(prefixes);
+        self
+    }
+    /// Set the points deducted per test below the minimum.
+    pub fn penalty(mut self, penalty: f64) -> Self {
+        self.penalty = Some(penalty);
+        self
+    }
+
+    /// Run the assertion density grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let prefixes = self.assertion_prefixes.unwrap_or_else(|| {
+            grade::DEFAULT_ASSERTION_PREFIXES
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        });
+        let builder = grade::AssertionDensityGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .files(self.files)
+            .maybe_min_assertions(self.min_assertions)
+            .assertion_prefixes(prefixes)
+            .maybe_penalty(self.penalty);
 
         builder
             .build()
             .run()
             .await
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
@@ -755,6 +2466,17 @@ impl QueryGraderBuilder {
             .collect();
         self
     }
+    /// Append the library query named `name`, keeping `capture`.
+    pub fn named_query(
+        mut self,
+        library: Ref<QueryLibrary>,
+        name: String,
+        capture: String,
+    ) -> RuneResult<Self> {
+        let query = library.get(name)?;
+        self.queries.push((query, Some(capture)));
+        Ok(self)
+    }
     /// Apply a constraint to the queries.
     pub fn constraint(mut self, constraint: QueryConstraint) -> Self {
         self.constraint = Some(constraint);
@@ -768,6 +2490,7 @@ impl QueryGraderBuilder {
 
     /// Run the query grader.
     pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
         let queries: Vec<grade::Query> = self
             .queries
             .into_iter()
@@ -793,8 +2516,8 @@ impl QueryGraderBuilder {
         builder
             .build()
             .run()
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
@@ -807,7 +2530,7 @@ pub fn grade_all(results: Vec<GradeResult>) -> RuneResult<Vec<GradeResult>> {
 pub fn show_result(results: Vec<GradeResult>) -> RuneResult<()> {
     let config = crate::java::grade::gradescope::GradescopeConfig::default();
     let inner_results: Vec<_> = results.into_iter().map(|r| r.into_inner()).collect();
-    grade::show_result(inner_results, config).map_err(host_err)
+    grade::show_result(inner_results, config, &crate::scripting::project_paths()).map_err(host_err)
 }
 
 /// Render results alias.
@@ -821,7 +2544,8 @@ pub fn show_result_with_config(
     config: RuneGradescopeConfig,
 ) -> RuneResult<()> {
     let inner_results: Vec<_> = results.into_iter().map(|r| r.into_inner()).collect();
-    grade::show_result(inner_results, config.inner).map_err(host_err)
+    grade::show_result(inner_results, config.inner, &crate::scripting::project_paths())
+        .map_err(host_err)
 }
 
 /// Render results with config alias.
@@ -843,29 +2567,87 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<ProjectPathsBuilder>()?;
     module.ty::<DocsGrader>()?;
     module.ty::<DocsGraderBuilder>()?;
+    module.ty::<PenaltyMode>()?;
     module.ty::<ByUnitTestGrader>()?;
     module.ty::<ByUnitTestGraderBuilder>()?;
     module.ty::<UnitTestGrader>()?;
     module.ty::<UnitTestGraderBuilder>()?;
+    module.ty::<CoverageGrader>()?;
+    module.ty::<CoverageGraderBuilder>()?;
     module.ty::<ByHiddenTestGrader>()?;
     module.ty::<ByHiddenTestGraderBuilder>()?;
     module.ty::<DiffGrader>()?;
     module.ty::<DiffGraderBuilder>()?;
+    module.ty::<RegexGrader>()?;
+    module.ty::<RegexGraderBuilder>()?;
+    module.ty::<FormatConstraintGrader>()?;
+    module.ty::<FormatConstraintGraderBuilder>()?;
+    module.ty::<OracleGrader>()?;
+    module.ty::<OracleGraderBuilder>()?;
+    module.ty::<ComplexityGrader>()?;
+    module.ty::<ComplexityGraderBuilder>()?;
+    module.ty::<MethodLengthGrader>()?;
+    module.ty::<MethodLengthGraderBuilder>()?;
+    module.ty::<DebugPrintGrader>()?;
+    module.ty::<DebugPrintGraderBuilder>()?;
+    module.ty::<MagicNumberGrader>()?;
+    module.ty::<MagicNumberGraderBuilder>()?;
+    module.ty::<PlaceholderGrader>()?;
+    module.ty::<PlaceholderGraderBuilder>()?;
+    module.ty::<EncapsulationGrader>()?;
+    module.ty::<EncapsulationGraderBuilder>()?;
+    module.ty::<OverrideGrader>()?;
+    module.ty::<OverrideGraderBuilder>()?;
+    module.ty::<AssertionDensityGrader>()?;
+    module.ty::<AssertionDensityGraderBuilder>()?;
     module.ty::<QueryConstraint>()?;
     module.ty::<QueryGrader>()?;
     module.ty::<QueryGraderBuilder>()?;
+    module.ty::<QueryLibrary>()?;
+    module.associated_function("requirement", GradeResult::requirement)?;
+    module.associated_function("score", GradeResult::score)?;
+    module.associated_function("out_of", GradeResult::out_of)?;
     module.associated_function("prompt", GradeResult::prompt)?;
+    module.associated_function("is_extra_credit", GradeResult::is_extra_credit)?;
+    module.associated_function("elapsed_secs", GradeResult::elapsed_secs)?;
+    module.associated_function("extra_credit", GradeResult::extra_credit)?;
 
     // Free constructors.
     module.function_meta(new_project)?;
     module.function_meta(new_project_from_paths)?;
+    module.function_meta(new_project_at_ref)?;
+    module.function_meta(new_project_from_zip)?;
     module.function_meta(new_project_paths)?;
+    module.function_meta(new_grade_result)?;
     module.function_meta(new_docs_grader)?;
     module.function_meta(new_by_unit_test_grader)?;
     module.function_meta(new_unit_test_grader)?;
+    module.function_meta(new_test_grader)?;
+    module.function_meta(new_mutation_grader)?;
+    module.function_meta(new_coverage_grader)?;
     module.function_meta(new_by_hidden_test_grader)?;
     module.function_meta(new_diff_grader)?;
+    module.function_meta(new_regex_grader)?;
+    module.function_meta(new_format_constraint_grader)?;
+    module.function_meta(new_oracle_grader)?;
+    module.function_meta(new_complexity_grader)?;
+    module.function_meta(new_method_length_grader)?;
+    module.function_meta(new_debug_print_grader)?;
+    module.function_meta(new_magic_number_grader)?;
+    module.function_meta(new_placeholder_grader)?;
+    module.function_meta(new_encapsulation_grader)?;
+    module.function_meta(new_override_grader)?;
+    module.function_meta(new_assertion_density_grader)?;
     module.function_meta(new_query_grader)?;
+    module.function_meta(load_queries)?;
+
+    module.associated_function("fingerprint", Project::fingerprint)?;
+    module.associated_function("normalized_fingerprint", Project::normalized_fingerprint)?;
+    module.associated_function("open_file", Project::open_file)?;
+    module.associated_function("files_by_type", Project::files_by_type)?;
+    module.associated_function("serve_project_code", Project::serve_project_code)?;
+    module.associated_function("get", QueryLibrary::get)?;
+    module.associated_function("names", QueryLibrary::names)?;
 
     // Builder setters.
     module.associated_function("root_dir", ProjectPathsBuilder::root_dir)?;
@@ -875,6 +2657,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.associated_function("lib_dir", ProjectPathsBuilder::lib_dir)?;
     module.associated_function("umm_dir", ProjectPathsBuilder::umm_dir)?;
     module.associated_function("report_dir", ProjectPathsBuilder::report_dir)?;
+    module.associated_function("reference_classes", ProjectPathsBuilder::reference_classes)?;
     module.associated_function("build", ProjectPathsBuilder::build)?;
 
     module.associated_function("project", DocsGraderBuilder::project)?;
@@ -882,13 +2665,19 @@ pub fn module() -> Result<Module, ContextError> {
     module.associated_function("req_name", DocsGraderBuilder::req_name)?;
     module.associated_function("out_of", DocsGraderBuilder::out_of)?;
     module.associated_function("penalty", DocsGraderBuilder::penalty)?;
+    module.associated_function("penalty_mode", DocsGraderBuilder::penalty_mode)?;
+    module.associated_function("active_retrieval", DocsGraderBuilder::active_retrieval)?;
     module.associated_function("run", DocsGraderBuilder::run)?;
 
     module.associated_function("test_files", ByUnitTestGraderBuilder::test_files)?;
     module.associated_function("expected_tests", ByUnitTestGraderBuilder::expected_tests)?;
+    module.associated_function("allow_extra_tests", ByUnitTestGraderBuilder::allow_extra_tests)?;
+    module.associated_function("min_test_methods", ByUnitTestGraderBuilder::min_test_methods)?;
     module.associated_function("project", ByUnitTestGraderBuilder::project)?;
     module.associated_function("out_of", ByUnitTestGraderBuilder::out_of)?;
     module.associated_function("req_name", ByUnitTestGraderBuilder::req_name)?;
+    module.associated_function("classpath_overlay", ByUnitTestGraderBuilder::classpath_overlay)?;
+    module.associated_function("active_retrieval", ByUnitTestGraderBuilder::active_retrieval)?;
     module.associated_function("run", ByUnitTestGraderBuilder::run)?;
 
     module.associated_function("req_name", UnitTestGraderBuilder::req_name)?;
@@ -898,12 +2687,37 @@ pub fn module() -> Result<Module, ContextError> {
     module.associated_function("target_class", UnitTestGraderBuilder::target_class)?;
     module.associated_function("excluded_methods", UnitTestGraderBuilder::excluded_methods)?;
     module.associated_function("avoid_calls_to", UnitTestGraderBuilder::avoid_calls_to)?;
+    module.associated_function(
+        "default_avoid_calls_to",
+        UnitTestGraderBuilder::default_avoid_calls_to,
+    )?;
+    module.associated_function("pit_args", UnitTestGraderBuilder::pit_args)?;
+    module.associated_function("active_retrieval", UnitTestGraderBuilder::active_retrieval)?;
     module.associated_function("run", UnitTestGraderBuilder::run)?;
+    module.associated_function("req_name", CoverageGraderBuilder::req_name)?;
+    module.associated_function("out_of", CoverageGraderBuilder::out_of)?;
+    module.associated_function("project", CoverageGraderBuilder::project)?;
+    module.associated_function("target_classes", CoverageGraderBuilder::target_classes)?;
+    module.associated_function("test_files", CoverageGraderBuilder::test_files)?;
+    module.associated_function("min_coverage", CoverageGraderBuilder::min_coverage)?;
+    module.associated_function("metric", CoverageGraderBuilder::metric)?;
+    module.associated_function("retrieval", CoverageGraderBuilder::retrieval)?;
+    module.associated_function("run", CoverageGraderBuilder::run)?;
 
     module.associated_function("url", ByHiddenTestGraderBuilder::url)?;
     module.associated_function("test_class_name", ByHiddenTestGraderBuilder::test_class_name)?;
     module.associated_function("out_of", ByHiddenTestGraderBuilder::out_of)?;
     module.associated_function("req_name", ByHiddenTestGraderBuilder::req_name)?;
+    module.associated_function("project", ByHiddenTestGraderBuilder::project)?;
+    module
+        .associated_function("classpath_overlay", ByHiddenTestGraderBuilder::classpath_overlay)?;
+    module.associated_function(
+        "download_timeout_secs",
+        ByHiddenTestGraderBuilder::download_timeout_secs,
+    )?;
+    module
+        .associated_function("max_download_bytes", ByHiddenTestGraderBuilder::max_download_bytes)?;
+    module.associated_function("active_retrieval", ByHiddenTestGraderBuilder::active_retrieval)?;
     module.associated_function("run", ByHiddenTestGraderBuilder::run)?;
 
     module.associated_function("req_name", DiffGraderBuilder::req_name)?;
@@ -913,7 +2727,112 @@ pub fn module() -> Result<Module, ContextError> {
     module.associated_function("cases", DiffGraderBuilder::cases)?;
     module.associated_function("ignore_case", DiffGraderBuilder::ignore_case)?;
     module.associated_function("preserve_whitespace", DiffGraderBuilder::preserve_whitespace)?;
+    module.associated_function("strip_ansi", DiffGraderBuilder::strip_ansi)?;
+    module.associated_function("normalizers", DiffGraderBuilder::normalizers)?;
+    module.associated_function("case_separator", DiffGraderBuilder::case_separator)?;
+    module.associated_function("case_label", DiffGraderBuilder::case_label)?;
+    module.associated_function("line_ending", DiffGraderBuilder::line_ending)?;
+    module.associated_function("stdout_only", DiffGraderBuilder::stdout_only)?;
+    module.associated_function("reference_file", DiffGraderBuilder::reference_file)?;
+    module.associated_function("inputs", DiffGraderBuilder::inputs)?;
+    module.associated_function("timeout", DiffGraderBuilder::timeout)?;
+    module.associated_function("args", DiffGraderBuilder::args)?;
+    module.associated_function("expect_with_args", DiffGraderBuilder::expect_with_args)?;
+    module.associated_function("expect_with_inputs", DiffGraderBuilder::expect_with_inputs)?;
+    module.associated_function("input_delay", DiffGraderBuilder::input_delay)?;
+    module.associated_function("active_retrieval", DiffGraderBuilder::active_retrieval)?;
     module.associated_function("run", DiffGraderBuilder::run)?;
+    module.associated_function("req_name", RegexGraderBuilder::req_name)?;
+    module.associated_function("out_of", RegexGraderBuilder::out_of)?;
+    module.associated_function("project", RegexGraderBuilder::project)?;
+    module.associated_function("file", RegexGraderBuilder::file)?;
+    module.associated_function("cases", RegexGraderBuilder::cases)?;
+    module.associated_function("full_match", RegexGraderBuilder::full_match)?;
+    module.associated_function("run", RegexGraderBuilder::run)?;
+    module.associated_function("req_name", FormatConstraintGraderBuilder::req_name)?;
+    module.associated_function("out_of", FormatConstraintGraderBuilder::out_of)?;
+    module.associated_function("project", FormatConstraintGraderBuilder::project)?;
+    module.associated_function("file", FormatConstraintGraderBuilder::file)?;
+    module.associated_function("input", FormatConstraintGraderBuilder::input)?;
+    module.associated_function("line_count", FormatConstraintGraderBuilder::line_count)?;
+    module.associated_function("line_pattern", FormatConstraintGraderBuilder::line_pattern)?;
+    module.associated_function("run", FormatConstraintGraderBuilder::run)?;
+    module.associated_function("req_name", OracleGraderBuilder::req_name)?;
+    module.associated_function("out_of", OracleGraderBuilder::out_of)?;
+    module.associated_function("project", OracleGraderBuilder::project)?;
+    module.associated_function("file", OracleGraderBuilder::file)?;
+    module.associated_function("inputs", OracleGraderBuilder::inputs)?;
+    module.associated_function("sorted_permutation", OracleGraderBuilder::sorted_permutation)?;
+    module.associated_function("property", OracleGraderBuilder::property)?;
+    module.associated_function("run", OracleGraderBuilder::run)?;
+    module.associated_function("req_name", ComplexityGraderBuilder::req_name)?;
+    module.associated_function("out_of", ComplexityGraderBuilder::out_of)?;
+    module.associated_function("project", ComplexityGraderBuilder::project)?;
+    module.associated_function("files", ComplexityGraderBuilder::files)?;
+    module.associated_function("max_complexity", ComplexityGraderBuilder::max_complexity)?;
+    module.associated_function("penalty", ComplexityGraderBuilder::penalty)?;
+    module.associated_function("run", ComplexityGraderBuilder::run)?;
+    module.associated_function("req_name", MethodLengthGraderBuilder::req_name)?;
+    module.associated_function("out_of", MethodLengthGraderBuilder::out_of)?;
+    module.associated_function("project", MethodLengthGraderBuilder::project)?;
+    module.associated_function("files", MethodLengthGraderBuilder::files)?;
+    module.associated_function("max_lines", MethodLengthGraderBuilder::max_lines)?;
+    module.associated_function("penalty", MethodLengthGraderBuilder::penalty)?;
+    module.associated_function("run", MethodLengthGraderBuilder::run)?;
+    module.associated_function("req_name", DebugPrintGraderBuilder::req_name)?;
+    module.associated_function("out_of", DebugPrintGraderBuilder::out_of)?;
+    module.associated_function("project", DebugPrintGraderBuilder::project)?;
+    module.associated_function("files", DebugPrintGraderBuilder::files)?;
+    module.associated_function("allowed_methods", DebugPrintGraderBuilder::allowed_methods)?;
+    module.associated_function("penalty", DebugPrintGraderBuilder::penalty)?;
+    module.associated_function("run", DebugPrintGraderBuilder::run)?;
+    module.associated_function("req_name", MagicNumberGraderBuilder::req_name)?;
+    module.associated_function("out_of", MagicNumberGraderBuilder::out_of)?;
+    module.associated_function("project", MagicNumberGraderBuilder::project)?;
+    module.associated_function("files", MagicNumberGraderBuilder::files)?;
+    module.associated_function("allowed_numbers", MagicNumberGraderBuilder::allowed_numbers)?;
+    module.associated_function("penalty", MagicNumberGraderBuilder::penalty)?;
+    module.associated_function("run", MagicNumberGraderBuilder::run)?;
+    module.associated_function("req_name", PlaceholderGraderBuilder::req_name)?;
+    module.associated_function("out_of", PlaceholderGraderBuilder::out_of)?;
+    module.associated_function("project", PlaceholderGraderBuilder::project)?;
+    module.associated_function("files", PlaceholderGraderBuilder::files)?;
+    module.associated_function("comment_patterns", PlaceholderGraderBuilder::comment_patterns)?;
+    module.associated_function("throw_patterns", PlaceholderGraderBuilder::throw_patterns)?;
+    module.associated_function("penalty", PlaceholderGraderBuilder::penalty)?;
+    module.associated_function("run", PlaceholderGraderBuilder::run)?;
+    module.associated_function("req_name", EncapsulationGraderBuilder::req_name)?;
+    module.associated_function("out_of", EncapsulationGraderBuilder::out_of)?;
+    module.associated_function("project", EncapsulationGraderBuilder::project)?;
+    module.associated_function("files", EncapsulationGraderBuilder::files)?;
+    module.associated_function("allow_constants", EncapsulationGraderBuilder::allow_constants)?;
+    module.associated_function("allow_final", EncapsulationGraderBuilder::allow_final)?;
+    module.associated_function("allowed_fields", EncapsulationGraderBuilder::allowed_fields)?;
+    module.associated_function("penalty", EncapsulationGraderBuilder::penalty)?;
+    module.associated_function("run", EncapsulationGraderBuilder::run)?;
+
+    module.associated_function("req_name", OverrideGraderBuilder::req_name)?;
+    module.associated_function("out_of", OverrideGraderBuilder::out_of)?;
+    module.associated_function("project", OverrideGraderBuilder::project)?;
+    module.associated_function("files", OverrideGraderBuilder::files)?;
+    module.associated_function("penalty", OverrideGraderBuilder::penalty)?;
+    module.associated_function("run", OverrideGraderBuilder::run)?;
+
+    module.associated_function("req_name", AssertionDensityGraderBuilder::req_name)?;
+    module.associated_function("out_of", AssertionDensityGraderBuilder::out_of)?;
+    module.associated_function("project", AssertionDensityGraderBuilder::project)?;
+    module.associated_function("files", AssertionDensityGraderBuilder::files)?;
+    module.associated_function("min_assertions", AssertionDensityGraderBuilder::min_assertions)?;
+    module.associated_function(
+        "assertion_prefixes",
+        AssertionDensityGraderBuilder::assertion_prefixes,
+    )?;
+    module.associated_function("penalty", AssertionDensityGraderBuilder::penalty)?;
+    module.associated_function("run", AssertionDensityGraderBuilder::run)?;
+
+    module.function_meta(PenaltyMode::linear)?;
+    module.function_meta(PenaltyMode::capped)?;
+    module.function_meta(PenaltyMode::logarithmic)?;
 
     module.function_meta(QueryConstraint::must_match_at_least_once)?;
     module.function_meta(QueryConstraint::must_match_exactly_n)?;
@@ -926,6 +2845,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.associated_function("file", QueryGraderBuilder::file)?;
     module.associated_function("queries", QueryGraderBuilder::queries)?;
     module.associated_function("queries_with_capture", QueryGraderBuilder::queries_with_capture)?;
+    module.associated_function("named_query", QueryGraderBuilder::named_query)?;
     module.associated_function("constraint", QueryGraderBuilder::constraint)?;
     module.associated_function("reason", QueryGraderBuilder::reason)?;
     module.associated_function("run", QueryGraderBuilder::run)?;
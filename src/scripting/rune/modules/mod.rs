@@ -6,6 +6,8 @@ pub mod config;
 pub mod gradescope;
 /// Java graders and project helpers.
 pub mod java;
+/// Discovery and reporting for mixed Java/Python directories.
+pub mod polyglot;
 /// Python graders and project helpers.
 pub mod python;
 /// Retrieval heuristics configuration.
@@ -0,0 +1,124 @@
+use rune::{
+    Any, ContextError, Module,
+    support::{Error as RuneError, Result as RuneResult},
+};
+
+use crate::scripting::rune::modules::{
+    gradescope::GradescopeConfig as RuneGradescopeConfig, java, python,
+};
+
+/// Free constructor: discover the Java and Python projects in the current
+/// directory.
+#[rune::function(path = new_project)]
+pub fn new_project() -> RuneResult<Project> {
+    Ok(Project {
+        inner: crate::polyglot::Project::new().map_err(host_err)?,
+    })
+}
+
+/// Free constructor: discover the Java and Python projects under `root`.
+#[rune::function(path = new_project_from_root)]
+pub fn new_project_from_root(root: String) -> RuneResult<Project> {
+    Ok(Project {
+        inner: crate::polyglot::Project::from_root(root).map_err(host_err)?,
+    })
+}
+
+/// Helper to convert host errors into Rune errors.
+fn host_err<E: std::fmt::Display>(e: E) -> RuneError {
+    RuneError::msg(e.to_string())
+}
+
+/// Rune-exposed wrapper around a mixed Java/Python directory.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::polyglot)]
+pub struct Project {
+    /// Underlying Rust project instance.
+    inner: crate::polyglot::Project,
+}
+
+impl Project {
+    /// The Java project, or `None` when there are no Java files.
+    pub fn java(&self) -> Option<java::Project> {
+        self.inner.java().cloned().map(java::Project::from)
+    }
+
+    /// The Python project, or `None` when there are no Python files.
+    pub fn python(&self) -> Option<python::Project> {
+        self.inner.python().cloned().map(python::Project::from)
+    }
+
+    /// Names of the languages found, e.g. `["java", "python"]`.
+    pub fn languages(&self) -> Vec<String> {
+        self.inner
+            .languages()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+}
+
+/// Joins Java and Python results into one list, Java first.
+fn combine(
+    java_results: Vec<java::GradeResult>,
+    python_results: Vec<python::GradeResult>,
+) -> Vec<crate::java::grade::GradeResult> {
+    java_results
+        .into_iter()
+        .map(java::GradeResult::into_inner)
+        .chain(
+            python_results
+                .into_iter()
+                .map(python::GradeResult::into_inner),
+        )
+        .collect()
+}
+
+/// Render Java and Python results as one table using default Gradescope
+/// config.
+pub fn show_results(
+    java_results: Vec<java::GradeResult>,
+    python_results: Vec<python::GradeResult>,
+) -> RuneResult<()> {
+    let config = crate::java::grade::gradescope::GradescopeConfig::default();
+    crate::java::grade::show_result(
+        combine(java_results, python_results),
+        config,
+        &crate::scripting::project_paths(),
+    )
+    .map_err(host_err)
+}
+
+/// Render Java and Python results as one table with an explicit Gradescope
+/// config.
+pub fn show_results_with_config(
+    java_results: Vec<java::GradeResult>,
+    python_results: Vec<python::GradeResult>,
+    config: RuneGradescopeConfig,
+) -> RuneResult<()> {
+    crate::java::grade::show_result(
+        combine(java_results, python_results),
+        config.inner,
+        &crate::scripting::project_paths(),
+    )
+    .map_err(host_err)
+}
+
+/// Install the `umm::polyglot` Rune module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("umm", ["polyglot"])?;
+
+    module.ty::<Project>()?;
+
+    module.function_meta(new_project)?;
+    module.function_meta(new_project_from_root)?;
+    module.associated_function("java", Project::java)?;
+    module.associated_function("python", Project::python)?;
+    module.associated_function("languages", Project::languages)?;
+
+    module.function("show_results", show_results).build()?;
+    module
+        .function("show_results_with_config", show_results_with_config)
+        .build()?;
+    Ok(module)
+}
@@ -7,6 +7,7 @@ use std::path::PathBuf;
 
 use rune::{
     Any, ContextError, Module, Ref,
+    runtime::Function,
     support::{Error as RuneError, Result as RuneResult},
 };
 use serde_json;
@@ -87,6 +88,21 @@ pub fn new_diff_grader() -> DiffGraderBuilder {
         cases:               Vec::new(),
         ignore_case:         false,
         preserve_whitespace: false,
+        strip_ansi:          false,
+        reference_file:      None,
+    }
+}
+
+/// Free constructor: start building an input/output oracle grader.
+#[rune::function(path = new_oracle_grader)]
+pub fn new_oracle_grader() -> OracleGraderBuilder {
+    OracleGraderBuilder {
+        req_name: None,
+        out_of:   None,
+        project:  None,
+        file:     None,
+        inputs:   Vec::new(),
+        oracle:   None,
     }
 }
 
@@ -133,6 +149,7 @@ pub fn new_code_review_grader() -> CodeReviewGraderBuilder {
     CodeReviewGraderBuilder {
         project:             None,
         files:               Vec::new(),
+        provided_files:      Vec::new(),
         instructions_path:   None,
         weekly_context_path: None,
         req_name:            None,
@@ -146,6 +163,14 @@ fn host_err<E: std::fmt::Display>(e: E) -> RuneError {
     RuneError::msg(e.to_string())
 }
 
+/// Records a finished grader's result as progress and applies the fail-fast
+/// policy to it.
+fn after_grade(result: InnerGradeResult) -> RuneResult<GradeResult> {
+    crate::scripting::record_progress(&result);
+    crate::scripting::check_fail_fast(&result).map_err(host_err)?;
+    Ok(GradeResult::from(result))
+}
+
 /// Helper to extract required builder fields without panicking.
 fn take_required<T>(opt: Option<T>, field: &str) -> RuneResult<T> {
     opt.ok_or_else(|| host_err(format!("Missing required field: {field}")))
@@ -159,6 +184,12 @@ pub struct Project {
     inner: crate::python::Project,
 }
 
+impl From<crate::python::Project> for Project {
+    fn from(inner: crate::python::Project) -> Self {
+        Self { inner }
+    }
+}
+
 impl Project {
     /// Replace the run context on this project.
     pub fn with_run_context(mut self, ctx: RunContext) -> Self {
@@ -439,6 +470,24 @@ impl GradeResult {
             .and_then(|msgs| serde_json::to_string_pretty(msgs).ok())
     }
 
+    /// Whether this requirement is extra credit.
+    pub fn is_extra_credit(&self) -> bool {
+        self.inner.is_extra_credit()
+    }
+
+    /// Seconds the grader took to run, if it was timed.
+    pub fn elapsed_secs(&self) -> Option<f64> {
+        self.inner.elapsed().map(|elapsed| elapsed.as_secs_f64())
+    }
+
+    /// Mark this requirement as extra credit, adding to the total without
+    /// raising its maximum.
+    pub fn extra_credit(self, extra_credit: bool) -> Self {
+        Self {
+            inner: self.inner.with_extra_credit(extra_credit),
+        }
+    }
+
     /// Consume the wrapper and return the underlying Rust result.
     pub(crate) fn into_inner(self) -> InnerGradeResult {
         self.inner
@@ -492,6 +541,10 @@ pub struct DiffGraderBuilder {
     ignore_case:         bool,
     /// Whether to preserve whitespace.
     preserve_whitespace: bool,
+    /// Whether to strip ANSI escape sequences from program output.
+    strip_ansi:          bool,
+    /// Reference solution whose output becomes each case's expectation.
+    reference_file:      Option<String>,
 }
 
 impl DiffGrader {}
@@ -532,6 +585,11 @@ impl DiffGraderBuilder {
         self.preserve_whitespace = preserve;
         self
     }
+    /// Strip ANSI escape sequences (e.g. colours) from program output.
+    pub fn strip_ansi(mut self, strip: bool) -> Self {
+        self.strip_ansi = strip;
+        self
+    }
 
     /// Add a single expected output case (no input).
     /// This is a clearer alternative to `.cases([(..., None)])`.
@@ -547,8 +605,23 @@ impl DiffGraderBuilder {
         self
     }
 
+    /// Run a reference solution on each case's input and expect its output
+    /// instead of the case's expected string.
+    pub fn reference_file(mut self, file: String) -> Self {
+        self.reference_file = Some(file);
+        self
+    }
+
+    /// Add a case per stdin input, for use with `reference_file`.
+    pub fn inputs(mut self, inputs: Vec<String>) -> Self {
+        self.cases
+            .extend(inputs.into_iter().map(|input| (String::new(), Some(input))));
+        self
+    }
+
     /// Run the diff grader.
     pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
         let project = take_required(self.project, "project")?.inner;
         let file = take_required(self.file, "file")?;
         let req_name = take_required(self.req_name, "req_name")?;
@@ -573,6 +646,8 @@ impl DiffGraderBuilder {
             .cases(cases)
             .ignore_case(self.ignore_case)
             .preserve_whitespace(self.preserve_whitespace)
+            .strip_ansi(self.strip_ansi)
+            .maybe_reference_file(self.reference_file)
             .req_name(req_name)
             .out_of(out_of);
 
@@ -580,8 +655,101 @@ impl DiffGraderBuilder {
             .build()
             .run()
             .await
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
+    }
+}
+
+/// Namespace for input/output oracle grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::python)]
+pub struct OracleGrader;
+
+/// Builder for input/output oracle grader.
+#[derive(Any, Clone)]
+#[rune(item = ::umm::python)]
+pub struct OracleGraderBuilder {
+    /// Requirement name.
+    req_name: Option<String>,
+    /// Maximum score.
+    out_of:   Option<f64>,
+    /// Project to grade.
+    project:  Option<Project>,
+    /// File to execute.
+    file:     Option<String>,
+    /// Stdin for each case.
+    inputs:   Vec<String>,
+    /// Property every output must satisfy.
+    oracle:   Option<grade::Oracle>,
+}
+
+impl OracleGrader {}
+
+impl OracleGraderBuilder {
+    /// Set requirement name.
+    pub fn req_name(mut self, name: String) -> Self {
+        self.req_name = Some(name);
+        self
+    }
+    /// Set maximum score.
+    pub fn out_of(mut self, out_of: f64) -> Self {
+        self.out_of = Some(out_of);
+        self
+    }
+    /// Attach project.
+    pub fn project(mut self, project: Ref<Project>) -> Self {
+        self.project = Some(project.clone());
+        self
+    }
+    /// Set file to run against.
+    pub fn file(mut self, file: String) -> Self {
+        self.file = Some(file);
+        self
+    }
+    /// Provide stdin for each case.
+    pub fn inputs(mut self, inputs: Vec<String>) -> Self {
+        self.inputs = inputs;
+        self
+    }
+    /// Require each output to be the input's numbers in ascending order.
+    pub fn sorted_permutation(mut self) -> Self {
+        self.oracle = Some(grade::Oracle::sorted_permutation());
+        self
+    }
+    /// Check each output with a Rune closure `|input, output| -> bool`;
+    /// `name` describes the property in reasons.
+    pub fn property(mut self, name: String, check: Function) -> RuneResult<Self> {
+        let check = check.into_sync().map_err(host_err)?;
+        self.oracle = Some(grade::Oracle::new(name.clone(), move |input, output| {
+            match check
+                .call::<bool>((input.to_string(), output.to_string()))
+                .into_result()
+            {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(format!("output is not the {name}")),
+                Err(e) => Err(format!("the oracle failed: {e}")),
+            }
+        }));
+        Ok(self)
+    }
+
+    /// Run the oracle grader.
+    pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
+        let builder = grade::OracleGrader::builder()
+            .req_name(take_required(self.req_name, "req_name")?)
+            .out_of(take_required(self.out_of, "out_of")?)
+            .project(take_required(self.project, "project")?.inner)
+            .file(take_required(self.file, "file")?)
+            .inputs(self.inputs)
+            .oracle(take_required(self.oracle, "oracle")?);
+
+        builder
+            .build()
+            .run()
+            .await
+            .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
@@ -873,6 +1041,7 @@ impl QueryGraderBuilder {
 
     /// Run the query grader.
     pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
         let queries: Vec<grade::Query> = self
             .queries
             .into_iter()
@@ -903,8 +1072,8 @@ impl QueryGraderBuilder {
             .build()
             .run()
             .await
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
@@ -964,6 +1133,7 @@ impl DocsGraderBuilder {
 
     /// Build with bon defaults and run.
     pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
         let builder = grade::DocsGrader::builder()
             .project(take_required(self.project, "project")?.inner)
             .files(self.files)
@@ -975,8 +1145,8 @@ impl DocsGraderBuilder {
             .build()
             .run()
             .await
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
@@ -1028,6 +1198,7 @@ impl TestGraderBuilder {
 
     /// Run the test grader.
     pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
         let builder = grade::TestGrader::builder()
             .project(take_required(self.project, "project")?.inner)
             .test_files(self.test_files)
@@ -1038,8 +1209,8 @@ impl TestGraderBuilder {
             .build()
             .run()
             .await
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
@@ -1056,6 +1227,8 @@ pub struct CodeReviewGraderBuilder {
     project:             Option<Project>,
     /// Files to grade.
     files:               Vec<String>,
+    /// Instructor-provided files left out of the review.
+    provided_files:      Vec<String>,
     /// Path to assignment instructions.
     instructions_path:   Option<String>,
     /// Path to weekly context.
@@ -1083,6 +1256,12 @@ impl CodeReviewGraderBuilder {
         self
     }
 
+    /// Set instructor-provided files to leave out of the review.
+    pub fn provided_files(mut self, files: Vec<String>) -> Self {
+        self.provided_files = files;
+        self
+    }
+
     /// Set the path to assignment instructions.
     pub fn instructions_path(mut self, path: String) -> Self {
         self.instructions_path = Some(path);
@@ -1115,9 +1294,11 @@ impl CodeReviewGraderBuilder {
 
     /// Run the code review grader.
     pub async fn run(self) -> RuneResult<GradeResult> {
+        crate::scripting::ensure_not_halted().map_err(host_err)?;
         let builder = grade::CodeReviewGrader::builder()
             .project(take_required(self.project, "project")?.inner)
             .files(self.files)
+            .provided_files(self.provided_files)
             .req_name(take_required(self.req_name, "req_name")?)
             .out_of(take_required(self.out_of, "out_of")?)
             .execute_files(self.execute_files)
@@ -1128,8 +1309,8 @@ impl CodeReviewGraderBuilder {
             .build()
             .run()
             .await
-            .map(GradeResult::from)
             .map_err(host_err)
+            .and_then(after_grade)
     }
 }
 
@@ -1142,7 +1323,8 @@ pub fn grade_all(results: Vec<GradeResult>) -> RuneResult<Vec<GradeResult>> {
 pub fn show_result(results: Vec<GradeResult>) -> RuneResult<()> {
     let config = crate::java::grade::gradescope::GradescopeConfig::default();
     let inner_results: Vec<_> = results.into_iter().map(|r| r.into_inner()).collect();
-    crate::java::grade::show_result(inner_results, config).map_err(host_err)
+    crate::java::grade::show_result(inner_results, config, &crate::scripting::project_paths())
+        .map_err(host_err)
 }
 
 /// Render results alias.
@@ -1156,7 +1338,8 @@ pub fn show_result_with_config(
     config: RuneGradescopeConfig,
 ) -> RuneResult<()> {
     let inner_results: Vec<_> = results.into_iter().map(|r| r.into_inner()).collect();
-    crate::java::grade::show_result(inner_results, config.inner).map_err(host_err)
+    crate::java::grade::show_result(inner_results, config.inner, &crate::scripting::project_paths())
+        .map_err(host_err)
 }
 
 /// Render results with config alias.
@@ -1181,6 +1364,8 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<DiffCase>()?;
     module.ty::<DiffGrader>()?;
     module.ty::<DiffGraderBuilder>()?;
+    module.ty::<OracleGrader>()?;
+    module.ty::<OracleGraderBuilder>()?;
     module.ty::<QueryConstraint>()?;
     module.ty::<QueryGrader>()?;
     module.ty::<QueryGraderBuilder>()?;
@@ -1193,6 +1378,9 @@ pub fn module() -> Result<Module, ContextError> {
 
     // GradeResult methods
     module.associated_function("prompt", GradeResult::prompt)?;
+    module.associated_function("is_extra_credit", GradeResult::is_extra_credit)?;
+    module.associated_function("elapsed_secs", GradeResult::elapsed_secs)?;
+    module.associated_function("extra_credit", GradeResult::extra_credit)?;
 
     // Free constructors
     module.function_meta(new_project)?;
@@ -1201,6 +1389,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.function_meta(new_project_paths)?;
     module.function_meta(new_run_context)?;
     module.function_meta(new_diff_grader)?;
+    module.function_meta(new_oracle_grader)?;
     module.function_meta(new_query_grader)?;
     module.function_meta(new_docs_grader)?;
     module.function_meta(new_test_grader)?;
@@ -1244,8 +1433,21 @@ pub fn module() -> Result<Module, ContextError> {
     module.associated_function("expect_with_input", DiffGraderBuilder::expect_with_input)?;
     module.associated_function("ignore_case", DiffGraderBuilder::ignore_case)?;
     module.associated_function("preserve_whitespace", DiffGraderBuilder::preserve_whitespace)?;
+    module.associated_function("strip_ansi", DiffGraderBuilder::strip_ansi)?;
+    module.associated_function("reference_file", DiffGraderBuilder::reference_file)?;
+    module.associated_function("inputs", DiffGraderBuilder::inputs)?;
     module.associated_function("run", DiffGraderBuilder::run)?;
 
+    // OracleGraderBuilder methods
+    module.associated_function("req_name", OracleGraderBuilder::req_name)?;
+    module.associated_function("out_of", OracleGraderBuilder::out_of)?;
+    module.associated_function("project", OracleGraderBuilder::project)?;
+    module.associated_function("file", OracleGraderBuilder::file)?;
+    module.associated_function("inputs", OracleGraderBuilder::inputs)?;
+    module.associated_function("sorted_permutation", OracleGraderBuilder::sorted_permutation)?;
+    module.associated_function("property", OracleGraderBuilder::property)?;
+    module.associated_function("run", OracleGraderBuilder::run)?;
+
     // QueryConstraint static methods
     module.function_meta(QueryConstraint::must_match_at_least_once)?;
     module.function_meta(QueryConstraint::must_match_exactly_n)?;
@@ -1322,6 +1524,7 @@ pub fn module() -> Result<Module, ContextError> {
     // CodeReviewGraderBuilder methods
     module.associated_function("project", CodeReviewGraderBuilder::project)?;
     module.associated_function("files", CodeReviewGraderBuilder::files)?;
+    module.associated_function("provided_files", CodeReviewGraderBuilder::provided_files)?;
     module.associated_function("instructions_path", CodeReviewGraderBuilder::instructions_path)?;
     module
         .associated_function("weekly_context_path", CodeReviewGraderBuilder::weekly_context_path)?;
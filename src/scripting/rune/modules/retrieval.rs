@@ -1,4 +1,7 @@
-use rune::{Any, ContextError, Module};
+use rune::{
+    Any, ContextError, Module,
+    support::{Error as RuneError, Result as RuneResult},
+};
 
 use crate::retrieval::HeuristicConfig as InnerHeuristicConfig;
 
@@ -7,7 +10,7 @@ use crate::retrieval::HeuristicConfig as InnerHeuristicConfig;
 #[rune(item = ::umm::retrieval)]
 pub struct HeuristicConfig {
     /// Wrapped Rust heuristic configuration.
-    inner: InnerHeuristicConfig,
+    pub(crate) inner: InnerHeuristicConfig,
 }
 
 impl HeuristicConfig {
@@ -43,12 +46,67 @@ impl HeuristicConfig {
         self
     }
 
+    /// Prepend the imports and field declarations of referenced files.
+    pub fn include_declarations(mut self, value: bool) -> Self {
+        self.inner.set_include_declarations(value);
+        self
+    }
+
     /// Apply the configuration globally.
     pub fn apply(self) {
         crate::config::set_heuristic_defaults(self.inner);
     }
 }
 
+/// Read whether imports and field declarations are globally prepended.
+pub fn include_declarations() -> bool {
+    crate::config::heuristic_defaults().include_declarations()
+}
+
+/// Read the globally applied snippet start offset.
+pub fn start_offset() -> usize {
+    crate::config::heuristic_defaults().start_offset()
+}
+
+/// Read the globally applied number of lines per snippet.
+pub fn num_lines() -> usize {
+    crate::config::heuristic_defaults().num_lines()
+}
+
+/// Read the globally applied maximum number of line references.
+pub fn max_line_refs() -> usize {
+    crate::config::heuristic_defaults().max_line_refs()
+}
+
+/// Read the globally applied full-file ratio threshold.
+pub fn full_file_ratio() -> f32 {
+    crate::config::heuristic_defaults().full_file_ratio()
+}
+
+/// Select the retrieval mode: `"heuristic"` sends snippets around diagnostics,
+/// `"active"` asks the retrieval service first and falls back to snippets.
+pub fn set_mode(mode: String) -> RuneResult<()> {
+    match mode.trim().to_ascii_lowercase().as_str() {
+        "heuristic" => crate::config::set_active_retrieval(false),
+        "active" => crate::config::set_active_retrieval(true),
+        other => {
+            return Err(RuneError::msg(format!(
+                "Unknown retrieval mode `{other}` (expected `heuristic` or `active`)"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Name of the current retrieval mode (`"heuristic"` or `"active"`).
+pub fn mode() -> String {
+    if crate::config::active_retrieval_enabled() {
+        "active".to_string()
+    } else {
+        "heuristic".to_string()
+    }
+}
+
 /// Install the `umm::retrieval` Rune module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("umm", ["retrieval"])?;
@@ -60,6 +118,19 @@ pub fn module() -> Result<Module, ContextError> {
     module.associated_function("num_lines", HeuristicConfig::num_lines)?;
     module.associated_function("max_line_refs", HeuristicConfig::max_line_refs)?;
     module.associated_function("full_file_ratio", HeuristicConfig::full_file_ratio)?;
+    module.associated_function("include_declarations", HeuristicConfig::include_declarations)?;
     module.associated_function("apply", HeuristicConfig::apply)?;
+
+    module.function("start_offset", start_offset).build()?;
+    module.function("num_lines", num_lines).build()?;
+    module.function("max_line_refs", max_line_refs).build()?;
+    module
+        .function("full_file_ratio", full_file_ratio)
+        .build()?;
+    module
+        .function("include_declarations", include_declarations)
+        .build()?;
+    module.function("set_mode", set_mode).build()?;
+    module.function("mode", mode).build()?;
     Ok(module)
 }
@@ -1,10 +1,16 @@
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashSet,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{Context, Result};
-use glob::glob;
+use bon::Builder;
+use uuid::Uuid;
 use which::which;
 
 /// Finds and returns the path to the `umm` binary (falls back to `./umm`).
@@ -15,22 +21,174 @@ pub fn umm_path() -> String {
     }
 }
 
-/// Generic glob helper to discover files under `root_dir` matching `extension`.
+/// Controls how [`find_files_with`] walks a directory tree.
+#[derive(Debug, Clone, Copy, Builder)]
+pub struct FindOptions {
+    /// Deepest directory level below the root that is searched; `0` only
+    /// looks at the root itself. Optional, default is no limit
+    pub max_depth:       Option<usize>,
+    /// Descend into symlinked directories and return symlinked files.
+    /// Optional, default is false
+    #[builder(default)]
+    pub follow_symlinks: bool,
+}
+
+/// Discovers files under `root_dir` matching `extension` without following
+/// symlinks.
+///
+/// As with the `**` glob this used to expand, any positive `search_depth`
+/// searches the whole tree, so sources nested as deep as Maven's
+/// `src/main/java/pkg/` are found; `0` only looks at `root_dir` itself. Use
+/// [`find_files_with`] for a real depth limit.
 pub fn find_files(extension: &str, search_depth: i8, root_dir: &Path) -> Result<Vec<PathBuf>> {
-    let mut pattern = root_dir.to_path_buf();
+    let options = FindOptions::builder()
+        .maybe_max_depth((search_depth <= 0).then_some(0))
+        .build();
+    find_files_with(extension, root_dir, options)
+}
+
+/// Discovers files under `root_dir` matching `extension`, sorted by path.
+///
+/// Each directory is read at most once, keyed by its canonical path, so
+/// symlink loops terminate even when `follow_symlinks` is set. Unreadable
+/// directories and broken links are skipped.
+pub fn find_files_with(
+    extension: &str,
+    root_dir: &Path,
+    options: FindOptions,
+) -> Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    let mut visited = HashSet::new();
+    let mut pending = vec![(root_dir.to_path_buf(), 0)];
+
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(canonical) = dir.canonicalize() else {
+            continue;
+        };
+        if !visited.insert(canonical) {
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_link = entry.file_type().is_ok_and(|kind| kind.is_symlink());
+            if is_link && !options.follow_symlinks {
+                continue;
+            }
+            let Ok(metadata) = fs::metadata(&path) else {
+                continue;
+            };
+
+            if metadata.is_dir() {
+                if options.max_depth.is_none_or(|max| depth < max) {
+                    pending.push((path, depth + 1));
+                }
+            } else if path.extension().is_some_and(|ext| ext == extension) {
+                found.push(path);
+            }
+        }
+    }
+
+    found.sort();
+    Ok(found)
+}
 
-    for _ in 0..search_depth {
-        pattern.push("**");
+/// Writes `contents` to `path` atomically; see [`write_atomic_with`].
+pub fn write_atomic(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
+    write_atomic_with(path, |file| file.write_all(contents.as_ref()))
+}
+
+/// Fills a temporary file beside `path` with `write`, then renames it into
+/// place, so readers only ever see the previous file or the complete new one.
+///
+/// If `write` fails (or the process dies before the rename), `path` is left
+/// untouched and the temporary file is removed where possible.
+pub fn write_atomic_with(
+    path: impl AsRef<Path>,
+    write: impl FnOnce(&mut fs::File) -> std::io::Result<()>,
+) -> Result<()> {
+    let path = path.as_ref();
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let name = path
+        .file_name()
+        .with_context(|| format!("{} is not a file path", path.display()))?
+        .to_string_lossy();
+    let temp = dir.join(format!(".{name}.{}.tmp", Uuid::new_v4()));
+
+    let written = fs::File::create(&temp)
+        .and_then(|mut file| {
+            write(&mut file)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp, path));
+
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(e).with_context(|| format!("Could not write {}", path.display()));
     }
+    Ok(())
+}
 
-    pattern.push(format!("*.{extension}"));
-    let pattern = pattern
-        .to_str()
-        .context("Could not convert root_dir to string")?
-        .to_string();
+/// Comment marker that flags instructor-provided starter code, which is
+/// compiled and run as usual but not reviewed as student work.
+pub const PROVIDED_MARKER: &str = "umm:provided";
+
+/// Returns true when a comment line in `code` (`//`, `/*`, `*`, or `#`)
+/// contains [`PROVIDED_MARKER`].
+pub fn has_provided_marker(code: &str) -> bool {
+    code.lines().any(|line| {
+        let line = line.trim_start();
+        ["//", "/*", "*", "#"]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+            && line.contains(PROVIDED_MARKER)
+    })
+}
+
+/// Removes ANSI escape sequences (colours, cursor movement, hyperlinks) from
+/// `text`, leaving only the printable content.
+///
+/// Handles CSI sequences (`ESC [ … final`), OSC sequences terminated by BEL or
+/// `ESC \`, and two-byte escapes such as `ESC c`.
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                // Parameter and intermediate bytes run until a final byte in
+                // `@`..=`~`.
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\u{7}' {
+                        break;
+                    }
+                    if c == '\u{1b}' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            Some(_) | None => {}
+        }
+    }
 
-    Ok(glob(&pattern)
-        .context("Could not create glob")?
-        .filter_map(Result::ok)
-        .collect())
+    out
 }
@@ -0,0 +1,106 @@
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+use std::{
+    future::Future,
+    io::Write,
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, Result, bail};
+use notify_debouncer_mini::{
+    DebounceEventResult, Debouncer, new_debouncer,
+    notify::{RecommendedWatcher, RecursiveMode},
+};
+use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+
+/// Watches the files with one extension under a directory through the
+/// operating system's file notifications.
+pub struct Watcher {
+    /// Directory watched recursively.
+    root:       PathBuf,
+    /// Extension of the watched files, without the dot.
+    extension:  String,
+    /// Keeps the notification watch alive; dropping it stops the events.
+    _debouncer: Debouncer<RecommendedWatcher>,
+    /// Batches of changed paths, one per quiet period.
+    events:     UnboundedReceiver<DebounceEventResult>,
+}
+
+impl Watcher {
+    /// Starts watching `*.{extension}` files under `root`. Changes are
+    /// reported once no further change arrives for `debounce`, so an editor
+    /// writing several files triggers one re-run.
+    pub fn new(
+        root: impl Into<PathBuf>,
+        extension: impl Into<String>,
+        debounce: Duration,
+    ) -> Result<Self> {
+        let root = root.into();
+        let (sender, events) = unbounded_channel();
+        let mut debouncer = new_debouncer(debounce, move |result: DebounceEventResult| {
+            let _ = sender.send(result);
+        })
+        .context("Could not start the file watcher")?;
+        debouncer
+            .watcher()
+            .watch(&root, RecursiveMode::Recursive)
+            .with_context(|| format!("Could not watch {}", root.display()))?;
+
+        Ok(Self {
+            root,
+            extension: extension.into(),
+            _debouncer: debouncer,
+            events,
+        })
+    }
+
+    /// Directory watched recursively.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns true if `path` has the watched extension.
+    fn is_watched(&self, path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|ext| ext == self.extension.as_str())
+    }
+
+    /// Waits until a watched file is added, removed, or edited.
+    pub async fn changed(&mut self) -> Result<()> {
+        loop {
+            match self.events.recv().await {
+                Some(Ok(events)) => {
+                    if events.iter().any(|event| self.is_watched(&event.path)) {
+                        return Ok(());
+                    }
+                }
+                Some(Err(e)) => return Err(e).context("File watcher failed"),
+                None => bail!("File watcher stopped unexpectedly"),
+            }
+        }
+    }
+
+    /// Calls `run` once, then again after every change, clearing the
+    /// terminal before each re-run. Stops when `run` returns
+    /// [`ControlFlow::Break`].
+    pub async fn run<F, Fut>(mut self, mut run: F) -> Result<()>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<ControlFlow<()>>>,
+    {
+        if run().await?.is_break() {
+            return Ok(());
+        }
+        loop {
+            self.changed().await?;
+            print!("\x1b[2J\x1b[H");
+            std::io::stdout().flush()?;
+            if run().await?.is_break() {
+                return Ok(());
+            }
+        }
+    }
+}
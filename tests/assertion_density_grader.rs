@@ -0,0 +1,104 @@
+use umm::java::{
+    Project,
+    grade::{AssertionDensityGrader, DEFAULT_ASSERTION_PREFIXES, test_assertions},
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("assertions")
+}
+
+fn default_prefixes() -> Vec<String> {
+    DEFAULT_ASSERTION_PREFIXES
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn test_assertions_counts_each_test_method() {
+    let file = project().identify("CalcTest").expect("identify CalcTest");
+    let tests = test_assertions(file.parser(), &default_prefixes()).expect("tests");
+    let found: Vec<_> = tests
+        .iter()
+        .map(|t| (t.method.as_str(), t.line, t.assertions))
+        .collect();
+
+    assert_eq!(
+        found,
+        [
+            ("CalcTest.addsNumbers", 9, 1),
+            ("CalcTest.subtractsNumbers", 14, 0),
+            ("CalcTest.checksBoth", 19, 3),
+            ("CalcTest.usesAssertStatement", 26, 1),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn assertion_free_test_is_flagged() {
+    let result = AssertionDensityGrader::builder()
+        .req_name("assertions")
+        .out_of(4.0)
+        .project(project())
+        .files(["CalcTest"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 3.0);
+    assert!(
+        result
+            .reason()
+            .contains("CalcTest.java:14 CalcTest.subtractsNumbers (0 assertion(s))"),
+        "reason: {}",
+        result.reason()
+    );
+    assert!(!result.reason().contains("addsNumbers"));
+    assert!(result.prompt().is_some());
+}
+
+#[tokio::test]
+async fn raising_the_minimum_flags_single_assertion_tests() {
+    let result = AssertionDensityGrader::builder()
+        .req_name("assertions")
+        .out_of(4.0)
+        .project(project())
+        .files(["CalcTest"])
+        .min_assertions(2)
+        .penalty(0.5)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 2.5);
+    assert!(
+        result
+            .reason()
+            .contains("3 test(s) make fewer than 2 assertion(s)")
+    );
+    assert!(!result.reason().contains("checksBoth"));
+}
+
+#[tokio::test]
+async fn tests_that_all_assert_get_full_marks() {
+    let result = AssertionDensityGrader::builder()
+        .req_name("assertions")
+        .out_of(4.0)
+        .project(project())
+        .files(["CalcTest"])
+        .min_assertions(0)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 4.0);
+    assert!(result.prompt().is_none());
+}
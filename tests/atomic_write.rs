@@ -0,0 +1,59 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use umm::util::{write_atomic, write_atomic_with};
+use uuid::Uuid;
+
+fn scratch() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("umm-atomic-{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).expect("create scratch dir");
+    dir
+}
+
+/// Writes half of `contents` and then fails, like a crash mid-write.
+fn interrupted(contents: &str) -> impl FnOnce(&mut fs::File) -> io::Result<()> + '_ {
+    move |file| {
+        file.write_all(&contents.as_bytes()[..contents.len() / 2])?;
+        Err(io::Error::other("interrupted"))
+    }
+}
+
+fn leftovers(dir: &Path) -> Vec<String> {
+    fs::read_dir(dir)
+        .expect("read scratch dir")
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect()
+}
+
+#[test]
+fn interrupted_write_leaves_no_partial_file() {
+    let dir = scratch();
+    let target = dir.join("results.json");
+
+    let err = write_atomic_with(&target, interrupted(r#"{"tests": []}"#));
+    assert!(err.is_err());
+    assert!(!target.exists(), "partial results.json was left behind");
+    assert!(leftovers(&dir).is_empty(), "temp files left: {:?}", leftovers(&dir));
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn interrupted_write_keeps_previous_contents() {
+    let dir = scratch();
+    let target = dir.join("FEEDBACK");
+    write_atomic(&target, "complete feedback").expect("first write");
+
+    let err = write_atomic_with(&target, interrupted("replacement feedback"));
+    assert!(err.is_err());
+    assert_eq!(fs::read_to_string(&target).unwrap(), "complete feedback");
+    assert_eq!(leftovers(&dir), vec!["FEEDBACK".to_string()]);
+
+    write_atomic(&target, "replacement feedback").expect("second write");
+    assert_eq!(fs::read_to_string(&target).unwrap(), "replacement feedback");
+
+    let _ = fs::remove_dir_all(dir);
+}
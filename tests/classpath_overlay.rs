@@ -0,0 +1,88 @@
+use std::{fs, path::PathBuf};
+
+use umm::java::{grade::tests::ByUnitTestGrader, paths::project_paths, project::Project};
+use uuid::Uuid;
+
+fn jar(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("jar_files")
+        .join(name)
+}
+
+/// Project whose `lib/` only carries the JUnit launcher, so anything else the
+/// tests import has to come from an overlay.
+fn project_with_junit_only() -> (Project, PathBuf) {
+    let lib = std::env::temp_dir().join(format!("umm-overlay-lib-{}", Uuid::new_v4()));
+    fs::create_dir_all(&lib).expect("create temp lib");
+    let launcher = "junit-platform-console-standalone-1.14.1.jar";
+    fs::copy(jar(launcher), lib.join(launcher)).expect("copy junit launcher");
+
+    let paths = project_paths()
+        .root_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/classpath-overlay"))
+        .lib_dir(lib.clone())
+        .build();
+    (Project::from_paths(paths).expect("load project"), lib)
+}
+
+#[tokio::test]
+async fn overlay_jar_is_visible_only_to_its_run() -> anyhow::Result<()> {
+    let (project, lib) = project_with_junit_only();
+    let grader = |overlay: Vec<String>| {
+        ByUnitTestGrader::builder()
+            .project(project.clone())
+            .test_files(["InitialsTest"])
+            .classpath_overlay(overlay)
+            .req_name("overlay")
+            .out_of(1.0)
+            .build()
+            .run()
+    };
+
+    let without = grader(Vec::new()).await?;
+    let commons = jar("commons-lang3-3.20.0.jar").display().to_string();
+    let with = grader(vec![commons]).await?;
+    let _ = fs::remove_dir_all(lib);
+
+    assert_eq!(without.grade_value(), 0.0);
+    assert_eq!(with.grade_value(), 1.0, "reason: {}", with.reason());
+    assert!(project.paths().extra_classpath().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn missing_overlay_jar_is_reported() {
+    let (project, lib) = project_with_junit_only();
+    let err = ByUnitTestGrader::builder()
+        .project(project)
+        .test_files(["InitialsTest"])
+        .classpath_overlay(["does/not/exist.jar"])
+        .req_name("overlay")
+        .out_of(1.0)
+        .build()
+        .run()
+        .await
+        .err()
+        .expect("missing jar should fail");
+    let _ = fs::remove_dir_all(lib);
+
+    assert!(format!("{err:#}").contains("Classpath overlay entry does not exist"));
+}
+
+#[tokio::test]
+async fn relative_overlay_paths_resolve_against_the_project_root() -> anyhow::Result<()> {
+    let (project, lib) = project_with_junit_only();
+    // Relative to `fixtures/java/classpath-overlay`, not the working directory.
+    let result = ByUnitTestGrader::builder()
+        .project(project)
+        .test_files(["InitialsTest"])
+        .classpath_overlay(["../../../jar_files/commons-lang3-3.20.0.jar"])
+        .req_name("overlay")
+        .out_of(1.0)
+        .build()
+        .run()
+        .await?;
+    let _ = fs::remove_dir_all(lib);
+
+    assert_eq!(result.grade_value(), 1.0, "reason: {}", result.reason());
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use umm::java::{
+    Project,
+    grade::{ComplexityGrader, method_complexities},
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("complexity")
+}
+
+#[test]
+fn complexity_counts_decision_points_per_method() {
+    let file = project().identify("Main").expect("identify Main");
+    let methods = method_complexities(file.parser()).expect("complexities");
+    let of = |name: &str| {
+        methods
+            .iter()
+            .find(|m| m.name == name)
+            .unwrap_or_else(|| panic!("{name} missing from {methods:?}"))
+            .complexity
+    };
+
+    assert_eq!(of("Main.add"), 1);
+    assert_eq!(of("Main.main"), 1);
+    // for, if, &&, else-if, ||, while, two cases, ternary, catch.
+    assert_eq!(of("Main.classify"), 11);
+}
+
+#[tokio::test]
+async fn complexity_grader_penalizes_methods_over_the_limit() {
+    let result = ComplexityGrader::builder()
+        .req_name("simple")
+        .out_of(5.0)
+        .project(project())
+        .files(["Main"])
+        .max_complexity(10)
+        .penalty(2.0)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 3.0);
+    assert!(
+        result
+            .reason()
+            .contains("Main.classify (line 6): complexity 11"),
+        "reason: {}",
+        result.reason()
+    );
+    assert!(!result.reason().contains("Main.add"), "reason: {}", result.reason());
+}
+
+#[tokio::test]
+async fn complexity_grader_gives_full_marks_within_the_limit() {
+    let result = ComplexityGrader::builder()
+        .req_name("simple")
+        .out_of(5.0)
+        .project(project())
+        .files(["Main"])
+        .max_complexity(11)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 5.0);
+    assert!(result.prompt().is_none());
+}
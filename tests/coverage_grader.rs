@@ -0,0 +1,177 @@
+use std::{fs, path::PathBuf};
+
+use umm::java::{
+    Project,
+    grade::{CoverageGrader, CoverageMetric, parse_coverage_csv, uncovered_lines},
+    paths::ProjectPaths,
+};
+use uuid::Uuid;
+
+fn fixture_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join("java")
+        .join("coverage-calc")
+}
+
+fn report(name: &str) -> String {
+    std::fs::read_to_string(fixture_root().join("reports").join(name)).expect("read report")
+}
+
+#[test]
+fn coverage_csv_rows_are_qualified_by_package() {
+    let rows = parse_coverage_csv(&report("jacoco.csv")).expect("parse csv");
+
+    assert_eq!(rows.len(), 2);
+    let calc = &rows[0];
+    assert_eq!(calc.class, "calc.Calc");
+    assert_eq!(calc.counts(CoverageMetric::Line), (4, 2));
+    assert_eq!(calc.counts(CoverageMetric::Branch), (1, 3));
+    assert_eq!(calc.counts(CoverageMetric::Instruction), (13, 12));
+    assert_eq!(rows[1].class, "calc.CalcTest");
+}
+
+#[test]
+fn coverage_csv_rejects_malformed_rows() {
+    let err = parse_coverage_csv("GROUP,PACKAGE,CLASS\nreport,calc,Calc,1,2\n")
+        .expect_err("too few columns");
+    assert!(err.to_string().contains("too few columns"), "error: {err}");
+}
+
+#[test]
+fn uncovered_lines_follow_the_metric() {
+    let xml = report("jacoco.xml");
+
+    let lines = uncovered_lines(&xml, CoverageMetric::Line).expect("parse xml");
+    assert_eq!(lines.len(), 1, "fully covered files are left out: {lines:?}");
+    assert_eq!(lines[0].class, "calc.Calc");
+    assert_eq!(lines[0].ranges, vec![10..=10, 16..=16]);
+
+    let branches = uncovered_lines(&xml, CoverageMetric::Branch).expect("parse xml");
+    assert_eq!(branches[0].ranges, vec![9..=9, 16..=16]);
+}
+
+#[test]
+fn uncovered_ranges_span_lines_without_code() {
+    let xml = r#"<package name="shapes/util"><sourcefile name="Area.java"><line nr="4" mi="2" ci="0" mb="0" cb="0"/><line nr="5" mi="3" ci="0" mb="0" cb="0"/><line nr="7" mi="1" ci="0" mb="0" cb="0"/><line nr="9" mi="0" ci="2" mb="0" cb="0"/><line nr="11" mi="4" ci="0" mb="0" cb="0"/></sourcefile></package>"#;
+
+    let lines = uncovered_lines(xml, CoverageMetric::Line).expect("parse xml");
+    assert_eq!(lines[0].class, "shapes.util.Area");
+    assert_eq!(lines[0].ranges, vec![4..=7, 11..=11]);
+}
+
+#[test]
+fn uncovered_lines_read_attributes_in_any_order() {
+    let xml = r#"<report><package name="calc"><sourcefile name="Calc.java"><line cb="0" mb="0" ci="0" mi="2" nr="4"/><line ci="3" nr="5" mi="0" cb="0" mb="0"/><line mb="0" cb="0" ci="0" mi="1" nr="6"/></sourcefile></package></report>"#;
+
+    let lines = uncovered_lines(xml, CoverageMetric::Line).expect("parse xml");
+    assert_eq!(lines[0].class, "calc.Calc");
+    assert_eq!(lines[0].ranges, vec![4..=4, 6..=6]);
+
+    let err = uncovered_lines("<report><package", CoverageMetric::Line).expect_err("truncated");
+    assert!(err.to_string().contains("not valid XML"), "error: {err}");
+}
+
+#[test]
+fn coverage_metric_parses_from_names() {
+    assert_eq!("Branch".parse::<CoverageMetric>().unwrap(), CoverageMetric::Branch);
+    assert_eq!("lines".parse::<CoverageMetric>().unwrap(), CoverageMetric::Line);
+    assert!("methods".parse::<CoverageMetric>().is_err());
+}
+
+/// Paths for the fixture project, building into a scratch directory. The
+/// `lib` directory is shared between runs, so JaCoCo is only downloaded once.
+fn scratch_paths() -> (PathBuf, ProjectPaths) {
+    let crate_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let scratch = std::env::temp_dir().join(format!("umm-coverage-{}", Uuid::new_v4()));
+    let lib = std::env::temp_dir().join("umm-coverage-lib");
+    fs::create_dir_all(&lib).expect("create lib");
+    let console = "junit-platform-console-standalone-1.14.1.jar";
+    if !lib.join(console).exists() {
+        fs::copy(crate_root.join("jar_files").join(console), lib.join(console))
+            .expect("copy JUnit console launcher");
+    }
+
+    let paths = ProjectPaths::from_parts(
+        fixture_root(),
+        None,
+        Some(scratch.join("target")),
+        None,
+        Some(lib),
+        Some(scratch.join(".umm")),
+        Some(scratch.join("reports")),
+    );
+    (scratch, paths)
+}
+
+#[tokio::test]
+async fn coverage_grader_measures_each_metric() -> anyhow::Result<()> {
+    let (scratch, paths) = scratch_paths();
+    let project = Project::from_paths(paths)?;
+
+    // `divide(6, 3)` and `add` run; the throw and all of `abs` do not.
+    let expected = [
+        (CoverageMetric::Line, "66.7% line coverage of calc.Calc", 10.0 * 4.0 / 6.0),
+        (CoverageMetric::Branch, "25.0% branch coverage of calc.Calc", 2.5),
+        (CoverageMetric::Instruction, "52.0% instruction coverage of calc.Calc", 5.2),
+    ];
+    for (metric, reason, score) in expected {
+        let result = CoverageGrader::builder()
+            .req_name("coverage")
+            .out_of(10.0)
+            .project(project.clone())
+            .target_classes(["calc.Calc"])
+            .test_files(["calc.CalcTest"])
+            .metric(metric)
+            .build()
+            .run()
+            .await?;
+
+        assert!(result.reason().contains(reason), "{metric}: {}", result.reason());
+        assert!(
+            (result.grade_value() - score).abs() < 1e-9,
+            "{metric}: {}",
+            result.grade_value()
+        );
+    }
+
+    let below = CoverageGrader::builder()
+        .req_name("coverage")
+        .out_of(10.0)
+        .project(project)
+        .target_classes(["calc.Calc"])
+        .test_files(["calc.CalcTest"])
+        .min_coverage(0.75)
+        .build()
+        .run()
+        .await?;
+    assert_eq!(below.grade_value(), 0.0, "reason: {}", below.reason());
+    assert!(below.reason().contains("below the required 75.0%"), "{}", below.reason());
+
+    let leftovers = fs::read_dir(scratch.join("reports"))
+        .map(|entries| entries.count())
+        .unwrap_or(0);
+    assert_eq!(leftovers, 0, "coverage runs should clean up their reports");
+
+    let _ = fs::remove_dir_all(scratch);
+    Ok(())
+}
+
+#[tokio::test]
+async fn coverage_grader_rejects_an_out_of_range_threshold() {
+    let (scratch, paths) = scratch_paths();
+    let err = CoverageGrader::builder()
+        .req_name("coverage")
+        .out_of(10.0)
+        .project(Project::from_paths(paths).expect("build project"))
+        .target_classes(["calc.Calc"])
+        .test_files(["calc.CalcTest"])
+        .min_coverage(75.0)
+        .build()
+        .run()
+        .await
+        .expect_err("min_coverage is a fraction");
+
+    assert!(err.to_string().contains("between 0 and 1"), "error: {err}");
+    let _ = fs::remove_dir_all(scratch);
+}
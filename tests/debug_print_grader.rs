@@ -0,0 +1,73 @@
+use umm::java::{
+    Project,
+    grade::{DebugPrintGrader, print_calls},
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("debug-print")
+}
+
+#[test]
+fn print_calls_report_enclosing_method_and_line() {
+    let file = project().identify("Main").expect("identify Main");
+    let calls = print_calls(file.parser()).expect("print calls");
+    let found: Vec<_> = calls
+        .iter()
+        .map(|c| (c.method.as_str(), c.line, c.call.as_str()))
+        .collect();
+
+    assert_eq!(
+        found,
+        [
+            ("Main.add", 4, "System.out.println"),
+            ("Main.display", 9, "System.out.printf"),
+            ("Main.main", 14, "System.out.println"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn debug_print_grader_flags_prints_outside_output_methods() {
+    let result = DebugPrintGrader::builder()
+        .req_name("no debug prints")
+        .out_of(3.0)
+        .project(project())
+        .files(["Main"])
+        .allowed_methods(["main", "Main.display"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 2.0);
+    assert!(
+        result
+            .reason()
+            .contains("Main.java:4 in Main.add: System.out.println"),
+        "reason: {}",
+        result.reason()
+    );
+    assert!(!result.reason().contains("Main.main"), "reason: {}", result.reason());
+    assert!(!result.reason().contains("Main.display"), "reason: {}", result.reason());
+}
+
+#[tokio::test]
+async fn debug_print_grader_only_allows_main_by_default() {
+    let result = DebugPrintGrader::builder()
+        .req_name("no debug prints")
+        .out_of(3.0)
+        .project(project())
+        .files(["Main"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 1.0);
+    assert!(result.prompt().is_some());
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use umm::{
+    java::{grade::build_heuristic_context, paths::project_paths, project::Project},
+    retrieval::HeuristicConfig,
+    types::LineRef,
+};
+
+fn context(include_declarations: bool) -> anyhow::Result<String> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/field-context");
+    let project = Project::from_paths(project_paths().root_dir(root).build())?;
+
+    // A NullPointerException at `names.add(name)`: the snippet alone does not
+    // show that `names` is never initialized.
+    let message = build_heuristic_context(
+        vec![LineRef {
+            line_number: 40,
+            file_name:   "Roster".to_string(),
+        }],
+        project,
+        HeuristicConfig {
+            start_offset: 2,
+            num_lines: 3,
+            max_line_refs: 1,
+            full_file_ratio: 1.0,
+            include_declarations,
+        },
+    )?;
+    let message = serde_json::to_value(message)?;
+    Ok(message["content"]
+        .as_str()
+        .expect("text content")
+        .to_string())
+}
+
+#[test]
+fn declarations_are_prepended_when_enabled() -> anyhow::Result<()> {
+    let content = context(true)?;
+    for expected in [
+        "Imports and fields from student's submission `Roster`",
+        "import java.util.ArrayList;",
+        "import java.util.List;",
+        "private List<String> names;",
+        "private int capacity = 30;",
+    ] {
+        assert!(content.contains(expected), "missing `{expected}` in:\n{content}");
+    }
+
+    let fields = content.find("private List<String> names;").unwrap();
+    let snippet = content
+        .find("names.add(name);")
+        .expect("snippet still present");
+    assert!(fields < snippet, "declarations should precede snippets:\n{content}");
+    Ok(())
+}
+
+#[test]
+fn declarations_are_left_out_by_default() -> anyhow::Result<()> {
+    let content = context(false)?;
+    assert!(content.contains("names.add(name);"), "{content}");
+    assert!(!content.contains("private List<String> names;"), "{content}");
+    assert!(!content.contains("import java.util.List;"), "{content}");
+    Ok(())
+}
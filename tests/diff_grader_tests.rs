@@ -1,23 +1,18 @@
-use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-use umm::java::{Project, grade::diff::DiffGrader, paths::ProjectPaths};
+use umm::java::{
+    LineEnding, RunOutput,
+    grade::diff::{DiffGrader, Normalizer, interleave_stdin},
+};
 
-fn fixture_root(name: &str) -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("fixtures")
-        .join("java")
-        .join(name)
-}
+#[path = "fixture_support.rs"]
+mod fixture_support;
 
-fn project(name: &str) -> Project {
-    let root = fixture_root(name);
-    let paths = ProjectPaths::from_parts(root, None, None, None, None, None, None);
-    Project::from_paths(paths).expect("build project")
-}
+use fixture_support::java_project;
 
 #[tokio::test]
 async fn diff_passes_on_exact_match_trimmed() {
-    let proj = project("diff-ok");
+    let proj = java_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("ok")
         .out_of(2.0)
@@ -34,7 +29,7 @@ async fn diff_passes_on_exact_match_trimmed() {
 
 #[tokio::test]
 async fn diff_detects_mismatch_with_preserve_whitespace() {
-    let proj = project("diff-ok");
+    let proj = java_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("ws")
         .out_of(1.0)
@@ -52,7 +47,7 @@ async fn diff_detects_mismatch_with_preserve_whitespace() {
 
 #[tokio::test]
 async fn diff_ignores_case_when_configured() {
-    let proj = project("diff-ok");
+    let proj = java_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("case")
         .out_of(1.5)
@@ -70,7 +65,7 @@ async fn diff_ignores_case_when_configured() {
 
 #[tokio::test]
 async fn diff_trims_when_whitespace_not_preserved() {
-    let proj = project("diff-ok");
+    let proj = java_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("trim")
         .out_of(1.0)
@@ -87,7 +82,7 @@ async fn diff_trims_when_whitespace_not_preserved() {
 
 #[tokio::test]
 async fn diff_runs_with_stdin_bytes() {
-    let proj = project("diff-stdin");
+    let proj = java_project("diff-stdin");
     let grader = DiffGrader::builder()
         .req_name("stdin")
         .out_of(1.0)
@@ -104,7 +99,7 @@ async fn diff_runs_with_stdin_bytes() {
 
 #[tokio::test]
 async fn diff_handles_runtime_failure() {
-    let proj = project("diff-runtime");
+    let proj = java_project("diff-runtime");
     let grader = DiffGrader::builder()
         .req_name("rt")
         .out_of(3.0)
@@ -121,7 +116,7 @@ async fn diff_handles_runtime_failure() {
 
 #[tokio::test]
 async fn diff_handles_compile_failure() {
-    let proj = project("diff-compile");
+    let proj = java_project("diff-compile");
     let grader = DiffGrader::builder()
         .req_name("compile")
         .out_of(3.0)
@@ -138,7 +133,7 @@ async fn diff_handles_compile_failure() {
 
 #[tokio::test]
 async fn diff_multiple_cases_stops_on_first_failure() {
-    let proj = project("diff-ok");
+    let proj = java_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("multi")
         .out_of(2.0)
@@ -158,7 +153,7 @@ async fn diff_multiple_cases_stops_on_first_failure() {
 
 #[tokio::test]
 async fn diff_errors_when_no_cases() {
-    let proj = project("diff-ok");
+    let proj = java_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("empty")
         .out_of(1.0)
@@ -169,3 +164,345 @@ async fn diff_errors_when_no_cases() {
     let err = grader.run().await;
     assert!(err.is_err(), "expected missing cases error");
 }
+
+#[tokio::test]
+async fn diff_preserve_whitespace_catches_indentation_in_ascii_art() {
+    let flattened = "*\n***\n*****\n";
+    let shaped = "  *\n ***\n*****\n";
+
+    let grade = |expected: &'static str, preserve: bool| async move {
+        DiffGrader::builder()
+            .req_name("tree")
+            .out_of(1.0)
+            .project(java_project("diff-ascii"))
+            .file("Main")
+            .cases(vec![(expected, None::<String>)])
+            .preserve_whitespace(preserve)
+            .build()
+            .run()
+            .await
+            .expect("grade")
+            .grade_value()
+    };
+
+    // Trimming hides the broken indentation; preserving whitespace exposes it.
+    assert_eq!(grade(flattened, false).await, 1.0);
+    assert_eq!(grade(flattened, true).await, 0.0);
+    assert_eq!(grade(shaped, true).await, 1.0);
+}
+
+#[tokio::test]
+async fn diff_strips_ansi_colors_when_configured() {
+    let colored = || {
+        DiffGrader::builder()
+            .req_name("ansi")
+            .out_of(1.0)
+            .project(java_project("diff-ansi"))
+            .file("Main")
+            .cases(vec![("hello world", None::<String>)])
+    };
+
+    let raw = colored().build().run().await.expect("grade");
+    assert_eq!(raw.grade_value(), 0.0);
+
+    let stripped = colored()
+        .strip_ansi(true)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+    assert_eq!(stripped.grade_value(), 1.0);
+}
+
+#[tokio::test]
+async fn diff_decodes_invalid_utf8_output_lossily() {
+    let grader = DiffGrader::builder()
+        .req_name("bytes")
+        .out_of(1.0)
+        .project(java_project("diff-bytes"))
+        .file("Main")
+        .cases(vec![("caf\u{FFFD}\u{FFFD}", None::<String>)])
+        .build()
+        .run()
+        .await
+        .expect("invalid UTF-8 output should not be an error");
+
+    assert_eq!(grader.grade_value(), 1.0, "reason: {}", grader.reason());
+}
+
+#[tokio::test]
+async fn diff_labels_and_separates_every_failing_case() {
+    let grader = DiffGrader::builder()
+        .req_name("cases")
+        .out_of(1.0)
+        .project(java_project("diff-ok"))
+        .file("Main")
+        .cases(vec![
+            ("goodbye world", None::<String>),
+            ("hello world", None::<String>),
+            ("hello there", None::<String>),
+        ])
+        .case_separator("\n===\n")
+        .case_label("Test #{n}")
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(grader.grade_value(), 0.0);
+    assert!(grader.reason().contains("(2 of 3 cases failed)"), "reason: {}", grader.reason());
+
+    let prompt = grader.prompt().expect("failing cases produce a prompt");
+    let prompt = serde_json::to_string(prompt).expect("serialize prompt");
+    assert!(prompt.contains("Test #1\\n"), "prompt: {prompt}");
+    assert!(prompt.contains("\\n===\\nTest #3\\n"), "prompt: {prompt}");
+    assert!(!prompt.contains("Test #2"), "prompt: {prompt}");
+}
+
+#[test]
+fn interleave_stdin_places_each_input_after_its_prompt() {
+    let output = "First number: Second number: Sum: 7\n";
+    assert_eq!(
+        interleave_stdin(output, "3\n4\n").as_deref(),
+        Some("First number: 3\nSecond number: 4\nSum: 7\n")
+    );
+    assert_eq!(interleave_stdin("Sum: 7\n", ""), None);
+    assert_eq!(interleave_stdin("no prompts here\n", "3\n"), None);
+}
+
+#[tokio::test]
+async fn diff_feedback_interleaves_input_with_prompts() {
+    let grader = DiffGrader::builder()
+        .req_name("interactive")
+        .out_of(1.0)
+        .project(java_project("diff-interactive"))
+        .file("Main")
+        .cases(vec![("First number: Second number: Sum: 7", Some("3\n4\n".to_string()))])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(grader.grade_value(), 0.0);
+    let prompt = grader.prompt().expect("failing cases produce a prompt");
+    let prompt = serde_json::to_string(prompt).expect("serialize prompt");
+    assert!(
+        prompt.contains("First number: 3\\nSecond number: 4\\nSum: -1"),
+        "prompt: {prompt}"
+    );
+}
+
+#[tokio::test]
+async fn diff_writes_unix_newlines_to_stdin_by_default() {
+    let grade = |ending: LineEnding| async move {
+        DiffGrader::builder()
+            .req_name("newline")
+            .out_of(1.0)
+            .project(java_project("diff-newline"))
+            .file("Main")
+            .cases(vec![("Hello, Alice! (5 letters)", Some("Alice"))])
+            .line_ending(ending)
+            .build()
+            .run()
+            .await
+            .expect("grade")
+    };
+
+    let unix = grade(LineEnding::default()).await;
+    assert_eq!(unix.grade_value(), 1.0, "reason: {}", unix.reason());
+
+    let windows = grade(LineEnding::CrLf).await;
+    assert_eq!(windows.grade_value(), 0.0);
+    assert!(windows.reason().contains("6 letters"), "reason: {}", windows.reason());
+}
+
+#[test]
+fn line_ending_normalizes_every_line_break() {
+    assert_eq!(LineEnding::Lf.apply("3\r\n4"), "3\n4\n");
+    assert_eq!(LineEnding::CrLf.apply("3\n4"), "3\r\n4\r\n");
+    assert_eq!("crlf".parse::<LineEnding>().unwrap(), LineEnding::CrLf);
+    assert!("cr".parse::<LineEnding>().is_err());
+}
+
+#[tokio::test]
+async fn run_captured_keeps_stdout_and_stderr_apart() {
+    let file = java_project("diff-stderr")
+        .identify("Main")
+        .expect("identify Main");
+
+    let captured = file.run_captured(None, LineEnding::Lf).await.expect("run");
+    assert_eq!(
+        captured,
+        RunOutput {
+            stdout:    "Result: 42\n".to_string(),
+            stderr:    "debug: computing\ndebug: done\n".to_string(),
+            exit_code: Some(0),
+        }
+    );
+
+    let merged = file.run(None).await.expect("run merged");
+    assert!(merged.contains("debug: computing"), "merged: {merged}");
+    assert!(merged.contains("Result: 42"), "merged: {merged}");
+}
+
+#[tokio::test]
+async fn run_captured_reports_a_failing_exit_code() {
+    let captured = java_project("diff-runtime")
+        .identify("Main")
+        .expect("identify Main")
+        .run_captured(None, LineEnding::Lf)
+        .await
+        .expect("run");
+
+    assert_eq!(captured.exit_code, Some(1));
+    assert!(captured.stdout.is_empty(), "stdout: {}", captured.stdout);
+    assert!(captured.stderr.contains("boom"), "stderr: {}", captured.stderr);
+    assert!(matches!(
+        captured.check_status(),
+        Err(umm::java::JavaFileError::AtRuntime { .. })
+    ));
+}
+
+#[tokio::test]
+async fn diff_compares_only_stdout_when_configured() {
+    let grade = |stdout_only| {
+        DiffGrader::builder()
+            .req_name("stdout")
+            .out_of(1.0)
+            .project(java_project("diff-stderr"))
+            .file("Main")
+            .cases(vec![("Result: 42", None::<String>)])
+            .stdout_only(stdout_only)
+            .build()
+            .run()
+    };
+
+    assert_eq!(grade(true).await.expect("grade").grade_value(), 1.0);
+    assert_eq!(grade(false).await.expect("grade").grade_value(), 0.0);
+}
+
+#[tokio::test]
+async fn diff_shows_stderr_in_feedback_when_comparing_stdout_only() {
+    let grader = DiffGrader::builder()
+        .req_name("stdout")
+        .out_of(1.0)
+        .project(java_project("diff-stderr"))
+        .file("Main")
+        .cases(vec![("Result: 43", None::<String>)])
+        .stdout_only(true)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(grader.grade_value(), 0.0);
+    assert!(!grader.reason().contains("debug"), "reason: {}", grader.reason());
+    let prompt = grader.prompt().expect("failing cases produce a prompt");
+    let prompt = serde_json::to_string(prompt).expect("serialize prompt");
+    assert!(
+        prompt.contains("Standard error (not compared):\\ndebug: computing\\ndebug: done\\n"),
+        "prompt: {prompt}"
+    );
+}
+
+#[test]
+fn normalizers_apply_in_order() {
+    let text = "Banana  \tYELLOW\napple Red";
+    let steps = [
+        Normalizer::CollapseWhitespace,
+        Normalizer::IgnoreCase,
+        Normalizer::SortLines,
+    ];
+    assert_eq!(Normalizer::apply_all(&steps, text), "apple red\nbanana yellow");
+
+    let sorted_first = [Normalizer::SortLines, Normalizer::IgnoreCase];
+    assert_eq!(Normalizer::apply_all(&sorted_first, "B\na"), "b\na");
+
+    let custom = [
+        Normalizer::custom(|text| text.replace("colour", "color")),
+        Normalizer::Trim,
+    ];
+    assert_eq!(Normalizer::apply_all(&custom, " colour\n"), "color");
+}
+
+#[test]
+fn diff_flags_populate_the_front_of_the_pipeline() {
+    let grader = DiffGrader::builder()
+        .req_name("pipeline")
+        .out_of(1.0)
+        .project(java_project("diff-ok"))
+        .file("Main")
+        .ignore_case(true)
+        .strip_ansi(true)
+        .normalizers([Normalizer::SortLines])
+        .build()
+        .normalizer(Normalizer::TrimLines);
+
+    assert_eq!(
+        format!("{:?}", grader.pipeline()),
+        "[StripAnsi, Trim, IgnoreCase, SortLines, TrimLines]"
+    );
+}
+
+#[tokio::test]
+async fn diff_composes_normalizers_for_unordered_output() {
+    let expected = "apple red\nbanana yellow\ncherry red";
+    let grader = || {
+        DiffGrader::builder()
+            .req_name("unordered")
+            .out_of(1.0)
+            .project(java_project("diff-normalize"))
+            .file("Main")
+            .cases(vec![(expected, None::<String>)])
+    };
+
+    let raw = grader().build().run().await.expect("grade");
+    assert_eq!(raw.grade_value(), 0.0);
+
+    let normalized = grader()
+        .ignore_case(true)
+        .normalizers([Normalizer::CollapseWhitespace, Normalizer::SortLines])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+    assert_eq!(normalized.grade_value(), 1.0, "reason: {}", normalized.reason());
+}
+
+#[tokio::test]
+async fn run_passes_arguments_after_the_class_name() {
+    let file = java_project("diff-args")
+        .identify("Main")
+        .expect("identify Main");
+
+    let plain = file.run(None).await.expect("run without args");
+    assert_eq!(plain.trim(), "Hello, nobody!");
+
+    let greeted = file
+        .with_args(["Ada", "Grace Hopper"])
+        .run(None)
+        .await
+        .expect("run with args");
+    assert_eq!(greeted.trim(), "Hello, Ada and Grace Hopper!");
+}
+
+#[tokio::test]
+async fn input_delay_writes_one_line_at_a_time() {
+    let file = java_project("diff-prompts")
+        .identify("Main")
+        .expect("identify Main")
+        .with_input_delay(Duration::from_millis(200));
+
+    let started = Instant::now();
+    let output = file
+        .run(Some("Grace\n85\nred".to_string()))
+        .await
+        .expect("run paced");
+
+    assert!(
+        started.elapsed() >= Duration::from_millis(600),
+        "elapsed: {:?}",
+        started.elapsed()
+    );
+    assert!(output.contains("Grace is 85 and likes red."), "output: {output}");
+}
@@ -0,0 +1,42 @@
+use umm::java::grade::PenaltyMode;
+
+#[test]
+fn linear_penalty_scales_with_nits() {
+    let mode = PenaltyMode::Linear;
+    assert_eq!(mode.total(0, 3.0), 0.0);
+    assert_eq!(mode.total(1, 3.0), 3.0);
+    assert_eq!(mode.total(8, 3.0), 24.0);
+    assert_eq!(mode.describe(8, 3.0), "-24 for 8 nits (3 each)");
+}
+
+#[test]
+fn capped_penalty_stops_at_max() {
+    let mode = PenaltyMode::Capped(5.0);
+    assert_eq!(mode.total(1, 2.0), 2.0);
+    assert_eq!(mode.total(2, 2.0), 4.0);
+    assert_eq!(mode.total(3, 2.0), 5.0);
+    assert_eq!(mode.total(50, 2.0), 5.0);
+    assert_eq!(mode.describe(50, 2.0), "-5 for 50 nits (2 each, capped at 5)");
+}
+
+#[test]
+fn logarithmic_penalty_grows_per_doubling() {
+    let mode = PenaltyMode::Logarithmic;
+    assert_eq!(mode.total(0, 2.0), 0.0);
+    assert_eq!(mode.total(1, 2.0), 2.0);
+    assert_eq!(mode.total(3, 2.0), 4.0);
+    assert_eq!(mode.total(7, 2.0), 6.0);
+    assert!(mode.total(4, 2.0) < PenaltyMode::Linear.total(4, 2.0));
+    assert_eq!(mode.describe(4, 2.0), "-4.64 for 4 nits (2 * log2(1 + nits))");
+}
+
+#[test]
+fn no_nits_reason_is_mode_independent() {
+    for mode in [
+        PenaltyMode::Linear,
+        PenaltyMode::Capped(1.0),
+        PenaltyMode::Logarithmic,
+    ] {
+        assert_eq!(mode.describe(0, 3.0), "No documentation nits found.");
+    }
+}
@@ -0,0 +1,74 @@
+use umm::java::{
+    Project,
+    grade::{
+        DiffGrader,
+        query::{Query, QueryConstraint, QueryGrader},
+    },
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("empty-submission")
+}
+
+#[test]
+fn whitespace_and_comment_only_files_are_empty() {
+    let project = project();
+
+    assert!(project.identify("Main").expect("identify Main").is_empty());
+    assert!(
+        project
+            .identify("Helper")
+            .expect("identify Helper")
+            .is_empty()
+    );
+    assert!(
+        !project
+            .identify("Other")
+            .expect("identify Other")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn diff_grader_explains_empty_file() {
+    let result = DiffGrader::builder()
+        .req_name("output")
+        .out_of(2.0)
+        .project(project())
+        .file("Main")
+        .cases(vec![("hello", None::<String>)])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 0.0);
+    assert_eq!(result.reason(), "`Main.java` is empty or contains no code.");
+}
+
+#[test]
+fn must_not_match_query_does_not_pass_an_empty_file() {
+    let result = QueryGrader::builder()
+        .req_name("no loops")
+        .out_of(3.0)
+        .project(project())
+        .file("Helper")
+        .queries(vec![
+            Query::new()
+                .set_query("((for_statement) @loop)".into())
+                .set_capture("loop".into()),
+        ])
+        .constraint(QueryConstraint::MustNotMatch)
+        .reason("must not use for loops")
+        .build()
+        .grade_by_query()
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 0.0);
+    assert_eq!(result.reason(), "`Helper.java` is empty or contains no code.");
+}
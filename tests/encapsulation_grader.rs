@@ -0,0 +1,117 @@
+use umm::java::{
+    Project,
+    grade::{EncapsulationGrader, field_declarations},
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("encapsulation")
+}
+
+#[test]
+fn field_declarations_list_each_variable_with_modifiers() {
+    let file = project().identify("Counter").expect("identify Counter");
+    let fields = field_declarations(file.parser()).expect("fields");
+    let found: Vec<_> = fields
+        .iter()
+        .map(|f| (f.name.as_str(), f.line, f.modifiers.join(" ")))
+        .collect();
+
+    assert_eq!(
+        found,
+        [
+            ("Counter.LIMIT", 3, "public static final".to_string()),
+            ("Counter.label", 4, "public final".to_string()),
+            ("Counter.count", 5, "public".to_string()),
+            ("Counter.instances", 6, "protected static".to_string()),
+            ("Counter.step", 7, String::new()),
+            ("Counter.offset", 7, String::new()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn encapsulated_class_gets_full_marks() {
+    let result = EncapsulationGrader::builder()
+        .req_name("encapsulation")
+        .out_of(4.0)
+        .project(project())
+        .files(["Account"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 4.0);
+    assert!(result.prompt().is_none());
+}
+
+#[tokio::test]
+async fn exposed_mutable_fields_are_flagged() {
+    let result = EncapsulationGrader::builder()
+        .req_name("encapsulation")
+        .out_of(5.0)
+        .project(project())
+        .files(["Account", "Counter"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 1.0);
+    for expected in [
+        "Counter.java:5 Counter.count: public count",
+        "Counter.java:6 Counter.instances: protected static instances",
+        "Counter.java:7 Counter.step: package-private step",
+        "Counter.java:7 Counter.offset: package-private offset",
+    ] {
+        assert!(result.reason().contains(expected), "missing `{expected}`: {}", result.reason());
+    }
+    assert!(!result.reason().contains("LIMIT"), "reason: {}", result.reason());
+    assert!(!result.reason().contains("label"), "reason: {}", result.reason());
+    assert!(!result.reason().contains("Account"), "reason: {}", result.reason());
+}
+
+#[tokio::test]
+async fn exceptions_are_configurable() {
+    let strict = EncapsulationGrader::builder()
+        .req_name("encapsulation")
+        .out_of(10.0)
+        .project(project())
+        .files(["Counter"])
+        .allow_final(false)
+        .allowed_fields(["Counter.step", "offset"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    // `label` is now flagged; constants and the named fields stay exempt.
+    assert_eq!(strict.grade_value(), 7.0);
+    assert!(strict.reason().contains("Counter.label"), "reason: {}", strict.reason());
+    assert!(!strict.reason().contains("LIMIT"), "reason: {}", strict.reason());
+    assert!(!strict.reason().contains("step"), "reason: {}", strict.reason());
+
+    let no_constants = EncapsulationGrader::builder()
+        .req_name("encapsulation")
+        .out_of(10.0)
+        .project(project())
+        .files(["Counter"])
+        .allow_final(false)
+        .allow_constants(false)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(no_constants.grade_value(), 4.0);
+    assert!(
+        no_constants.reason().contains("Counter.LIMIT"),
+        "reason: {}",
+        no_constants.reason()
+    );
+}
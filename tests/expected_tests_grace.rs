@@ -0,0 +1,57 @@
+use umm::java::{grade::tests::ByUnitTestGrader, project::Project};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project_with_jars;
+
+fn project() -> Project {
+    java_project_with_jars("test-factory")
+}
+
+#[tokio::test]
+async fn extra_tests_are_only_reported_without_the_flag() -> anyhow::Result<()> {
+    let grade = |allow_extra_tests: bool| async move {
+        ByUnitTestGrader::builder()
+            .project(project())
+            .test_files(["SquaresTest"])
+            .expected_tests(["squaresZero"])
+            .allow_extra_tests(allow_extra_tests)
+            .req_name("grace")
+            .out_of(4.0)
+            .build()
+            .run()
+            .await
+    };
+
+    let strict = grade(false).await?;
+    assert_eq!(strict.grade_value(), 0.0);
+    assert!(
+        strict
+            .reason()
+            .contains("- Unexpected test called squaresSmallNumbers")
+    );
+
+    let lenient = grade(true).await?;
+    assert!(!lenient.reason().contains("Unexpected test"), "reason: {}", lenient.reason());
+    assert_eq!(lenient.reason(), "- 4/4 tests passing.");
+    Ok(())
+}
+
+#[tokio::test]
+async fn missing_tests_are_still_reported_with_the_flag() -> anyhow::Result<()> {
+    let result = ByUnitTestGrader::builder()
+        .project(project())
+        .test_files(["SquaresTest"])
+        .expected_tests(["squaresZero", "squaresNegatives"])
+        .allow_extra_tests(true)
+        .req_name("grace")
+        .out_of(4.0)
+        .build()
+        .run()
+        .await?;
+
+    assert_eq!(result.grade_value(), 0.0);
+    assert!(result.reason().contains("- squaresNegatives not found."));
+    Ok(())
+}
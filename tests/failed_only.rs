@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use umm::java::{JavaFileError, file::failed_test_methods, paths::project_paths, project::Project};
+use uuid::Uuid;
+
+fn project(umm_dir: PathBuf) -> anyhow::Result<Project> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let paths = project_paths()
+        .root_dir(root.join("fixtures/java/failed-only"))
+        .lib_dir(root.join("jar_files"))
+        .umm_dir(umm_dir)
+        .build();
+    Project::from_paths(paths)
+}
+
+#[test]
+fn failed_test_methods_reads_junit_method_sources() {
+    let output = "\
+Failures (2):
+  JUnit Jupiter:CalcTest:subtractsNumbers()
+    MethodSource [className = 'CalcTest', methodName = 'subtractsNumbers', methodParameterTypes = \
+                  '']
+    => org.opentest4j.AssertionFailedError: expected: <1> but was: <-1>
+  JUnit Jupiter:CalcTest:subtractsNumbers()
+    MethodSource [className = 'CalcTest', methodName = 'subtractsNumbers', methodParameterTypes = \
+                  '']
+  JUnit Jupiter:Outer$Inner:works()
+    MethodSource [className = 'pkg.Outer$Inner', methodName = 'works', methodParameterTypes = '']
+";
+    assert_eq!(
+        failed_test_methods(output),
+        ["CalcTest#subtractsNumbers", "pkg.Outer$Inner#works"]
+    );
+}
+
+#[tokio::test]
+async fn failed_only_reruns_just_the_previous_failures() -> anyhow::Result<()> {
+    let umm_dir = std::env::temp_dir().join(format!("umm-failed-only-{}", Uuid::new_v4()));
+    let project = project(umm_dir.clone())?;
+    let file = project.identify("CalcTest")?;
+
+    assert!(file.last_failed_tests()?.is_empty());
+    let first = file.test(Vec::<&str>::new(), Some(&project)).await;
+    assert!(matches!(first, Err(JavaFileError::FailedTests { .. })), "{first:?}");
+    assert_eq!(file.last_failed_tests()?, ["CalcTest#subtractsNumbers"]);
+
+    let rerun = file.test_failed_only(Some(&project)).await;
+    let Err(JavaFileError::FailedTests { test_results, .. }) = rerun else {
+        panic!("re-run should still fail: {rerun:?}");
+    };
+    assert!(test_results.contains("subtractsNumbers"), "{test_results}");
+    assert!(!test_results.contains("addsNumbers"), "{test_results}");
+    assert!(!test_results.contains("addsZero"), "{test_results}");
+
+    let _ = std::fs::remove_dir_all(&umm_dir);
+    Ok(())
+}
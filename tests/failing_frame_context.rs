@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use umm::java::{grade::tests::ByUnitTestGrader, paths::project_paths, project::Project};
+
+fn fixture_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/failing-frame")
+}
+
+fn jar_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("jar_files")
+}
+
+#[tokio::test]
+async fn failing_frame_method_is_included_in_context() -> anyhow::Result<()> {
+    let paths = project_paths()
+        .root_dir(fixture_root())
+        .lib_dir(jar_dir())
+        .build();
+    let project = Project::from_paths(paths)?;
+
+    let result = ByUnitTestGrader::builder()
+        .project(project)
+        .test_files(["PickerTest"])
+        .req_name("picker")
+        .out_of(2.0)
+        .build()
+        .run()
+        .await?;
+
+    assert_eq!(result.grade_value(), 1.0);
+
+    let prompt = serde_json::to_string(result.prompt().expect("failing tests produce a prompt"))?;
+    assert!(
+        prompt.contains("Method body from student's submission `Picker#pick`"),
+        "prompt was: {prompt}"
+    );
+    assert!(
+        !prompt.contains("`Picker#count`"),
+        "only methods from failing frames should be pulled in: {prompt}"
+    );
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use umm::java::grade::{Grade, GradeResult, generate_feedback};
+use uuid::Uuid;
+
+#[test]
+fn generate_feedback_writes_to_a_custom_path() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join(format!("umm-feedback-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("feedback.md");
+
+    // Full marks never reach Supabase, so this runs offline.
+    let result = GradeResult::builder()
+        .requirement("compiles")
+        .grade(Grade::new(2.0, 2.0))
+        .reason("ok")
+        .build();
+    generate_feedback([result], Some(path.as_path()))?;
+
+    let written = std::fs::read_to_string(&path)?;
+    assert!(written.starts_with("## Understanding Your Autograder Results"), "{written}");
+    assert!(
+        written
+            .contains("This type of feedback cannot be generated for submissions without penalty."),
+        "{written}"
+    );
+    assert!(!dir.join("FEEDBACK").exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use std::{fs, path::PathBuf};
+
+use umm::util::{FindOptions, find_files, find_files_with};
+use uuid::Uuid;
+
+fn scratch_dir() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("umm-find-files-{}", Uuid::new_v4()));
+    fs::create_dir_all(root.join("src").join("pkg")).expect("create dirs");
+    fs::write(root.join("src").join("Main.java"), "class Main {}").expect("write Main");
+    fs::write(root.join("src").join("pkg").join("Util.java"), "class Util {}").expect("write Util");
+    root
+}
+
+#[test]
+fn find_files_with_respects_max_depth() {
+    let root = scratch_dir();
+
+    let shallow = FindOptions::builder().max_depth(1).build();
+    let shallow = find_files_with("java", &root, shallow).expect("walk");
+    assert_eq!(shallow, [root.join("src").join("Main.java")]);
+
+    let deep = FindOptions::builder().max_depth(2).build();
+    let deep = find_files_with("java", &root, deep).expect("walk");
+    assert_eq!(
+        deep,
+        [
+            root.join("src").join("Main.java"),
+            root.join("src").join("pkg").join("Util.java")
+        ]
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn find_files_searches_the_whole_tree() {
+    let root = scratch_dir();
+    let nested = root
+        .join("src")
+        .join("main")
+        .join("java")
+        .join("com")
+        .join("example");
+    fs::create_dir_all(&nested).expect("create maven layout");
+    fs::write(nested.join("App.java"), "class App {}").expect("write App");
+
+    // Callers pass small depths, but like the glob search this replaced,
+    // nothing below the root is skipped.
+    let found = find_files("java", 1, &root).expect("walk");
+    assert_eq!(
+        found,
+        [
+            root.join("src").join("Main.java"),
+            nested.join("App.java"),
+            root.join("src").join("pkg").join("Util.java"),
+        ]
+    );
+
+    let top_only = find_files("java", 0, &root).expect("walk");
+    assert!(top_only.is_empty(), "{top_only:?}");
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[cfg(unix)]
+#[test]
+fn find_files_terminates_on_symlink_loops() {
+    use std::os::unix::fs::symlink;
+
+    let root = scratch_dir();
+    // `src/pkg/loop` points back at `src`, so following links naively never
+    // ends.
+    symlink(root.join("src"), root.join("src").join("pkg").join("loop")).expect("symlink");
+
+    let expected = [
+        root.join("src").join("Main.java"),
+        root.join("src").join("pkg").join("Util.java"),
+    ];
+
+    let skipped = find_files("java", 100, &root).expect("walk");
+    assert_eq!(skipped, expected);
+
+    let followed = FindOptions::builder()
+        .max_depth(100)
+        .follow_symlinks(true)
+        .build();
+    let followed = find_files_with("java", &root, followed).expect("walk");
+    assert_eq!(followed, expected);
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,37 @@
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+use umm::{java, python};
+
+/// Directory of the fixture project `name` under `fixtures/<language>`.
+pub fn fixture_root(language: &str, name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(language)
+        .join(name)
+}
+
+/// The Java fixture project `name`, laid out with the default directories.
+pub fn java_project(name: &str) -> java::Project {
+    let root = fixture_root("java", name);
+    let paths = java::paths::ProjectPaths::from_parts(root, None, None, None, None, None, None);
+    java::Project::from_paths(paths).expect("build project")
+}
+
+/// The Java fixture project `name`, with the bundled JUnit jars on its
+/// classpath so its tests can run.
+pub fn java_project_with_jars(name: &str) -> java::Project {
+    let paths = java::paths::project_paths()
+        .root_dir(fixture_root("java", name))
+        .lib_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("jar_files"))
+        .build();
+    java::Project::from_paths(paths).expect("build project")
+}
+
+/// The Python fixture project `name`, laid out with the default directories.
+pub fn python_project(name: &str) -> python::Project {
+    let root = fixture_root("python", name);
+    let paths = python::paths::ProjectPaths::from_parts(root, None, None, None, None, None, None);
+    python::Project::from_paths(paths).expect("build project")
+}
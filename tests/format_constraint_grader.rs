@@ -0,0 +1,98 @@
+use umm::java::{Project, grade::FormatConstraintGrader};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("format-output")
+}
+
+#[tokio::test]
+async fn format_passes_when_count_and_lines_match() {
+    let result = FormatConstraintGrader::builder()
+        .req_name("format")
+        .out_of(2.0)
+        .project(project())
+        .file("Report")
+        .line_count(3)
+        .line_pattern(r"\w+=\w+")
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 2.0, "reason: {}", result.reason());
+    assert!(result.prompt().is_none());
+}
+
+#[tokio::test]
+async fn format_reports_wrong_line_count() {
+    let result = FormatConstraintGrader::builder()
+        .req_name("count")
+        .out_of(1.0)
+        .project(project())
+        .file("Report")
+        .line_count(4)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 0.0);
+    let reason = result.reason();
+    assert!(reason.starts_with("Expected 4 line(s) of output; got 3"), "reason: {reason}");
+    assert!(result.prompt().is_some());
+}
+
+#[tokio::test]
+async fn format_reports_first_malformed_line() {
+    let result = FormatConstraintGrader::builder()
+        .req_name("pattern")
+        .out_of(1.0)
+        .project(project())
+        .file("Broken")
+        .line_count(3)
+        .line_pattern(r"\w+=\w+")
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 0.0);
+    let reason = result.reason();
+    assert!(reason.starts_with("Line 2 did not match"), "reason: {reason}");
+    assert!(reason.contains("got \"age: 36\""), "reason: {reason}");
+}
+
+#[tokio::test]
+async fn format_requires_a_constraint() {
+    let err = FormatConstraintGrader::builder()
+        .req_name("none")
+        .out_of(1.0)
+        .project(project())
+        .file("Report")
+        .build()
+        .run()
+        .await
+        .expect_err("no constraints");
+
+    assert!(err.to_string().contains("line_count or a line_pattern"));
+}
+
+#[tokio::test]
+async fn format_rejects_invalid_patterns_before_running() {
+    let err = FormatConstraintGrader::builder()
+        .req_name("bad")
+        .out_of(1.0)
+        .project(project())
+        .file("Report")
+        .line_pattern("(")
+        .build()
+        .run()
+        .await
+        .expect_err("invalid regex");
+
+    assert!(err.to_string().contains("Invalid line pattern"));
+}
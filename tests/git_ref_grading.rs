@@ -0,0 +1,209 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use umm::java::{
+    grade::{Query, QueryGrader},
+    paths::project_paths,
+    project::Project,
+};
+use uuid::Uuid;
+
+const V1: &str = r#"public class Main {
+    public static void main(String[] args) {
+        System.out.println("deadline version");
+    }
+}
+"#;
+
+const V2: &str = r#"public class Main {
+    public static void main(String[] args) {
+        for (int i = 0; i < 3; i++) {
+            System.out.println("late version " + i);
+        }
+    }
+}
+"#;
+
+fn git(repo: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["-c", "user.name=umm", "-c", "user.email=umm@example.com"])
+        .args(["-c", "commit.gpgsign=false", "-c", "tag.gpgsign=false"])
+        .args(args)
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Repo with a `deadline` tag on the first commit and a later commit on top.
+fn two_commit_repo() -> PathBuf {
+    let repo = std::env::temp_dir().join(format!("umm-git-ref-{}", Uuid::new_v4()));
+    fs::create_dir_all(repo.join("src")).expect("create repo");
+    git(&repo, &["init", "-q"]);
+
+    fs::write(repo.join("src/Main.java"), V1).expect("write v1");
+    git(&repo, &["add", "-A"]);
+    git(&repo, &["commit", "-q", "-m", "v1"]);
+    git(&repo, &["tag", "deadline"]);
+
+    fs::write(repo.join("src/Main.java"), V2).expect("write v2");
+    git(&repo, &["commit", "-q", "-am", "v2"]);
+    repo
+}
+
+/// Repo whose Java project lives in `hw1/`, with the same two commits as
+/// [`two_commit_repo`] and a stray `Main.java` at the top.
+fn nested_project_repo() -> PathBuf {
+    let repo = std::env::temp_dir().join(format!("umm-git-ref-{}", Uuid::new_v4()));
+    fs::create_dir_all(repo.join("hw1/src")).expect("create repo");
+    git(&repo, &["init", "-q"]);
+
+    fs::write(repo.join("Main.java"), V2).expect("write stray main");
+    fs::write(repo.join("hw1/src/Main.java"), V1).expect("write v1");
+    git(&repo, &["add", "-A"]);
+    git(&repo, &["commit", "-q", "-m", "v1"]);
+    git(&repo, &["tag", "deadline"]);
+
+    fs::write(repo.join("hw1/src/Main.java"), V2).expect("write v2");
+    git(&repo, &["commit", "-q", "-am", "v2"]);
+    repo
+}
+
+async fn loop_grade(repo: &Path, git_ref: &str) -> anyhow::Result<f64> {
+    let paths = project_paths().root_dir(repo).build();
+    let (project, worktree) = Project::from_git_ref(&paths, git_ref).await?;
+    assert!(project.paths().root_dir().starts_with(worktree.path()));
+
+    let result = QueryGrader::builder()
+        .req_name("loops")
+        .out_of(1.0)
+        .project(project)
+        .file("Main")
+        .queries(vec![
+            Query::new()
+                .set_query("((for_statement) @loop)".into())
+                .set_capture("loop".into()),
+        ])
+        .build()
+        .grade_by_query()?;
+
+    let checkout = worktree.path().to_path_buf();
+    drop(worktree);
+    assert!(!checkout.exists(), "worktree should be removed on drop");
+    Ok(result.grade_value())
+}
+
+#[tokio::test]
+async fn grades_each_commit_in_its_own_worktree() -> anyhow::Result<()> {
+    let repo = two_commit_repo();
+
+    assert_eq!(loop_grade(&repo, "deadline").await?, 0.0);
+    assert_eq!(loop_grade(&repo, "HEAD").await?, 1.0);
+    assert_eq!(fs::read_to_string(repo.join("src/Main.java"))?, V2);
+
+    let _ = fs::remove_dir_all(repo);
+    Ok(())
+}
+
+#[tokio::test]
+async fn nested_project_is_found_in_the_worktree() -> anyhow::Result<()> {
+    let repo = nested_project_repo();
+    let project_dir = repo.join("hw1");
+
+    assert_eq!(loop_grade(&project_dir, "deadline").await?, 0.0);
+    assert_eq!(loop_grade(&project_dir, "HEAD").await?, 1.0);
+
+    let paths = project_paths().root_dir(&project_dir).build();
+    let (project, worktree) = Project::from_git_ref(&paths, "deadline").await?;
+    assert_eq!(project.paths().root_dir(), worktree.path().join("hw1"));
+    assert_eq!(project.paths().source_dir(), worktree.path().join("hw1/src"));
+    assert_eq!(project.files().len(), 1, "only the nested project's files");
+
+    let _ = fs::remove_dir_all(repo);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_ref_is_reported() {
+    let repo = two_commit_repo();
+    let paths = project_paths().root_dir(&repo).build();
+
+    let err = Project::from_git_ref(&paths, "no-such-tag")
+        .await
+        .expect_err("missing ref");
+    assert!(
+        err.to_string()
+            .contains("Could not check out `no-such-tag`"),
+        "{err:#}"
+    );
+
+    let _ = fs::remove_dir_all(repo);
+}
+
+#[test]
+fn cli_grades_the_requested_ref() {
+    let repo = two_commit_repo();
+    let script = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/rune/git_ref.rn");
+
+    let output = cargo_bin_cmd!("umm")
+        .current_dir(&repo)
+        .args(["java", "grade"])
+        .arg(&script)
+        .args(["--ref", "deadline"])
+        .output()
+        .expect("run umm");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("deadline version"), "stdout: {stdout}");
+    assert!(!stdout.contains("late version"), "stdout: {stdout}");
+
+    let _ = fs::remove_dir_all(repo);
+}
+
+#[test]
+fn cli_grades_a_nested_project_without_changing_directory() {
+    let repo = nested_project_repo();
+    let script = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/rune/git_ref.rn");
+
+    let output = cargo_bin_cmd!("umm")
+        .current_dir(repo.join("hw1"))
+        .args(["java", "grade"])
+        .arg(&script)
+        .args(["--ref", "deadline"])
+        .output()
+        .expect("run umm");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("deadline version"), "stdout: {stdout}");
+    assert!(!stdout.contains("late version"), "stdout: {stdout}");
+
+    let _ = fs::remove_dir_all(repo);
+}
+
+#[test]
+fn cli_paths_builder_defaults_to_the_requested_ref() {
+    let repo = nested_project_repo();
+    let script = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/rune/git_ref_paths.rn");
+
+    let output = cargo_bin_cmd!("umm")
+        .current_dir(repo.join("hw1"))
+        .args(["java", "grade"])
+        .arg(&script)
+        .args(["--ref", "deadline"])
+        .output()
+        .expect("run umm");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("deadline version"), "stdout: {stdout}");
+    assert!(!stdout.contains("late version"), "stdout: {stdout}");
+
+    let _ = fs::remove_dir_all(repo);
+}
@@ -0,0 +1,48 @@
+use umm::java::grade::{Grade, GradeStyle, LetterScale};
+
+#[test]
+fn fraction_style_matches_display() {
+    let grade = Grade::new(8.0, 10.0);
+    assert_eq!(grade.format(GradeStyle::Fraction), "8.00/10.00");
+    assert_eq!(grade.format(GradeStyle::Fraction), grade.to_string());
+}
+
+#[test]
+fn percent_style_handles_boundaries() {
+    assert_eq!(Grade::new(0.0, 10.0).format(GradeStyle::Percent), "0.00%");
+    assert_eq!(Grade::new(10.0, 10.0).format(GradeStyle::Percent), "100.00%");
+    assert_eq!(Grade::new(2.0, 3.0).format(GradeStyle::Percent), "66.67%");
+    assert_eq!(Grade::new(0.0, 0.0).format(GradeStyle::Percent), "0.00%");
+}
+
+#[test]
+fn letter_style_cutoffs_are_inclusive() {
+    let letter =
+        |grade: f64| Grade::new(grade, 10.0).format(GradeStyle::Letter(LetterScale::default()));
+
+    assert_eq!(letter(9.0), "A (90.00%)");
+    assert_eq!(letter(8.99), "B (89.90%)");
+    assert_eq!(letter(8.0), "B (80.00%)");
+    assert_eq!(letter(7.0), "C (70.00%)");
+    assert_eq!(letter(6.0), "D (60.00%)");
+    assert_eq!(letter(5.99), "F (59.90%)");
+    assert_eq!(letter(0.0), "F (0.00%)");
+}
+
+#[test]
+fn letter_style_accepts_custom_scale() {
+    let scale = LetterScale::new([(50.0, "Pass"), (85.0, "Distinction")], "Fail");
+
+    assert_eq!(scale.letter(85.0), "Distinction");
+    assert_eq!(scale.letter(84.9), "Pass");
+    assert_eq!(scale.letter(50.0), "Pass");
+    assert_eq!(scale.letter(49.9), "Fail");
+    assert_eq!(Grade::new(17.0, 20.0).format(GradeStyle::Letter(scale)), "Distinction (85.00%)");
+}
+
+#[test]
+fn letter_style_tolerates_float_noise_at_cutoffs() {
+    // 8.1 / 30 * 100 evaluates to 26.999999999999996.
+    let scale = LetterScale::new([(27.0, "P")], "F");
+    assert_eq!(scale.letter(Grade::new(8.1, 30.0).percent()), "P");
+}
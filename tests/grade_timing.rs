@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use umm::java::{Project, grade::diff::DiffGrader};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("slow-main")
+}
+
+#[tokio::test]
+async fn run_records_how_long_the_grader_took() {
+    let result = DiffGrader::builder()
+        .req_name("slow")
+        .out_of(1.0)
+        .project(project())
+        .file("Main")
+        .cases(vec![("done", None::<String>)])
+        .build()
+        .run()
+        .await
+        .expect("grade slow main");
+
+    assert_eq!(result.grade_value(), 1.0, "reason: {}", result.reason());
+    let elapsed = result.elapsed().expect("elapsed time is recorded");
+    assert!(elapsed >= Duration::from_millis(300), "elapsed: {elapsed:?}");
+}
@@ -0,0 +1,68 @@
+use umm::java::{
+    Project,
+    grade::{FormatConstraintGrader, GradeResult, Oracle, OracleGrader, RegexGrader},
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("diff-timeout")
+}
+
+fn assert_timed_out(name: &str, result: &GradeResult) {
+    assert_eq!(result.grade_value(), 0.0, "{name}");
+    assert!(
+        result.reason().contains("did not finish within 2s"),
+        "{name} reason: {}",
+        result.reason()
+    );
+    let prompt = format!("{:?}", result.prompt());
+    assert!(prompt.contains("counting down"), "{name} prompt: {prompt}");
+}
+
+// Configuration is read once per process, so every grader shares one test.
+#[tokio::test]
+async fn output_graders_report_the_time_limit() -> anyhow::Result<()> {
+    unsafe {
+        std::env::set_var("UMM_JAVA_TIMEOUT_SECS", "2");
+    }
+
+    let regex = RegexGrader::builder()
+        .req_name("liftoff")
+        .out_of(1.0)
+        .project(project())
+        .file("Main")
+        .cases([("liftoff", None::<String>)])
+        .build()
+        .run()
+        .await?;
+    assert_timed_out("regex", &regex);
+
+    let format = FormatConstraintGrader::builder()
+        .req_name("two lines")
+        .out_of(1.0)
+        .project(project())
+        .file("Main")
+        .line_count(2)
+        .build()
+        .run()
+        .await?;
+    assert_timed_out("format", &format);
+
+    let oracle = OracleGrader::builder()
+        .req_name("sorted")
+        .out_of(1.0)
+        .project(project())
+        .file("Main")
+        .inputs([""])
+        .oracle(Oracle::sorted_permutation())
+        .build()
+        .run()
+        .await?;
+    assert_timed_out("oracle", &oracle);
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::{fs, path::PathBuf};
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value;
+use umm::java::grade::{Grade, GradeResult, ProgressEvent, ProgressWriter};
+use uuid::Uuid;
+
+fn fixtures_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+fn progress_file() -> PathBuf {
+    std::env::temp_dir().join(format!("umm-progress-{}.jsonl", Uuid::new_v4()))
+}
+
+fn result(requirement: &str, grade: f64, out_of: f64) -> GradeResult {
+    GradeResult::builder()
+        .requirement(requirement)
+        .grade(Grade::new(grade, out_of))
+        .reason("because")
+        .maybe_prompt(None)
+        .build()
+}
+
+#[test]
+fn each_completed_unit_writes_one_json_line() -> anyhow::Result<()> {
+    let path = progress_file();
+    let results = [result("1.1", 2.0, 2.0), result("1.2", 1.5, 3.0)];
+
+    let writer = ProgressWriter::append(&path)?;
+    for r in &results {
+        writer.requirement(Some("alice"), r)?;
+    }
+    writer.submission(Some("alice"), &results)?;
+
+    // A second writer on the same file appends rather than truncating.
+    ProgressWriter::append(&path)?.submission(None, &[])?;
+
+    let contents = fs::read_to_string(&path)?;
+    let events: Vec<ProgressEvent> = contents
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(events.len(), 4);
+
+    match &events[1] {
+        ProgressEvent::Requirement {
+            submission,
+            requirement,
+            grade,
+            out_of,
+            reason,
+            ..
+        } => {
+            assert_eq!(submission.as_deref(), Some("alice"));
+            assert_eq!(requirement, "1.2");
+            assert_eq!((*grade, *out_of), (1.5, 3.0));
+            assert_eq!(reason, "because");
+        }
+        other => panic!("expected a requirement event, got {other:?}"),
+    }
+    match &events[2] {
+        ProgressEvent::Submission {
+            requirements,
+            grade,
+            out_of,
+            ..
+        } => assert_eq!((*requirements, *grade, *out_of), (2, 3.5, 5.0)),
+        other => panic!("expected a submission event, got {other:?}"),
+    }
+    assert!(matches!(
+        &events[3],
+        ProgressEvent::Submission {
+            submission: None,
+            requirements: 0,
+            ..
+        }
+    ));
+
+    let _ = fs::remove_file(&path);
+    Ok(())
+}
+
+#[test]
+fn grade_progress_flag_streams_each_requirement() -> anyhow::Result<()> {
+    let path = progress_file();
+    cargo_bin_cmd!("umm")
+        .current_dir(fixtures_root().join("java").join("encapsulation"))
+        .env("CLICOLOR", "0")
+        .arg("java")
+        .arg("grade")
+        .arg(fixtures_root().join("rune").join("progress.rn"))
+        .arg("--progress")
+        .arg(&path)
+        .assert()
+        .success();
+
+    let lines: Vec<Value> = fs::read_to_string(&path)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    let events: Vec<(&str, &str)> = lines
+        .iter()
+        .map(|line| {
+            (
+                line["event"].as_str().unwrap_or_default(),
+                line["requirement"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        events,
+        [
+            ("requirement", "account"),
+            ("requirement", "counter"),
+            ("submission", "")
+        ]
+    );
+    assert!(
+        lines
+            .iter()
+            .all(|line| line["submission"] == "encapsulation")
+    );
+    assert_eq!(lines[2]["requirements"], 2);
+    assert_eq!(lines[2]["out_of"], 7.0);
+
+    let _ = fs::remove_file(&path);
+    Ok(())
+}
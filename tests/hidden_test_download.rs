@@ -0,0 +1,64 @@
+use std::{
+    io::Write,
+    thread,
+    time::{Duration, Instant},
+};
+
+use umm::java::{Project, grade::ByHiddenTestGrader};
+
+#[path = "http_support.rs"]
+mod http_support;
+
+use http_support::MockServer;
+
+fn hidden_grader(mock: &MockServer) -> ByHiddenTestGrader {
+    ByHiddenTestGrader::builder()
+        .url(format!("{}/HiddenTest.java", mock.url))
+        .test_class_name("HiddenTest")
+        .out_of(5.0)
+        .req_name("hidden")
+        .project(Project::default())
+        .download_timeout_secs(1.0)
+        .max_download_bytes(1024)
+        .build()
+}
+
+#[tokio::test]
+async fn stalled_download_times_out() {
+    let mock = MockServer::start(|_, stream| {
+        // Send headers, then never finish the body.
+        let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\npartial");
+        thread::sleep(Duration::from_secs(30));
+    });
+
+    let started = Instant::now();
+    let Err(err) = hidden_grader(&mock).run().await else {
+        panic!("a stalled download should fail");
+    };
+
+    assert!(started.elapsed() < Duration::from_secs(10), "took {:?}", started.elapsed());
+    assert!(
+        err.to_string()
+            .contains("Hidden test download timed out after 1s"),
+        "error: {err:#}"
+    );
+}
+
+#[tokio::test]
+async fn oversized_download_is_rejected() {
+    let mock = MockServer::start(|_, stream| {
+        // No Content-Length, so the limit has to be enforced while streaming.
+        let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n");
+        let _ = stream.write_all(&[b'x'; 4096]);
+    });
+
+    let Err(err) = hidden_grader(&mock).run().await else {
+        panic!("an oversized download should fail");
+    };
+
+    assert!(
+        err.to_string()
+            .contains("Hidden test download exceeds allowed size (more than 1024 bytes)"),
+        "error: {err:#}"
+    );
+}
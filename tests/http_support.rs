@@ -0,0 +1,112 @@
+#![allow(dead_code)]
+
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// A request seen by a [`MockServer`]: the request line and headers, then the
+/// body.
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub head: String,
+    pub body: String,
+}
+
+impl Request {
+    /// The request line, e.g. `POST /grades HTTP/1.1`.
+    pub fn request_line(&self) -> &str {
+        self.head.lines().next().unwrap_or_default()
+    }
+
+    /// Returns true if the request carries header `name` set to `value`. The
+    /// name is compared case-insensitively.
+    pub fn has_header(&self, name: &str, value: &str) -> bool {
+        self.head.lines().skip(1).any(|line| {
+            line.split_once(':')
+                .is_some_and(|(n, v)| n.eq_ignore_ascii_case(name) && v.trim() == value)
+        })
+    }
+}
+
+/// Local HTTP server that records every request and lets a callback answer
+/// it. Each connection is served on its own thread.
+pub struct MockServer {
+    pub url:      String,
+    pub requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    /// Starts serving on a free local port. `reply` writes the response for
+    /// each request, after the request is recorded.
+    pub fn start(reply: impl Fn(&Request, &mut TcpStream) + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = Arc::clone(&requests);
+        let reply = Arc::new(reply);
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let recorded = Arc::clone(&recorded);
+                let reply = Arc::clone(&reply);
+                thread::spawn(move || {
+                    let request = read_request(&mut stream);
+                    recorded.lock().unwrap().push(request.clone());
+                    reply(&request, &mut stream);
+                });
+            }
+        });
+
+        Self { url, requests }
+    }
+}
+
+/// Writes a complete response with `status` and a JSON `body`, then asks the
+/// client to close the connection.
+pub fn respond(stream: &mut TcpStream, status: u16, body: &str) {
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\nContent-Length: \
+         {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+}
+
+/// Reads one HTTP request (headers plus `Content-Length` body).
+pub fn read_request(stream: &mut TcpStream) -> Request {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let n = stream.read(&mut chunk).unwrap_or(0);
+        buf.extend_from_slice(&chunk[..n]);
+
+        let text = String::from_utf8_lossy(&buf).to_string();
+        let Some(end) = text.find("\r\n\r\n") else {
+            if n == 0 {
+                return Request {
+                    head: text,
+                    body: String::new(),
+                };
+            }
+            continue;
+        };
+        let body_len = text[..end]
+            .lines()
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.eq_ignore_ascii_case("content-length")
+                    .then(|| value.trim().parse::<usize>().ok())
+                    .flatten()
+            })
+            .unwrap_or(0);
+        if n == 0 || buf.len() >= end + 4 + body_len {
+            return Request {
+                head: text[..end].to_string(),
+                body: text[end + 4..].to_string(),
+            };
+        }
+    }
+}
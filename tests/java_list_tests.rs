@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+
+fn list_tests(extra: &[&str]) -> String {
+    let output = cargo_bin_cmd!("umm")
+        .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/multi-class"))
+        .args(["java", "list-tests"])
+        .args(extra)
+        .output()
+        .expect("run umm");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn java_list_tests_prints_one_test_per_line() {
+    let stdout = list_tests(&[]);
+    assert_eq!(stdout, "shop.InventoryTest#addsItems\nshop.InventoryTest#keepsLabel\n");
+}
+
+#[test]
+fn java_list_tests_json_groups_by_test_file() {
+    let stdout = list_tests(&["--json"]);
+    let tests: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(
+        tests,
+        serde_json::json!({
+            "shop.InventoryTest": ["shop.InventoryTest#addsItems", "shop.InventoryTest#keepsLabel"]
+        })
+    );
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+
+fn outline(extra: &[&str]) -> String {
+    let output = cargo_bin_cmd!("umm")
+        .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/multi-class"))
+        .args(["java", "outline"])
+        .args(extra)
+        .output()
+        .expect("run umm");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn java_outline_lists_classes() {
+    let stdout = outline(&[]);
+    assert!(stdout.starts_with("<project>"), "stdout: {stdout}");
+    assert!(stdout.contains("class shop.Inventory"), "stdout: {stdout}");
+    assert!(stdout.contains("class shop.Item"), "stdout: {stdout}");
+    assert!(stdout.contains("InventoryTest"), "stdout: {stdout}");
+}
+
+#[test]
+fn java_outline_markdown_has_heading_per_file() {
+    let stdout = outline(&["--markdown"]);
+    assert!(stdout.starts_with("# Project outline"), "stdout: {stdout}");
+    assert!(stdout.contains("## `shop.Inventory` (class_with_main)"), "stdout: {stdout}");
+    assert!(stdout.contains("class shop.Item"), "stdout: {stdout}");
+    assert_eq!(stdout.matches("\n## ").count(), 2, "stdout: {stdout}");
+}
@@ -1,4 +1,4 @@
-use umm::java::{Parser, queries::CLASSNAME_QUERY};
+use umm::java::{Parser, QueryCompileError, queries::CLASSNAME_QUERY};
 
 #[test]
 fn query_returns_class_name_capture() {
@@ -16,6 +16,22 @@ fn query_errors_on_invalid_query() {
     assert!(message.contains("Failed to compile tree-sitter query"));
 }
 
+#[test]
+fn invalid_query_reports_structured_compile_error() {
+    let parser = Parser::new("class Foo {}".to_string()).expect("parser should initialize");
+    let query = "(class_declaration\n  name: (not_a_node) @name)";
+    let err = parser.query(query).unwrap_err();
+    let compile = err
+        .downcast_ref::<QueryCompileError>()
+        .expect("compile error should be preserved");
+    assert_eq!(compile.query, query);
+    assert_eq!(compile.kind, "NodeType");
+    assert_eq!(compile.line, 2);
+
+    let validated = Parser::validate_query(query).unwrap_err();
+    assert_eq!(&validated, compile);
+}
+
 #[test]
 fn query_capture_positions_errors_on_missing_capture() {
     let parser = Parser::new("class Foo {}".to_string()).expect("parser should initialize");
@@ -39,3 +55,62 @@ fn set_code_updates_tree() {
     assert_eq!(captures.len(), 1);
     assert_eq!(captures[0].get("name").map(String::as_str), Some("Bar"));
 }
+
+#[test]
+fn pooled_parser_keeps_trees_independent_across_many_parses() {
+    // Each thread reuses one pooled tree-sitter parser for every file it
+    // parses; earlier trees must stay intact and later ones must not inherit
+    // state from malformed input.
+    let handles: Vec<_> = (0..4)
+        .map(|thread| {
+            std::thread::spawn(move || {
+                let parsers: Vec<Parser> = (0..250)
+                    .map(|i| {
+                        let source = if i % 10 == 0 {
+                            format!("class Broken{thread}x{i} {{ void f( {{")
+                        } else {
+                            format!("class C{thread}x{i} {{ int f() {{ return {i}; }} }}")
+                        };
+                        Parser::new(source).expect("parser should initialize")
+                    })
+                    .collect();
+
+                for (i, parser) in parsers.iter().enumerate().filter(|(i, _)| i % 10 != 0) {
+                    let captures = parser.query(CLASSNAME_QUERY).expect("query should succeed");
+                    let expected = format!("C{thread}x{i}");
+                    assert_eq!(captures.len(), 1);
+                    assert_eq!(captures[0].get("name"), Some(&expected));
+                    assert!(!parser.root_node().unwrap().has_error());
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().expect("parser thread panicked");
+    }
+}
+
+#[test]
+fn clean_code_has_no_syntax_errors() {
+    let parser =
+        Parser::new("class Foo { int x = 1; }".to_string()).expect("parser should initialize");
+    assert!(!parser.has_errors());
+    assert!(parser.syntax_errors().is_empty());
+}
+
+#[test]
+fn broken_code_reports_syntax_errors() {
+    let parser = Parser::new("class Foo {\n  void f() {\n    int x = \n  }\n}".to_string())
+        .expect("parser should initialize");
+    assert!(parser.has_errors());
+
+    let errors = parser.syntax_errors();
+    assert!(!errors.is_empty());
+    assert!(errors.iter().all(|e| (2..=4).contains(&e.line)), "{errors:?}");
+    assert!(
+        errors[0]
+            .to_string()
+            .starts_with(&format!("line {}, column ", errors[0].line))
+    );
+}
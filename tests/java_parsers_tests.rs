@@ -66,3 +66,11 @@ fn javac_diag_parses_error_and_warning() {
 fn javac_diag_rejects_invalid_line() {
     assert!(parser::parse_diag("not a diagnostic line").is_err());
 }
+
+#[test]
+fn junit_summary_parses_failed_containers() {
+    let failed = parser::num_containers_failed("[         1 containers failed      ]")
+        .expect("parse failed containers line");
+    assert_eq!(failed, 1);
+    assert!(parser::num_containers_failed("[         1 tests failed           ]").is_err());
+}
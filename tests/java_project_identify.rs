@@ -0,0 +1,151 @@
+use std::{fs, path::PathBuf};
+
+use umm::java::{FileType, paths::project_paths, project::Project};
+use uuid::Uuid;
+
+fn fixture_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/readme-all")
+}
+
+#[test]
+fn identify_accepts_relative_and_absolute_paths() -> anyhow::Result<()> {
+    let project = Project::from_paths(project_paths().root_dir(fixture_root()).build())?;
+
+    let absolute = fixture_root().join("src/Main.java");
+    let by_absolute = project.identify(&absolute.display().to_string())?;
+    let by_root_relative = project.identify("src/Main.java")?;
+    let by_cwd_relative = project.identify("fixtures/java/readme-all/src/Main.java")?;
+
+    assert_eq!(by_absolute.proper_name(), "Main");
+    assert_eq!(by_root_relative.path(), by_absolute.path());
+    assert_eq!(by_cwd_relative.path(), by_absolute.path());
+    Ok(())
+}
+
+#[test]
+fn identify_resolves_absolute_path_when_stored_relative() -> anyhow::Result<()> {
+    // Integration tests run from the crate root, so this root is relative.
+    let relative_root = PathBuf::from("fixtures/java/readme-all");
+    let project = Project::from_paths(project_paths().root_dir(relative_root).build())?;
+
+    let absolute = fixture_root().join("test/MainTest.java");
+    let file = project.identify(&absolute.display().to_string())?;
+
+    assert_eq!(file.proper_name(), "MainTest");
+    assert!(file.path().is_relative());
+    Ok(())
+}
+
+/// Project with two `Util.java` files, in `src/a/pkg/` and `src/b/pkg/`.
+fn same_named_files_project() -> (PathBuf, Project) {
+    let root = std::env::temp_dir().join(format!("umm-identify-{}", Uuid::new_v4()));
+    for package in ["a", "b"] {
+        let dir = root.join("src").join(package).join("pkg");
+        fs::create_dir_all(&dir).expect("create package dir");
+        fs::write(
+            dir.join("Util.java"),
+            format!("package {package}.pkg;\n\npublic class Util {{}}\n"),
+        )
+        .expect("write Util");
+    }
+    let project =
+        Project::from_paths(project_paths().root_dir(&root).build()).expect("load project");
+    (root, project)
+}
+
+#[test]
+fn identify_rejects_an_empty_name() {
+    let (root, project) = same_named_files_project();
+
+    let err = project.identify("").expect_err("empty name");
+    assert!(err.to_string().contains("empty name"), "{err:#}");
+    assert!(!project.contains("  "));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn identify_rejects_an_ambiguous_path_suffix() {
+    let (root, project) = same_named_files_project();
+
+    let err = project
+        .identify("pkg/Util.java")
+        .expect_err("two files end with it");
+    assert!(err.to_string().contains("ambiguous"), "{err:#}");
+    assert!(!project.contains("pkg/Util.java"));
+
+    let unique = project
+        .identify("b/pkg/Util.java")
+        .expect("one file ends with it");
+    assert_eq!(unique.proper_name(), "b.pkg.Util");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn multi_class_files_describe_every_top_level_type() -> anyhow::Result<()> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/multi-class");
+    let project = Project::from_paths(project_paths().root_dir(root).build())?;
+
+    let source = project.identify("shop.Inventory")?;
+    assert_eq!(source.simple_name(), "Inventory");
+    assert_eq!(source.kind(), &FileType::ClassWithMain);
+    let description = source.description();
+    assert!(description.contains("class shop.Item"), "{description}");
+    assert!(description.contains("class shop.Inventory"), "{description}");
+    assert!(description.contains("String label()"), "{description}");
+
+    let tests = project.identify("shop.InventoryTest")?;
+    assert_eq!(tests.kind(), &FileType::Test);
+    assert_eq!(
+        tests.test_methods(),
+        vec!["shop.InventoryTest#addsItems", "shop.ItemTest#keepsLabel"]
+    );
+    Ok(())
+}
+
+#[test]
+fn tests_are_attributed_to_enums_and_records_too() -> anyhow::Result<()> {
+    let root = std::env::temp_dir().join(format!("umm-identify-{}", Uuid::new_v4()));
+    fs::create_dir_all(root.join("test"))?;
+    fs::write(
+        root.join("test/PointTest.java"),
+        "import org.junit.jupiter.api.Test;\n\npublic class PointTest {\n  @Test\n  void first() \
+         {}\n}\n\nenum Axis {\n  X;\n\n  @Test\n  void second() {}\n}\n\nrecord Point(int x) {\n  \
+         @Test\n  void third() {}\n}\n",
+    )?;
+    let project = Project::from_paths(project_paths().root_dir(&root).build())?;
+
+    assert_eq!(
+        project.identify("PointTest")?.test_methods(),
+        vec!["PointTest#first", "Axis#second", "Point#third"]
+    );
+
+    let _ = fs::remove_dir_all(root);
+    Ok(())
+}
+
+#[tokio::test]
+async fn named_tests_run_in_secondary_classes() -> anyhow::Result<()> {
+    let crate_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let scratch = std::env::temp_dir().join(format!("umm-multi-class-{}", Uuid::new_v4()));
+    let paths = project_paths()
+        .root_dir(crate_root.join("fixtures/java/multi-class"))
+        .build_dir(scratch.join("target"))
+        .lib_dir(crate_root.join("jar_files"))
+        .umm_dir(scratch.join(".umm"))
+        .build();
+    let project = Project::from_paths(paths)?;
+
+    // `keepsLabel` lives in `ItemTest`, not the file's primary class.
+    let output = project
+        .identify("shop.InventoryTest")?
+        .test(vec!["keepsLabel"], Some(&project))
+        .await?;
+    assert!(output.contains("1 tests successful"), "{output}");
+    assert!(output.contains("keepsLabel"), "{output}");
+    assert!(!output.contains("addsItems"), "{output}");
+
+    let _ = fs::remove_dir_all(scratch);
+    Ok(())
+}
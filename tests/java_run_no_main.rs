@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use umm::java::{FileType, JavaFileError, paths::project_paths, project::Project};
+
+fn fixture_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/multi-class")
+}
+
+#[tokio::test]
+async fn running_a_test_file_reports_missing_main() -> anyhow::Result<()> {
+    let project = Project::from_paths(project_paths().root_dir(fixture_root()).build())?;
+    let file = project.identify("shop.InventoryTest")?;
+
+    match file.run(None).await {
+        Err(JavaFileError::NoMainMethod { name, kind }) => {
+            assert_eq!(name, "shop.InventoryTest");
+            assert_eq!(kind, FileType::Test);
+        }
+        Err(other) => panic!("expected NoMainMethod, got {other:?}"),
+        Ok(out) => panic!("expected NoMainMethod, got output {out}"),
+    }
+    Ok(())
+}
+
+#[test]
+fn cli_suggests_the_class_with_main() {
+    let output = cargo_bin_cmd!("umm")
+        .current_dir(fixture_root())
+        .args(["java", "run", "InventoryTest"])
+        .output()
+        .expect("run umm");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            "`shop.InventoryTest` has no `public static void main(String[] args)` method"
+        ),
+        "stderr: {stderr}"
+    );
+    assert!(stderr.contains("Try `umm java run shop.Inventory`"), "stderr: {stderr}");
+}
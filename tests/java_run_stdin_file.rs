@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+
+fn fixture() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/stdin-file")
+}
+
+#[test]
+fn java_run_feeds_stdin_file_to_program() {
+    let output = cargo_bin_cmd!("umm")
+        .current_dir(fixture())
+        .args(["java", "run", "Main", "--stdin-file", "numbers.txt"])
+        .output()
+        .expect("run umm");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("lines: 3"), "stdout: {stdout}");
+    assert!(stdout.contains("sum: 12"), "stdout: {stdout}");
+}
+
+#[test]
+fn java_run_reports_missing_stdin_file() {
+    let output = cargo_bin_cmd!("umm")
+        .current_dir(fixture())
+        .args(["java", "run", "Main", "--stdin-file", "missing.txt"])
+        .output()
+        .expect("run umm");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Could not read stdin file missing.txt"), "stderr: {stderr}");
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use umm::java::{JavaFileError, Project, grade::diff::DiffGrader};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("diff-timeout")
+}
+
+#[tokio::test]
+async fn run_with_timeout_kills_the_program_and_keeps_its_output() -> anyhow::Result<()> {
+    let file = project().identify("Main")?;
+
+    match file
+        .run_with_timeout(Some(String::new()), Duration::from_secs(2))
+        .await
+    {
+        Err(JavaFileError::TimedOut {
+            partial_output,
+            timeout,
+        }) => {
+            assert_eq!(timeout, Duration::from_secs(2));
+            assert!(partial_output.contains("counting down"), "output: {partial_output}");
+            assert!(!partial_output.contains("liftoff"), "output: {partial_output}");
+        }
+        Err(other) => panic!("expected TimedOut, got {other:?}"),
+        Ok(out) => panic!("expected TimedOut, got output {out}"),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn diff_grades_a_timeout_instead_of_failing() {
+    let result = DiffGrader::builder()
+        .req_name("liftoff")
+        .out_of(2.0)
+        .project(project())
+        .file("Main")
+        .cases(vec![("counting down\nliftoff", None::<String>)])
+        .timeout(Duration::from_secs(2))
+        .build()
+        .run()
+        .await
+        .expect("a timeout is graded, not an error");
+
+    assert_eq!(result.grade_value(), 0.0);
+    assert!(
+        result.reason().contains("did not finish within 2s"),
+        "reason: {}",
+        result.reason()
+    );
+    let prompt = format!("{:?}", result.prompt());
+    assert!(prompt.contains("counting down"), "prompt: {prompt}");
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use umm::kotlin::{FileType, Project, grade::QueryGrader};
+
+fn project() -> Project {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/kotlin/shapes");
+    Project::from_root(root).expect("load kotlin project")
+}
+
+#[test]
+fn kotlin_files_are_parsed_and_classified() {
+    let project = project();
+
+    let shapes = project.identify("Shapes").expect("Shapes.kt");
+    assert_eq!(shapes.kind(), &FileType::Class);
+    assert_eq!(shapes.package_name(), Some("shapes"));
+    assert_eq!(shapes.classes(), ["Circle", "Square", "Describer"]);
+    assert!(shapes.functions().contains(&"describe".to_string()));
+    assert_eq!(shapes.imports(), ["kotlin.math.PI"]);
+
+    let main = project.identify("Main.kt").expect("Main.kt");
+    assert_eq!(main.kind(), &FileType::ClassWithMain);
+    assert_eq!(main.jvm_class(), "shapes.MainKt");
+    assert!(project.contains("shapes.MainKt"));
+}
+
+#[tokio::test]
+async fn kotlin_query_grader_checks_structure() {
+    let passing = QueryGrader::builder()
+        .project(project())
+        .file("Shapes")
+        .req_name("structure")
+        .out_of(2.0)
+        .build()
+        .class_with_name("Circle".into())
+        .uses_when()
+        .run()
+        .await
+        .expect("grade");
+    assert_eq!(passing.grade_value(), 2.0);
+
+    let failing = QueryGrader::builder()
+        .project(project())
+        .file("Shapes")
+        .req_name("structure")
+        .out_of(2.0)
+        .build()
+        .function_with_name("perimeter".into())
+        .run()
+        .await
+        .expect("grade");
+    assert_eq!(failing.grade_value(), 0.0);
+    assert!(
+        failing
+            .reason()
+            .contains("Expected at least 1 match, found 0")
+    );
+}
@@ -0,0 +1,77 @@
+use umm::java::{
+    Project,
+    grade::{MagicNumberGrader, magic_numbers},
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("magic-numbers")
+}
+
+#[test]
+fn magic_numbers_skip_named_constants() {
+    let file = project().identify("Circle").expect("identify Circle");
+    let numbers = magic_numbers(file.parser()).expect("magic numbers");
+    let found: Vec<_> = numbers
+        .iter()
+        .map(|n| (n.method.as_str(), n.line, n.literal.as_str()))
+        .collect();
+
+    assert_eq!(
+        found,
+        [
+            ("Circle.circumference", 15, "2"),
+            ("Circle.circumference", 15, "3.14159"),
+            ("Circle.clamp", 23, "0"),
+            ("Circle.clamp", 23, "1"),
+            ("Circle.clamp", 23, "1"),
+            ("Circle.mask", 27, "0xFFL"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn magic_number_grader_flags_inline_literals() {
+    let result = MagicNumberGrader::builder()
+        .req_name("magic numbers")
+        .out_of(5.0)
+        .project(project())
+        .files(["Circle"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 2.0);
+    let reason = result.reason();
+    assert!(reason.starts_with("3 magic number(s):"), "reason: {reason}");
+    assert!(
+        reason.contains("Circle.java:15 in Circle.circumference: 3.14159"),
+        "reason: {reason}"
+    );
+    assert!(reason.contains("Circle.java:27 in Circle.mask: 0xFFL"), "reason: {reason}");
+    assert!(!reason.contains("Circle.java:2 "), "named constant flagged: {reason}");
+    assert!(result.prompt().is_some());
+}
+
+#[tokio::test]
+async fn magic_number_allowlist_matches_by_value() {
+    let result = MagicNumberGrader::builder()
+        .req_name("magic numbers")
+        .out_of(5.0)
+        .project(project())
+        .files(["Circle"])
+        .allowed_numbers(["0", "1", "2", "255"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 4.0, "reason: {}", result.reason());
+    assert!(result.reason().contains("3.14159"), "reason: {}", result.reason());
+    assert!(!result.reason().contains("0xFFL"), "reason: {}", result.reason());
+}
@@ -0,0 +1,76 @@
+use umm::java::{
+    Project,
+    grade::{MethodLengthGrader, method_lengths},
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("method-length")
+}
+
+#[test]
+fn method_lengths_span_signature_to_closing_brace() {
+    let file = project().identify("Main").expect("identify Main");
+    let methods = method_lengths(file.parser()).expect("lengths");
+    let of = |name: &str| {
+        methods
+            .iter()
+            .find(|m| m.name == name)
+            .unwrap_or_else(|| panic!("{name} missing from {methods:?}"))
+            .clone()
+    };
+
+    assert_eq!(of("Main.add").lines(), 3);
+    assert_eq!(of("Main.main").lines(), 3);
+    // The Javadoc above `report` is not part of its span.
+    let report = of("Main.report");
+    assert_eq!((report.start_line, report.end_line), (9, 24));
+    assert_eq!(report.lines(), 16);
+}
+
+#[tokio::test]
+async fn method_length_grader_penalizes_long_methods() {
+    let result = MethodLengthGrader::builder()
+        .req_name("short methods")
+        .out_of(5.0)
+        .project(project())
+        .files(["Main"])
+        .max_lines(10)
+        .penalty(2.0)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 3.0);
+    assert!(
+        result
+            .reason()
+            .contains("Main.report (lines 9-24): 16 lines"),
+        "reason: {}",
+        result.reason()
+    );
+    assert!(!result.reason().contains("Main.add"), "reason: {}", result.reason());
+    assert!(result.prompt().is_some());
+}
+
+#[tokio::test]
+async fn method_length_grader_gives_full_marks_for_short_methods() {
+    let result = MethodLengthGrader::builder()
+        .req_name("short methods")
+        .out_of(5.0)
+        .project(project())
+        .files(["Main"])
+        .max_lines(16)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 5.0);
+    assert!(result.prompt().is_none());
+}
@@ -0,0 +1,46 @@
+use umm::java::{grade::tests::ByUnitTestGrader, project::Project};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project_with_jars;
+
+fn project() -> Project {
+    java_project_with_jars("test-factory")
+}
+
+async fn grade(min_test_methods: usize) -> anyhow::Result<umm::java::grade::GradeResult> {
+    ByUnitTestGrader::builder()
+        .project(project())
+        .test_files(["SquaresTest"])
+        .min_test_methods(min_test_methods)
+        .req_name("minimum")
+        .out_of(4.0)
+        .build()
+        .run()
+        .await
+}
+
+#[tokio::test]
+async fn too_few_test_methods_scores_zero() -> anyhow::Result<()> {
+    let result = grade(3).await?;
+
+    assert_eq!(result.grade_value(), 0.0);
+    assert!(
+        result
+            .reason()
+            .contains("Found 2 test method(s), but at least 3 are required."),
+        "reason: {}",
+        result.reason()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn enough_test_methods_runs_the_tests() -> anyhow::Result<()> {
+    let result = grade(2).await?;
+
+    assert_eq!(result.reason(), "- 4/4 tests passing.");
+    assert_eq!(result.grade_value(), 4.0);
+    Ok(())
+}
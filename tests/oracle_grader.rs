@@ -0,0 +1,97 @@
+use umm::java::{
+    Project,
+    grade::{Oracle, OracleGrader},
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("oracle-sort")
+}
+
+#[test]
+fn sorted_permutation_oracle_checks_order_and_contents() {
+    let oracle = Oracle::sorted_permutation();
+
+    assert!(oracle.check("3 1 2", "1 2 3\n").is_ok());
+    assert!(oracle.check("4\n2\n2", "2, 2, 4").is_ok());
+
+    let unsorted = oracle.check("3 1 2", "1 3 2").unwrap_err();
+    assert_eq!(unsorted, "output is not sorted: 3 comes before 2");
+
+    let dropped = oracle.check("2 1 2", "1 2").unwrap_err();
+    assert!(dropped.contains("not a permutation of the input"), "{dropped}");
+    assert!(dropped.contains("expected 3 number(s) [1 2 2]"), "{dropped}");
+
+    let garbage = oracle.check("1", "one").unwrap_err();
+    assert_eq!(garbage, "output token `one` is not a number");
+}
+
+#[test]
+fn predicate_oracles_report_their_name() {
+    let oracle = Oracle::from_predicate("echo of the input", |input, output| input == output);
+
+    assert!(oracle.check("hi", "hi").is_ok());
+    assert_eq!(oracle.check("hi", "bye").unwrap_err(), "output is not the echo of the input");
+}
+
+#[tokio::test]
+async fn oracle_grader_passes_a_correct_sort() {
+    let result = OracleGrader::builder()
+        .req_name("sort")
+        .out_of(3.0)
+        .project(project())
+        .file("Sorter")
+        .inputs(["5 3 9 1", "4\n4\n-2\n7", "42"])
+        .oracle(Oracle::sorted_permutation())
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 3.0, "reason: {}", result.reason());
+    assert_eq!(result.reason(), "Every output was the sorted permutation of the input");
+    assert!(result.prompt().is_none());
+}
+
+#[tokio::test]
+async fn oracle_grader_reports_the_violating_case() {
+    let result = OracleGrader::builder()
+        .req_name("sort")
+        .out_of(3.0)
+        .project(project())
+        .file("DedupSorter")
+        .inputs(["5 3 9 1", "4\n4\n-2\n7"])
+        .oracle(Oracle::sorted_permutation())
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 0.0);
+    let reason = result.reason();
+    assert!(reason.starts_with(r"Case 2 (input: `4\n4\n-2\n7`)"), "reason: {reason}");
+    assert!(reason.contains("not a permutation of the input"), "reason: {reason}");
+    assert!(reason.contains("got 3 number(s) [-2 4 7]"), "reason: {reason}");
+    assert!(result.prompt().is_some());
+}
+
+#[tokio::test]
+async fn oracle_grader_requires_inputs() {
+    let err = OracleGrader::builder()
+        .req_name("sort")
+        .out_of(1.0)
+        .project(project())
+        .file("Sorter")
+        .inputs(Vec::<String>::new())
+        .oracle(Oracle::sorted_permutation())
+        .build()
+        .run()
+        .await
+        .expect_err("no inputs");
+
+    assert!(err.to_string().contains("at least one input"));
+}
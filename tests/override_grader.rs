@@ -0,0 +1,74 @@
+use umm::java::{
+    Project,
+    grade::{OverrideGrader, missing_overrides},
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("overrides")
+}
+
+#[test]
+fn missing_overrides_resolve_superclasses_and_interfaces() {
+    let project = project();
+    let file = project.identify("Dog").expect("identify Dog");
+    let missing = missing_overrides(&project, file.parser()).expect("missing overrides");
+    let found: Vec<_> = missing
+        .iter()
+        .map(|m| (m.name.as_str(), m.line, m.overridden.as_str()))
+        .collect();
+
+    // `describe(int)` is an overload and `create()` is static, so neither
+    // overrides anything.
+    assert_eq!(
+        found,
+        [
+            ("Dog.speak", 4, "Animal.speak"),
+            ("Dog.name", 17, "Named.name"),
+            ("Dog.hold", 21, "Holder.hold"),
+            ("Dog.legs", 25, "Animal.legs"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn annotated_overrides_get_full_marks() {
+    let result = OverrideGrader::builder()
+        .req_name("overrides")
+        .out_of(2.0)
+        .project(project())
+        .files(["Cat"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 2.0);
+    assert!(result.prompt().is_none());
+}
+
+#[tokio::test]
+async fn each_missing_annotation_costs_the_penalty() {
+    let result = OverrideGrader::builder()
+        .req_name("overrides")
+        .out_of(5.0)
+        .project(project())
+        .files(["Dog", "Cat"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 1.0);
+    assert_eq!(
+        result.reason(),
+        "4 overriding method(s) lack `@Override`:\n- Dog.java:4 Dog.speak overrides \
+         Animal.speak\n- Dog.java:17 Dog.name overrides Named.name\n- Dog.java:21 Dog.hold \
+         overrides Holder.hold\n- Dog.java:25 Dog.legs overrides Animal.legs"
+    );
+    assert!(result.prompt().is_some());
+}
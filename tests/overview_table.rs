@@ -0,0 +1,50 @@
+use umm::java::grade::{
+    Grade, GradeResult, TableDetail, gradescope::GradescopeConfig, overview_table,
+};
+
+const LONG_REASON: &str =
+    "Expected the loop to stop at the last element but it ran one past the end";
+
+fn results() -> Vec<GradeResult> {
+    vec![
+        GradeResult::builder()
+            .requirement("1.1")
+            .grade(Grade::new(5.0, 5.0))
+            .reason("All tests passed")
+            .build(),
+        GradeResult::builder()
+            .requirement("1.2")
+            .grade(Grade::new(2.0, 5.0))
+            .reason(LONG_REASON)
+            .build(),
+    ]
+}
+
+#[test]
+fn summary_mode_omits_the_reason_column() {
+    let config = GradescopeConfig::builder()
+        .table_detail(TableDetail::Summary)
+        .build();
+    let table = overview_table(&results(), &config);
+
+    assert!(table.contains("Requirement"), "{table}");
+    assert!(table.contains("Grade"));
+    assert!(table.contains("2.00/5.00"));
+    assert!(table.contains("Total: 7.00/10.00"));
+    assert!(!table.contains("Reason"), "{table}");
+    assert!(!table.contains("All tests passed"));
+}
+
+#[test]
+fn full_mode_fits_reasons_to_the_table_width() {
+    let wide = GradescopeConfig::builder().table_width(120).build();
+    let table = overview_table(&results(), &wide);
+    assert!(table.contains("Reason"));
+    assert!(table.contains(LONG_REASON), "{table}");
+    assert!(table.lines().all(|line| line.chars().count() <= 120), "{table}");
+
+    let narrow = GradescopeConfig::builder().table_width(60).build();
+    let table = overview_table(&results(), &narrow);
+    assert!(!table.contains(LONG_REASON), "{table}");
+    assert!(table.lines().all(|line| line.chars().count() <= 60), "{table}");
+}
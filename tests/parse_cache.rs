@@ -0,0 +1,79 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use umm::java::{ParseCache, paths::project_paths, project::Project};
+use uuid::Uuid;
+
+fn workspace() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("umm-parse-cache-{}", Uuid::new_v4()));
+    fs::create_dir_all(root.join("src")).expect("create temp src");
+    fs::write(root.join("src/Main.java"), "public class Main { int x = 1; }\n")
+        .expect("write Main.java");
+    fs::write(root.join("src/Helper.java"), "public class Helper {}\n").expect("write Helper.java");
+    root
+}
+
+fn load(root: &Path, cache: &ParseCache) -> Project {
+    let paths = project_paths().root_dir(root.to_path_buf()).build();
+    Project::from_paths_cached(paths, cache).expect("load project")
+}
+
+#[test]
+fn unchanged_files_are_not_reparsed() {
+    let root = workspace();
+    let cache = ParseCache::new();
+
+    let first = load(&root, &cache);
+    assert_eq!(cache.parses(), 2);
+
+    let second = load(&root, &cache);
+    assert_eq!(cache.parses(), 2, "second discovery should reuse cached files");
+    assert_eq!(first.fingerprint(), second.fingerprint());
+    assert!(second.contains("Helper"));
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn changed_files_are_reparsed() {
+    let root = workspace();
+    let cache = ParseCache::new();
+
+    let before = load(&root, &cache);
+    fs::write(root.join("src/Main.java"), "public class Main { int answer = 42; }\n")
+        .expect("rewrite Main.java");
+
+    let after = load(&root, &cache);
+    assert_eq!(cache.parses(), 3, "only the edited file should be reparsed");
+    assert_eq!(cache.len(), 2);
+    assert_ne!(before.fingerprint(), after.fingerprint());
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn same_size_edits_are_reparsed() {
+    let root = workspace();
+    let cache = ParseCache::new();
+    let main = root.join("src/Main.java");
+
+    let before = load(&root, &cache);
+    let modified = fs::metadata(&main)
+        .and_then(|meta| meta.modified())
+        .expect("read mtime");
+    // Same length, same timestamp: only the contents tell the versions apart.
+    fs::write(&main, "public class Main { int y = 2; }\n").expect("rewrite Main.java");
+    fs::File::options()
+        .write(true)
+        .open(&main)
+        .and_then(|file| file.set_modified(modified))
+        .expect("restore mtime");
+
+    let after = load(&root, &cache);
+    assert_eq!(cache.parses(), 3, "the edited file should be reparsed");
+    assert_ne!(before.fingerprint(), after.fingerprint());
+
+    let _ = fs::remove_dir_all(root);
+}
@@ -0,0 +1,54 @@
+use std::{fs, path::PathBuf};
+
+use umm::java::{
+    paths::project_paths,
+    util::{classpath, path_scans, sourcepath},
+};
+use uuid::Uuid;
+
+fn workspace() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("umm-path-cache-{}", Uuid::new_v4()));
+    fs::create_dir_all(root.join("src")).expect("create temp src");
+    fs::create_dir_all(root.join("lib")).expect("create temp lib");
+    fs::write(root.join("src/Main.java"), "public class Main {}\n").expect("write Main.java");
+    fs::write(root.join("lib/first.jar"), "").expect("write first.jar");
+    root
+}
+
+// The scan counter is process-wide, so every assertion lives in one test.
+#[test]
+fn paths_are_rescanned_only_when_directories_change() {
+    let root = workspace();
+    let paths = project_paths().root_dir(root.clone()).build();
+
+    let before = path_scans();
+    let first = classpath(&paths).expect("classpath");
+    let again = classpath(&paths).expect("classpath");
+    assert_eq!(first, again);
+    assert!(first.contains("first.jar"), "classpath: {first}");
+
+    let sources = sourcepath(&paths).expect("sourcepath");
+    assert_eq!(sources, sourcepath(&paths).expect("sourcepath"));
+    assert_eq!(path_scans() - before, 2, "repeated calls should reuse cached paths");
+
+    fs::write(root.join("lib/second.jar"), "").expect("write second.jar");
+    let updated = classpath(&paths).expect("classpath");
+    assert!(updated.contains("second.jar"), "classpath: {updated}");
+    assert_eq!(path_scans() - before, 3, "a new jar should trigger one rescan");
+
+    // Files added below the top-level directories invalidate the cache too.
+    fs::create_dir_all(root.join("lib/sub")).expect("create lib/sub");
+    classpath(&paths).expect("classpath");
+    fs::write(root.join("lib/sub/nested.jar"), "").expect("write nested.jar");
+    let nested = classpath(&paths).expect("classpath");
+    assert!(nested.contains("nested.jar"), "classpath: {nested}");
+
+    fs::create_dir_all(root.join("src/pkg")).expect("create src/pkg");
+    sourcepath(&paths).expect("sourcepath");
+    fs::write(root.join("src/pkg/New.java"), "package pkg;\n\npublic class New {}\n")
+        .expect("write New.java");
+    let sources = sourcepath(&paths).expect("sourcepath");
+    assert!(sources.contains("New.java"), "sourcepath: {sources}");
+
+    let _ = fs::remove_dir_all(root);
+}
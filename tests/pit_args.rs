@@ -2,7 +2,11 @@ use std::{fs, path::PathBuf};
 
 use umm::java::{
     Project,
-    grade::tests::{MutationInputs, UnitTestGrader},
+    grade::{
+        DEFAULT_AVOID_CALLS_TO, PitFailure, merge_avoid_calls_to,
+        tests::{MutationInputs, UnitTestGrader},
+        validate_pit_args,
+    },
     paths::ProjectPaths,
 };
 use uuid::Uuid;
@@ -29,9 +33,11 @@ fn pit_args_include_report_dir_under_umm() {
         vec!["java.io".into()],
     );
 
-    let args = UnitTestGrader::build_mutation_args(&project, &inputs).expect("mutation args");
+    let run_dir = UnitTestGrader::mutation_run_dir(&project);
+    let args =
+        UnitTestGrader::build_mutation_args(&project, &inputs, &run_dir).expect("mutation args");
 
-    // Assert reportDir is set to .umm/test_reports
+    // Assert reportDir is this run's directory under .umm/test_reports
     let idx = args
         .iter()
         .position(|a| a == "--reportDir")
@@ -40,7 +46,10 @@ fn pit_args_include_report_dir_under_umm() {
         .get(idx + 1)
         .and_then(|v: &std::ffi::OsString| v.to_str())
         .expect("reportDir value");
-    assert_eq!(report_arg, paths.report_dir().to_str().unwrap());
+    assert_eq!(report_arg, run_dir.to_str().unwrap());
+    assert_eq!(run_dir.parent(), Some(paths.report_dir()));
+    assert!(run_dir.is_dir(), "run directory is created");
+    assert_ne!(run_dir, UnitTestGrader::mutation_run_dir(&project));
 
     // Ensure classpath includes build then lib entries (order matters)
     let cp_idx = args
@@ -62,3 +71,159 @@ fn pit_args_include_report_dir_under_umm() {
 
     let _ = fs::remove_dir_all(root);
 }
+
+#[test]
+fn pit_args_append_custom_arguments() {
+    let root = temp_root();
+    let paths = ProjectPaths::from_parts(root.clone(), None, None, None, None, None, None);
+    let project = Project::from_paths(paths).expect("build project");
+
+    let inputs = MutationInputs::new(
+        vec!["example.ExampleTest".into()],
+        vec!["example.Example".into()],
+        Vec::<String>::new(),
+        Vec::<String>::new(),
+    )
+    .with_extra_args(vec![
+        "--mutableCodePaths".to_string(),
+        "target/classes".to_string(),
+        "--mutators".to_string(),
+        "ALL".to_string(),
+    ]);
+
+    let args: Vec<String> = UnitTestGrader::build_mutation_args(
+        &project,
+        &inputs,
+        &UnitTestGrader::mutation_run_dir(&project),
+    )
+    .expect("mutation args")
+    .into_iter()
+    .map(|a| a.to_string_lossy().into_owned())
+    .collect();
+
+    let tail = &args[args.len() - 4..];
+    assert_eq!(tail, ["--mutableCodePaths", "target/classes", "--mutators", "ALL"]);
+    // The custom mutator group replaces the STRONGER default.
+    assert_eq!(args.iter().filter(|a| *a == "--mutators").count(), 1);
+    assert!(!args.iter().any(|a| a == "STRONGER"), "args were {args:?}");
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn pit_args_reject_obvious_mistakes() {
+    let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+    assert!(validate_pit_args(&args(&["--mutationThreshold", "80"])).is_ok());
+    assert!(validate_pit_args(&args(&["--coverageThreshold=75"])).is_ok());
+
+    let reserved = validate_pit_args(&args(&["--reportDir", "/tmp/x"])).unwrap_err();
+    assert!(reserved.to_string().contains("managed by umm"), "{reserved}");
+
+    let single_dash = validate_pit_args(&args(&["-mutators", "ALL"])).unwrap_err();
+    assert!(single_dash.to_string().contains("--mutators"), "{single_dash}");
+
+    let dangling = validate_pit_args(&args(&["ALL"])).unwrap_err();
+    assert!(dangling.to_string().contains("without a preceding"), "{dangling}");
+
+    let repeated = validate_pit_args(&args(&["--threads", "2", "--threads=4"])).unwrap_err();
+    assert!(repeated.to_string().contains("more than once"), "{repeated}");
+
+    assert!(validate_pit_args(&args(&["--mutators", " "])).is_err());
+}
+
+#[test]
+fn avoid_calls_to_defaults_merge_with_user_values() {
+    let root = temp_root();
+    let paths = ProjectPaths::from_parts(root.clone(), None, None, None, None, None, None);
+    let project = Project::from_paths(paths).expect("build project");
+    let user = ["com.example.Audit", "org.slf4j", " ", "com.example.Audit"];
+
+    let grader = UnitTestGrader::builder()
+        .req_name("mutation")
+        .out_of(10.0)
+        .project(project.clone())
+        .target_test(["example.ExampleTest"])
+        .target_class(["example.Example"])
+        .avoid_calls_to(user)
+        .build();
+    assert_eq!(grader.default_avoid_calls_to, DEFAULT_AVOID_CALLS_TO);
+    let merged = merge_avoid_calls_to(&grader.default_avoid_calls_to, &grader.avoid_calls_to);
+    let mut expected: Vec<&str> = DEFAULT_AVOID_CALLS_TO.to_vec();
+    expected.push("com.example.Audit");
+    assert_eq!(merged, expected);
+
+    let inputs = MutationInputs::new(
+        grader.target_test.clone(),
+        grader.target_class.clone(),
+        Vec::<String>::new(),
+        merged,
+    );
+    let run_dir = UnitTestGrader::mutation_run_dir(&project);
+    let args =
+        UnitTestGrader::build_mutation_args(&project, &inputs, &run_dir).expect("mutation args");
+    let idx = args
+        .iter()
+        .position(|a| a == "--avoidCallsTo")
+        .expect("avoidCallsTo flag");
+    assert_eq!(args[idx + 1], expected.join(",").as_str());
+
+    let overridden = UnitTestGrader::builder()
+        .req_name("mutation")
+        .out_of(10.0)
+        .project(project)
+        .target_test(["example.ExampleTest"])
+        .target_class(["example.Example"])
+        .default_avoid_calls_to(["java.util.logging"])
+        .avoid_calls_to(user)
+        .build();
+    assert_eq!(
+        merge_avoid_calls_to(&overridden.default_avoid_calls_to, &overridden.avoid_calls_to),
+        ["java.util.logging", "com.example.Audit", "org.slf4j"]
+    );
+
+    let strings = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    assert!(merge_avoid_calls_to(&[], &strings(&["", "  "])).is_empty());
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn pit_failures_are_classified_from_output() {
+    let not_green = "\
+7:42:01 PM PIT >> INFO : Calculated coverage in 0 seconds.
+Exception in thread \"main\" org.pitest.help.PitHelpError: 1 tests did not pass without mutation \
+                     when calculating line coverage. Mutation testing requires a green suite.
+See http://pitest.org for more details.
+\tat org.pitest.coverage.execute.DefaultCoverageGenerator.verifyBuildSuitableForMutationTesting";
+    assert_eq!(PitFailure::classify(not_green), PitFailure::TestsNotGreen);
+    assert!(
+        PitFailure::TestsNotGreen
+            .reason()
+            .contains("some tests fail")
+    );
+
+    let no_mutations = "org.pitest.help.PitHelpError: No mutations found. This probably means \
+                        there is an issue with either the supplied classpath or filters.";
+    assert_eq!(PitFailure::classify(no_mutations), PitFailure::NoMutations);
+
+    let no_tests = "PIT >> SEVERE : No test library found on classpath. PIT requires either JUnit \
+                    4 (but can process JUnit 3 tests), JUnit 5 or TestNG";
+    assert_eq!(PitFailure::classify(no_tests), PitFailure::NoTestsFound);
+
+    let classpath = "java.lang.NoClassDefFoundError: example/Example";
+    assert_eq!(PitFailure::classify(classpath), PitFailure::ClasspathProblem);
+
+    let version = "java.lang.UnsupportedClassVersionError: Example has been compiled by a more \
+                   recent version of the Java Runtime";
+    assert_eq!(PitFailure::classify(version), PitFailure::JavaVersionMismatch);
+
+    let minion = "PIT >> WARNING : Minion exited abnormally due to MEMORY_ERROR";
+    assert_eq!(PitFailure::classify(minion), PitFailure::ResourceLimit);
+
+    assert_eq!(PitFailure::classify("something odd"), PitFailure::Unknown);
+    assert_eq!(
+        PitFailure::Unknown.reason(),
+        "Something went wrong while running mutation tests, skipping."
+    );
+}
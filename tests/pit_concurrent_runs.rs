@@ -0,0 +1,48 @@
+use std::{fs, path::PathBuf};
+
+use umm::java::{Project, grade::tests::UnitTestGrader, paths::ProjectPaths};
+use uuid::Uuid;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_mutation_runs_keep_separate_reports() -> anyhow::Result<()> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let scratch = std::env::temp_dir().join(format!("umm-pit-runs-{}", Uuid::new_v4()));
+    let paths = ProjectPaths::from_parts(
+        root.join("fixtures/java/pit-calc"),
+        None,
+        Some(scratch.join("target")),
+        None,
+        Some(root.join("jar_files")),
+        Some(scratch.join(".umm")),
+        Some(scratch.join("reports")),
+    );
+    let project = Project::from_paths(paths)?;
+    project.identify("calc.CalcTest")?.check().await?;
+
+    let grader = || {
+        UnitTestGrader::builder()
+            .project(project.clone())
+            .req_name("mutation")
+            .out_of(20.0)
+            .target_test(["calc.CalcTest"])
+            .target_class(["calc.Calc"])
+            .build()
+    };
+    let (first, second) = tokio::join!(grader().grade_unit_tests(), grader().grade_unit_tests());
+
+    // Three mutants survive, at four points each.
+    for result in [first?, second?] {
+        assert_eq!(result.grade_value(), 8.0, "reason: {}", result.reason());
+    }
+
+    let reports: Vec<PathBuf> = fs::read_dir(scratch.join("reports"))?
+        .map(|entry| entry.map(|entry| entry.path().join("mutations.csv")))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(reports.len(), 2, "one report directory per run: {reports:?}");
+    for report in reports {
+        assert_eq!(fs::read_to_string(&report)?.lines().count(), 9, "{}", report.display());
+    }
+
+    let _ = fs::remove_dir_all(scratch);
+    Ok(())
+}
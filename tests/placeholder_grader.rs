@@ -0,0 +1,121 @@
+use regex::Regex;
+use umm::java::{
+    Project,
+    grade::{
+        DEFAULT_COMMENT_PATTERNS, DEFAULT_THROW_PATTERNS, PlaceholderGrader, PlaceholderKind,
+        placeholders,
+    },
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("placeholders")
+}
+
+fn compile(patterns: &[&str]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .map(|pattern| Regex::new(pattern).expect("valid pattern"))
+        .collect()
+}
+
+#[test]
+fn placeholders_find_todo_comments_and_stub_throws() {
+    let file = project().identify("Stack").expect("identify Stack");
+    let found = placeholders(
+        file.parser(),
+        &compile(DEFAULT_COMMENT_PATTERNS),
+        &compile(DEFAULT_THROW_PATTERNS),
+    )
+    .expect("placeholders");
+    let found: Vec<_> = found
+        .iter()
+        .map(|p| (p.location.as_str(), p.line, p.kind, p.text.as_str()))
+        .collect();
+
+    assert_eq!(
+        found,
+        [
+            (
+                "Stack.pop",
+                13,
+                PlaceholderKind::Comment,
+                "// TODO: remove and return the top item"
+            ),
+            (
+                "Stack.pop",
+                14,
+                PlaceholderKind::Throw,
+                "throw new UnsupportedOperationException(\"Not implemented yet\");"
+            ),
+            ("Stack", 26, PlaceholderKind::Comment, "* FIXME: count only non-null items"),
+            (
+                "Stack.size",
+                29,
+                PlaceholderKind::Throw,
+                "throw new RuntimeException(\"not implemented\");"
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn placeholder_grader_reports_locations() {
+    let result = PlaceholderGrader::builder()
+        .req_name("placeholders")
+        .out_of(5.0)
+        .project(project())
+        .files(["Stack"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 1.0);
+    let reason = result.reason();
+    assert!(reason.starts_with("4 placeholder(s) left unimplemented:"), "reason: {reason}");
+    assert!(
+        reason.contains("Stack.java:13 in Stack.pop: // TODO: remove and return the top item"),
+        "reason: {reason}"
+    );
+    assert!(reason.contains("Stack.java:29 in Stack.size: throw new"), "reason: {reason}");
+    assert!(!reason.contains("IllegalStateException"), "real throw flagged: {reason}");
+    assert!(result.prompt().is_some());
+}
+
+#[tokio::test]
+async fn placeholder_grader_uses_configured_patterns() {
+    let result = PlaceholderGrader::builder()
+        .req_name("placeholders")
+        .out_of(5.0)
+        .project(project())
+        .files(["Stack"])
+        .comment_patterns(["FIXME"])
+        .throw_patterns(Vec::<String>::new())
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 4.0, "reason: {}", result.reason());
+    assert!(
+        result.reason().contains("Stack.java:26 in Stack: "),
+        "reason: {}",
+        result.reason()
+    );
+
+    let invalid = PlaceholderGrader::builder()
+        .req_name("placeholders")
+        .out_of(5.0)
+        .project(project())
+        .files(["Stack"])
+        .comment_patterns(["TODO("])
+        .build()
+        .run()
+        .await;
+    assert!(invalid.is_err());
+}
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use umm::polyglot::Project;
+
+fn fixtures_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+#[test]
+fn discovers_both_language_projects() -> anyhow::Result<()> {
+    let project = Project::from_root(fixtures_root().join("polyglot/mixed"))?;
+
+    assert_eq!(project.languages(), ["java", "python"]);
+    let java = project.java().expect("java project");
+    assert!(java.identify("Main").is_ok());
+    let python = project.python().expect("python project");
+    assert!(python.identify("report").is_ok());
+    Ok(())
+}
+
+#[test]
+fn single_language_directory_has_one_project() -> anyhow::Result<()> {
+    let project = Project::from_root(fixtures_root().join("python/diff-ok"))?;
+
+    assert_eq!(project.languages(), ["python"]);
+    assert!(project.java().is_none());
+    Ok(())
+}
+
+#[test]
+fn rune_script_reports_java_and_python_results_together() {
+    let output = cargo_bin_cmd!("umm")
+        .current_dir(fixtures_root().join("polyglot/mixed"))
+        .env("CLICOLOR", "0")
+        .args(["java", "grade"])
+        .arg(fixtures_root().join("rune/polyglot.rn"))
+        .output()
+        .expect("run umm");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert!(stdout.contains("languages=2"), "stdout: {stdout}");
+    let all = format!("{stdout}{stderr}");
+    assert!(all.contains("java-output"), "output: {all}");
+    assert!(all.contains("python-output"), "output: {all}");
+    assert!(all.contains("Total: 10.00/10.00"), "output: {all}");
+}
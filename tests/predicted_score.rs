@@ -0,0 +1,88 @@
+use std::{
+    io::{BufRead, BufReader},
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use umm::java::grade::PredictedScore;
+use uuid::Uuid;
+
+#[path = "http_support.rs"]
+mod http_support;
+
+use http_support::{MockServer, respond};
+
+fn fixtures_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+#[test]
+fn predicted_score_is_printed_before_any_llm_call() {
+    let score_seen = Arc::new(AtomicBool::new(false));
+    // One entry per LLM request: was the score already on stdout?
+    let calls = Arc::new(Mutex::new(Vec::<bool>::new()));
+
+    let mock = {
+        let score_seen = Arc::clone(&score_seen);
+        let calls = Arc::clone(&calls);
+        MockServer::start(move |_, stream| {
+            // The client is blocked on this response, so anything it was
+            // going to print first has had time to reach the pipe.
+            thread::sleep(Duration::from_millis(300));
+            calls
+                .lock()
+                .unwrap()
+                .push(score_seen.load(Ordering::SeqCst));
+            respond(stream, 400, r#"{"error":{"message":"mock","type":"invalid_request_error"}}"#);
+        })
+    };
+
+    // Debug mode writes results.json to the working directory, so grade a
+    // scratch copy of the fixture.
+    let workdir = std::env::temp_dir().join(format!("umm-predicted-score-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&workdir).expect("create scratch project");
+    std::fs::copy(
+        fixtures_root().join("java/predicted-score/Main.java"),
+        workdir.join("Main.java"),
+    )
+    .expect("copy fixture");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_umm"))
+        .current_dir(&workdir)
+        .env("CLICOLOR", "0")
+        .env("OPENAI_ENDPOINT", &mock.url)
+        .env("OPENAI_API_KEY_SLO", "test-key")
+        .env("OPENAI_MODEL", "test-model")
+        .args(["java", "grade"])
+        .arg(fixtures_root().join("rune").join("predicted_score.rn"))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn umm");
+
+    let mut predicted = None;
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line.expect("read stdout");
+        if let Ok(score) = serde_json::from_str::<PredictedScore>(&line) {
+            score_seen.store(true, Ordering::SeqCst);
+            predicted = Some(score);
+        }
+    }
+    let _ = child.wait();
+    let _ = std::fs::remove_dir_all(&workdir);
+
+    let predicted = predicted.expect("predicted_score line on stdout");
+    assert_eq!(predicted.event, "predicted_score");
+    assert_eq!(predicted.score, 2.0);
+    assert_eq!(predicted.out_of, 2.0);
+    assert!(predicted.passed);
+
+    let calls = calls.lock().unwrap();
+    assert!(!calls.is_empty(), "SLO feedback never reached the mock LLM");
+    assert!(calls[0], "first LLM call happened before the score was printed");
+}
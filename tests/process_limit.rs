@@ -0,0 +1,61 @@
+use std::{
+    ffi::OsString,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use umm::process::{self, StdinSource};
+
+// The limit is process-wide, so every assertion lives in one test.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_processes_stay_within_the_limit() {
+    process::set_max_processes(2);
+    assert_eq!(process::max_processes(), 2);
+
+    let done = Arc::new(AtomicBool::new(false));
+    let watcher = {
+        let done = Arc::clone(&done);
+        tokio::spawn(async move {
+            let mut peak = 0;
+            while !done.load(Ordering::Relaxed) {
+                peak = peak.max(process::active_processes());
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            peak
+        })
+    };
+
+    let started = Instant::now();
+    let runs: Vec<_> = (0..6)
+        .map(|_| {
+            tokio::spawn(async {
+                process::run_collect(
+                    "sleep",
+                    &[OsString::from("0.2")],
+                    StdinSource::Null,
+                    None,
+                    &[],
+                    None,
+                )
+                .await
+            })
+        })
+        .collect();
+    for run in runs {
+        let collected = run.await.expect("join").expect("run sleep");
+        assert!(collected.status.success());
+    }
+    let elapsed = started.elapsed();
+    done.store(true, Ordering::Relaxed);
+    let peak = watcher.await.expect("join watcher");
+
+    assert!((1..=2).contains(&peak), "at most two processes should run at once, saw {peak}");
+    assert!(
+        elapsed >= Duration::from_millis(550),
+        "six 200ms runs two at a time took {elapsed:?}"
+    );
+    assert_eq!(process::active_processes(), 0);
+}
@@ -0,0 +1,41 @@
+use umm::java::project::Project;
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("find-method")
+}
+
+#[test]
+fn find_method_resolves_the_declaring_class() {
+    let project = project();
+
+    let (file, body) = project
+        .find_method("shapes.Square", "area")
+        .expect("Square#area");
+    assert_eq!(file.proper_name(), "shapes.Square");
+    assert!(body.contains("return side * side;"), "body: {body}");
+
+    let (file, body) = project
+        .find_method("shapes.Circle", "area")
+        .expect("Circle#area");
+    assert_eq!(file.proper_name(), "shapes.Circle");
+    assert!(body.contains("Math.PI * radius * radius"), "body: {body}");
+}
+
+#[test]
+fn find_method_is_scoped_to_the_named_class() {
+    let project = project();
+
+    let (_, body) = project
+        .find_method("shapes.Square", "perimeter")
+        .expect("Square#perimeter");
+    assert!(body.contains("return 4 * side;"), "body: {body}");
+
+    assert!(project.find_method("shapes.Square", "text").is_none());
+    assert!(project.find_method("shapes.Circle", "perimeter").is_none());
+    assert!(project.find_method("shapes.Triangle", "area").is_none());
+}
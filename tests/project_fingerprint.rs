@@ -0,0 +1,62 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use umm::java::{paths::project_paths, project::Project};
+use uuid::Uuid;
+
+fn submission(source: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("umm-fingerprint-{}", Uuid::new_v4()));
+    fs::create_dir_all(root.join("src")).expect("create temp src");
+    fs::write(root.join("src/Main.java"), source).expect("write Main.java");
+    root
+}
+
+fn load(root: &Path) -> Project {
+    Project::from_paths(project_paths().root_dir(root.to_path_buf()).build()).expect("load project")
+}
+
+#[test]
+fn whitespace_only_changes_share_normalized_fingerprint() {
+    let tidy = submission(
+        "public class Main {\n    public static void main(String[] args) {\n        \
+         System.out.println(1 + 2);\n    }\n}\n",
+    );
+    let messy = submission(
+        "public class Main{\n\tpublic static void main(String[] \
+         args){\n\t\tSystem.out.println(1+2);\n\n\t}\n}",
+    );
+
+    let (a, b) = (load(&tidy), load(&messy));
+
+    assert_eq!(a.normalized_fingerprint(), b.normalized_fingerprint());
+    assert_ne!(a.fingerprint(), b.fingerprint());
+    assert_eq!(a.fingerprint(), load(&tidy).fingerprint());
+    assert_eq!(a.fingerprint().len(), 64);
+
+    let _ = fs::remove_dir_all(tidy);
+    let _ = fs::remove_dir_all(messy);
+}
+
+#[test]
+fn different_code_changes_normalized_fingerprint() {
+    let one = submission("public class Main { int x = 1; }\n");
+    let two = submission("public class Main { int x = 2; }\n");
+
+    assert_ne!(load(&one).normalized_fingerprint(), load(&two).normalized_fingerprint());
+
+    let _ = fs::remove_dir_all(one);
+    let _ = fs::remove_dir_all(two);
+}
+
+#[test]
+fn whitespace_inside_tokens_changes_normalized_fingerprint() {
+    let spaced = submission("public class Main { String s = \"a b\"; }\n");
+    let joined = submission("public class Main { String s = \"ab\"; }\n");
+
+    assert_ne!(load(&spaced).normalized_fingerprint(), load(&joined).normalized_fingerprint());
+
+    let _ = fs::remove_dir_all(spaced);
+    let _ = fs::remove_dir_all(joined);
+}
@@ -0,0 +1,51 @@
+use umm::java::Project;
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("metrics")
+}
+
+#[test]
+fn metrics_count_code_lines_methods_and_types_per_file() {
+    let metrics = project().metrics().expect("metrics");
+    let names: Vec<_> = metrics.files.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["Circle", "Shape"]);
+
+    let circle = &metrics.files[0];
+    // Blank lines and the leading block comment are skipped; the line with a
+    // trailing comment still counts.
+    assert_eq!(circle.loc, 15);
+    assert_eq!(circle.methods, 3);
+    assert_eq!(circle.classes, 1);
+    assert_eq!(circle.interfaces, 0);
+
+    let shape = &metrics.files[1];
+    assert_eq!(shape.loc, 4);
+    assert_eq!(shape.methods, 2);
+    assert_eq!(shape.classes, 0);
+    assert_eq!(shape.interfaces, 1);
+
+    assert_eq!(metrics.loc, 19);
+    assert_eq!(metrics.methods, 5);
+    assert_eq!(metrics.classes, 1);
+    assert_eq!(metrics.interfaces, 1);
+}
+
+#[test]
+fn total_loc_matches_aggregate_metrics() {
+    let project = project();
+    assert_eq!(project.total_loc().expect("loc"), 19);
+}
+
+#[test]
+fn metrics_serialize_to_json() {
+    let metrics = project().metrics().expect("metrics");
+    let json = serde_json::to_value(&metrics).expect("serialize");
+    assert_eq!(json["loc"], 19);
+    assert_eq!(json["files"][1]["name"], "Shape");
+    assert_eq!(json["files"][1]["methods"], 2);
+}
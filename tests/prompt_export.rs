@@ -0,0 +1,56 @@
+use std::{io::ErrorKind, net::TcpListener};
+
+use umm::java::grade::{Grade, GradeResult, generate_feedback};
+use uuid::Uuid;
+
+#[test]
+fn exported_prompts_are_written_locally_instead_of_posted() -> anyhow::Result<()> {
+    // A listener posing as Supabase; any request would show up as a connection.
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    listener.set_nonblocking(true)?;
+    // Configuration is read once per process, so set it before first use.
+    unsafe {
+        std::env::set_var("SUPABASE_URL", format!("http://{}", listener.local_addr()?));
+        std::env::set_var("SUPABASE_ANON_KEY", "anon-key");
+    }
+
+    let dir = std::env::temp_dir().join(format!("umm-prompts-{}", Uuid::new_v4()));
+    let prompts = dir.join("prompts");
+    umm::config::set_prompt_export_dir(Some(prompts.clone()));
+
+    let result = GradeResult::builder()
+        .requirement("squares")
+        .grade(Grade::new(1.0, 2.0))
+        .reason("1/2 tests passing")
+        .build();
+    let feedback = dir.join("feedback.md");
+    generate_feedback([result], Some(feedback.as_path()))?;
+
+    let files: Vec<_> = std::fs::read_dir(&prompts)?.collect::<Result<_, _>>()?;
+    assert_eq!(files.len(), 1);
+    let path = files[0].path();
+    let id = path.file_stem().unwrap().to_string_lossy().into_owned();
+    assert!(Uuid::parse_str(&id).is_ok(), "{}", path.display());
+
+    let row: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    assert_eq!(row["id"], id.as_str());
+    assert_eq!(row["requirement_name"], "squares");
+    assert_eq!(row["reason"], "1/2 tests passing");
+    assert_eq!(row["status"], "not_started");
+
+    let written = std::fs::read_to_string(&feedback)?;
+    assert!(
+        written.contains("- Prompt for `squares` (refer rubric) was saved to"),
+        "{written}"
+    );
+    assert!(written.contains(&id), "{written}");
+
+    assert_eq!(
+        listener.accept().err().map(|e| e.kind()),
+        Some(ErrorKind::WouldBlock),
+        "no request should reach the database"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
@@ -1,29 +1,15 @@
 //! Tests for Python diff grader functionality.
 
-use std::path::PathBuf;
-
-use umm::python::{
-    Project,
-    grade::diff::{DiffCase, DiffGrader},
-    paths::ProjectPaths,
-};
-
-fn fixture_root(name: &str) -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("fixtures")
-        .join("python")
-        .join(name)
-}
+use umm::python::grade::diff::{DiffCase, DiffGrader};
 
-fn project(name: &str) -> Project {
-    let root = fixture_root(name);
-    let paths = ProjectPaths::from_parts(root, None, None, None, None, None, None);
-    Project::from_paths(paths).expect("build project")
-}
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::python_project;
 
 #[tokio::test]
 async fn diff_passes_on_exact_match_trimmed() {
-    let proj = project("diff-ok");
+    let proj = python_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("ok")
         .out_of(2.0)
@@ -40,7 +26,7 @@ async fn diff_passes_on_exact_match_trimmed() {
 
 #[tokio::test]
 async fn diff_detects_mismatch_with_preserve_whitespace() {
-    let proj = project("diff-ok");
+    let proj = python_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("ws")
         .out_of(1.0)
@@ -58,7 +44,7 @@ async fn diff_detects_mismatch_with_preserve_whitespace() {
 
 #[tokio::test]
 async fn diff_ignores_case_when_configured() {
-    let proj = project("diff-ok");
+    let proj = python_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("case")
         .out_of(1.5)
@@ -76,7 +62,7 @@ async fn diff_ignores_case_when_configured() {
 
 #[tokio::test]
 async fn diff_trims_when_whitespace_not_preserved() {
-    let proj = project("diff-ok");
+    let proj = python_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("trim")
         .out_of(1.0)
@@ -93,7 +79,7 @@ async fn diff_trims_when_whitespace_not_preserved() {
 
 #[tokio::test]
 async fn diff_runs_with_stdin_bytes() {
-    let proj = project("diff-stdin");
+    let proj = python_project("diff-stdin");
     let grader = DiffGrader::builder()
         .req_name("stdin")
         .out_of(1.0)
@@ -110,7 +96,7 @@ async fn diff_runs_with_stdin_bytes() {
 
 #[tokio::test]
 async fn diff_handles_runtime_failure() {
-    let proj = project("diff-runtime");
+    let proj = python_project("diff-runtime");
     let grader = DiffGrader::builder()
         .req_name("rt")
         .out_of(3.0)
@@ -127,7 +113,7 @@ async fn diff_handles_runtime_failure() {
 
 #[tokio::test]
 async fn diff_handles_syntax_error() {
-    let proj = project("diff-syntax-error");
+    let proj = python_project("diff-syntax-error");
     let grader = DiffGrader::builder()
         .req_name("syntax")
         .out_of(3.0)
@@ -144,7 +130,7 @@ async fn diff_handles_syntax_error() {
 
 #[tokio::test]
 async fn diff_multiple_cases_stops_on_first_failure() {
-    let proj = project("diff-ok");
+    let proj = python_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("multi")
         .out_of(2.0)
@@ -164,7 +150,7 @@ async fn diff_multiple_cases_stops_on_first_failure() {
 
 #[tokio::test]
 async fn diff_errors_when_no_cases() {
-    let proj = project("diff-ok");
+    let proj = python_project("diff-ok");
     let grader = DiffGrader::builder()
         .req_name("empty")
         .out_of(1.0)
@@ -175,3 +161,26 @@ async fn diff_errors_when_no_cases() {
     let err = grader.run().await;
     assert!(err.is_err(), "expected missing cases error");
 }
+
+#[tokio::test]
+async fn diff_strips_ansi_colors_when_configured() {
+    let colored = || {
+        DiffGrader::builder()
+            .req_name("ansi")
+            .out_of(1.0)
+            .project(python_project("diff-ansi"))
+            .file("main")
+            .cases(vec![DiffCase::new("hello world")])
+    };
+
+    let raw = colored().build().run().await.expect("grade");
+    assert_eq!(raw.grade_value(), 0.0);
+
+    let stripped = colored()
+        .strip_ansi(true)
+        .build()
+        .run()
+        .await
+        .expect("grade");
+    assert_eq!(stripped.grade_value(), 1.0);
+}
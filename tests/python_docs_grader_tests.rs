@@ -1,23 +1,13 @@
-use std::path::PathBuf;
+use umm::python::grade::docs::DocsGrader;
 
-use umm::python::{Project, grade::docs::DocsGrader, paths::ProjectPaths};
+#[path = "fixture_support.rs"]
+mod fixture_support;
 
-fn fixture_root(name: &str) -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("fixtures")
-        .join("python")
-        .join(name)
-}
-
-fn project_for(name: &str) -> Project {
-    let root = fixture_root(name);
-    let paths = ProjectPaths::from_parts(root, None, None, None, None, None, None);
-    Project::from_paths(paths).expect("build project")
-}
+use fixture_support::python_project;
 
 #[tokio::test]
 async fn docstring_must_be_leading_statement() {
-    let project = project_for("docs-late-string");
+    let project = python_project("docs-late-string");
     let grader = DocsGrader::builder()
         .project(project)
         .files(vec!["main.py"])
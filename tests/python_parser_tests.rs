@@ -3,22 +3,14 @@
 use std::path::PathBuf;
 
 use umm::{
-    python::{Parser, Project, grade::context::get_source_context, paths::ProjectPaths},
+    python::{Parser, grade::context::get_source_context},
     types::LineRef,
 };
 
-fn fixture_root(name: &str) -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("fixtures")
-        .join("python")
-        .join(name)
-}
+#[path = "fixture_support.rs"]
+mod fixture_support;
 
-fn project_for(name: &str) -> Project {
-    let root = fixture_root(name);
-    let paths = ProjectPaths::from_parts(root, None, None, None, None, None, None);
-    Project::from_paths(paths).expect("build project")
-}
+use fixture_support::{fixture_root, python_project};
 
 fn parse_fixture(path: &str) -> Parser {
     let full = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path);
@@ -100,7 +92,7 @@ def greet(name):
 
 #[test]
 fn project_discovers_files() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     // Use count() instead of is_empty() since files() returns an iterator
     let file_count = project.files().count();
 
@@ -109,7 +101,7 @@ fn project_discovers_files() {
 
 #[test]
 fn project_identifies_file_by_name() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     let file = project.identify("example");
 
     assert!(file.is_ok(), "should identify example.py");
@@ -117,7 +109,7 @@ fn project_identifies_file_by_name() {
 
 #[test]
 fn project_file_has_main() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     let file = project.identify("example").expect("identify file");
 
     assert!(file.has_main(), "example.py should have a main block");
@@ -125,7 +117,7 @@ fn project_file_has_main() {
 
 #[test]
 fn project_file_lists_functions() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     let file = project.identify("example").expect("identify file");
     let functions = file.functions();
 
@@ -135,7 +127,7 @@ fn project_file_lists_functions() {
 
 #[test]
 fn project_file_lists_classes() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     let file = project.identify("example").expect("identify file");
     let classes = file.classes();
 
@@ -144,8 +136,8 @@ fn project_file_lists_classes() {
 
 #[test]
 fn project_identifies_file_with_prefixed_path() {
-    let project = project_for("query-cases");
-    let root = fixture_root("query-cases");
+    let project = python_project("query-cases");
+    let root = fixture_root("python", "query-cases");
 
     let abs_path = root.join("example.py");
     let rel_path = PathBuf::from("fixtures/python/query-cases/example.py");
@@ -166,9 +158,9 @@ fn project_identifies_file_with_prefixed_path() {
 
 #[test]
 fn get_source_context_handles_prefixed_line_refs() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     let file = project.identify("example").expect("identify file");
-    let abs_path = fixture_root("query-cases").join("example.py");
+    let abs_path = fixture_root("python", "query-cases").join("example.py");
 
     let line_ref = LineRef {
         file_name:   abs_path.to_string_lossy().to_string(),
@@ -3,23 +3,14 @@
 use std::path::PathBuf;
 
 use umm::python::{
-    Parser, Project,
+    Parser,
     grade::query::{Query, QueryConstraint, QueryGrader},
-    paths::ProjectPaths,
 };
 
-fn fixture_root(name: &str) -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("fixtures")
-        .join("python")
-        .join(name)
-}
+#[path = "fixture_support.rs"]
+mod fixture_support;
 
-fn project_for(name: &str) -> Project {
-    let root = fixture_root(name);
-    let paths = ProjectPaths::from_parts(root, None, None, None, None, None, None);
-    Project::from_paths(paths).expect("build project")
-}
+use fixture_support::python_project;
 
 fn parse_fixture(path: &str) -> Parser {
     let full = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path);
@@ -86,7 +77,7 @@ fn parser_finds_list_comprehension() {
 
 #[tokio::test]
 async fn query_grader_at_least_once_succeeds() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     let grader = QueryGrader::builder()
         .req_name("q1")
         .out_of(5.0)
@@ -109,7 +100,7 @@ async fn query_grader_at_least_once_succeeds() {
 
 #[tokio::test]
 async fn query_grader_exact_count_succeeds() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     // Count classes - should be 1
     let grader = QueryGrader::builder()
         .req_name("q2")
@@ -133,7 +124,7 @@ async fn query_grader_exact_count_succeeds() {
 
 #[tokio::test]
 async fn query_grader_exact_count_fails() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     // Expect 10 classes when only 1 exists
     let grader = QueryGrader::builder()
         .req_name("q3")
@@ -157,7 +148,7 @@ async fn query_grader_exact_count_fails() {
 
 #[tokio::test]
 async fn query_grader_must_not_match_passes_on_zero() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     // Check for a nonexistent class
     let grader = QueryGrader::builder()
         .req_name("q4")
@@ -184,7 +175,7 @@ async fn query_grader_must_not_match_passes_on_zero() {
 
 #[tokio::test]
 async fn query_grader_must_not_match_fails_when_present() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     // Check for Calculator class (which exists)
     let grader = QueryGrader::builder()
         .req_name("q5")
@@ -211,7 +202,7 @@ async fn query_grader_must_not_match_fails_when_present() {
 
 #[tokio::test]
 async fn query_grader_filter_predicate_applies() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     let filtered = Query::new()
         .set_query("(function_definition name: (identifier) @name)".into())
         .set_capture("name".into())
@@ -235,7 +226,7 @@ async fn query_grader_filter_predicate_applies() {
 
 #[tokio::test]
 async fn query_grader_rejects_unknown_file() {
-    let project = project_for("query-cases");
+    let project = python_project("query-cases");
     let result = QueryGrader::builder()
         .req_name("q7")
         .out_of(1.0)
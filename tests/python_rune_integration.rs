@@ -50,3 +50,10 @@ fn python_rune_query_constraints() {
     insta::assert_snapshot!("python_rune_query_constraints_stdout", stdout);
     insta::assert_snapshot!("python_rune_query_constraints_stderr", stderr);
 }
+
+#[test]
+fn python_rune_reference_and_oracle_graders() {
+    let (_, stderr) = run_python_script("python_reference_oracle.rn", "oracle-sort");
+    assert!(stderr.contains("matches reference"), "stderr was:\n{stderr}");
+    assert!(stderr.contains("Total: 3.00/5.00"), "stderr was:\n{stderr}");
+}
@@ -1,23 +1,15 @@
 use std::path::PathBuf;
 
 use umm::java::{
-    Parser, Project,
-    grade::query::{Query, QueryConstraint, QueryGrader},
-    paths::ProjectPaths,
+    Parser,
+    grade::query::{Query, QueryConstraint, QueryError, QueryGrader},
+    queries::QueryLibrary,
 };
 
-fn fixture_root(name: &str) -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("fixtures")
-        .join("java")
-        .join(name)
-}
+#[path = "fixture_support.rs"]
+mod fixture_support;
 
-fn project_for(name: &str) -> Project {
-    let root = fixture_root(name);
-    let paths = ProjectPaths::from_parts(root, None, None, None, None, None, None);
-    Project::from_paths(paths).expect("build project")
-}
+use fixture_support::java_project;
 
 fn parse_fixture(path: &str) -> Parser {
     let full = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path);
@@ -65,7 +57,7 @@ fn control_flow_queries_cover_if_for_while() {
 
 #[test]
 fn query_grader_exact_count_succeeds() {
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     let parser = parse_fixture("fixtures/java/query-cases/src/query/Example.java");
     let invocation_query = "((method_invocation (identifier) @body))";
     let expected_count = parser
@@ -93,7 +85,7 @@ fn query_grader_exact_count_succeeds() {
 
 #[test]
 fn query_grader_exact_count_fails_and_sets_prompt() {
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     let grader = QueryGrader::builder()
         .req_name("q2")
         .out_of(5.0)
@@ -115,7 +107,7 @@ fn query_grader_exact_count_fails_and_sets_prompt() {
 
 #[test]
 fn query_grader_filter_predicate_applies() {
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     let filtered = Query::new()
         .set_query(
             "((local_variable_declaration declarator: (variable_declarator name: (identifier) \
@@ -142,7 +134,7 @@ fn query_grader_filter_predicate_applies() {
 
 #[test]
 fn query_grader_must_not_match_passes_on_zero() {
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     let grader = QueryGrader::builder()
         .req_name("q4")
         .out_of(3.0)
@@ -165,7 +157,7 @@ fn query_grader_must_not_match_passes_on_zero() {
 
 #[test]
 fn query_grader_must_not_match_fails_when_present() {
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     let grader = QueryGrader::builder()
         .req_name("q5")
         .out_of(3.0)
@@ -187,7 +179,7 @@ fn query_grader_must_not_match_fails_when_present() {
 
 #[test]
 fn query_grader_at_least_once_fails_when_zero() {
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     let grader = QueryGrader::builder()
         .req_name("q6")
         .out_of(2.0)
@@ -210,7 +202,7 @@ fn query_grader_at_least_once_fails_when_zero() {
 
 #[test]
 fn query_grader_chained_queries_operate_on_matches() {
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     // First query: get the body of foo; second: find if_statement inside that body.
     let q1 = Query::new()
         .set_query(format!(include_str!("../src/java/queries/method_body_with_name.scm"), "foo"))
@@ -236,7 +228,7 @@ fn query_grader_chained_queries_operate_on_matches() {
 
 #[test]
 fn query_grader_errors_when_capture_missing() {
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     let grader = QueryGrader::builder()
         .req_name("q8")
         .out_of(1.0)
@@ -255,7 +247,7 @@ fn query_grader_errors_when_capture_missing() {
 
 #[test]
 fn query_grader_rejects_unknown_file() {
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     let result = QueryGrader::builder()
         .req_name("q9")
         .out_of(1.0)
@@ -278,7 +270,7 @@ fn query_grader_rejects_unknown_file() {
 
 #[test]
 fn query_grader_nested_queries_yield_no_matches_propagates_error() {
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     // First query matches foo body; second intentionally looks for a capture that
     // won't exist.
     let q1 = Query::new()
@@ -306,7 +298,7 @@ fn query_grader_nested_queries_yield_no_matches_propagates_error() {
 #[test]
 fn query_grader_multiple_files_independent() {
     // Use the same project but switch files to confirm independent selection.
-    let project = project_for("query-cases");
+    let project = java_project("query-cases");
     // First grader on Example succeeds.
     let g1 = QueryGrader::builder()
         .req_name("g1")
@@ -341,3 +333,620 @@ fn query_grader_multiple_files_independent() {
     let g2 = g2.grade_by_query().expect("grade");
     assert_eq!(g2.grade_value(), 0.0);
 }
+
+fn modifier_grader(file: &str, class_name: &str, modifier: &str) -> QueryGrader {
+    QueryGrader::builder()
+        .req_name("modifiers")
+        .out_of(2.0)
+        .project(java_project("modifiers"))
+        .file(file)
+        .build()
+        .class_has_modifier(class_name.into(), modifier.into())
+}
+
+#[test]
+fn class_has_modifier_passes_when_final_present() {
+    let grade = modifier_grader("Square", "Square", "final")
+        .run()
+        .expect("grade");
+
+    assert_eq!(grade.grade_value(), 2.0);
+    assert_eq!(grade.reason(), "Class `Square` is declared `final`.");
+}
+
+#[test]
+fn class_has_modifier_fails_and_reports_actual_modifiers() {
+    let grade = modifier_grader("Shape", "Shape", "final")
+        .run()
+        .expect("grade");
+
+    assert_eq!(grade.grade_value(), 0.0);
+    assert_eq!(
+        grade.reason(),
+        "Class `Shape` must be declared `final` (declared modifiers: `public abstract`)."
+    );
+}
+
+#[test]
+fn class_has_modifier_fails_without_any_modifiers() {
+    let grade = modifier_grader("Circle", "Circle", "final")
+        .run()
+        .expect("grade");
+
+    assert_eq!(grade.grade_value(), 0.0);
+    assert!(grade.reason().contains("no modifiers"), "reason: {}", grade.reason());
+}
+
+#[test]
+fn class_has_modifier_matches_abstract() {
+    let grade = modifier_grader("Shape", "Shape", "abstract")
+        .run()
+        .expect("grade");
+
+    assert_eq!(grade.grade_value(), 2.0);
+    assert_eq!(grade.reason(), "Class `Shape` is declared `abstract`.");
+}
+
+fn constructor_grader(param_types: &[&str]) -> QueryGrader {
+    QueryGrader::builder()
+        .req_name("constructor")
+        .out_of(3.0)
+        .project(java_project("constructors"))
+        .file("Person")
+        .build()
+        .constructor_with_params(param_types.iter().map(|t| t.to_string()).collect())
+}
+
+#[test]
+fn constructor_with_params_matches_exact_types() {
+    let grade = constructor_grader(&["String", "int"]).run().expect("grade");
+    assert_eq!(grade.grade_value(), 3.0);
+
+    let grade = constructor_grader(&["String"]).run().expect("grade");
+    assert_eq!(grade.grade_value(), 3.0, "`final` should be ignored");
+
+    let grade = constructor_grader(&["Map<String,Integer>"])
+        .run()
+        .expect("grade");
+    assert_eq!(grade.grade_value(), 3.0, "whitespace in generics should be ignored");
+
+    let grade = constructor_grader(&[]).run().expect("grade");
+    assert_eq!(grade.grade_value(), 3.0, "no-arg constructor should match");
+}
+
+#[test]
+fn constructor_with_params_rejects_wrong_order_and_lists_declared() {
+    let grade = constructor_grader(&["int", "String"]).run().expect("grade");
+
+    assert_eq!(grade.grade_value(), 0.0);
+    assert_eq!(
+        grade.reason(),
+        "Expected a constructor taking (int, String) in `Person` (declared constructors: (), \
+         (String), (String, int), (Map<String, Integer>))."
+    );
+}
+
+#[test]
+fn constructor_with_params_reads_trailing_dimensions_and_annotated_params() {
+    let grid = |param_types: &[&str]| {
+        QueryGrader::builder()
+            .req_name("constructor")
+            .out_of(1.0)
+            .project(java_project("constructors"))
+            .file("Grid")
+            .build()
+            .constructor_with_params(param_types.iter().map(|t| t.to_string()).collect())
+            .run()
+            .expect("grade")
+    };
+
+    // `Grid(int cells[])` declares an `int[]`, not an `int`.
+    assert_eq!(grid(&["int[]"]).grade_value(), 1.0);
+    assert_eq!(grid(&["int"]).grade_value(), 0.0);
+
+    let annotated = grid(&["int[]", "String"]);
+    assert_eq!(annotated.grade_value(), 1.0, "the annotation should be ignored");
+    assert_eq!(grid(&["String", "int..."]).grade_value(), 1.0);
+
+    let missing = grid(&["long"]);
+    assert!(
+        missing
+            .reason()
+            .ends_with("(declared constructors: (int[]), (int[], String), (String, int...))."),
+        "{}",
+        missing.reason()
+    );
+}
+
+fn query_dir(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join("queries")
+        .join(name)
+}
+
+#[test]
+fn query_library_loads_and_runs_external_queries() {
+    let library = QueryLibrary::load(query_dir("course")).expect("load queries");
+    assert_eq!(library.names(), vec!["sum_variable", "while_loops"]);
+
+    let grader = QueryGrader::builder()
+        .req_name("external")
+        .out_of(2.0)
+        .project(java_project("query-cases"))
+        .file("query.Example")
+        .constraint(QueryConstraint::MustMatchExactlyNTimes(1))
+        .reason("expected one while loop")
+        .build()
+        .named_query(&library, "while_loops")
+        .expect("known query")
+        .capture("loop".into())
+        .expect("capture");
+
+    let result = grader.grade_by_query().expect("grade");
+    assert_eq!(result.grade_value(), 2.0);
+}
+
+#[test]
+fn query_library_rejects_invalid_queries_at_load_time() {
+    let err = QueryLibrary::load(query_dir("broken")).expect_err("typo should fail");
+    let message = format!("{err:#}");
+    assert!(message.contains("typo.scm"), "unexpected error: {message}");
+}
+
+#[test]
+fn query_library_reports_unknown_names() {
+    let library = QueryLibrary::load(query_dir("course")).expect("load queries");
+    let Err(err) = QueryGrader::builder()
+        .req_name("external")
+        .out_of(1.0)
+        .project(java_project("query-cases"))
+        .file("query.Example")
+        .build()
+        .named_query(&library, "for_loops")
+    else {
+        panic!("unknown query should be rejected");
+    };
+
+    assert!(matches!(err, QueryError::UnknownNamedQuery { .. }));
+    assert!(err.to_string().contains("sum_variable, while_loops"));
+}
+
+fn recursion_grade(method: &str, forbid: bool) -> f64 {
+    let grader = QueryGrader::builder()
+        .req_name("recursion")
+        .out_of(1.0)
+        .project(java_project("query-cases"))
+        .file("query.Factorials")
+        .build();
+    let grader = if forbid {
+        grader.must_not_use_recursion(method.into())
+    } else {
+        grader.uses_recursion(method.into())
+    };
+    grader.grade_by_query().expect("grade").grade_value()
+}
+
+#[test]
+fn uses_recursion_detects_self_calls() {
+    assert_eq!(recursion_grade("recursive", false), 1.0);
+    assert_eq!(recursion_grade("viaThis", false), 1.0);
+    assert_eq!(recursion_grade("iterative", false), 0.0);
+    assert_eq!(recursion_grade("delegating", false), 0.0);
+}
+
+#[test]
+fn must_not_use_recursion_rejects_recursive_methods() {
+    assert_eq!(recursion_grade("recursive", true), 0.0);
+    assert_eq!(recursion_grade("iterative", true), 1.0);
+    assert_eq!(recursion_grade("delegating", true), 1.0);
+    assert_eq!(recursion_grade("missing", true), 0.0);
+}
+
+fn base_case_grade(method: &str) -> (f64, String) {
+    let result = QueryGrader::builder()
+        .req_name("base case")
+        .out_of(1.0)
+        .project(java_project("query-cases"))
+        .file("query.Factorials")
+        .build()
+        .recursion_has_base_case(method.into())
+        .grade_by_query()
+        .expect("grade");
+    (result.grade_value(), result.reason().to_string())
+}
+
+#[test]
+fn recursion_has_base_case_accepts_guarded_recursion() {
+    for method in ["recursive", "viaThis", "countdown", "iterative"] {
+        let (grade, reason) = base_case_grade(method);
+        assert_eq!(grade, 1.0, "{method}: {reason}");
+    }
+    let (_, reason) = base_case_grade("recursive");
+    assert_eq!(reason, "Recursive method `recursive` must have a base case.");
+}
+
+#[test]
+fn recursion_has_base_case_flags_missing_base_case() {
+    let (grade, reason) = base_case_grade("noBaseCase");
+    assert_eq!(grade, 0.0);
+    assert_eq!(
+        reason,
+        "Recursive method `noBaseCase` must have a base case that returns without calling \
+         `noBaseCase`, but none was found, so it may recurse forever."
+    );
+
+    let (grade, reason) = base_case_grade("missing");
+    assert_eq!(grade, 0.0);
+    assert!(reason.contains("no method named `missing` was found"), "reason: {reason}");
+}
+
+fn calls_grade(caller: &str, callee: &str) -> (f64, String) {
+    let result = QueryGrader::builder()
+        .req_name("calls")
+        .out_of(1.0)
+        .project(java_project("query-cases"))
+        .file("query.Sorter")
+        .build()
+        .method_calls_method(caller.into(), callee.into())
+        .grade_by_query()
+        .expect("grade");
+    (result.grade_value(), result.reason().to_string())
+}
+
+#[test]
+fn method_calls_method_passes_when_caller_calls_callee() {
+    let (grade, reason) = calls_grade("bubbleSort", "swap");
+    assert_eq!(grade, 1.0, "reason: {reason}");
+    assert_eq!(reason, "Method `bubbleSort` must call `swap`.");
+
+    let (grade, reason) = calls_grade("librarySort", "sort");
+    assert_eq!(grade, 1.0, "calls on a receiver count: {reason}");
+}
+
+#[test]
+fn method_calls_method_reports_a_missing_call() {
+    let (grade, reason) = calls_grade("librarySort", "swap");
+    assert_eq!(grade, 0.0);
+    assert_eq!(reason, "Method `librarySort` must call `swap`, but it does not.");
+}
+
+#[test]
+fn method_calls_method_reports_a_missing_caller() {
+    let (grade, reason) = calls_grade("quickSort", "swap");
+    assert_eq!(grade, 0.0);
+    assert_eq!(
+        reason,
+        "Method `quickSort` must call `swap`, but no method named `quickSort` was found."
+    );
+}
+
+fn hardcoded_grade(file: &str) -> f64 {
+    QueryGrader::builder()
+        .req_name("hardcoded")
+        .out_of(1.0)
+        .project(java_project("query-cases"))
+        .file(file)
+        .build()
+        .must_not_hardcode_output()
+        .grade_by_query()
+        .expect("grade")
+        .grade_value()
+}
+
+#[test]
+fn must_not_hardcode_output_flags_print_only_main() {
+    assert_eq!(hardcoded_grade("query.Hardcoded"), 0.0);
+    assert_eq!(hardcoded_grade("query.Computed"), 1.0);
+    assert_eq!(hardcoded_grade("query.Delegating"), 1.0);
+}
+
+#[test]
+fn must_not_hardcode_output_ignores_annotations_and_passes_without_main() {
+    assert_eq!(hardcoded_grade("query.AnnotatedHardcoded"), 0.0);
+    assert_eq!(hardcoded_grade("query.Library"), 1.0);
+}
+
+fn switch_grader(file: &str, arrow_only: bool) -> QueryGrader {
+    let grader = QueryGrader::builder()
+        .req_name("switch")
+        .out_of(1.0)
+        .project(java_project("query-cases"))
+        .file(file)
+        .build();
+    if arrow_only {
+        grader.uses_switch_expression()
+    } else {
+        grader.uses_switch()
+    }
+}
+
+#[test]
+fn uses_switch_accepts_classic_and_arrow_forms() {
+    for file in ["query.ClassicSwitch", "query.ArrowSwitch"] {
+        let result = switch_grader(file, false).grade_by_query().expect("grade");
+        assert_eq!(result.grade_value(), 1.0, "{file}: {}", result.reason());
+    }
+
+    let missing = switch_grader("query.Computed", false)
+        .grade_by_query()
+        .expect("grade");
+    assert_eq!(missing.grade_value(), 0.0);
+    assert_eq!(missing.reason(), "`query.Computed` must use a `switch` (found: no switch).");
+}
+
+#[test]
+fn uses_switch_expression_requires_arrow_form() {
+    let arrow = switch_grader("query.ArrowSwitch", true)
+        .grade_by_query()
+        .expect("grade");
+    assert_eq!(arrow.grade_value(), 1.0);
+
+    let classic = switch_grader("query.ClassicSwitch", true)
+        .grade_by_query()
+        .expect("grade");
+    assert_eq!(classic.grade_value(), 0.0);
+    assert!(
+        classic
+            .reason()
+            .contains("(found: only a classic `case ...:` switch)"),
+        "reason: {}",
+        classic.reason()
+    );
+}
+
+#[test]
+fn query_grader_reports_malformed_query_instead_of_panicking() {
+    let project = java_project("query-cases");
+    let grader = QueryGrader::builder()
+        .req_name("q-bad")
+        .out_of(2.0)
+        .project(project.clone())
+        .file("query.Example")
+        .queries(vec![
+            Query::new()
+                .set_query("((method_invocation (identifier) @body)".into())
+                .set_capture("body".into()),
+        ])
+        .constraint(QueryConstraint::MustMatchAtLeastOnce)
+        .reason("malformed query")
+        .build();
+
+    match grader.run_query() {
+        Err(QueryError::DuringQueryExecution { q, e }) => {
+            assert_eq!(q, "((method_invocation (identifier) @body)");
+            assert!(e.starts_with("Syntax error at line 1"), "error: {e}");
+        }
+        other => panic!("expected DuringQueryExecution, got {other:?}"),
+    }
+
+    let result = grader.grade_by_query().expect("grade");
+    assert_eq!(result.grade_value(), 0.0);
+}
+
+#[test]
+fn file_import_helpers_report_violations_with_lines() {
+    let project = java_project("imports");
+    let reader = project.identify("Reader").expect("identify Reader");
+
+    let imports = reader.import_declarations().expect("imports");
+    let listed: Vec<_> = imports
+        .iter()
+        .map(|i| (i.path.as_str(), i.line, i.is_static))
+        .collect();
+    assert_eq!(
+        listed,
+        [
+            ("java.util.*", 1, false),
+            ("java.io.BufferedReader", 2, false),
+            ("java.lang.Math.max", 3, true),
+            ("com.google.gson.Gson", 4, false),
+            ("org.apache.commons.lang3.StringUtils", 5, false),
+        ]
+    );
+
+    let scanner = reader
+        .forbidden_imports("java.util.Scanner")
+        .expect("scanner");
+    assert_eq!(scanner.len(), 1);
+    assert_eq!(scanner[0].to_string(), "`import java.util.*;` on line 1");
+    assert!(
+        reader
+            .forbidden_imports("java.util.function.Function")
+            .expect("function")
+            .is_empty()
+    );
+    assert_eq!(
+        reader
+            .forbidden_imports("org.apache.*")
+            .expect("apache")
+            .len(),
+        1
+    );
+
+    let outside: Vec<_> = reader
+        .non_stdlib_imports()
+        .expect("non-stdlib")
+        .into_iter()
+        .map(|i| i.line)
+        .collect();
+    assert_eq!(outside, [4, 5]);
+}
+
+fn import_grader(file: &str) -> QueryGrader {
+    QueryGrader::builder()
+        .req_name("imports")
+        .out_of(1.0)
+        .project(java_project("imports"))
+        .file(file)
+        .build()
+}
+
+#[test]
+fn must_not_import_names_the_violating_import() {
+    let result = import_grader("Reader")
+        .must_not_import("java.util.Scanner".into())
+        .grade_by_query()
+        .expect("grade");
+    assert_eq!(result.grade_value(), 0.0);
+    assert_eq!(
+        result.reason(),
+        "`Reader` must not import `java.util.Scanner` (found: `import java.util.*;` on line 1)."
+    );
+
+    let result = import_grader("Plain")
+        .must_not_import("java.util.Scanner".into())
+        .grade_by_query()
+        .expect("grade");
+    assert_eq!(result.grade_value(), 1.0);
+    assert_eq!(result.reason(), "`Plain` must not import `java.util.Scanner`.");
+
+    let result = import_grader("NoImports")
+        .must_not_import("java.util.Scanner".into())
+        .grade_by_query()
+        .expect("grade");
+    assert_eq!(result.grade_value(), 1.0, "{}", result.reason());
+}
+
+#[test]
+fn only_stdlib_imports_flags_third_party_imports() {
+    let result = import_grader("Reader")
+        .only_stdlib_imports()
+        .grade_by_query()
+        .expect("grade");
+    assert_eq!(result.grade_value(), 0.0);
+    assert!(
+        result
+            .reason()
+            .contains("`import com.google.gson.Gson;` on line 4"),
+        "reason: {}",
+        result.reason()
+    );
+    assert!(
+        result
+            .reason()
+            .contains("`import org.apache.commons.lang3.StringUtils;` on line 5")
+    );
+
+    let result = import_grader("Plain")
+        .only_stdlib_imports()
+        .grade_by_query()
+        .expect("grade");
+    assert_eq!(result.grade_value(), 1.0);
+}
+
+#[test]
+fn query_grader_reports_syntax_errors_instead_of_not_matching() {
+    let result = QueryGrader::builder()
+        .req_name("no-loops")
+        .out_of(2.0)
+        .project(java_project("syntax-error"))
+        .file("Broken")
+        .queries(vec![
+            Query::new()
+                .set_query("((for_statement) @loop)".into())
+                .set_capture("loop".into()),
+        ])
+        .constraint(QueryConstraint::MustNotMatch)
+        .reason("should not use loops")
+        .build()
+        .grade_by_query()
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 0.0);
+    let reason = result.reason();
+    assert!(
+        reason.starts_with("`Broken.java` has syntax errors, so it could not be graded (line 3"),
+        "reason: {reason}"
+    );
+}
+
+#[test]
+fn query_graders_run_concurrently_without_a_global_lock() {
+    let project = java_project("query-cases");
+    let (done_tx, done_rx) = std::sync::mpsc::channel();
+
+    for keep in ["sum", "i"] {
+        let project = project.clone();
+        let done_tx = done_tx.clone();
+        std::thread::spawn(move || {
+            for _ in 0..20 {
+                let filtered = Query::new()
+                    .set_query(
+                        "((local_variable_declaration declarator: (variable_declarator name: \
+                         (identifier) @var)))"
+                            .into(),
+                    )
+                    .set_capture("var".into())
+                    .set_filter_fn(move |v| v == keep);
+                let grader = QueryGrader::builder()
+                    .req_name(keep)
+                    .out_of(1.0)
+                    .project(project.clone())
+                    .file("query.Example")
+                    .queries(vec![filtered])
+                    .constraint(QueryConstraint::MustMatchExactlyNTimes(1))
+                    .reason("should keep one variable")
+                    .build()
+                    .grade_by_query()
+                    .expect("grade");
+                assert_eq!(grader.grade_value(), 1.0, "reason: {}", grader.reason());
+            }
+            done_tx.send(keep).expect("report completion");
+        });
+    }
+    drop(done_tx);
+
+    for _ in 0..2 {
+        done_rx
+            .recv_timeout(std::time::Duration::from_secs(60))
+            .expect("both graders finish without deadlocking");
+    }
+}
+
+fn annotation_grader(file: &str, target: &str, annotation: &str) -> QueryGrader {
+    QueryGrader::builder()
+        .req_name("annotations")
+        .out_of(1.0)
+        .project(java_project("annotations"))
+        .file(file)
+        .build()
+        .has_annotation(target.into(), annotation.into())
+}
+
+#[test]
+fn has_annotation_passes_on_interface_and_method() {
+    for (file, target, annotation) in [
+        ("Shape", "Shape", "FunctionalInterface"),
+        ("Circle", "area", "@Override"),
+        ("Circle", "radius", "Deprecated"),
+    ] {
+        let grade = annotation_grader(file, target, annotation)
+            .run()
+            .expect("grade");
+        assert_eq!(grade.grade_value(), 1.0, "{target}: {}", grade.reason());
+    }
+}
+
+#[test]
+fn has_annotation_fails_and_reports_found_annotations() {
+    let grade = annotation_grader("Circle", "Circle", "Deprecated")
+        .run()
+        .expect("grade");
+
+    assert_eq!(grade.grade_value(), 0.0);
+    assert_eq!(
+        grade.reason(),
+        "`Circle` must be annotated `@Deprecated` (annotations found: `@SuppressWarnings`)."
+    );
+}
+
+#[test]
+fn has_annotation_fails_on_unannotated_method() {
+    let grade = annotation_grader("Circle", "diameter", "Override")
+        .run()
+        .expect("grade");
+
+    assert_eq!(grade.grade_value(), 0.0);
+    assert!(grade.reason().contains("no annotations found"), "reason: {}", grade.reason());
+}
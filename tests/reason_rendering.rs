@@ -0,0 +1,75 @@
+use umm::java::grade::{Grade, GradeResult, Reason, ReasonTarget};
+
+fn mismatches() -> Reason {
+    Reason::new()
+        .summary("Tests will not be run until the following is fixed:")
+        .point("squaresNegatives not found.")
+        .point("Unexpected test called squaresSmallNumbers")
+}
+
+#[test]
+fn terminal_rendering_is_a_plain_list() {
+    assert_eq!(
+        mismatches().render(ReasonTarget::Terminal),
+        "Tests will not be run until the following is fixed:\n- squaresNegatives not found.\n- \
+         Unexpected test called squaresSmallNumbers"
+    );
+}
+
+#[test]
+fn markdown_rendering_separates_headline_from_list() {
+    assert_eq!(
+        mismatches().render(ReasonTarget::Markdown),
+        "**Tests will not be run until the following is fixed:**\n\n- squaresNegatives not \
+         found.\n- Unexpected test called squaresSmallNumbers"
+    );
+}
+
+#[test]
+fn multi_line_points_stay_under_their_bullet() {
+    let reason = Reason::new()
+        .point("Case 1: FAILED\n-expected\n+actual")
+        .point("Case 2: PASSED");
+
+    assert_eq!(
+        reason.render(ReasonTarget::Terminal),
+        "- Case 1: FAILED\n  -expected\n  +actual\n- Case 2: PASSED"
+    );
+    // Fenced, so the diff lines are not read as a nested list.
+    assert_eq!(
+        reason.render(ReasonTarget::Markdown),
+        "- Case 1: FAILED\n\n  ```\n  -expected\n  +actual\n  ```\n- Case 2: PASSED"
+    );
+}
+
+#[test]
+fn single_point_renders_bare() {
+    let reason = Reason::new().point("Query 1: Expected no matches, found 2 matches");
+    assert_eq!(
+        reason.render(ReasonTarget::Markdown),
+        "Query 1: Expected no matches, found 2 matches"
+    );
+    assert_eq!(reason.to_string(), "Query 1: Expected no matches, found 2 matches");
+}
+
+#[test]
+fn grade_result_falls_back_to_plain_reason() {
+    let plain = GradeResult::builder()
+        .requirement("plain")
+        .grade(Grade::new(1.0, 2.0))
+        .reason("Half credit")
+        .build();
+    assert_eq!(plain.reason_as(ReasonTarget::Markdown), "Half credit");
+
+    let structured = GradeResult::builder()
+        .requirement("structured")
+        .grade(Grade::new(0.0, 2.0))
+        .reason(mismatches().render(ReasonTarget::Terminal))
+        .details(mismatches())
+        .build();
+    assert_eq!(structured.reason(), mismatches().render(ReasonTarget::Terminal));
+    assert_eq!(
+        structured.reason_as(ReasonTarget::Markdown),
+        mismatches().render(ReasonTarget::Markdown)
+    );
+}
@@ -0,0 +1,96 @@
+use std::{fs, path::PathBuf, process::Command};
+
+use umm::java::{Project, paths::project_paths};
+use uuid::Uuid;
+
+const GREETER: &str = r#"public class Greeter {
+    public static String greet(String name) {
+        return "Hello, " + name + "!";
+    }
+}
+"#;
+
+/// Compiles the reference `Greeter` class and packages it as a jar under a
+/// fresh temp directory, returning `(temp dir, jar path)`.
+fn reference_jar() -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("umm-reference-{}", Uuid::new_v4()));
+    let classes = dir.join("classes");
+    fs::create_dir_all(&classes).expect("create classes dir");
+    let source = dir.join("Greeter.java");
+    fs::write(&source, GREETER).expect("write reference source");
+
+    let status = Command::new("javac")
+        .arg("-d")
+        .arg(&classes)
+        .arg(&source)
+        .status()
+        .expect("spawn javac");
+    assert!(status.success(), "javac failed for reference class");
+
+    let jar = dir.join("greeter.jar");
+    let status = Command::new("jar")
+        .arg("cf")
+        .arg(&jar)
+        .arg("-C")
+        .arg(&classes)
+        .arg(".")
+        .status()
+        .expect("spawn jar");
+    assert!(status.success(), "jar failed for reference class");
+
+    (dir, jar)
+}
+
+fn project(build_dir: PathBuf, reference: Option<PathBuf>) -> Project {
+    let paths = project_paths()
+        .root_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/reference-classes"))
+        .build_dir(build_dir)
+        .build()
+        .with_reference_classes(reference);
+    Project::from_paths(paths).expect("load project")
+}
+
+#[tokio::test]
+async fn student_class_runs_against_reference_jar() {
+    let (dir, jar) = reference_jar();
+    let source_build = dir.join("source-target");
+    let reference_build = dir.join("reference-target");
+
+    // Without reference classes, the shipped `Greeter` source is compiled.
+    let output = project(source_build.clone(), None)
+        .identify("Main")
+        .expect("identify Main")
+        .run(None)
+        .await
+        .expect("run against shipped source");
+    assert_eq!(output.trim(), "Compiled from source, student!");
+    assert!(source_build.join("Greeter.class").exists());
+
+    let output = project(reference_build.clone(), Some(jar))
+        .identify("Main")
+        .expect("identify Main")
+        .run(None)
+        .await
+        .expect("run against reference jar");
+    assert_eq!(output.trim(), "Hello, student!");
+
+    // Only the student's file is compiled; `Greeter` comes from the jar even
+    // though its source sits next to `Main`.
+    assert!(reference_build.join("Main.class").exists());
+    assert!(!reference_build.join("Greeter.class").exists());
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn missing_reference_entry_is_reported() {
+    let paths = project_paths()
+        .root_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/reference-classes"))
+        .build()
+        .with_reference_classes([PathBuf::from("does/not/exist.jar")]);
+    let err = umm::java::util::classpath(&paths).expect_err("missing entry should fail");
+    assert!(
+        err.to_string()
+            .contains("Reference classes entry does not exist")
+    );
+}
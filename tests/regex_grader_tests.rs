@@ -0,0 +1,100 @@
+use umm::java::grade::RegexGrader;
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+#[tokio::test]
+async fn regex_passes_when_output_contains_a_match() {
+    let result = RegexGrader::builder()
+        .req_name("contains")
+        .out_of(2.0)
+        .project(java_project("diff-ok"))
+        .file("Main")
+        .cases(vec![(r"wor\w+", None::<String>)])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 2.0, "reason: {}", result.reason());
+}
+
+#[tokio::test]
+async fn regex_reports_first_non_matching_case() {
+    let result = RegexGrader::builder()
+        .req_name("stdin")
+        .out_of(1.0)
+        .project(java_project("diff-stdin"))
+        .file("Main")
+        .cases(vec![
+            (r"^Total: \d+$", Some("Total: 42")),
+            (r"^Total: \d+$", Some("Total: many")),
+        ])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    assert_eq!(result.grade_value(), 0.0);
+    let reason = result.reason();
+    assert!(reason.starts_with("Case 2 (input: `Total: many`)"), "reason: {reason}");
+    assert!(reason.contains("did not contain a match for"), "reason: {reason}");
+    assert!(reason.contains("got \"Total: many\""), "reason: {reason}");
+    assert!(result.prompt().is_some());
+}
+
+#[tokio::test]
+async fn regex_full_match_requires_the_whole_output() {
+    let grade = |full: bool| async move {
+        RegexGrader::builder()
+            .req_name("full")
+            .out_of(1.0)
+            .project(java_project("diff-ok"))
+            .file("Main")
+            .cases(vec![("hello", None::<String>)])
+            .full_match(full)
+            .build()
+            .run()
+            .await
+            .expect("grade")
+    };
+
+    assert_eq!(grade(false).await.grade_value(), 1.0);
+    let strict = grade(true).await;
+    assert_eq!(strict.grade_value(), 0.0);
+    assert!(
+        strict.reason().contains("output did not match `hello`"),
+        "reason: {}",
+        strict.reason()
+    );
+}
+
+#[tokio::test]
+async fn regex_rejects_invalid_patterns_before_running() {
+    let err = RegexGrader::builder()
+        .req_name("bad")
+        .out_of(1.0)
+        .project(java_project("diff-ok"))
+        .file("Main")
+        .cases(vec![("(unclosed", None::<String>)])
+        .build()
+        .run()
+        .await
+        .expect_err("invalid pattern");
+
+    assert!(err.to_string().contains("Invalid pattern for case 1"), "{err}");
+}
+
+#[tokio::test]
+async fn regex_errors_when_no_cases() {
+    let grader = RegexGrader::builder()
+        .req_name("empty")
+        .out_of(1.0)
+        .project(java_project("diff-ok"))
+        .file("Main")
+        .build();
+
+    assert!(grader.run().await.is_err(), "expected missing cases error");
+}
@@ -0,0 +1,42 @@
+use umm::java::{grade::tests::ByUnitTestGrader, project::Project};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project_with_jars;
+
+fn project() -> Project {
+    java_project_with_jars("repeated-test")
+}
+
+#[test]
+fn repeated_tests_are_discovered_once_with_their_count() -> anyhow::Result<()> {
+    let file = project().identify("CounterTest")?;
+    let mut methods = file.test_methods();
+    methods.sort();
+
+    // `@Timeout(5)` alongside `@Test` must not list `countsOnce` twice.
+    assert_eq!(methods, vec!["CounterTest#countsOnce", "CounterTest#countsRepeatedly"]);
+    assert_eq!(file.test_repetitions("CounterTest#countsRepeatedly"), 4);
+    assert_eq!(file.test_repetitions("CounterTest#countsOnce"), 1);
+    assert_eq!(file.expected_test_runs(), 5);
+    Ok(())
+}
+
+#[tokio::test]
+async fn repeated_test_repetitions_are_counted_individually() -> anyhow::Result<()> {
+    let result = ByUnitTestGrader::builder()
+        .project(project())
+        .test_files(["CounterTest"])
+        .expected_tests(["CounterTest#countsOnce", "CounterTest#countsRepeatedly"])
+        .req_name("repeated")
+        .out_of(5.0)
+        .build()
+        .run()
+        .await?;
+
+    // One plain test plus four repetitions, of which the fourth fails.
+    assert_eq!(result.reason(), "- 4/5 tests passing.");
+    assert_eq!(result.grade_value(), 4.0);
+    Ok(())
+}
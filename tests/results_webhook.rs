@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use umm::java::grade::{Grade, GradeResult, ResultsWebhook, WebhookPayload};
+
+#[path = "http_support.rs"]
+mod http_support;
+
+use http_support::{MockServer, respond};
+
+/// Answers the webhook's attempts with `statuses`, in order.
+fn mock_webhook(statuses: &'static [u16]) -> MockServer {
+    let attempt = AtomicUsize::new(0);
+    MockServer::start(move |_, stream| {
+        respond(stream, statuses[attempt.fetch_add(1, Ordering::SeqCst)], "")
+    })
+}
+
+fn results() -> Vec<GradeResult> {
+    vec![
+        GradeResult::builder()
+            .requirement("compiles")
+            .grade(Grade::new(5.0, 5.0))
+            .reason("Compiled cleanly")
+            .build(),
+        GradeResult::builder()
+            .requirement("tests")
+            .grade(Grade::new(3.0, 10.0))
+            .reason("7 tests failed")
+            .build(),
+    ]
+}
+
+#[tokio::test]
+async fn webhook_posts_results_with_auth_header_after_retrying() {
+    let mock = mock_webhook(&[500, 200]);
+    let webhook = ResultsWebhook::builder()
+        .url(format!("{}/grades", mock.url))
+        .auth_header("Bearer secret-token")
+        .build();
+
+    assert!(webhook.send(&results()).await, "second attempt should be delivered");
+
+    let requests = mock.requests.lock().unwrap();
+    let [first, second] = &requests[..] else {
+        panic!("expected two attempts, got {requests:?}");
+    };
+    assert!(first.request_line().starts_with("POST /grades "), "{first:?}");
+    assert_eq!(first.body, second.body);
+    assert!(second.has_header("authorization", "Bearer secret-token"), "{second:?}");
+
+    let payload: WebhookPayload = serde_json::from_str(&second.body).expect("json payload");
+    assert_eq!(payload, WebhookPayload::new(&results()));
+    assert_eq!((payload.grade, payload.out_of), (8.0, 15.0));
+    assert_eq!(payload.results[1].requirement, "tests");
+    assert_eq!(payload.results[1].reason, "7 tests failed");
+}
+
+#[tokio::test]
+async fn webhook_failures_do_not_error() {
+    let mock = mock_webhook(&[503, 503]);
+    let webhook = ResultsWebhook::builder()
+        .url(format!("{}/grades", mock.url))
+        .attempts(2)
+        .build();
+
+    assert!(!webhook.send(&results()).await);
+    assert_eq!(mock.requests.lock().unwrap().len(), 2, "each attempt should reach the webhook");
+}
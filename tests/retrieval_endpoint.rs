@@ -0,0 +1,28 @@
+use umm::{config, java::grade::build_active_retrieval_context};
+
+#[path = "retrieval_support.rs"]
+mod retrieval_support;
+
+use retrieval_support::{mock_retrieval, picker_project};
+
+#[test]
+fn active_retrieval_posts_to_the_configured_endpoint() -> anyhow::Result<()> {
+    let mock = mock_retrieval();
+    // Configuration is read once per process, so set it before first use.
+    unsafe {
+        std::env::set_var("UMM_RETRIEVAL_ENDPOINT", format!("{}/retrieve", mock.url));
+        std::env::remove_var("UMM_RETRIEVAL_BACKEND");
+    }
+    config::set_active_retrieval(true);
+
+    let message = build_active_retrieval_context(&picker_project(), "1 test failed".into(), None)?;
+
+    let requests = mock.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].request_line().starts_with("POST /retrieve "), "{:?}", requests[0]);
+    assert!(requests[0].body.contains("1 test failed"), "{:?}", requests[0]);
+
+    let message = serde_json::to_string(&message)?;
+    assert!(message.contains("`Picker#pick`"), "message was: {message}");
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use umm::{
+    java::{grade::get_source_context, project::Project},
+    retrieval::{DEFAULT_MAX_LINE_REFS, HeuristicConfig, build_context_message_with_config},
+    types::LineRef,
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("placeholders")
+}
+
+/// Diagnostics far enough apart that their windows never merge.
+fn line_refs() -> Vec<LineRef> {
+    [3, 10, 18, 26]
+        .into_iter()
+        .map(|line_number| LineRef {
+            line_number,
+            file_name: "Stack".to_string(),
+        })
+        .collect()
+}
+
+fn config(max_line_refs: usize) -> HeuristicConfig {
+    HeuristicConfig {
+        start_offset: 0,
+        num_lines: 1,
+        max_line_refs,
+        full_file_ratio: 1.0,
+        include_declarations: false,
+    }
+}
+
+fn snippet_count(message: impl serde::Serialize) -> usize {
+    let message = serde_json::to_value(message).expect("serialize message");
+    let content = message["content"].as_str().expect("text content");
+    content.matches("- Lines ").count()
+}
+
+#[test]
+fn max_line_refs_caps_rendered_snippets() -> anyhow::Result<()> {
+    assert_eq!(HeuristicConfig::default().max_line_refs, DEFAULT_MAX_LINE_REFS);
+
+    let capped = get_source_context(line_refs(), project(), config(2), false, None)?;
+    assert_eq!(snippet_count(capped), 2);
+
+    let uncapped = get_source_context(line_refs(), project(), config(10), false, None)?;
+    assert_eq!(snippet_count(uncapped), 4);
+    Ok(())
+}
+
+#[test]
+fn build_context_message_threads_the_line_ref_cap() -> anyhow::Result<()> {
+    let message =
+        build_context_message_with_config(&project(), None, line_refs(), config(3), Some(false))?;
+    assert_eq!(snippet_count(message), 3);
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use umm::{
+    config,
+    retrieval::{HeuristicConfig, RetrievalMode, select_retrieval_mode},
+};
+
+fn is_active(mode: &RetrievalMode) -> bool {
+    matches!(mode, RetrievalMode::Active { .. })
+}
+
+#[test]
+fn grader_override_beats_global_active_retrieval() {
+    let cfg = HeuristicConfig::default();
+    let output = || Some("expected:<1> but was:<2>".to_string());
+
+    config::set_active_retrieval(true);
+    assert!(is_active(&select_retrieval_mode(cfg, output(), None)));
+    assert!(!is_active(&select_retrieval_mode(cfg, output(), Some(false))));
+
+    config::set_active_retrieval(false);
+    assert!(!is_active(&select_retrieval_mode(cfg, output(), None)));
+    assert!(is_active(&select_retrieval_mode(cfg, output(), Some(true))));
+
+    // Without grader output there is nothing to retrieve against.
+    assert!(!is_active(&select_retrieval_mode(cfg, None, Some(true))));
+}
@@ -0,0 +1,40 @@
+use umm::{config, java::grade::build_active_retrieval_context};
+
+#[path = "retrieval_support.rs"]
+mod retrieval_support;
+
+use retrieval_support::{mock_retrieval, picker_project};
+
+#[test]
+fn openai_backend_sends_the_tool_to_the_chat_api() -> anyhow::Result<()> {
+    let mock = mock_retrieval();
+    // Configuration is read once per process, so set it before first use.
+    unsafe {
+        std::env::set_var("UMM_RETRIEVAL_BACKEND", "openai");
+        std::env::set_var("UMM_RETRIEVAL_MODEL", "retrieval-model");
+        std::env::set_var("OPENAI_ENDPOINT", &mock.url);
+        std::env::set_var("OPENAI_API_KEY_SLO", "test-key");
+        std::env::set_var("OPENAI_MODEL", "feedback-model");
+    }
+    config::set_active_retrieval(true);
+
+    let message = build_active_retrieval_context(&picker_project(), "1 test failed".into())?;
+
+    let requests = mock.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert!(
+        request
+            .request_line()
+            .starts_with("POST /chat/completions "),
+        "{request:?}"
+    );
+
+    let body: serde_json::Value = serde_json::from_str(&request.body)?;
+    assert_eq!(body["model"], "retrieval-model");
+    assert_eq!(body["tools"][0]["function"]["name"], "get_method_bodies");
+
+    let message = serde_json::to_string(&message)?;
+    assert!(message.contains("`Picker#pick`"), "message was: {message}");
+    Ok(())
+}
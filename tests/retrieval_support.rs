@@ -0,0 +1,41 @@
+use umm::java::project::Project;
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+#[path = "http_support.rs"]
+mod http_support;
+
+use http_support::{MockServer, respond};
+
+const COMPLETION: &str = r#"{
+  "id": "chatcmpl-mock",
+  "object": "chat.completion",
+  "created": 0,
+  "model": "mock",
+  "choices": [{
+    "index": 0,
+    "finish_reason": "tool_calls",
+    "message": {
+      "role": "assistant",
+      "content": null,
+      "tool_calls": [{
+        "id": "call-1",
+        "type": "function",
+        "function": {
+          "name": "get_method_bodies",
+          "arguments": "{\"params\":[{\"className\":\"Picker\",\"methodName\":\"pick\"}]}"
+        }
+      }]
+    }
+  }]
+}"#;
+
+/// Starts a mock retrieval server answering every request with
+/// [`COMPLETION`].
+pub fn mock_retrieval() -> MockServer {
+    MockServer::start(|_, stream| respond(stream, 200, COMPLETION))
+}
+
+pub fn picker_project() -> Project {
+    fixture_support::java_project("failing-frame")
+}
@@ -99,6 +99,99 @@ fn rune_gradescope_json() {
     let _ = fs::remove_file(results_path);
 }
 
+#[test]
+fn rune_extra_credit_counts_beyond_out_of() {
+    let workdir = project_dir("diff-ok");
+    let results_path = workdir.join("results.json");
+    let _ = fs::remove_file(&results_path);
+
+    let (stdout, stderr) = run_script("extra_credit.rn", "diff-ok");
+    assert!(stdout.contains("bonus is extra credit: true"), "stdout was:\n{stdout}");
+    assert!(
+        stdout.contains(r#""score":3.0,"out_of":2.0,"passed":true"#),
+        "stdout was:\n{stdout}"
+    );
+    assert!(stderr.contains("bonus (extra credit)"), "stderr was:\n{stderr}");
+    assert!(stderr.contains("Total: 3.00/2.00"), "stderr was:\n{stderr}");
+
+    let contents = fs::read_to_string(&results_path).expect("results.json written");
+    let _ = fs::remove_file(results_path);
+    let json: serde_json::Value = serde_json::from_str(&contents).expect("valid results.json");
+    let bonus = json["tests"]
+        .as_array()
+        .and_then(|tests| tests.iter().find(|t| t["name"] == "bonus"))
+        .expect("bonus test case");
+    assert_eq!(bonus["score"], 1.0);
+    assert_eq!(bonus["max_score"], 0.0);
+    assert_eq!(bonus["status"], "passed");
+}
+
+#[test]
+fn rune_custom_grade_result_is_rendered_with_grader_results() {
+    let (stdout, stderr) = run_script("custom_result.rn", "diff-ok");
+    assert!(stdout.contains("style is a full score: false"), "stdout was:\n{stdout}");
+    assert!(stderr.contains("2 of 3 style checks"), "stderr was:\n{stderr}");
+    assert!(stderr.contains("2.00/3.00"), "stderr was:\n{stderr}");
+    assert!(stderr.contains("Total: 4.00/5.00"), "stderr was:\n{stderr}");
+}
+
+#[test]
+fn rune_fail_fast_skips_remaining_graders() {
+    let (stdout, stderr) = run_script("fail_fast.rn", "diff-ok");
+    assert!(stdout.contains("fail fast enabled: true"), "stdout was:\n{stdout}");
+    assert!(!stdout.contains("second grader reached"), "stdout was:\n{stdout}");
+    assert!(
+        stderr.contains("Fail-fast: `wrong output` scored 0.00/2.00"),
+        "stderr was:\n{stderr}"
+    );
+    assert!(!stderr.contains("right output"), "stderr was:\n{stderr}");
+}
+
+#[test]
+fn rune_retrieval_config_round_trips() {
+    let (stdout, _stderr) = run_script("retrieval_config.rn", "rune-hello");
+    for expected in [
+        "start_offset=1",
+        "num_lines=2",
+        "max_line_refs=3",
+        "full_file_ratio=0.5",
+        "include_declarations=true",
+        "mode=active active=true",
+        "mode=heuristic active=false",
+        "bogus mode rejected",
+    ] {
+        assert!(stdout.contains(expected), "missing `{expected}` in stdout:\n{stdout}");
+    }
+}
+
+#[test]
+fn rune_reads_allowlisted_config_and_env() {
+    let mut cmd = cargo_bin_cmd!("umm");
+    cmd.current_dir(project_dir("rune-hello"))
+        .env("CLICOLOR", "0")
+        .env("UMM_COURSE", "ITSC 1213")
+        .env("UMM_TERM", "Spring 2026")
+        .env("UMM_SECTION", "002")
+        .env_remove("UMM_DEBUG_GRADING")
+        .env("OPENAI_API_KEY_SLO", "sk-not-for-scripts")
+        .arg("java")
+        .arg("grade")
+        .arg(rune_script("config_env.rn"));
+
+    let output = cmd.assert().success().get_output().clone();
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    for expected in [
+        "course=ITSC 1213",
+        "term=Spring 2026",
+        "section=002",
+        "debug=off",
+        "secret rejected",
+    ] {
+        assert!(stdout.contains(expected), "missing `{expected}` in stdout:\n{stdout}");
+    }
+    assert!(!stdout.contains("sk-not-for-scripts"), "stdout was:\n{stdout}");
+}
+
 #[test]
 fn rune_query_grader() {
     let (stdout, stderr) = run_script("query.rn", "rune-hello");
@@ -112,3 +205,72 @@ fn rune_query_constraints() {
     insta::assert_snapshot!("rune_query_constraints_stdout", stdout);
     insta::assert_snapshot!("rune_query_constraints_stderr", stderr);
 }
+
+#[test]
+fn rune_unit_test_grader() {
+    let (stdout, _stderr) = run_script("unit_tests.rn", "readme-all");
+    assert!(stdout.contains("full marks: true"), "stdout was:\n{stdout}");
+    assert!(stdout.contains("visible tests"), "stdout was:\n{stdout}");
+}
+
+#[test]
+fn rune_docs_grader_penalizes_missing_javadoc() {
+    let (stdout, stderr) = run_script("docs_missing.rn", "docs-missing");
+    assert!(stdout.contains("penalized once: true"), "stdout was:\n{stdout}");
+    assert!(stderr.contains("-1 due to 1 nits"), "stderr was:\n{stderr}");
+}
+
+#[test]
+fn rune_project_open_file() {
+    let (stdout, _stderr) = run_script("open_file.rn", "rune-hello");
+    assert!(stdout.contains("has greeting: true"), "stdout was:\n{stdout}");
+    assert!(stdout.contains("missing: Cannot open `Missing`"), "stdout was:\n{stdout}");
+}
+
+#[test]
+fn rune_project_groups_files_by_type() {
+    let (stdout, _stderr) = run_script("files_by_type.rn", "mixed-kinds");
+    for line in [
+        r#"interface: ["shapes.Shape"]"#,
+        r#"class: ["shapes.Circle", "shapes.Square"]"#,
+        r#"class_with_main: ["App"]"#,
+        r#"test: ["CircleTest"]"#,
+    ] {
+        assert!(stdout.contains(line), "missing `{line}` in stdout:\n{stdout}");
+    }
+}
+
+#[test]
+fn rune_slo_list_enables_named_slos() {
+    let (stdout, _stderr) = run_script("enabled_slos.rn", "rune-hello");
+    assert!(
+        stdout.contains(r#"enabled: ["slo_logic", "slo_testing"]"#),
+        "stdout was:\n{stdout}"
+    );
+    assert!(stdout.contains("typo rejected: Unknown SLO `logc`"), "stdout was:\n{stdout}");
+}
+
+#[test]
+fn rune_reference_and_oracle_graders() {
+    let (stdout, stderr) = run_script("reference_oracle.rn", "oracle-sort");
+    assert!(stdout.contains("reference score: 0.0"), "stdout was:\n{stdout}");
+    assert!(stdout.contains("sorted score: 2.0"), "stdout was:\n{stdout}");
+    assert!(stdout.contains("property score: 1.0"), "stdout was:\n{stdout}");
+    assert!(stderr.contains("Total: 3.00/5.00"), "stderr was:\n{stderr}");
+}
+
+#[test]
+fn rune_diff_grader_passes_program_arguments() {
+    let (stdout, stderr) = run_script("program_args.rn", "diff-args");
+    assert!(stdout.contains("no args score: 1.0"), "stdout was:\n{stdout}");
+    assert!(stdout.contains("args score: 2.0"), "stdout was:\n{stdout}");
+    assert!(stderr.contains("Total: 3.00/3.00"), "stderr was:\n{stderr}");
+}
+
+#[test]
+fn rune_diff_grader_feeds_input_lines() {
+    let (stdout, stderr) = run_script("input_lines.rn", "diff-prompts");
+    assert!(stdout.contains("joined score: 1.0"), "stdout was:\n{stdout}");
+    assert!(stdout.contains("paced score: 2.0"), "stdout was:\n{stdout}");
+    assert!(stderr.contains("Total: 3.00/3.00"), "stderr was:\n{stderr}");
+}
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value;
+use umm::java::grade::{diagnostics_from_output, sarif_json, sarif_log};
+
+const JAVAC_OUTPUT: &str = "\
+./src/Main.java:3: error: cannot find symbol
+        System.out.println(count);
+                           ^
+./src/util/Helper.java:12: warning: [deprecation] Integer(int) in Integer has been deprecated
+        return new Integer(value);
+               ^
+";
+
+#[test]
+fn sarif_log_maps_javac_diagnostics() {
+    let diags = diagnostics_from_output(JAVAC_OUTPUT);
+    assert_eq!(diags.len(), 2);
+
+    let log = serde_json::to_value(sarif_log("javac", &diags)).expect("serialize");
+    assert_eq!(log["version"], "2.1.0");
+    assert!(log["$schema"].as_str().unwrap().contains("sarif-2.1.0"));
+    assert_eq!(log["runs"][0]["tool"]["driver"]["name"], "javac");
+
+    let results = log["runs"][0]["results"].as_array().expect("results");
+    assert_eq!(results.len(), 2);
+
+    let error = &results[0];
+    assert_eq!(error["ruleId"], "javac.error");
+    assert_eq!(error["level"], "error");
+    assert_eq!(error["message"]["text"], "cannot find symbol");
+    let location = &error["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/Main.java");
+    assert_eq!(location["region"]["startLine"], 3);
+
+    let warning = &results[1];
+    assert_eq!(warning["ruleId"], "javac.warning");
+    assert_eq!(warning["level"], "warning");
+    assert!(
+        warning["message"]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("[deprecation]")
+    );
+    let location = &warning["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/util/Helper.java");
+    assert_eq!(location["region"]["startLine"], 12);
+}
+
+#[test]
+fn sarif_json_round_trips() {
+    let json = sarif_json("javac", &[]).expect("render");
+    let log: Value = serde_json::from_str(&json).expect("valid json");
+    assert_eq!(log["runs"][0]["results"].as_array().map(Vec::len), Some(0));
+}
+
+#[test]
+fn check_cli_emits_sarif_for_compile_errors() {
+    let fixture = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/diff-compile");
+    let output = cargo_bin_cmd!("umm")
+        .current_dir(fixture)
+        .args(["java", "check", "Main", "--sarif"])
+        .output()
+        .expect("run umm");
+
+    assert!(!output.status.success());
+    let log: Value = serde_json::from_slice(&output.stdout).expect("stdout is SARIF");
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["level"], "error");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert!(
+        location["artifactLocation"]["uri"]
+            .as_str()
+            .unwrap()
+            .ends_with("Main.java")
+    );
+    assert_eq!(location["region"]["startLine"], 3);
+}
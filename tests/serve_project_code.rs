@@ -0,0 +1,59 @@
+use umm::java::SUBMISSION_URL_BASE;
+use uuid::Uuid;
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+#[path = "http_support.rs"]
+mod http_support;
+
+use fixture_support::java_project;
+use http_support::{MockServer, respond};
+
+fn submission_id(url: &str) -> Uuid {
+    let id = url
+        .strip_prefix(SUBMISSION_URL_BASE)
+        .unwrap_or_else(|| panic!("unexpected submission URL: {url}"));
+    Uuid::parse_str(id).expect("submission id should be a UUID")
+}
+
+#[test]
+fn serve_project_code_returns_the_submission_url() -> anyhow::Result<()> {
+    // Stands in for Supabase's PostgREST endpoint.
+    let mock = MockServer::start(|_, stream| respond(stream, 201, ""));
+    // Configuration is read once per process, so set it before first use.
+    unsafe {
+        std::env::set_var("SUPABASE_URL", &mock.url);
+        std::env::set_var("SUPABASE_ANON_KEY", "anon-key");
+    }
+    let project = java_project("rune-hello");
+
+    // Outside any runtime, the blocking form starts its own.
+    let blocking = submission_id(&project.serve_project_code()?);
+
+    // Inside a runtime, both the async form and the blocking form work.
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    let (from_async, nested) = runtime.block_on(async {
+        let from_async = project.serve_project_code_async().await?;
+        let nested = project.serve_project_code()?;
+        anyhow::Ok((submission_id(&from_async), submission_id(&nested)))
+    })?;
+    assert_ne!(blocking, from_async);
+    assert_ne!(from_async, nested);
+
+    let requests = mock.requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    for (request, id) in requests.iter().zip([blocking, from_async, nested]) {
+        assert!(
+            request
+                .request_line()
+                .starts_with("POST /rest/v1/submissions "),
+            "{request:?}"
+        );
+        assert!(request.has_header("apikey", "anon-key"), "{request:?}");
+        assert!(request.body.contains(&format!("\"id\":\"{id}\"")), "{request:?}");
+        assert!(request.body.contains("\"Main.java\":"), "{request:?}");
+    }
+    Ok(())
+}
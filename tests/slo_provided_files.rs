@@ -0,0 +1,56 @@
+use umm::java::{Project, grade::slo_input_files};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("provided-files")
+}
+
+fn names(files: &[&str]) -> Vec<String> {
+    files.iter().map(ToString::to_string).collect()
+}
+
+fn slo_input(project: &Project, provided: &[&str]) -> Vec<String> {
+    slo_input_files(project, &names(&["Main", "Greeter", "Names"]), &names(provided))
+        .iter()
+        .map(|file| file.proper_name())
+        .collect()
+}
+
+#[test]
+fn files_marked_as_provided_are_left_out_of_slo_input() {
+    let project = project();
+    assert!(
+        project
+            .identify("Names")
+            .expect("identify Names")
+            .is_provided()
+    );
+    assert!(
+        !project
+            .identify("Greeter")
+            .expect("identify Greeter")
+            .is_provided()
+    );
+
+    assert_eq!(slo_input(&project, &[]), ["Main", "Greeter"]);
+}
+
+#[test]
+fn files_listed_as_provided_are_left_out_of_slo_input() {
+    assert_eq!(slo_input(&project(), &["Greeter"]), ["Main"]);
+}
+
+#[tokio::test]
+async fn provided_files_are_still_compiled() {
+    let output = project()
+        .identify("Main")
+        .expect("identify Main")
+        .run(None)
+        .await
+        .expect("run");
+    assert_eq!(output.trim(), "Hello, World!");
+}
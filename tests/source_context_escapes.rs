@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use umm::{
+    java::{grade::build_heuristic_context, paths::project_paths, project::Project},
+    retrieval::HeuristicConfig,
+    types::LineRef,
+};
+
+fn fixture_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/java/escaped-literals")
+}
+
+#[test]
+fn source_snippets_keep_escaped_string_literals() -> anyhow::Result<()> {
+    let paths = project_paths().root_dir(fixture_root()).build();
+    let project = Project::from_paths(paths)?;
+
+    let message = build_heuristic_context(
+        vec![LineRef {
+            line_number: 7,
+            file_name:   "Escapes".to_string(),
+        }],
+        project,
+        HeuristicConfig {
+            start_offset:         6,
+            num_lines:            12,
+            max_line_refs:        1,
+            full_file_ratio:      0.0,
+            include_declarations: false,
+        },
+    )?;
+    let message = serde_json::to_value(message)?;
+    let content = message["content"].as_str().expect("text content");
+
+    for line in [
+        r#"        return "say \"hi\"";"#,
+        r#"        return "C:\\temp\\";"#,
+        r#"        return "a\\\"b";"#,
+    ] {
+        assert!(content.contains(line), "missing `{line}` in:\n{content}");
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use umm::java::{
+    JavaFileError,
+    grade::{KnownSymbols, diagnostics_from_output, suggest_unresolved_symbols},
+};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+const JAVAC_OUTPUT: &str = "\
+./src/Main.java:3: error: cannot find symbol
+        Conter counter = new Conter();
+        ^
+  symbol:   class Conter
+  location: class Main
+./src/Main.java:4: error: cannot find symbol
+        new Counter().incremnt();
+                     ^
+  symbol:   method incremnt()
+  location: class Counter
+./src/Main.java:5: error: cannot find symbol
+        Sytem.out.println(total);
+        ^
+  symbol:   variable Sytem
+  location: class Main
+./src/Main.java:6: error: cannot find symbol
+        frobnicate();
+        ^
+  symbol:   method frobnicate()
+  location: class Main
+";
+
+fn known() -> KnownSymbols {
+    KnownSymbols::new(["Counter", "Main"], ["increment", "getCount", "main"])
+}
+
+#[test]
+fn suggestions_match_the_symbol_kind() {
+    let known = known();
+    assert_eq!(known.suggest("class", "Conter"), Some("Counter"));
+    assert_eq!(known.suggest("method", "incremnt"), Some("increment"));
+    assert_eq!(known.suggest("variable", "Sytem"), Some("System"));
+    assert_eq!(known.suggest("class", "system"), Some("System"));
+    assert_eq!(known.suggest("method", "Conter"), None);
+    assert_eq!(known.suggest("method", "frobnicate"), None);
+    assert_eq!(known.suggest("class", "Counter"), None);
+}
+
+#[test]
+fn cannot_find_symbol_diagnostics_gain_suggestions() {
+    let mut diags = diagnostics_from_output(JAVAC_OUTPUT);
+    let annotated = suggest_unresolved_symbols(JAVAC_OUTPUT, &mut diags, &known());
+
+    let messages: Vec<&str> = diags.iter().map(|diag| diag.message()).collect();
+    assert_eq!(
+        messages,
+        [
+            "Error: cannot find symbol `Conter`; did you mean `Counter`?",
+            "Error: cannot find symbol `incremnt`; did you mean `increment`?",
+            "Error: cannot find symbol `Sytem`; did you mean `System`?",
+            "Error: cannot find symbol",
+        ]
+    );
+    assert!(annotated.contains("  symbol:   class Conter\n  did you mean `Counter`?\n  location"));
+    assert!(annotated.ends_with("  location: class Main\n"));
+    assert_eq!(annotated.matches("did you mean").count(), 3);
+}
+
+#[tokio::test]
+async fn check_file_suggests_project_class_names() {
+    let project = java_project("typo");
+
+    match project.check_file("Main").await {
+        Err(JavaFileError::DuringCompilation { stacktrace, diags }) => {
+            assert!(stacktrace.contains("did you mean `Counter`?"), "{stacktrace}");
+            assert!(
+                diags
+                    .iter()
+                    .any(|diag| diag.message().ends_with("did you mean `Counter`?")),
+                "{diags:?}"
+            );
+        }
+        other => panic!("expected a compile error, got {other:?}"),
+    }
+}
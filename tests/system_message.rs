@@ -0,0 +1,60 @@
+use umm::java::{JavaPrompts, Project, grade::EncapsulationGrader};
+use uuid::Uuid;
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project;
+
+fn project() -> Project {
+    java_project("encapsulation")
+}
+
+/// System message of the prompt a failing encapsulation grade produces.
+async fn prompt_system_message() -> String {
+    let result = EncapsulationGrader::builder()
+        .req_name("encapsulation")
+        .out_of(5.0)
+        .project(project())
+        .files(["Counter"])
+        .build()
+        .run()
+        .await
+        .expect("grade");
+
+    let prompt = result.prompt().expect("a failing grade has a prompt");
+    let prompt = serde_json::to_value(prompt).expect("serialize prompt");
+    assert_eq!(prompt[0]["role"], "system");
+    prompt[0]["content"]
+        .as_str()
+        .expect("system message is text")
+        .to_string()
+}
+
+#[tokio::test]
+async fn course_system_message_replaces_the_built_in_one() {
+    let path = std::env::temp_dir().join(format!("umm-system-{}.md", Uuid::new_v4()));
+    std::fs::write(&path, "You are the ITSC 1213 TA. Never write code for students.")
+        .expect("write system message");
+    // Configuration is read once per process, so set it before first use.
+    unsafe {
+        std::env::set_var("UMM_SYSTEM_MESSAGE_FILE", &path);
+    }
+
+    assert_eq!(
+        prompt_system_message().await,
+        "You are the ITSC 1213 TA. Never write code for students."
+    );
+    assert_eq!(
+        umm::config::python_prompts().system_message(),
+        "You are the ITSC 1213 TA. Never write code for students."
+    );
+
+    umm::config::set_system_message(Some("You are the CS 101 TA.".to_string()));
+    assert_eq!(prompt_system_message().await, "You are the CS 101 TA.");
+
+    umm::config::set_system_message(None);
+    assert_eq!(prompt_system_message().await, JavaPrompts::load().system_message());
+
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,72 @@
+use umm::java::{grade::tests::ByUnitTestGrader, project::Project};
+
+#[path = "fixture_support.rs"]
+mod fixture_support;
+
+use fixture_support::java_project_with_jars;
+
+fn project() -> Project {
+    java_project_with_jars("test-factory")
+}
+
+#[test]
+fn test_factory_methods_are_discovered() -> anyhow::Result<()> {
+    let file = project().identify("SquaresTest")?;
+    let mut methods = file.test_methods();
+    methods.sort();
+
+    assert_eq!(methods, vec!["SquaresTest#squaresSmallNumbers", "SquaresTest#squaresZero"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_factory_dynamic_tests_are_counted() -> anyhow::Result<()> {
+    let result = ByUnitTestGrader::builder()
+        .project(project())
+        .test_files(["SquaresTest"])
+        .expected_tests(["SquaresTest#squaresZero", "SquaresTest#squaresSmallNumbers"])
+        .req_name("factory")
+        .out_of(4.0)
+        .build()
+        .run()
+        .await?;
+
+    // One @Test plus three dynamic tests generated by the factory.
+    assert_eq!(result.reason(), "- 4/4 tests passing.");
+    assert_eq!(result.grade_value(), 4.0);
+    Ok(())
+}
+
+fn failing_containers() -> Project {
+    java_project_with_jars("failing-containers")
+}
+
+async fn grade(test_file: &str) -> anyhow::Result<umm::java::grade::GradeResult> {
+    ByUnitTestGrader::builder()
+        .project(failing_containers())
+        .test_files([test_file])
+        .req_name("containers")
+        .out_of(2.0)
+        .build()
+        .run()
+        .await
+}
+
+#[tokio::test]
+async fn throwing_test_factory_counts_as_a_failing_test() -> anyhow::Result<()> {
+    let result = grade("BrokenFactoryTest").await?;
+
+    assert_eq!(result.reason(), "- 1/2 tests passing.");
+    assert_eq!(result.grade_value(), 1.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn failed_class_setup_does_not_add_tests() -> anyhow::Result<()> {
+    // The class container fails, but its two tests are already found.
+    let result = grade("SetupFailsTest").await?;
+
+    assert_eq!(result.reason(), "- 0/2 tests passing.");
+    assert_eq!(result.grade_value(), 0.0);
+    Ok(())
+}
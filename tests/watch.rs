@@ -0,0 +1,77 @@
+use std::{
+    fs,
+    ops::ControlFlow,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use umm::watch::Watcher;
+use uuid::Uuid;
+
+fn scratch_dir() -> PathBuf {
+    let root = std::env::temp_dir().join(format!("umm-watch-{}", Uuid::new_v4()));
+    fs::create_dir_all(root.join("src")).expect("create dirs");
+    fs::write(root.join("src").join("Main.java"), "class Main {}").expect("write Main");
+    root
+}
+
+/// Waits briefly for the watcher to report a change.
+async fn reports_change(watcher: &mut Watcher) -> bool {
+    tokio::time::timeout(Duration::from_secs(2), watcher.changed())
+        .await
+        .is_ok_and(|result| result.is_ok())
+}
+
+#[tokio::test]
+async fn reports_edits_additions_and_removals() -> anyhow::Result<()> {
+    let root = scratch_dir();
+    let mut watcher = Watcher::new(&root, "java", Duration::from_millis(50))?;
+
+    fs::write(root.join("src").join("Main.java"), "class Main { int x; }")?;
+    assert!(reports_change(&mut watcher).await, "edit");
+
+    fs::write(root.join("src").join("notes.txt"), "ignored")?;
+    assert!(!reports_change(&mut watcher).await, "only watched extensions count");
+
+    fs::write(root.join("src").join("Util.java"), "class Util {}")?;
+    assert!(reports_change(&mut watcher).await, "addition");
+
+    fs::remove_file(root.join("src").join("Util.java"))?;
+    assert!(reports_change(&mut watcher).await, "removal");
+
+    let _ = fs::remove_dir_all(&root);
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_change_triggers_a_rerun() -> anyhow::Result<()> {
+    let root = scratch_dir();
+    let watcher = Watcher::new(&root, "java", Duration::from_millis(20))?;
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    let main = root.join("src").join("Main.java");
+    let counter = Arc::clone(&runs);
+    let watched = watcher.run(move || {
+        let counter = Arc::clone(&counter);
+        let main = main.clone();
+        async move {
+            match counter.fetch_add(1, Ordering::SeqCst) {
+                // First run: edit a file, as a student saving their work would.
+                0 => {
+                    fs::write(&main, "class Main { void changed() {} }")?;
+                    Ok(ControlFlow::Continue(()))
+                }
+                _ => Ok(ControlFlow::Break(())),
+            }
+        }
+    });
+    tokio::time::timeout(Duration::from_secs(10), watched).await??;
+
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    let _ = fs::remove_dir_all(&root);
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use umm::java::{grade::diff::DiffGrader, project::Project};
+
+fn fixture_zip(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures/java/zip-submission")
+        .join(name)
+}
+
+async fn grade_zip(name: &str) -> anyhow::Result<f64> {
+    let (project, unzipped) = Project::from_zip(fixture_zip(name)).await?;
+    assert!(unzipped.root().join("src/Main.java").is_file());
+
+    let result = DiffGrader::builder()
+        .req_name("hello")
+        .out_of(1.0)
+        .project(project)
+        .file("Main")
+        .cases(vec![("hello from a zip", None::<String>)])
+        .build()
+        .run()
+        .await?;
+    Ok(result.grade_value())
+}
+
+#[tokio::test]
+async fn grades_a_flat_zip() -> anyhow::Result<()> {
+    assert_eq!(grade_zip("flat.zip").await?, 1.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn grades_a_zip_with_a_single_root_folder() -> anyhow::Result<()> {
+    assert_eq!(grade_zip("github.zip").await?, 1.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn extracted_files_are_removed_on_drop() -> anyhow::Result<()> {
+    let (project, unzipped) = Project::from_zip(fixture_zip("github.zip")).await?;
+    let extracted = unzipped.path().to_path_buf();
+    assert!(unzipped.root().ends_with("hello-main"));
+    assert!(project.identify("Main").is_ok());
+
+    drop(project);
+    drop(unzipped);
+    assert!(!extracted.exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn missing_archives_are_reported() {
+    let err = Project::from_zip(fixture_zip("missing.zip"))
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("Could not find archive"), "{err:#}");
+}